@@ -0,0 +1,359 @@
+//! Encrypted keystore account implementation.
+//!
+//! Loads the solver key from an Ethereum V3 JSON keystore (scrypt or pbkdf2
+//! key derivation with AES-128-CTR encryption), so no plaintext key ever has
+//! to appear in configuration. The password is read from a file, an
+//! environment variable, or an interactive prompt. Decrypted key material and
+//! passwords are zeroized as soon as they are no longer needed.
+
+use crate::implementations::local::LocalWallet;
+use crate::{AccountError, AccountInterface, AccountSigner};
+use alloy_dyn_abi::TypedData;
+use alloy_primitives::Address as AlloyAddress;
+use alloy_signer_local::PrivateKeySigner;
+use async_trait::async_trait;
+use solver_types::{
+	Address, ConfigSchema, Field, FieldType, Schema, Signature, Transaction, ValidationError,
+};
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+/// Where the keystore password is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PasswordSource {
+	/// File holding the password, typically a mounted secret.
+	File(PathBuf),
+	/// Environment variable holding the password.
+	Env(String),
+	/// Interactive prompt on the terminal.
+	Prompt,
+}
+
+impl PasswordSource {
+	/// Determines the password source from account configuration.
+	///
+	/// Falls back to an interactive prompt when neither `password_file` nor
+	/// `password_env` is set.
+	pub fn from_config(config: &toml::Value) -> Self {
+		if let Some(path) = config.get("password_file").and_then(|v| v.as_str()) {
+			Self::File(PathBuf::from(path))
+		} else if let Some(var) = config.get("password_env").and_then(|v| v.as_str()) {
+			Self::Env(var.to_string())
+		} else {
+			Self::Prompt
+		}
+	}
+
+	/// Reads the password, showing `prompt` when asking interactively.
+	pub fn read(&self, prompt: &str) -> Result<Zeroizing<String>, AccountError> {
+		match self {
+			Self::File(path) => {
+				let contents = Zeroizing::new(std::fs::read_to_string(path).map_err(|e| {
+					AccountError::Implementation(format!(
+						"Failed to read password file {}: {}",
+						path.display(),
+						e
+					))
+				})?);
+				Ok(Zeroizing::new(
+					contents.trim_end_matches(['\r', '\n']).to_string(),
+				))
+			},
+			Self::Env(var) => std::env::var(var).map(Zeroizing::new).map_err(|_| {
+				AccountError::Implementation(format!("Password env var {} is not set", var))
+			}),
+			Self::Prompt => rpassword::prompt_password(prompt)
+				.map(Zeroizing::new)
+				.map_err(|e| {
+					AccountError::Implementation(format!("Failed to read password: {}", e))
+				}),
+		}
+	}
+}
+
+/// Account backed by an encrypted V3 keystore file.
+///
+/// The keystore is decrypted once at startup; signing then behaves exactly
+/// like a local wallet.
+#[derive(Debug)]
+pub struct KeystoreWallet {
+	/// Wallet holding the decrypted key.
+	wallet: LocalWallet,
+}
+
+impl KeystoreWallet {
+	/// Decrypts a keystore file with the given password.
+	pub fn decrypt(path: &Path, password: &str) -> Result<Self, AccountError> {
+		let secret = Zeroizing::new(eth_keystore::decrypt_key(path, password).map_err(|e| {
+			AccountError::InvalidKey(format!(
+				"Failed to decrypt keystore {}: {}",
+				path.display(),
+				e
+			))
+		})?);
+		let signer = PrivateKeySigner::from_slice(&secret)
+			.map_err(|e| AccountError::InvalidKey(format!("Invalid keystore key: {}", e)))?;
+
+		Ok(Self {
+			wallet: LocalWallet::from_signer(signer),
+		})
+	}
+}
+
+/// Generates a new random key and writes it to an encrypted keystore.
+///
+/// Returns the path of the keystore file and the address of the new key.
+pub fn create_keystore(
+	dir: &Path,
+	name: Option<&str>,
+	password: &str,
+) -> Result<(PathBuf, AlloyAddress), AccountError> {
+	let (secret, file_name) = eth_keystore::new(dir, &mut rand::thread_rng(), password, name)
+		.map_err(|e| AccountError::Implementation(format!("Failed to create keystore: {}", e)))?;
+	let secret = Zeroizing::new(secret);
+	let signer = PrivateKeySigner::from_slice(&secret)
+		.map_err(|e| AccountError::InvalidKey(format!("Invalid generated key: {}", e)))?;
+
+	Ok((dir.join(file_name), signer.address()))
+}
+
+/// Encrypts an existing hex private key into a keystore.
+///
+/// Returns the path of the keystore file and the address of the key.
+pub fn import_keystore(
+	dir: &Path,
+	name: Option<&str>,
+	private_key_hex: &str,
+	password: &str,
+) -> Result<(PathBuf, AlloyAddress), AccountError> {
+	let signer = private_key_hex
+		.trim()
+		.parse::<PrivateKeySigner>()
+		.map_err(|e| AccountError::InvalidKey(format!("Invalid private key: {}", e)))?;
+	let secret = Zeroizing::new(signer.to_bytes().to_vec());
+
+	let file_name =
+		eth_keystore::encrypt_key(dir, &mut rand::thread_rng(), &*secret, password, name).map_err(
+			|e| AccountError::Implementation(format!("Failed to write keystore: {}", e)),
+		)?;
+
+	Ok((dir.join(file_name), signer.address()))
+}
+
+/// Configuration schema for KeystoreWallet.
+pub struct KeystoreWalletSchema;
+
+impl KeystoreWalletSchema {
+	/// Static validation method for use before instance creation
+	pub fn validate_config(config: &toml::Value) -> Result<(), ValidationError> {
+		let instance = Self;
+		instance.validate(config)
+	}
+}
+
+impl ConfigSchema for KeystoreWalletSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![Field::new("path", FieldType::String)],
+			// Optional fields
+			vec![
+				Field::new("password_file", FieldType::String),
+				Field::new("password_env", FieldType::String),
+			],
+		);
+		schema.validate(config)?;
+
+		if config.get("password_file").is_some() && config.get("password_env").is_some() {
+			return Err(ValidationError::InvalidValue {
+				field: "password_file".to_string(),
+				message: "Set only one of password_file or password_env".to_string(),
+			});
+		}
+
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl AccountInterface for KeystoreWallet {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(KeystoreWalletSchema)
+	}
+
+	async fn address(&self) -> Result<Address, AccountError> {
+		self.wallet.address().await
+	}
+
+	async fn sign_transaction(&self, tx: &Transaction) -> Result<Signature, AccountError> {
+		self.wallet.sign_transaction(tx).await
+	}
+
+	async fn sign_message(&self, message: &[u8]) -> Result<Signature, AccountError> {
+		self.wallet.sign_message(message).await
+	}
+
+	async fn sign_typed_data(&self, typed_data: &TypedData) -> Result<Signature, AccountError> {
+		self.wallet.sign_typed_data(typed_data).await
+	}
+
+	fn signer(&self) -> AccountSigner {
+		self.wallet.signer()
+	}
+}
+
+/// Factory function to create a keystore account from configuration.
+///
+/// Configuration parameters:
+/// - `path`: Path of the V3 keystore JSON file
+/// - `password_file` (optional): File holding the password; a trailing newline is ignored
+/// - `password_env` (optional): Environment variable holding the password
+///
+/// Without `password_file` or `password_env` the password is prompted for
+/// on the terminal.
+pub fn create_account(config: &toml::Value) -> Result<Box<dyn AccountInterface>, AccountError> {
+	KeystoreWalletSchema::validate_config(config)
+		.map_err(|e| AccountError::InvalidKey(format!("Invalid configuration: {}", e)))?;
+
+	let path = config
+		.get("path")
+		.and_then(|v| v.as_str())
+		.map(PathBuf::from)
+		.expect("path already validated");
+
+	let password = PasswordSource::from_config(config)
+		.read(&format!("Password for keystore {}: ", path.display()))?;
+
+	Ok(Box::new(KeystoreWallet::decrypt(&path, &password)?))
+}
+
+/// Registry for the keystore account implementation.
+pub struct Registry;
+
+impl solver_types::ImplementationRegistry for Registry {
+	const NAME: &'static str = "keystore";
+	type Factory = crate::AccountFactory;
+
+	fn factory() -> Self::Factory {
+		create_account
+	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(KeystoreWalletSchema)
+	}
+}
+
+impl crate::AccountRegistry for Registry {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::str::FromStr;
+
+	// Test private key (FOR TESTING ONLY!)
+	const TEST_PRIVATE_KEY: &str =
+		"0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+	const TEST_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+	// Test vector from the Web3 Secret Storage definition
+	const PBKDF2_KEYSTORE: &str = r#"{
+		"crypto": {
+			"cipher": "aes-128-ctr",
+			"cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+			"ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+			"kdf": "pbkdf2",
+			"kdfparams": {
+				"c": 262144,
+				"dklen": 32,
+				"prf": "hmac-sha256",
+				"salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+			},
+			"mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+		},
+		"id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+		"version": 3
+	}"#;
+	const PBKDF2_PRIVATE_KEY: &str =
+		"7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+
+	fn config(entries: &[(&str, &str)]) -> toml::Value {
+		toml::Value::Table(
+			entries
+				.iter()
+				.map(|(k, v)| (k.to_string(), toml::Value::String(v.to_string())))
+				.collect(),
+		)
+	}
+
+	#[tokio::test]
+	async fn test_import_and_decrypt_scrypt_keystore() {
+		let dir = tempfile::tempdir().unwrap();
+		let (path, address) =
+			import_keystore(dir.path(), Some("solver"), TEST_PRIVATE_KEY, "hunter2").unwrap();
+		assert_eq!(address, AlloyAddress::from_str(TEST_ADDRESS).unwrap());
+
+		let wallet = KeystoreWallet::decrypt(&path, "hunter2").unwrap();
+		assert_eq!(wallet.signer().address(), address);
+
+		assert!(matches!(
+			KeystoreWallet::decrypt(&path, "wrong"),
+			Err(AccountError::InvalidKey(_))
+		));
+	}
+
+	#[test]
+	fn test_decrypt_pbkdf2_keystore() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("pbkdf2.json");
+		std::fs::write(&path, PBKDF2_KEYSTORE).unwrap();
+
+		let wallet = KeystoreWallet::decrypt(&path, "testpassword").unwrap();
+		let expected: PrivateKeySigner = PBKDF2_PRIVATE_KEY.parse().unwrap();
+		assert_eq!(wallet.signer().address(), expected.address());
+	}
+
+	#[test]
+	fn test_create_keystore_with_password_file() {
+		let dir = tempfile::tempdir().unwrap();
+		let (path, address) = create_keystore(dir.path(), None, "s3cret").unwrap();
+
+		let password_path = dir.path().join("password");
+		std::fs::write(&password_path, "s3cret\n").unwrap();
+		let config = config(&[
+			("path", path.to_str().unwrap()),
+			("password_file", password_path.to_str().unwrap()),
+		]);
+
+		let account = create_account(&config).unwrap();
+		assert_eq!(account.signer().address(), address);
+	}
+
+	#[test]
+	fn test_password_source_from_config() {
+		assert_eq!(
+			PasswordSource::from_config(&config(&[("password_env", "KEYSTORE_PW")])),
+			PasswordSource::Env("KEYSTORE_PW".to_string())
+		);
+		assert_eq!(
+			PasswordSource::from_config(&config(&[])),
+			PasswordSource::Prompt
+		);
+		assert!(
+			PasswordSource::Env("SOLVER_TEST_UNSET_PASSWORD_VAR".to_string())
+				.read("")
+				.is_err()
+		);
+	}
+
+	#[test]
+	fn test_schema_validation() {
+		assert!(KeystoreWalletSchema::validate_config(&config(&[("path", "key.json")])).is_ok());
+		assert!(KeystoreWalletSchema::validate_config(&config(&[])).is_err());
+		assert!(KeystoreWalletSchema::validate_config(&config(&[
+			("path", "key.json"),
+			("password_file", "pw"),
+			("password_env", "PW"),
+		]))
+		.is_err());
+	}
+}
@@ -3,17 +3,16 @@
 //! This module provides concrete implementations of the AccountInterface trait,
 //! currently supporting local private key wallets using the Alloy library.
 
-use crate::{AccountError, AccountInterface};
+use crate::{AccountError, AccountInterface, AccountSigner, SolverSigner};
 use alloy_consensus::TxLegacy;
+use alloy_dyn_abi::TypedData;
 use alloy_network::TxSigner;
-use alloy_primitives::{Address as AlloyAddress, Bytes, TxKind};
-use alloy_signer::Signer;
+use alloy_primitives::{Address as AlloyAddress, Bytes, TxKind, B256};
+use alloy_signer::{Signer, SignerSync};
 use alloy_signer_local::PrivateKeySigner;
 use async_trait::async_trait;
-use solver_types::{
-	with_0x_prefix, Address, ConfigSchema, Field, FieldType, Schema, SecretString, Signature,
-	Transaction,
-};
+use solver_types::{Address, ConfigSchema, Field, FieldType, Schema, Signature, Transaction};
+use std::sync::Arc;
 
 /// Local wallet implementation using Alloy's signer.
 ///
@@ -39,9 +38,48 @@ impl LocalWallet {
 		Ok(Self { signer })
 	}
 
-	/// Returns the private key as a SecretString with 0x prefix.
-	pub fn get_private_key(&self) -> SecretString {
-		SecretString::from(&with_0x_prefix(&hex::encode(self.signer.to_bytes())) as &str)
+	/// Creates a LocalWallet from an already decrypted signer.
+	pub(crate) fn from_signer(signer: PrivateKeySigner) -> Self {
+		Self { signer }
+	}
+}
+
+/// Converts a solver transaction into a signable legacy transaction.
+pub(crate) fn legacy_transaction(tx: &Transaction) -> Result<TxLegacy, AccountError> {
+	let to = if let Some(to_addr) = &tx.to {
+		if to_addr.0.len() != 20 {
+			return Err(AccountError::SigningFailed(
+				"Invalid address length".to_string(),
+			));
+		}
+		let mut addr_bytes = [0u8; 20];
+		addr_bytes.copy_from_slice(&to_addr.0);
+		TxKind::Call(AlloyAddress::from(addr_bytes))
+	} else {
+		TxKind::Create
+	};
+
+	Ok(TxLegacy {
+		chain_id: Some(tx.chain_id),
+		nonce: tx.nonce.unwrap_or(0),
+		gas_price: tx.gas_price.unwrap_or(0),
+		gas_limit: tx.gas_limit.unwrap_or(0),
+		to,
+		value: tx.value,
+		input: Bytes::from(tx.data.clone()),
+	})
+}
+
+/// Signer backend holding the key in process memory.
+#[async_trait]
+impl SolverSigner for PrivateKeySigner {
+	fn address(&self) -> AlloyAddress {
+		Signer::address(self)
+	}
+
+	async fn sign_hash(&self, hash: &B256) -> Result<alloy_primitives::Signature, AccountError> {
+		SignerSync::sign_hash_sync(self, hash)
+			.map_err(|e| AccountError::SigningFailed(format!("Failed to sign hash: {}", e)))
 	}
 }
 
@@ -96,39 +134,14 @@ impl AccountInterface for LocalWallet {
 	}
 
 	async fn address(&self) -> Result<Address, AccountError> {
-		let alloy_address = self.signer.address();
+		let alloy_address = Signer::address(&self.signer);
 		Ok(Address(alloy_address.as_slice().to_vec()))
 	}
 
 	async fn sign_transaction(&self, tx: &Transaction) -> Result<Signature, AccountError> {
-		let to = if let Some(to_addr) = &tx.to {
-			if to_addr.0.len() != 20 {
-				return Err(AccountError::SigningFailed(
-					"Invalid address length".to_string(),
-				));
-			}
-			let mut addr_bytes = [0u8; 20];
-			addr_bytes.copy_from_slice(&to_addr.0);
-			TxKind::Call(AlloyAddress::from(addr_bytes))
-		} else {
-			TxKind::Create
-		};
-
-		let value = tx.value;
+		let mut legacy_tx = legacy_transaction(tx)?;
 
-		let mut legacy_tx = TxLegacy {
-			chain_id: Some(tx.chain_id),
-			nonce: tx.nonce.unwrap_or(0),
-			gas_price: tx.gas_price.unwrap_or(0),
-			gas_limit: tx.gas_limit.unwrap_or(0),
-			to,
-			value,
-			input: Bytes::from(tx.data.clone()),
-		};
-
-		let signature = self
-			.signer
-			.sign_transaction(&mut legacy_tx)
+		let signature = TxSigner::sign_transaction(&self.signer, &mut legacy_tx)
 			.await
 			.map_err(|e| {
 				AccountError::SigningFailed(format!("Failed to sign transaction: {}", e))
@@ -139,16 +152,20 @@ impl AccountInterface for LocalWallet {
 
 	async fn sign_message(&self, message: &[u8]) -> Result<Signature, AccountError> {
 		// Use Alloy's signer to sign the message (handles EIP-191 internally)
-		let signature =
-			self.signer.sign_message(message).await.map_err(|e| {
-				AccountError::SigningFailed(format!("Failed to sign message: {}", e))
-			})?;
+		let signature = Signer::sign_message(&self.signer, message)
+			.await
+			.map_err(|e| AccountError::SigningFailed(format!("Failed to sign message: {}", e)))?;
+
+		Ok(signature.into())
+	}
 
+	async fn sign_typed_data(&self, typed_data: &TypedData) -> Result<Signature, AccountError> {
+		let signature = SolverSigner::sign_typed_data(&self.signer, typed_data).await?;
 		Ok(signature.into())
 	}
 
-	fn get_private_key(&self) -> SecretString {
-		self.get_private_key()
+	fn signer(&self) -> AccountSigner {
+		AccountSigner::new(Arc::new(self.signer.clone()))
 	}
 }
 
@@ -186,6 +203,10 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_account
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(LocalWalletSchema)
+	}
 }
 
 impl crate::AccountRegistry for Registry {}
@@ -238,13 +259,38 @@ mod tests {
 		assert!(matches!(result.unwrap_err(), AccountError::InvalidKey(_)));
 	}
 
-	#[test]
-	fn test_local_wallet_get_private_key() {
+	#[tokio::test]
+	async fn test_local_wallet_signer_matches_account() {
 		let wallet = LocalWallet::new(TEST_PRIVATE_KEY).unwrap();
-		let private_key = wallet.get_private_key();
-		let private_key_str = private_key.with_exposed(|s| s.to_string());
-		assert!(private_key_str.starts_with("0x"));
-		assert_eq!(private_key_str.len(), 66); // 0x + 64 hex chars
+		let signer = wallet.signer();
+		let address = wallet.address().await.unwrap();
+		assert_eq!(signer.address().as_slice(), address.0.as_slice());
+
+		// Signatures through the handle recover to the account address
+		let message = b"Hello, World!";
+		let signature = signer.sign_message(message).await.unwrap();
+		let recovered = signature.recover_address_from_msg(message).unwrap();
+		assert_eq!(recovered, signer.address());
+		assert_eq!(
+			solver_types::Signature::from(signature).0,
+			wallet.sign_message(message).await.unwrap().0
+		);
+	}
+
+	#[tokio::test]
+	async fn test_signer_rejects_mismatched_chain_id() {
+		let signer = LocalWallet::new(TEST_PRIVATE_KEY)
+			.unwrap()
+			.signer()
+			.with_chain_id(Some(1));
+		let mut tx = TxLegacy {
+			chain_id: Some(10),
+			..Default::default()
+		};
+
+		assert!(TxSigner::sign_transaction(&signer, &mut tx).await.is_err());
+		tx.chain_id = Some(1);
+		assert!(TxSigner::sign_transaction(&signer, &mut tx).await.is_ok());
 	}
 
 	#[test]
@@ -329,11 +375,36 @@ mod tests {
 		assert!(!signature.0.is_empty());
 	}
 
+	#[tokio::test]
+	async fn test_account_interface_sign_typed_data() {
+		let wallet = LocalWallet::new(TEST_PRIVATE_KEY).unwrap();
+		let typed_data: TypedData = serde_json::from_value(serde_json::json!({
+			"types": {
+				"EIP712Domain": [
+					{ "name": "name", "type": "string" },
+					{ "name": "chainId", "type": "uint256" }
+				],
+				"Mail": [{ "name": "contents", "type": "string" }]
+			},
+			"primaryType": "Mail",
+			"domain": { "name": "Test", "chainId": 1 },
+			"message": { "contents": "Hello" }
+		}))
+		.unwrap();
+
+		let signature = wallet.sign_typed_data(&typed_data).await.unwrap();
+		let signature = alloy_primitives::Signature::try_from(signature.0.as_slice()).unwrap();
+		let hash = typed_data.eip712_signing_hash().unwrap();
+		let recovered = signature.recover_address_from_prehash(&hash).unwrap();
+		assert_eq!(recovered, Signer::address(&wallet.signer));
+	}
+
 	#[test]
 	fn test_create_account_valid_config() {
 		let config = create_test_config(TEST_PRIVATE_KEY);
 		let account = create_account(&config).unwrap();
-		assert!(!account.get_private_key().with_exposed(|s| s.is_empty()));
+		let expected = LocalWallet::new(TEST_PRIVATE_KEY).unwrap();
+		assert_eq!(account.signer().address(), expected.signer().address());
 	}
 
 	#[test]
@@ -360,7 +431,7 @@ mod tests {
 		let factory = Registry::factory();
 		let config = create_test_config(TEST_PRIVATE_KEY);
 		let account = factory(&config).unwrap();
-		assert!(!account.get_private_key().with_exposed(|s| s.is_empty()));
+		assert_eq!(account.signer().address().len(), 20);
 	}
 
 	#[test]
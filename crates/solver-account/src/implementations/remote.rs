@@ -0,0 +1,875 @@
+//! Remote signer account implementation.
+//!
+//! Delegates signing to an external JSON-RPC signing service such as
+//! Web3Signer or Clef, so the solver process never holds key material. The
+//! service is reached over HTTP(S) with optional bearer token or mutual TLS
+//! authentication and is expected to speak `eth_accounts`, `eth_sign`,
+//! `eth_signTransaction` and `eth_signTypedData_v4`. Transient failures are
+//! retried with exponential backoff, and every returned signature is
+//! verified against the configured address before it is used.
+
+use crate::implementations::local::legacy_transaction;
+use crate::{AccountError, AccountInterface, AccountSigner, SolverSigner};
+use alloy_consensus::{SignableTransaction, Transaction as _, TxEnvelope};
+use alloy_dyn_abi::TypedData;
+use alloy_eips::eip2718::{Decodable2718, Typed2718};
+use alloy_primitives::{Address as AlloyAddress, Signature as AlloySignature, TxKind, B256};
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use solver_types::{
+	Address, ConfigSchema, Field, FieldType, Schema, Signature, Transaction, ValidationError,
+};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use zeroize::Zeroizing;
+
+/// Default timeout of a single signing request.
+const DEFAULT_TIMEOUT_MS: u64 = 5_000;
+
+/// Default number of retries after a transient failure.
+const DEFAULT_MAX_RETRIES: u32 = 2;
+
+/// Default delay before the first retry; doubled on every further attempt.
+const DEFAULT_RETRY_BACKOFF_MS: u64 = 250;
+
+/// JSON-RPC response envelope returned by the signing service.
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+	result: Option<Value>,
+	error: Option<RpcError>,
+}
+
+/// JSON-RPC error object returned by the signing service.
+#[derive(Debug, Deserialize)]
+struct RpcError {
+	code: i64,
+	message: String,
+}
+
+/// Outcome of a failed request, deciding whether it is retried.
+enum RequestFailure {
+	/// Network errors, timeouts and overloaded services.
+	Transient(AccountError),
+	/// Refused or malformed requests that would fail again.
+	Permanent(AccountError),
+}
+
+/// Signer backend that forwards signing requests to a JSON-RPC service.
+#[derive(Debug)]
+pub struct RemoteSigner {
+	/// HTTP client carrying timeouts and authentication.
+	client: reqwest::Client,
+	/// JSON-RPC endpoint of the signing service.
+	url: String,
+	/// Address of the key the solver signs with.
+	address: AlloyAddress,
+	/// Number of retries after a transient failure.
+	max_retries: u32,
+	/// Delay before the first retry.
+	retry_backoff: Duration,
+	/// Id of the next JSON-RPC request.
+	next_id: AtomicU64,
+}
+
+impl RemoteSigner {
+	/// Creates a signer for `address` using an already configured HTTP client.
+	pub fn new(
+		client: reqwest::Client,
+		url: impl Into<String>,
+		address: AlloyAddress,
+		max_retries: u32,
+		retry_backoff: Duration,
+	) -> Self {
+		Self {
+			client,
+			url: url.into(),
+			address,
+			max_retries,
+			retry_backoff,
+			next_id: AtomicU64::new(1),
+		}
+	}
+
+	/// Returns the accounts the signing service is willing to sign for.
+	pub async fn accounts(&self) -> Result<Vec<AlloyAddress>, AccountError> {
+		let result = self.call("eth_accounts", json!([])).await?;
+		let accounts: Vec<String> = serde_json::from_value(result).map_err(|e| {
+			AccountError::Implementation(format!("Invalid eth_accounts response: {}", e))
+		})?;
+
+		accounts
+			.iter()
+			.map(|account| {
+				AlloyAddress::from_str(account).map_err(|e| {
+					AccountError::Implementation(format!(
+						"Invalid address '{}' from remote signer: {}",
+						account, e
+					))
+				})
+			})
+			.collect()
+	}
+
+	/// Checks that the signing service allows signing for the configured address.
+	pub async fn ensure_allowed(&self) -> Result<(), AccountError> {
+		let accounts = self.accounts().await?;
+		if accounts.contains(&self.address) {
+			Ok(())
+		} else {
+			Err(AccountError::InvalidKey(format!(
+				"Remote signer does not allow signing for {}",
+				self.address
+			)))
+		}
+	}
+
+	/// Sends a JSON-RPC request, retrying transient failures.
+	async fn call(&self, method: &str, params: Value) -> Result<Value, AccountError> {
+		let request = json!({
+			"jsonrpc": "2.0",
+			"id": self.next_id.fetch_add(1, Ordering::Relaxed),
+			"method": method,
+			"params": params,
+		});
+
+		let mut attempt = 0;
+		loop {
+			match self.send(&request).await {
+				Ok(result) => return Ok(result),
+				Err(RequestFailure::Transient(e)) if attempt < self.max_retries => {
+					let delay = self.retry_backoff * 2u32.saturating_pow(attempt);
+					attempt += 1;
+					tracing::warn!(
+						method,
+						attempt,
+						delay_ms = delay.as_millis() as u64,
+						"Remote signer request failed, retrying: {}",
+						e
+					);
+					tokio::time::sleep(delay).await;
+				},
+				Err(RequestFailure::Transient(e)) | Err(RequestFailure::Permanent(e)) => {
+					return Err(e)
+				},
+			}
+		}
+	}
+
+	/// Sends a single JSON-RPC request.
+	async fn send(&self, request: &Value) -> Result<Value, RequestFailure> {
+		let response = self
+			.client
+			.post(&self.url)
+			.json(request)
+			.send()
+			.await
+			.map_err(|e| {
+				RequestFailure::Transient(AccountError::Implementation(format!(
+					"Remote signer request failed: {}",
+					e
+				)))
+			})?;
+
+		let status = response.status();
+		if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+			return Err(RequestFailure::Transient(AccountError::Implementation(
+				format!("Remote signer returned HTTP {}", status),
+			)));
+		}
+		if !status.is_success() {
+			return Err(RequestFailure::Permanent(AccountError::SigningFailed(
+				format!("Remote signer returned HTTP {}", status),
+			)));
+		}
+
+		let response: RpcResponse = response.json().await.map_err(|e| {
+			RequestFailure::Permanent(AccountError::Implementation(format!(
+				"Invalid remote signer response: {}",
+				e
+			)))
+		})?;
+		if let Some(error) = response.error {
+			return Err(RequestFailure::Permanent(AccountError::SigningFailed(
+				format!("Remote signer error {}: {}", error.code, error.message),
+			)));
+		}
+		response.result.ok_or_else(|| {
+			RequestFailure::Permanent(AccountError::Implementation(
+				"Remote signer response has no result".to_string(),
+			))
+		})
+	}
+
+	/// Checks that a signature over `hash` was produced by the configured key.
+	fn verify(
+		&self,
+		signature: AlloySignature,
+		hash: &B256,
+	) -> Result<AlloySignature, AccountError> {
+		let signer = signature
+			.recover_address_from_prehash(hash)
+			.map_err(|e| AccountError::SigningFailed(format!("Invalid remote signature: {}", e)))?;
+		if signer != self.address {
+			return Err(AccountError::SigningFailed(format!(
+				"Remote signature was produced by {} instead of {}",
+				signer, self.address
+			)));
+		}
+		Ok(signature)
+	}
+}
+
+#[async_trait]
+impl SolverSigner for RemoteSigner {
+	fn address(&self) -> AlloyAddress {
+		self.address
+	}
+
+	async fn sign_hash(&self, _hash: &B256) -> Result<AlloySignature, AccountError> {
+		Err(AccountError::SigningFailed(
+			"Remote signer does not sign raw digests".to_string(),
+		))
+	}
+
+	fn signs_digests(&self) -> bool {
+		false
+	}
+
+	async fn sign_message(&self, message: &[u8]) -> Result<AlloySignature, AccountError> {
+		let result = self
+			.call(
+				"eth_sign",
+				json!([
+					self.address.to_string(),
+					format!("0x{}", hex::encode(message))
+				]),
+			)
+			.await?;
+		let signature = parse_signature(&result)?;
+		self.verify(signature, &alloy_primitives::eip191_hash_message(message))
+	}
+
+	async fn sign_typed_data(
+		&self,
+		typed_data: &TypedData,
+	) -> Result<AlloySignature, AccountError> {
+		let hash = typed_data.eip712_signing_hash().map_err(|e| {
+			AccountError::SigningFailed(format!("Failed to hash typed data: {}", e))
+		})?;
+		let result = self
+			.call(
+				"eth_signTypedData_v4",
+				json!([self.address.to_string(), typed_data]),
+			)
+			.await?;
+		let signature = parse_signature(&result)?;
+		self.verify(signature, &hash)
+	}
+
+	async fn sign_transaction(
+		&self,
+		tx: &mut dyn SignableTransaction<AlloySignature>,
+	) -> Result<AlloySignature, AccountError> {
+		let request = transaction_request(self.address, tx)?;
+		let result = self.call("eth_signTransaction", json!([request])).await?;
+
+		// Web3Signer returns the raw transaction, Clef wraps it in an object
+		let raw = result
+			.get("raw")
+			.unwrap_or(&result)
+			.as_str()
+			.ok_or_else(|| {
+				AccountError::SigningFailed("Remote signer returned no raw transaction".to_string())
+			})?;
+		let bytes = hex::decode(raw.trim_start_matches("0x"))
+			.map_err(|e| AccountError::SigningFailed(format!("Invalid raw transaction: {}", e)))?;
+		let envelope = TxEnvelope::decode_2718(&mut bytes.as_slice())
+			.map_err(|e| AccountError::SigningFailed(format!("Invalid raw transaction: {}", e)))?;
+
+		// Verifying against our own hash also rejects a modified transaction
+		self.verify(*envelope.signature(), &tx.signature_hash())
+	}
+}
+
+/// Builds the `eth_signTransaction` request for a transaction.
+fn transaction_request(
+	from: AlloyAddress,
+	tx: &dyn SignableTransaction<AlloySignature>,
+) -> Result<Value, AccountError> {
+	if !matches!(tx.ty(), 0..=2) {
+		return Err(AccountError::SigningFailed(format!(
+			"Remote signer does not support transaction type {}",
+			tx.ty()
+		)));
+	}
+	if tx.access_list().is_some_and(|list| !list.0.is_empty()) {
+		return Err(AccountError::SigningFailed(
+			"Remote signer does not support access lists".to_string(),
+		));
+	}
+
+	let mut request = json!({
+		"from": from.to_string(),
+		"type": format!("{:#x}", tx.ty()),
+		"nonce": format!("{:#x}", tx.nonce()),
+		"gas": format!("{:#x}", tx.gas_limit()),
+		"value": format!("{:#x}", tx.value()),
+		"data": format!("0x{}", hex::encode(tx.input())),
+	});
+	if let TxKind::Call(to) = tx.kind() {
+		request["to"] = json!(to.to_string());
+	}
+	if let Some(chain_id) = tx.chain_id() {
+		request["chainId"] = json!(format!("{:#x}", chain_id));
+	}
+	if tx.is_dynamic_fee() {
+		request["maxFeePerGas"] = json!(format!("{:#x}", tx.max_fee_per_gas()));
+		request["maxPriorityFeePerGas"] = json!(format!(
+			"{:#x}",
+			tx.max_priority_fee_per_gas().unwrap_or_default()
+		));
+	} else {
+		request["gasPrice"] = json!(format!("{:#x}", tx.gas_price().unwrap_or_default()));
+	}
+	Ok(request)
+}
+
+/// Parses a 65-byte hex signature returned by the signing service.
+fn parse_signature(result: &Value) -> Result<AlloySignature, AccountError> {
+	let hex_signature = result.as_str().ok_or_else(|| {
+		AccountError::SigningFailed("Remote signer returned no signature".to_string())
+	})?;
+	let bytes = hex::decode(hex_signature.trim_start_matches("0x"))
+		.map_err(|e| AccountError::SigningFailed(format!("Invalid signature: {}", e)))?;
+	AlloySignature::from_raw(&bytes)
+		.map_err(|e| AccountError::SigningFailed(format!("Invalid signature: {}", e)))
+}
+
+/// Account whose key lives in a remote signing service.
+#[derive(Debug)]
+pub struct RemoteAccount {
+	/// Signer forwarding requests to the service.
+	signer: Arc<RemoteSigner>,
+}
+
+impl RemoteAccount {
+	/// Creates an account backed by the given remote signer.
+	pub fn new(signer: RemoteSigner) -> Self {
+		Self {
+			signer: Arc::new(signer),
+		}
+	}
+}
+
+#[async_trait]
+impl AccountInterface for RemoteAccount {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(RemoteAccountSchema)
+	}
+
+	async fn address(&self) -> Result<Address, AccountError> {
+		Ok(Address(self.signer.address.as_slice().to_vec()))
+	}
+
+	async fn sign_transaction(&self, tx: &Transaction) -> Result<Signature, AccountError> {
+		let mut legacy_tx = legacy_transaction(tx)?;
+		let signature =
+			SolverSigner::sign_transaction(self.signer.as_ref(), &mut legacy_tx).await?;
+		Ok(signature.into())
+	}
+
+	async fn sign_message(&self, message: &[u8]) -> Result<Signature, AccountError> {
+		let signature = SolverSigner::sign_message(self.signer.as_ref(), message).await?;
+		Ok(signature.into())
+	}
+
+	async fn sign_typed_data(&self, typed_data: &TypedData) -> Result<Signature, AccountError> {
+		let signature = SolverSigner::sign_typed_data(self.signer.as_ref(), typed_data).await?;
+		Ok(signature.into())
+	}
+
+	fn signer(&self) -> AccountSigner {
+		AccountSigner::new(self.signer.clone())
+	}
+}
+
+/// Configuration schema for RemoteAccount.
+pub struct RemoteAccountSchema;
+
+impl RemoteAccountSchema {
+	/// Static validation method for use before instance creation
+	pub fn validate_config(config: &toml::Value) -> Result<(), ValidationError> {
+		let instance = Self;
+		instance.validate(config)
+	}
+}
+
+impl ConfigSchema for RemoteAccountSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![
+				Field::new("url", FieldType::String).with_validator(|value| match value.as_str() {
+					Some(url) if url.starts_with("http://") || url.starts_with("https://") => {
+						Ok(())
+					},
+					_ => Err("url must be an http:// or https:// URL".to_string()),
+				}),
+				Field::new("address", FieldType::String).with_validator(|value| {
+					match value.as_str().map(AlloyAddress::from_str) {
+						Some(Ok(_)) => Ok(()),
+						_ => Err("address must be a 20-byte hex address".to_string()),
+					}
+				}),
+			],
+			// Optional fields
+			vec![
+				Field::new(
+					"timeout_ms",
+					FieldType::Integer {
+						min: Some(1),
+						max: None,
+					},
+				),
+				Field::new(
+					"max_retries",
+					FieldType::Integer {
+						min: Some(0),
+						max: Some(10),
+					},
+				),
+				Field::new(
+					"retry_backoff_ms",
+					FieldType::Integer {
+						min: Some(0),
+						max: None,
+					},
+				),
+				Field::new("bearer_token_env", FieldType::String),
+				Field::new("tls_ca_cert", FieldType::String),
+				Field::new("tls_client_cert", FieldType::String),
+				Field::new("tls_client_key", FieldType::String),
+			],
+		);
+		schema.validate(config)?;
+
+		if config.get("tls_client_cert").is_some() != config.get("tls_client_key").is_some() {
+			return Err(ValidationError::InvalidValue {
+				field: "tls_client_cert".to_string(),
+				message: "tls_client_cert and tls_client_key must be set together".to_string(),
+			});
+		}
+
+		Ok(())
+	}
+}
+
+/// Builds the HTTP client with the configured timeout and authentication.
+fn build_client(config: &toml::Value) -> Result<reqwest::Client, AccountError> {
+	let str_field = |key: &str| config.get(key).and_then(|v| v.as_str());
+	let read_file = |key: &str, path: &str| {
+		std::fs::read(path).map_err(|e| {
+			AccountError::Implementation(format!("Failed to read {} {}: {}", key, path, e))
+		})
+	};
+
+	let timeout_ms = config
+		.get("timeout_ms")
+		.and_then(|v| v.as_integer())
+		.map(|v| v as u64)
+		.unwrap_or(DEFAULT_TIMEOUT_MS);
+	let mut builder = reqwest::Client::builder()
+		.use_rustls_tls()
+		.timeout(Duration::from_millis(timeout_ms));
+
+	if let Some(var) = str_field("bearer_token_env") {
+		let token = Zeroizing::new(std::env::var(var).map_err(|_| {
+			AccountError::Implementation(format!("Bearer token env var {} is not set", var))
+		})?);
+		let mut value = HeaderValue::from_str(&format!("Bearer {}", token.as_str()))
+			.map_err(|_| AccountError::InvalidKey("Invalid bearer token".to_string()))?;
+		value.set_sensitive(true);
+		let mut headers = HeaderMap::new();
+		headers.insert(AUTHORIZATION, value);
+		builder = builder.default_headers(headers);
+	}
+
+	if let Some(path) = str_field("tls_ca_cert") {
+		let certificate = reqwest::Certificate::from_pem(&read_file("tls_ca_cert", path)?)
+			.map_err(|e| AccountError::Implementation(format!("Invalid tls_ca_cert: {}", e)))?;
+		builder = builder.add_root_certificate(certificate);
+	}
+
+	if let (Some(cert), Some(key)) = (str_field("tls_client_cert"), str_field("tls_client_key")) {
+		let mut pem = Zeroizing::new(read_file("tls_client_cert", cert)?);
+		pem.push(b'\n');
+		pem.extend_from_slice(&Zeroizing::new(read_file("tls_client_key", key)?));
+		let identity = reqwest::Identity::from_pem(&pem).map_err(|e| {
+			AccountError::InvalidKey(format!("Invalid TLS client certificate: {}", e))
+		})?;
+		builder = builder.identity(identity);
+	}
+
+	builder
+		.build()
+		.map_err(|e| AccountError::Implementation(format!("Failed to build HTTP client: {}", e)))
+}
+
+/// Factory function to create a remote signer account from configuration.
+///
+/// Configuration parameters:
+/// - `url`: JSON-RPC endpoint of the signing service
+/// - `address`: Address of the key to sign with
+/// - `timeout_ms` (optional): Timeout of a single request (default: 5000)
+/// - `max_retries` (optional): Retries after a transient failure (default: 2)
+/// - `retry_backoff_ms` (optional): Delay before the first retry, doubled per attempt (default: 250)
+/// - `bearer_token_env` (optional): Environment variable holding a bearer token
+/// - `tls_ca_cert` (optional): PEM CA certificate used to verify the service
+/// - `tls_client_cert` / `tls_client_key` (optional): PEM client certificate and key for mTLS
+///
+/// Fails at startup if the signing service does not list `address` among
+/// its accounts.
+pub fn create_account(config: &toml::Value) -> Result<Box<dyn AccountInterface>, AccountError> {
+	RemoteAccountSchema::validate_config(config)
+		.map_err(|e| AccountError::InvalidKey(format!("Invalid configuration: {}", e)))?;
+
+	let url = config
+		.get("url")
+		.and_then(|v| v.as_str())
+		.expect("url already validated");
+	let address = config
+		.get("address")
+		.and_then(|v| v.as_str())
+		.and_then(|v| AlloyAddress::from_str(v).ok())
+		.expect("address already validated");
+	let max_retries = config
+		.get("max_retries")
+		.and_then(|v| v.as_integer())
+		.map(|v| v as u32)
+		.unwrap_or(DEFAULT_MAX_RETRIES);
+	let retry_backoff_ms = config
+		.get("retry_backoff_ms")
+		.and_then(|v| v.as_integer())
+		.map(|v| v as u64)
+		.unwrap_or(DEFAULT_RETRY_BACKOFF_MS);
+
+	let signer = RemoteSigner::new(
+		build_client(config)?,
+		url,
+		address,
+		max_retries,
+		Duration::from_millis(retry_backoff_ms),
+	);
+
+	tokio::task::block_in_place(|| {
+		tokio::runtime::Handle::current().block_on(signer.ensure_allowed())
+	})?;
+
+	Ok(Box::new(RemoteAccount::new(signer)))
+}
+
+/// Registry for the remote signer account implementation.
+pub struct Registry;
+
+impl solver_types::ImplementationRegistry for Registry {
+	const NAME: &'static str = "remote";
+	type Factory = crate::AccountFactory;
+
+	fn factory() -> Self::Factory {
+		create_account
+	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(RemoteAccountSchema)
+	}
+}
+
+impl crate::AccountRegistry for Registry {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::implementations::local::LocalWallet;
+	use alloy_consensus::{TxEip1559, TxLegacy};
+	use alloy_eips::eip2718::Encodable2718;
+	use alloy_network::{TxSigner, TxSignerSync};
+	use alloy_primitives::U256;
+	use alloy_signer::SignerSync;
+	use alloy_signer_local::PrivateKeySigner;
+	use axum::{extract::State, http::HeaderMap, response::IntoResponse, routing::post, Json};
+	use solver_types::utils::tests::builders::TransactionBuilder;
+	use std::sync::atomic::AtomicUsize;
+
+	// Test private key (FOR TESTING ONLY!)
+	const TEST_PRIVATE_KEY: &str =
+		"0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+	const OTHER_ADDRESS: &str = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8";
+
+	/// Mock signing service holding the test key.
+	#[derive(Clone)]
+	struct MockSigner {
+		key: PrivateKeySigner,
+		/// Number of requests still to be answered with HTTP 503.
+		failures: Arc<AtomicUsize>,
+		/// Bearer token required on every request, if any.
+		token: Option<&'static str>,
+	}
+
+	fn quantity(value: &Value, key: &str) -> u128 {
+		value
+			.get(key)
+			.and_then(Value::as_str)
+			.map(|v| u128::from_str_radix(v.trim_start_matches("0x"), 16).unwrap())
+			.unwrap_or_default()
+	}
+
+	fn sign_request(key: &PrivateKeySigner, request: &Value) -> String {
+		let to = match request.get("to").and_then(Value::as_str) {
+			Some(to) => TxKind::Call(to.parse().unwrap()),
+			None => TxKind::Create,
+		};
+		let input = hex::decode(request["data"].as_str().unwrap().trim_start_matches("0x"))
+			.unwrap()
+			.into();
+		let chain_id = quantity(request, "chainId") as u64;
+		let envelope: TxEnvelope = if request.get("maxFeePerGas").is_some() {
+			let mut tx = TxEip1559 {
+				chain_id,
+				nonce: quantity(request, "nonce") as u64,
+				gas_limit: quantity(request, "gas") as u64,
+				max_fee_per_gas: quantity(request, "maxFeePerGas"),
+				max_priority_fee_per_gas: quantity(request, "maxPriorityFeePerGas"),
+				to,
+				value: U256::from(quantity(request, "value")),
+				input,
+				..Default::default()
+			};
+			let signature = key.sign_transaction_sync(&mut tx).unwrap();
+			tx.into_signed(signature).into()
+		} else {
+			let mut tx = TxLegacy {
+				chain_id: Some(chain_id),
+				nonce: quantity(request, "nonce") as u64,
+				gas_price: quantity(request, "gasPrice"),
+				gas_limit: quantity(request, "gas") as u64,
+				to,
+				value: U256::from(quantity(request, "value")),
+				input,
+			};
+			let signature = key.sign_transaction_sync(&mut tx).unwrap();
+			tx.into_signed(signature).into()
+		};
+		format!("0x{}", hex::encode(envelope.encoded_2718()))
+	}
+
+	async fn handle(
+		State(mock): State<MockSigner>,
+		headers: HeaderMap,
+		Json(request): Json<Value>,
+	) -> axum::response::Response {
+		if let Some(token) = mock.token {
+			let expected = format!("Bearer {}", token);
+			if headers.get(AUTHORIZATION).and_then(|v| v.to_str().ok()) != Some(&expected) {
+				return StatusCode::UNAUTHORIZED.into_response();
+			}
+		}
+		if mock
+			.failures
+			.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+			.is_ok()
+		{
+			return StatusCode::SERVICE_UNAVAILABLE.into_response();
+		}
+
+		let params = &request["params"];
+		let result = match request["method"].as_str().unwrap() {
+			"eth_accounts" => json!([mock.key.address().to_string()]),
+			"eth_sign" => {
+				let message =
+					hex::decode(params[1].as_str().unwrap().trim_start_matches("0x")).unwrap();
+				let signature = mock.key.sign_message_sync(&message).unwrap();
+				json!(format!("0x{}", hex::encode(signature.as_bytes())))
+			},
+			"eth_signTypedData_v4" => {
+				let typed_data: TypedData = serde_json::from_value(params[1].clone()).unwrap();
+				let hash = typed_data.eip712_signing_hash().unwrap();
+				let signature = mock.key.sign_hash_sync(&hash).unwrap();
+				json!(format!("0x{}", hex::encode(signature.as_bytes())))
+			},
+			"eth_signTransaction" => json!(sign_request(&mock.key, &params[0])),
+			method => {
+				return Json(json!({
+					"jsonrpc": "2.0",
+					"id": request["id"],
+					"error": { "code": -32601, "message": format!("{} not found", method) },
+				}))
+				.into_response()
+			},
+		};
+		Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })).into_response()
+	}
+
+	/// Starts the mock signing service and returns its URL.
+	async fn start_mock(failures: usize, token: Option<&'static str>) -> String {
+		let mock = MockSigner {
+			key: TEST_PRIVATE_KEY.parse().unwrap(),
+			failures: Arc::new(AtomicUsize::new(failures)),
+			token,
+		};
+		let app = axum::Router::new()
+			.route("/", post(handle))
+			.with_state(mock);
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let url = format!("http://{}", listener.local_addr().unwrap());
+		tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+		url
+	}
+
+	fn config(url: &str, address: &str, extra: &[(&str, toml::Value)]) -> toml::Value {
+		let mut table = toml::map::Map::new();
+		table.insert("url".to_string(), toml::Value::String(url.to_string()));
+		table.insert(
+			"address".to_string(),
+			toml::Value::String(address.to_string()),
+		);
+		table.insert("retry_backoff_ms".to_string(), toml::Value::Integer(10));
+		for (key, value) in extra {
+			table.insert(key.to_string(), value.clone());
+		}
+		toml::Value::Table(table)
+	}
+
+	fn local() -> LocalWallet {
+		LocalWallet::new(TEST_PRIVATE_KEY).unwrap()
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn test_remote_signatures_match_local_key() {
+		let url = start_mock(0, None).await;
+		let local = local();
+		let address = local.signer().address().to_string();
+		let account = create_account(&config(&url, &address, &[])).unwrap();
+
+		let message = b"remote signer";
+		assert_eq!(
+			account.sign_message(message).await.unwrap().0,
+			local.sign_message(message).await.unwrap().0
+		);
+
+		let tx = TransactionBuilder::new().gas_price_gwei(21).build();
+		assert_eq!(
+			account.sign_transaction(&tx).await.unwrap().0,
+			local.sign_transaction(&tx).await.unwrap().0
+		);
+
+		let mut tx = TxEip1559 {
+			chain_id: 1,
+			nonce: 3,
+			gas_limit: 21_000,
+			max_fee_per_gas: 30_000_000_000,
+			max_priority_fee_per_gas: 1_000_000_000,
+			to: TxKind::Call(OTHER_ADDRESS.parse().unwrap()),
+			value: U256::from(1_000u64),
+			..Default::default()
+		};
+		let expected = local.signer().sign_transaction(&mut tx).await.unwrap();
+		assert_eq!(
+			account.signer().sign_transaction(&mut tx).await.unwrap(),
+			expected
+		);
+
+		let typed_data: TypedData = serde_json::from_value(json!({
+			"types": {
+				"EIP712Domain": [{ "name": "name", "type": "string" }],
+				"Quote": [{ "name": "amount", "type": "uint256" }]
+			},
+			"primaryType": "Quote",
+			"domain": { "name": "Solver" },
+			"message": { "amount": "42" }
+		}))
+		.unwrap();
+		assert_eq!(
+			account.signer().sign_typed_data(&typed_data).await.unwrap(),
+			local.signer().sign_typed_data(&typed_data).await.unwrap()
+		);
+
+		// Typed data signed through Alloy's `Signer` goes to eth_signTypedData_v4
+		let expected = local.signer().sign_typed_data(&typed_data).await.unwrap();
+		assert_eq!(
+			alloy_signer::Signer::sign_dynamic_typed_data(&account.signer(), &typed_data)
+				.await
+				.unwrap(),
+			expected
+		);
+		assert!(!account.signer().signs_digests());
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn test_rejects_address_not_allowed_by_signer() {
+		let url = start_mock(0, None).await;
+		let result = create_account(&config(&url, OTHER_ADDRESS, &[]));
+		assert!(matches!(result, Err(AccountError::InvalidKey(_))));
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn test_retries_transient_failures() {
+		let address = local().signer().address().to_string();
+
+		let url = start_mock(2, None).await;
+		assert!(create_account(&config(&url, &address, &[])).is_ok());
+
+		let url = start_mock(3, None).await;
+		assert!(create_account(&config(&url, &address, &[])).is_err());
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn test_bearer_token_authentication() {
+		let address = local().signer().address().to_string();
+		let url = start_mock(0, Some("s3cret")).await;
+		std::env::set_var("SOLVER_TEST_REMOTE_SIGNER_TOKEN", "s3cret");
+
+		// Authentication failures are not retried
+		let unauthenticated = config(&url, &address, &[("max_retries", toml::Value::Integer(0))]);
+		assert!(create_account(&unauthenticated).is_err());
+
+		let authenticated = config(
+			&url,
+			&address,
+			&[(
+				"bearer_token_env",
+				toml::Value::String("SOLVER_TEST_REMOTE_SIGNER_TOKEN".to_string()),
+			)],
+		);
+		let account = create_account(&authenticated).unwrap();
+		assert!(account.sign_message(b"hello").await.is_ok());
+	}
+
+	#[test]
+	fn test_schema_validation() {
+		let address = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+		assert!(
+			RemoteAccountSchema::validate_config(&config("https://signer:9000", address, &[]))
+				.is_ok()
+		);
+		assert!(
+			RemoteAccountSchema::validate_config(&config("signer:9000", address, &[])).is_err()
+		);
+		assert!(
+			RemoteAccountSchema::validate_config(&config("https://signer", "0x1234", &[])).is_err()
+		);
+		assert!(RemoteAccountSchema::validate_config(&config(
+			"https://signer",
+			address,
+			&[(
+				"tls_client_cert",
+				toml::Value::String("client.pem".to_string())
+			)],
+		))
+		.is_err());
+	}
+}
@@ -2,19 +2,25 @@
 //!
 //! This module provides abstractions for managing cryptographic accounts and signing operations
 //! within the OIF solver ecosystem. It defines interfaces and services for account operations
-//! such as address retrieval and transaction signing.
+//! such as address retrieval and transaction signing. Key material never leaves an account
+//! implementation; other components sign through the `AccountSigner` it provides.
 
+use alloy_dyn_abi::TypedData;
 use async_trait::async_trait;
-use solver_types::{
-	Address, ConfigSchema, ImplementationRegistry, SecretString, Signature, Transaction,
-};
+use solver_types::{Address, ConfigSchema, ImplementationRegistry, Signature, Transaction};
 use thiserror::Error;
 
 /// Re-export implementations
 pub mod implementations {
+	pub mod keystore;
 	pub mod local;
+	pub mod remote;
 }
 
+pub mod signer;
+
+pub use signer::{AccountSigner, SolverSigner};
+
 /// Errors that can occur during account operations.
 #[derive(Debug, Error)]
 pub enum AccountError {
@@ -60,11 +66,18 @@ pub trait AccountInterface: Send + Sync {
 	/// This is useful for message authentication and verification purposes.
 	async fn sign_message(&self, message: &[u8]) -> Result<Signature, AccountError>;
 
-	/// Returns the private key as a SecretString with 0x prefix.
+	/// Signs EIP-712 typed data using the account's private key.
+	///
+	/// Takes the full typed data (types, domain and message) so that signers which
+	/// display or check the payload, such as remote signing services, receive it
+	/// rather than a bare digest.
+	async fn sign_typed_data(&self, typed_data: &TypedData) -> Result<Signature, AccountError>;
+
+	/// Returns a signer handle for this account.
 	///
-	/// This is required for all account implementations as it's used by
-	/// delivery implementations for transaction signing.
-	fn get_private_key(&self) -> SecretString;
+	/// Delivery implementations and other signing paths use this handle
+	/// instead of raw key material.
+	fn signer(&self) -> AccountSigner;
 }
 
 /// Type alias for account factory functions.
@@ -84,9 +97,30 @@ pub trait AccountRegistry: ImplementationRegistry<Factory = AccountFactory> {}
 /// Returns a vector of (name, factory) tuples for all available account implementations.
 /// This is used by the factory registry to automatically register all implementations.
 pub fn get_all_implementations() -> Vec<(&'static str, AccountFactory)> {
-	use implementations::local;
+	use implementations::{keystore, local, remote};
 
-	vec![(local::Registry::NAME, local::Registry::factory())]
+	vec![
+		(local::Registry::NAME, local::Registry::factory()),
+		(keystore::Registry::NAME, keystore::Registry::factory()),
+		(remote::Registry::NAME, remote::Registry::factory()),
+	]
+}
+
+/// Get configuration schemas for all registered account implementations.
+///
+/// Returns a vector of (name, schema) tuples, used to validate configuration
+/// without constructing the implementations.
+pub fn get_all_schemas() -> Vec<(&'static str, Box<dyn ConfigSchema>)> {
+	use implementations::{keystore, local, remote};
+
+	vec![
+		(local::Registry::NAME, local::Registry::config_schema()),
+		(
+			keystore::Registry::NAME,
+			keystore::Registry::config_schema(),
+		),
+		(remote::Registry::NAME, remote::Registry::config_schema()),
+	]
 }
 
 /// Service that manages account operations.
@@ -121,10 +155,24 @@ impl AccountService {
 		self.implementation.sign_transaction(tx).await
 	}
 
-	/// Returns the private key as a SecretString.
+	/// Signs an arbitrary message using the managed account.
+	///
+	/// This method delegates to the underlying implementation's sign_message method.
+	pub async fn sign_message(&self, message: &[u8]) -> Result<Signature, AccountError> {
+		self.implementation.sign_message(message).await
+	}
+
+	/// Signs EIP-712 typed data using the managed account.
+	///
+	/// This method delegates to the underlying implementation's sign_typed_data method.
+	pub async fn sign_typed_data(&self, typed_data: &TypedData) -> Result<Signature, AccountError> {
+		self.implementation.sign_typed_data(typed_data).await
+	}
+
+	/// Returns a signer handle for the managed account.
 	///
 	/// This is used by delivery implementations for transaction signing.
-	pub fn get_private_key(&self) -> SecretString {
-		self.implementation.get_private_key()
+	pub fn signer(&self) -> AccountSigner {
+		self.implementation.signer()
 	}
 }
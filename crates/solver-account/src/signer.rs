@@ -0,0 +1,180 @@
+//! Signer abstraction used by all signing paths of the solver.
+//!
+//! Components never handle key material. They sign through an `AccountSigner`
+//! obtained from `AccountService`, which wraps any `SolverSigner` backend and
+//! plugs into Alloy wallets and Signet fill signing through the standard
+//! `Signer` and `TxSigner` traits.
+
+use crate::AccountError;
+use alloy_consensus::SignableTransaction;
+use alloy_dyn_abi::TypedData;
+use alloy_network::TxSigner;
+use alloy_primitives::{eip191_hash_message, Address as AlloyAddress, ChainId, Signature, B256};
+use async_trait::async_trait;
+use std::fmt;
+use std::sync::Arc;
+
+/// Backend capable of producing signatures for a single address.
+///
+/// Transaction, EIP-191 and EIP-712 signatures are all computed over a
+/// 32-byte digest, so signing a hash is the only operation a backend needs.
+/// This allows signers that never expose their key, such as keystores or
+/// remote signing services. Backends that refuse to sign raw digests, like
+/// most remote signing services, override the higher level methods instead.
+#[async_trait]
+pub trait SolverSigner: Send + Sync + fmt::Debug {
+	/// Returns the address of the signing key.
+	fn address(&self) -> AlloyAddress;
+
+	/// Signs a 32-byte digest.
+	async fn sign_hash(&self, hash: &B256) -> Result<Signature, AccountError>;
+
+	/// Returns whether the backend signs raw digests through `sign_hash`.
+	fn signs_digests(&self) -> bool {
+		true
+	}
+
+	/// Signs a message with the EIP-191 personal message prefix.
+	async fn sign_message(&self, message: &[u8]) -> Result<Signature, AccountError> {
+		self.sign_hash(&eip191_hash_message(message)).await
+	}
+
+	/// Signs EIP-712 typed data.
+	async fn sign_typed_data(&self, typed_data: &TypedData) -> Result<Signature, AccountError> {
+		let hash = typed_data.eip712_signing_hash().map_err(|e| {
+			AccountError::SigningFailed(format!("Failed to hash typed data: {}", e))
+		})?;
+		self.sign_hash(&hash).await
+	}
+
+	/// Signs a transaction.
+	async fn sign_transaction(
+		&self,
+		tx: &mut dyn SignableTransaction<Signature>,
+	) -> Result<Signature, AccountError> {
+		self.sign_hash(&tx.signature_hash()).await
+	}
+}
+
+/// Cloneable handle to a signer backend.
+///
+/// Implements Alloy's `Signer` and `TxSigner` so it can be used wherever a
+/// local private key signer was used before.
+#[derive(Clone)]
+pub struct AccountSigner {
+	/// The signer backend.
+	inner: Arc<dyn SolverSigner>,
+	/// Chain ID enforced on signed transactions, if any.
+	chain_id: Option<ChainId>,
+}
+
+impl AccountSigner {
+	/// Creates a handle for the given signer backend.
+	pub fn new(inner: Arc<dyn SolverSigner>) -> Self {
+		Self {
+			inner,
+			chain_id: None,
+		}
+	}
+
+	/// Returns the address of the signing key.
+	pub fn address(&self) -> AlloyAddress {
+		self.inner.address()
+	}
+
+	/// Returns whether the backend signs raw digests.
+	///
+	/// Callers that only have a digest to sign, such as Signet fill signing,
+	/// cannot use a signer for which this returns false.
+	pub fn signs_digests(&self) -> bool {
+		self.inner.signs_digests()
+	}
+
+	/// Signs a 32-byte digest.
+	pub async fn sign_hash(&self, hash: &B256) -> Result<Signature, AccountError> {
+		self.inner.sign_hash(hash).await
+	}
+
+	/// Signs a message with the EIP-191 personal message prefix.
+	pub async fn sign_message(&self, message: &[u8]) -> Result<Signature, AccountError> {
+		self.inner.sign_message(message).await
+	}
+
+	/// Signs EIP-712 typed data.
+	pub async fn sign_typed_data(&self, typed_data: &TypedData) -> Result<Signature, AccountError> {
+		self.inner.sign_typed_data(typed_data).await
+	}
+}
+
+impl fmt::Debug for AccountSigner {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("AccountSigner")
+			.field("address", &self.address())
+			.field("chain_id", &self.chain_id)
+			.finish()
+	}
+}
+
+#[async_trait]
+impl alloy_signer::Signer for AccountSigner {
+	async fn sign_hash(&self, hash: &B256) -> alloy_signer::Result<Signature> {
+		self.inner
+			.sign_hash(hash)
+			.await
+			.map_err(alloy_signer::Error::other)
+	}
+
+	async fn sign_message(&self, message: &[u8]) -> alloy_signer::Result<Signature> {
+		self.inner
+			.sign_message(message)
+			.await
+			.map_err(alloy_signer::Error::other)
+	}
+
+	async fn sign_dynamic_typed_data(
+		&self,
+		payload: &TypedData,
+	) -> alloy_signer::Result<Signature> {
+		self.inner
+			.sign_typed_data(payload)
+			.await
+			.map_err(alloy_signer::Error::other)
+	}
+
+	fn address(&self) -> AlloyAddress {
+		self.inner.address()
+	}
+
+	fn chain_id(&self) -> Option<ChainId> {
+		self.chain_id
+	}
+
+	fn set_chain_id(&mut self, chain_id: Option<ChainId>) {
+		self.chain_id = chain_id;
+	}
+}
+
+#[async_trait]
+impl TxSigner<Signature> for AccountSigner {
+	fn address(&self) -> AlloyAddress {
+		self.inner.address()
+	}
+
+	async fn sign_transaction(
+		&self,
+		tx: &mut dyn SignableTransaction<Signature>,
+	) -> alloy_signer::Result<Signature> {
+		if let Some(chain_id) = self.chain_id {
+			if !tx.set_chain_id_checked(chain_id) {
+				return Err(alloy_signer::Error::other(format!(
+					"transaction chain ID does not match signer chain ID {}",
+					chain_id
+				)));
+			}
+		}
+		self.inner
+			.sign_transaction(tx)
+			.await
+			.map_err(alloy_signer::Error::other)
+	}
+}
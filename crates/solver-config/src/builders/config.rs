@@ -141,6 +141,8 @@ impl ConfigBuilder {
 			account: AccountConfig {
 				primary: self.account_primary,
 				implementations: HashMap::new(),
+				roles: Default::default(),
+				filler_pools: HashMap::new(),
 			},
 			discovery: DiscoveryConfig {
 				implementations: HashMap::new(),
@@ -161,7 +163,13 @@ impl ConfigBuilder {
 			api: self.api,
 			gas: Some(GasConfig {
 				flows: HashMap::new(),
+				learned: Default::default(),
 			}),
+			approvals: Default::default(),
+			inventory: Default::default(),
+			rebalance: Default::default(),
+			chain_state: Default::default(),
+			simulation: Default::default(),
 		}
 	}
 }
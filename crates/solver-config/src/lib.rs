@@ -12,18 +12,28 @@
 
 pub mod builders;
 mod loader;
+pub mod reload;
 
 pub use builders::config::ConfigBuilder;
+pub use reload::{ChangeKind, ConfigChange, ConfigDiff};
 
+use arc_swap::ArcSwap;
 use regex::Regex;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use solver_types::{networks::deserialize_networks, NetworksConfig};
+use solver_types::{networks::deserialize_networks, FeeUrgency, NetworksConfig, TransactionType};
 use std::collections::HashMap;
 use std::path::Path;
 use std::str::FromStr;
+use std::sync::Arc;
 use thiserror::Error;
 
+/// Configuration shared between components that must observe hot reloads.
+///
+/// Readers call `load()` for a consistent snapshot; the config watcher swaps
+/// in a new `Config` once a reload has been validated.
+pub type SharedConfig = Arc<ArcSwap<Config>>;
+
 /// Errors that can occur during configuration operations.
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -78,6 +88,21 @@ pub struct Config {
 	/// Optional gas configuration for precomputed/overridden gas units by flow.
 	#[serde(default)]
 	pub gas: Option<GasConfig>,
+	/// Token allowance policy for settler contracts.
+	#[serde(default)]
+	pub approvals: ApprovalConfig,
+	/// Background inventory monitoring and per-token balance thresholds.
+	#[serde(default)]
+	pub inventory: InventoryConfig,
+	/// Cross-chain rebalancing of inventory between networks.
+	#[serde(default)]
+	pub rebalance: RebalanceConfig,
+	/// Background cache of chain data, fee history and solver balances.
+	#[serde(default)]
+	pub chain_state: ChainStateConfig,
+	/// Pre-flight simulation of prepare, fill and claim transactions.
+	#[serde(default)]
+	pub simulation: SimulationConfig,
 }
 
 /// Domain configuration for EIP-712 signatures in quotes.
@@ -151,12 +176,91 @@ fn default_transaction_poll_interval_seconds() -> u64 {
 }
 
 /// Configuration for account management.
+///
+/// Each entry in `implementations` is a named account. The name selects the
+/// account implementation unless the entry sets `implementation` explicitly,
+/// which allows several accounts of the same kind (e.g. multiple local keys).
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AccountConfig {
 	/// Which implementation to use as primary.
 	pub primary: String,
 	/// Map of account implementation names to their configurations.
 	pub implementations: HashMap<String, toml::Value>,
+	/// Accounts assigned to each solver role. Unset roles use the primary account.
+	#[serde(default)]
+	pub roles: AccountRoles,
+	/// Filler accounts per chain ID. Fills on a chain are spread across its pool;
+	/// chains without a pool fill from the filler role account.
+	#[serde(default, deserialize_with = "deserialize_filler_pools")]
+	pub filler_pools: HashMap<u64, Vec<String>>,
+}
+
+/// Account names assigned to the solver's key roles.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct AccountRoles {
+	/// Sends fill transactions on destination chains.
+	pub filler: Option<String>,
+	/// Solver of record for fills; sends post-fill and claim transactions.
+	pub claimer: Option<String>,
+	/// Sends prepare (open) transactions for off-chain orders.
+	pub opener: Option<String>,
+	/// Owns the token allowances granted to settlement contracts.
+	pub approver: Option<String>,
+}
+
+impl AccountConfig {
+	/// Returns the account implementation used by the named account.
+	pub fn implementation_of<'a>(&'a self, name: &'a str) -> &'a str {
+		self.implementations
+			.get(name)
+			.and_then(|config| config.get("implementation"))
+			.and_then(|value| value.as_str())
+			.unwrap_or(name)
+	}
+
+	/// Returns the account name used for fills.
+	pub fn filler(&self) -> &str {
+		self.roles.filler.as_deref().unwrap_or(&self.primary)
+	}
+
+	/// Returns the account name used for claims.
+	pub fn claimer(&self) -> &str {
+		self.roles.claimer.as_deref().unwrap_or(&self.primary)
+	}
+
+	/// Returns the account name used to open orders.
+	pub fn opener(&self) -> &str {
+		self.roles.opener.as_deref().unwrap_or(&self.primary)
+	}
+
+	/// Returns the account name used for token approvals.
+	pub fn approver(&self) -> &str {
+		self.roles.approver.as_deref().unwrap_or(&self.primary)
+	}
+
+	/// Returns the filler accounts for a chain, falling back to the filler role.
+	pub fn fillers_for(&self, chain_id: u64) -> Vec<&str> {
+		match self.filler_pools.get(&chain_id) {
+			Some(pool) if !pool.is_empty() => pool.iter().map(String::as_str).collect(),
+			_ => vec![self.filler()],
+		}
+	}
+}
+
+/// Deserializes filler pools keyed by chain ID strings.
+fn deserialize_filler_pools<'de, D>(deserializer: D) -> Result<HashMap<u64, Vec<String>>, D::Error>
+where
+	D: serde::Deserializer<'de>,
+{
+	let string_map: HashMap<String, Vec<String>> = HashMap::deserialize(deserializer)?;
+	string_map
+		.into_iter()
+		.map(|(key, value)| {
+			key.parse::<u64>()
+				.map(|chain_id| (chain_id, value))
+				.map_err(|e| serde::de::Error::custom(format!("Invalid chain_id '{}': {}", key, e)))
+		})
+		.collect()
 }
 
 /// Configuration for order discovery.
@@ -281,6 +385,375 @@ pub struct GasFlowUnits {
 	pub claim: Option<u64>,
 }
 
+/// How much allowance the solver grants settler contracts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalPolicy {
+	/// Approve the maximum amount once.
+	#[default]
+	Unlimited,
+	/// Approve exactly what the account's unconfirmed fills spend, right
+	/// before each fill.
+	Exact,
+	/// Keep the allowance topped up to a fixed cap.
+	RollingCap,
+}
+
+/// Configuration for token approvals to settler contracts.
+///
+/// Allowances are checked at startup and before every fill. An allowance is
+/// topped up when it drops below `refill_threshold_bps` of the policy target
+/// or below what the fill needs.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApprovalConfig {
+	/// Default policy for all tokens.
+	#[serde(default)]
+	pub policy: ApprovalPolicy,
+	/// Allowance target for `rolling_cap`, in whole token units.
+	#[serde(default)]
+	pub cap: Option<Decimal>,
+	/// Share of the target, in basis points, below which an allowance is topped up.
+	/// Defaults to 5000 (50%).
+	#[serde(default = "default_refill_threshold_bps")]
+	pub refill_threshold_bps: u32,
+	/// Per-token settings that take precedence over the defaults.
+	#[serde(default)]
+	pub overrides: Vec<TokenApprovalOverride>,
+}
+
+impl Default for ApprovalConfig {
+	fn default() -> Self {
+		Self {
+			policy: ApprovalPolicy::default(),
+			cap: None,
+			refill_threshold_bps: default_refill_threshold_bps(),
+			overrides: Vec::new(),
+		}
+	}
+}
+
+/// Approval settings for a single token.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TokenApprovalOverride {
+	/// Chain ID the token is deployed on.
+	pub chain_id: u64,
+	/// Token contract address.
+	pub token: String,
+	/// Policy for this token; defaults to the global policy.
+	#[serde(default)]
+	pub policy: Option<ApprovalPolicy>,
+	/// Allowance target for `rolling_cap`, in whole token units.
+	#[serde(default)]
+	pub cap: Option<Decimal>,
+	/// Whether a non-zero allowance must be set to zero before it can be changed,
+	/// as required by USDT-style tokens.
+	#[serde(default)]
+	pub reset_to_zero: bool,
+}
+
+/// Effective approval settings for a token after applying overrides.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenApprovalSettings {
+	pub policy: ApprovalPolicy,
+	pub cap: Option<Decimal>,
+	pub refill_threshold_bps: u32,
+	pub reset_to_zero: bool,
+}
+
+impl ApprovalConfig {
+	/// Returns the approval settings for a token, given as a hex address.
+	pub fn settings_for(&self, chain_id: u64, token: &str) -> TokenApprovalSettings {
+		let token = solver_types::without_0x_prefix(token);
+		let token_override = self.overrides.iter().find(|o| {
+			o.chain_id == chain_id
+				&& solver_types::without_0x_prefix(&o.token).eq_ignore_ascii_case(token)
+		});
+
+		TokenApprovalSettings {
+			policy: token_override.and_then(|o| o.policy).unwrap_or(self.policy),
+			cap: token_override.and_then(|o| o.cap).or(self.cap),
+			refill_threshold_bps: self.refill_threshold_bps,
+			reset_to_zero: token_override.is_some_and(|o| o.reset_to_zero),
+		}
+	}
+}
+
+/// Returns the default approval refill threshold in basis points.
+fn default_refill_threshold_bps() -> u32 {
+	5000 // Default to 50% of the target
+}
+
+/// Configuration for the background inventory monitor.
+///
+/// Balances of the filler accounts are snapshotted every `interval_seconds`
+/// and compared against the per-token thresholds. Quoting and filling are
+/// paused for routes whose destination inventory cannot cover a typical order.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InventoryConfig {
+	/// Whether balances are monitored. Defaults to true.
+	#[serde(default = "default_inventory_enabled")]
+	pub enabled: bool,
+	/// Seconds between balance snapshots. Defaults to 60.
+	#[serde(default = "default_inventory_interval_seconds")]
+	pub interval_seconds: u64,
+	/// How long snapshots are kept in storage, in seconds. Defaults to 7 days.
+	#[serde(default = "default_inventory_history_retention_seconds")]
+	pub history_retention_seconds: u64,
+	/// Balance thresholds per token.
+	#[serde(default)]
+	pub thresholds: Vec<InventoryThreshold>,
+}
+
+impl Default for InventoryConfig {
+	fn default() -> Self {
+		Self {
+			enabled: default_inventory_enabled(),
+			interval_seconds: default_inventory_interval_seconds(),
+			history_retention_seconds: default_inventory_history_retention_seconds(),
+			thresholds: Vec::new(),
+		}
+	}
+}
+
+/// Configuration for the background chain-state cache.
+///
+/// Each network's block number is polled every `poll_interval_ms`. When a new
+/// block arrives, the chain data, fee history and solver balances read by
+/// quoting and execution are refreshed. Snapshots older than `max_age_seconds`
+/// are refreshed on read instead of being served.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChainStateConfig {
+	/// Whether chain state is cached. Defaults to true; when false every read
+	/// goes to the RPC.
+	#[serde(default = "default_chain_state_enabled")]
+	pub enabled: bool,
+	/// Milliseconds between block number polls. Defaults to 2000.
+	#[serde(default = "default_chain_state_poll_interval_ms")]
+	pub poll_interval_ms: u64,
+	/// Oldest snapshot served to readers, in seconds. Defaults to 30.
+	#[serde(default = "default_chain_state_max_age_seconds")]
+	pub max_age_seconds: u64,
+}
+
+impl Default for ChainStateConfig {
+	fn default() -> Self {
+		Self {
+			enabled: default_chain_state_enabled(),
+			poll_interval_ms: default_chain_state_poll_interval_ms(),
+			max_age_seconds: default_chain_state_max_age_seconds(),
+		}
+	}
+}
+
+/// Returns whether chain state is cached by default.
+fn default_chain_state_enabled() -> bool {
+	true
+}
+
+/// Returns the default interval between block number polls in milliseconds.
+fn default_chain_state_poll_interval_ms() -> u64 {
+	2000
+}
+
+/// Returns the default maximum age of a served chain-state snapshot in seconds.
+fn default_chain_state_max_age_seconds() -> u64 {
+	30
+}
+
+/// Configuration for pre-flight transaction simulation.
+///
+/// Prepare, fill and claim transactions are simulated against the pending
+/// block before they are sent. Reverts that may clear up, such as a missing
+/// allowance, are retried every `retry_delay_seconds`, at most `max_retries`
+/// times before the order is failed. Claims double the delay with each retry
+/// and are never failed on a revert that may clear up or is unexpected.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SimulationConfig {
+	/// Whether transactions are simulated before sending. Defaults to true.
+	#[serde(default = "default_simulation_enabled")]
+	pub enabled: bool,
+	/// Retries of a transaction whose simulation reverted. Defaults to 3.
+	#[serde(default = "default_simulation_max_retries")]
+	pub max_retries: u32,
+	/// Seconds between retries. Defaults to 30.
+	#[serde(default = "default_simulation_retry_delay_seconds")]
+	pub retry_delay_seconds: u64,
+}
+
+impl Default for SimulationConfig {
+	fn default() -> Self {
+		Self {
+			enabled: default_simulation_enabled(),
+			max_retries: default_simulation_max_retries(),
+			retry_delay_seconds: default_simulation_retry_delay_seconds(),
+		}
+	}
+}
+
+/// Returns whether transactions are simulated by default.
+fn default_simulation_enabled() -> bool {
+	true
+}
+
+/// Returns the default number of retries after a simulated revert.
+fn default_simulation_max_retries() -> u32 {
+	3
+}
+
+/// Returns the default delay between retries in seconds.
+fn default_simulation_retry_delay_seconds() -> u64 {
+	30
+}
+
+/// Balance thresholds for a single token, or a chain's native asset.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InventoryThreshold {
+	/// Chain ID the asset is held on.
+	pub chain_id: u64,
+	/// Token contract address; omitted for the native asset.
+	#[serde(default)]
+	pub token: Option<String>,
+	/// Total balance, in whole units, below which a low-balance event is published.
+	#[serde(default)]
+	pub min_balance: Option<Decimal>,
+	/// Size of a typical order, in whole units. Routes to the chain are paused
+	/// while no filler account holds this much; for the native asset this is
+	/// the gas reserve a fill needs.
+	#[serde(default)]
+	pub typical_order: Option<Decimal>,
+}
+
+impl InventoryConfig {
+	/// Returns the thresholds for a token given as a hex address, or for the
+	/// native asset when `token` is `None`.
+	pub fn threshold_for(&self, chain_id: u64, token: Option<&str>) -> Option<&InventoryThreshold> {
+		let token = token.map(solver_types::without_0x_prefix);
+		self.thresholds.iter().find(|t| {
+			t.chain_id == chain_id
+				&& match (t.token.as_deref(), token) {
+					(Some(configured), Some(token)) => {
+						solver_types::without_0x_prefix(configured).eq_ignore_ascii_case(token)
+					},
+					(None, None) => true,
+					_ => false,
+				}
+		})
+	}
+}
+
+/// Returns whether inventory monitoring is enabled by default.
+fn default_inventory_enabled() -> bool {
+	true
+}
+
+/// Returns the default interval between inventory snapshots in seconds.
+fn default_inventory_interval_seconds() -> u64 {
+	60 // Default to 1 minute
+}
+
+/// Returns the default retention of inventory snapshots in seconds.
+fn default_inventory_history_retention_seconds() -> u64 {
+	7 * 24 * 60 * 60 // Default to 7 days
+}
+
+/// Configuration for cross-chain inventory rebalancing.
+///
+/// Every `interval_seconds` the balances of each asset are compared against
+/// their target allocation and funds are moved between chains through the
+/// configured bridge implementations.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RebalanceConfig {
+	/// Whether funds are rebalanced. Defaults to false.
+	#[serde(default)]
+	pub enabled: bool,
+	/// Seconds between rebalancing runs. Defaults to 300.
+	#[serde(default = "default_rebalance_interval_seconds")]
+	pub interval_seconds: u64,
+	/// Which bridge implementation to try first.
+	#[serde(default)]
+	pub primary: Option<String>,
+	/// Map of bridge implementation names to their configurations.
+	#[serde(default)]
+	pub implementations: HashMap<String, toml::Value>,
+	/// Assets to rebalance and their target allocations.
+	#[serde(default)]
+	pub assets: Vec<RebalanceAsset>,
+}
+
+impl Default for RebalanceConfig {
+	fn default() -> Self {
+		Self {
+			enabled: false,
+			interval_seconds: default_rebalance_interval_seconds(),
+			primary: None,
+			implementations: HashMap::new(),
+			assets: Vec::new(),
+		}
+	}
+}
+
+impl RebalanceConfig {
+	/// Returns the bridge implementation names in the order they are tried:
+	/// the primary first, then the rest by name.
+	pub fn bridge_order(&self) -> Vec<String> {
+		let mut names: Vec<String> = self.implementations.keys().cloned().collect();
+		names.sort();
+		if let Some(primary) = &self.primary {
+			if let Some(index) = names.iter().position(|name| name == primary) {
+				let primary = names.remove(index);
+				names.insert(0, primary);
+			}
+		}
+		names
+	}
+}
+
+/// An asset held on several chains and its target allocation.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RebalanceAsset {
+	/// Asset name used in logs and events, e.g. "USDC".
+	pub symbol: String,
+	/// Smallest transfer worth making, in whole units.
+	pub min_transfer: Decimal,
+	/// Largest single transfer, in whole units.
+	pub max_transfer: Decimal,
+	/// Minimum seconds between transfers on the same route. Defaults to 3600.
+	#[serde(default = "default_rebalance_cooldown_seconds")]
+	pub cooldown_seconds: u64,
+	/// Deviation from the target, in basis points of the total, that is
+	/// tolerated before funds are moved. Defaults to 500.
+	#[serde(default = "default_rebalance_tolerance_bps")]
+	pub tolerance_bps: u32,
+	/// Share of the asset each chain should hold.
+	pub allocations: Vec<RebalanceAllocation>,
+}
+
+/// Target share of an asset on a single chain.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RebalanceAllocation {
+	/// Chain ID the asset is held on.
+	pub chain_id: u64,
+	/// Token contract address on the chain.
+	pub token: String,
+	/// Share of the total the chain should hold, in basis points.
+	pub target_bps: u32,
+}
+
+/// Returns the default interval between rebalancing runs in seconds.
+fn default_rebalance_interval_seconds() -> u64 {
+	300 // Default to 5 minutes
+}
+
+/// Returns the default cooldown between transfers on a route in seconds.
+fn default_rebalance_cooldown_seconds() -> u64 {
+	3600 // Default to 1 hour
+}
+
+/// Returns the default rebalancing tolerance in basis points.
+fn default_rebalance_tolerance_bps() -> u32 {
+	500 // Default to 5% of the total
+}
+
 /// Configuration for pricing services.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PricingConfig {
@@ -296,6 +769,132 @@ pub struct GasConfig {
 	/// Map of flow key -> GasFlowUnits
 	/// Example keys: "permit2_escrow", "compact_resource_lock"
 	pub flows: HashMap<String, GasFlowUnits>,
+	/// Gas units learned from confirmed transaction receipts.
+	#[serde(default)]
+	pub learned: LearnedGasConfig,
+	/// EIP-1559 fees applied to the solver's transactions.
+	#[serde(default)]
+	pub fees: FeeStrategyConfig,
+}
+
+/// Configuration for gas units learned from confirmed transactions.
+///
+/// The solver records the gas used by its own transactions per flow, chain,
+/// transaction type and number of outputs. Once a key has enough samples, the
+/// chosen percentile replaces the configured flow units when quoting and
+/// checking profitability.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LearnedGasConfig {
+	/// Whether learned gas units are used for estimates. Defaults to true.
+	/// Samples are recorded either way.
+	#[serde(default = "default_learned_gas_enabled")]
+	pub enabled: bool,
+	/// Percentile of recorded gas used for estimates, 1-100. Defaults to 90.
+	#[serde(default = "default_learned_gas_percentile")]
+	pub percentile: u8,
+	/// Samples a key needs before learned units replace configured ones.
+	/// Defaults to 20.
+	#[serde(default = "default_learned_gas_min_samples")]
+	pub min_samples: usize,
+	/// Most recent samples kept per key. Defaults to 200.
+	#[serde(default = "default_learned_gas_window")]
+	pub window: usize,
+}
+
+impl Default for LearnedGasConfig {
+	fn default() -> Self {
+		Self {
+			enabled: default_learned_gas_enabled(),
+			percentile: default_learned_gas_percentile(),
+			min_samples: default_learned_gas_min_samples(),
+			window: default_learned_gas_window(),
+		}
+	}
+}
+
+/// Returns whether learned gas units are used by default.
+fn default_learned_gas_enabled() -> bool {
+	true
+}
+
+/// Returns the default percentile of learned gas units.
+fn default_learned_gas_percentile() -> u8 {
+	90
+}
+
+/// Returns the default number of samples before learned gas units are used.
+fn default_learned_gas_min_samples() -> usize {
+	20
+}
+
+/// Returns the default number of gas samples kept per key.
+fn default_learned_gas_window() -> usize {
+	200
+}
+
+/// Configuration for the fees set on the solver's transactions.
+///
+/// Fees are estimated per chain from recent fee history. Each transaction is
+/// priced at the urgency of its step: fills race other solvers, while claims
+/// can wait for cheaper blocks. Chains without EIP-1559 use their legacy gas
+/// price.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FeeStrategyConfig {
+	/// Whether fees are set explicitly. Defaults to true; when false they are
+	/// left to the provider.
+	#[serde(default = "default_fee_strategy_enabled")]
+	pub enabled: bool,
+	/// Urgency of prepare transactions. Defaults to medium.
+	#[serde(default = "default_prepare_urgency")]
+	pub prepare: FeeUrgency,
+	/// Urgency of fill, post-fill and relay transactions. Defaults to high.
+	#[serde(default = "default_fill_urgency")]
+	pub fill: FeeUrgency,
+	/// Urgency of pre-claim and claim transactions. Defaults to low.
+	#[serde(default = "default_claim_urgency")]
+	pub claim: FeeUrgency,
+}
+
+impl FeeStrategyConfig {
+	/// Returns the urgency transactions of a type are priced at.
+	pub fn urgency(&self, tx_type: TransactionType) -> FeeUrgency {
+		match tx_type {
+			TransactionType::Prepare => self.prepare,
+			TransactionType::Fill | TransactionType::PostFill | TransactionType::Relay => self.fill,
+			TransactionType::PreClaim | TransactionType::Claim => self.claim,
+		}
+	}
+}
+
+impl Default for FeeStrategyConfig {
+	fn default() -> Self {
+		Self {
+			enabled: default_fee_strategy_enabled(),
+			prepare: default_prepare_urgency(),
+			fill: default_fill_urgency(),
+			claim: default_claim_urgency(),
+		}
+	}
+}
+
+/// Returns whether transaction fees are set explicitly by default.
+fn default_fee_strategy_enabled() -> bool {
+	true
+}
+
+/// Returns the default urgency of prepare transactions.
+fn default_prepare_urgency() -> FeeUrgency {
+	FeeUrgency::Medium
+}
+
+/// Returns the default urgency of fill transactions.
+fn default_fill_urgency() -> FeeUrgency {
+	FeeUrgency::High
+}
+
+/// Returns the default urgency of claim transactions.
+fn default_claim_urgency() -> FeeUrgency {
+	FeeUrgency::Low
 }
 
 /// Configuration for quote generation parameters.
@@ -430,6 +1029,21 @@ impl Config {
 		loader.load_config(file_name).await
 	}
 
+	/// Reloads configuration from a file and diffs it against this configuration.
+	///
+	/// The new configuration goes through the same loading and validation as
+	/// at startup. Callers decide whether to apply it based on the returned diff.
+	pub async fn reload_from_file(&self, path: &str) -> Result<(Self, ConfigDiff), ConfigError> {
+		let candidate = Self::from_file(path).await?;
+		let diff = ConfigDiff::between(self, &candidate)?;
+		Ok((candidate, diff))
+	}
+
+	/// Wraps this configuration for sharing across hot-reload aware components.
+	pub fn into_shared(self) -> SharedConfig {
+		Arc::new(ArcSwap::from_pointee(self))
+	}
+
 	/// Validates the configuration to ensure all required fields are properly set.
 	///
 	/// This method performs comprehensive validation across all configuration sections:
@@ -547,6 +1161,7 @@ impl Config {
 				"Account implementation cannot be empty".into(),
 			));
 		}
+		self.validate_account_roles()?;
 
 		// Validate discovery config
 		if self.discovery.implementations.is_empty() {
@@ -606,12 +1221,324 @@ impl Config {
 		// 	}
 		// }
 
+		// Validate token approval policy
+		self.validate_approvals()?;
+
+		// Validate inventory thresholds
+		self.validate_inventory()?;
+
+		// Validate rebalancing targets
+		self.validate_rebalance()?;
+
+		// Validate learned gas settings
+		self.validate_learned_gas()?;
+
+		// Validate chain-state cache settings
+		if self.chain_state.poll_interval_ms == 0 {
+			return Err(ConfigError::Validation(
+				"chain_state.poll_interval_ms must be greater than 0".into(),
+			));
+		}
+
+		// Validate pre-flight simulation settings
+		if self.simulation.retry_delay_seconds == 0 {
+			return Err(ConfigError::Validation(
+				"simulation.retry_delay_seconds must be greater than 0".into(),
+			));
+		}
+
 		// Validate settlement configurations and coverage
 		self.validate_settlement_coverage()?;
 
 		Ok(())
 	}
 
+	/// Validates the inventory monitor settings and per-token thresholds.
+	fn validate_inventory(&self) -> Result<(), ConfigError> {
+		let inventory = &self.inventory;
+		if inventory.interval_seconds == 0 {
+			return Err(ConfigError::Validation(
+				"inventory.interval_seconds must be greater than 0".into(),
+			));
+		}
+
+		for threshold in &inventory.thresholds {
+			let asset = threshold.token.as_deref().unwrap_or("native asset");
+			if !self.networks.contains_key(&threshold.chain_id) {
+				return Err(ConfigError::Validation(format!(
+					"Inventory threshold references unknown network {}",
+					threshold.chain_id
+				)));
+			}
+			if let Some(token) = &threshold.token {
+				if solver_types::parse_address(token).is_err() {
+					return Err(ConfigError::Validation(format!(
+						"Inventory threshold has invalid token address '{}'",
+						token
+					)));
+				}
+			}
+			if threshold.min_balance.is_none() && threshold.typical_order.is_none() {
+				return Err(ConfigError::Validation(format!(
+					"Inventory threshold for {} on network {} needs min_balance or typical_order",
+					asset, threshold.chain_id
+				)));
+			}
+			if threshold.min_balance.is_some_and(|v| v < Decimal::ZERO)
+				|| threshold.typical_order.is_some_and(|v| v < Decimal::ZERO)
+			{
+				return Err(ConfigError::Validation(format!(
+					"Inventory threshold for {} on network {} cannot be negative",
+					asset, threshold.chain_id
+				)));
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Returns the learned gas settings, or their defaults without a `[gas]` section.
+	pub fn learned_gas(&self) -> LearnedGasConfig {
+		self.gas
+			.as_ref()
+			.map(|gas| gas.learned.clone())
+			.unwrap_or_default()
+	}
+
+	/// Returns the fee strategy, or its defaults without a `[gas]` section.
+	pub fn fee_strategy(&self) -> FeeStrategyConfig {
+		self.gas
+			.as_ref()
+			.map(|gas| gas.fees.clone())
+			.unwrap_or_default()
+	}
+
+	/// Validates the percentile and sample counts of learned gas units.
+	fn validate_learned_gas(&self) -> Result<(), ConfigError> {
+		let Some(learned) = self.gas.as_ref().map(|gas| &gas.learned) else {
+			return Ok(());
+		};
+		if learned.percentile == 0 || learned.percentile > 100 {
+			return Err(ConfigError::Validation(
+				"gas.learned.percentile must be between 1 and 100".into(),
+			));
+		}
+		if learned.min_samples == 0 {
+			return Err(ConfigError::Validation(
+				"gas.learned.min_samples must be greater than 0".into(),
+			));
+		}
+		if learned.window < learned.min_samples {
+			return Err(ConfigError::Validation(
+				"gas.learned.window cannot be smaller than gas.learned.min_samples".into(),
+			));
+		}
+		Ok(())
+	}
+
+	/// Validates the rebalancing bridges and per-asset target allocations.
+	fn validate_rebalance(&self) -> Result<(), ConfigError> {
+		let rebalance = &self.rebalance;
+		if rebalance.interval_seconds == 0 {
+			return Err(ConfigError::Validation(
+				"rebalance.interval_seconds must be greater than 0".into(),
+			));
+		}
+		if rebalance.enabled && rebalance.implementations.is_empty() {
+			return Err(ConfigError::Validation(
+				"At least one bridge implementation required when rebalancing is enabled".into(),
+			));
+		}
+		if let Some(primary) = &rebalance.primary {
+			if !rebalance.implementations.contains_key(primary) {
+				return Err(ConfigError::Validation(format!(
+					"Primary bridge '{}' not found in rebalance.implementations",
+					primary
+				)));
+			}
+		}
+
+		for asset in &rebalance.assets {
+			if asset.allocations.len() < 2 {
+				return Err(ConfigError::Validation(format!(
+					"Rebalance asset {} needs allocations on at least two networks",
+					asset.symbol
+				)));
+			}
+			if asset.min_transfer <= Decimal::ZERO || asset.min_transfer > asset.max_transfer {
+				return Err(ConfigError::Validation(format!(
+					"Rebalance asset {} needs 0 < min_transfer <= max_transfer",
+					asset.symbol
+				)));
+			}
+			if asset.tolerance_bps > 10_000 {
+				return Err(ConfigError::Validation(format!(
+					"Rebalance asset {} tolerance_bps cannot exceed 10000",
+					asset.symbol
+				)));
+			}
+
+			let mut decimals = None;
+			let mut chains = Vec::new();
+			for allocation in &asset.allocations {
+				if chains.contains(&allocation.chain_id) {
+					return Err(ConfigError::Validation(format!(
+						"Rebalance asset {} has more than one allocation on network {}",
+						asset.symbol, allocation.chain_id
+					)));
+				}
+				chains.push(allocation.chain_id);
+
+				let network = self.networks.get(&allocation.chain_id).ok_or_else(|| {
+					ConfigError::Validation(format!(
+						"Rebalance asset {} references unknown network {}",
+						asset.symbol, allocation.chain_id
+					))
+				})?;
+				let address = solver_types::parse_address(&allocation.token).map_err(|_| {
+					ConfigError::Validation(format!(
+						"Rebalance asset {} has invalid token address '{}'",
+						asset.symbol, allocation.token
+					))
+				})?;
+				let token = network
+					.tokens
+					.iter()
+					.find(|t| t.address == address)
+					.ok_or_else(|| {
+						ConfigError::Validation(format!(
+							"Rebalance asset {} token {} is not configured on network {}",
+							asset.symbol, allocation.token, allocation.chain_id
+						))
+					})?;
+				if decimals.is_some_and(|d| d != token.decimals) {
+					return Err(ConfigError::Validation(format!(
+						"Rebalance asset {} must use the same decimals on every network",
+						asset.symbol
+					)));
+				}
+				decimals = Some(token.decimals);
+			}
+
+			let total_bps: u32 = asset.allocations.iter().map(|a| a.target_bps).sum();
+			if total_bps != 10_000 {
+				return Err(ConfigError::Validation(format!(
+					"Rebalance asset {} allocations must sum to 10000 bps, got {}",
+					asset.symbol, total_bps
+				)));
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Validates the approval policy and its per-token overrides.
+	fn validate_approvals(&self) -> Result<(), ConfigError> {
+		let approvals = &self.approvals;
+		if approvals.refill_threshold_bps > 10_000 {
+			return Err(ConfigError::Validation(
+				"approvals.refill_threshold_bps cannot exceed 10000".into(),
+			));
+		}
+		if approvals.policy == ApprovalPolicy::RollingCap && approvals.cap.is_none() {
+			// Every token must then have its own cap
+			for (chain_id, network) in &self.networks {
+				for token in &network.tokens {
+					let address = token.address.to_string();
+					if approvals.settings_for(*chain_id, &address).cap.is_none() {
+						return Err(ConfigError::Validation(format!(
+							"approvals.cap is required for rolling_cap policy (token {} on network {})",
+							token.symbol, chain_id
+						)));
+					}
+				}
+			}
+		}
+
+		for token_override in &approvals.overrides {
+			if !self.networks.contains_key(&token_override.chain_id) {
+				return Err(ConfigError::Validation(format!(
+					"Approval override references unknown network {}",
+					token_override.chain_id
+				)));
+			}
+			if solver_types::parse_address(&token_override.token).is_err() {
+				return Err(ConfigError::Validation(format!(
+					"Approval override has invalid token address '{}'",
+					token_override.token
+				)));
+			}
+			let policy = token_override.policy.unwrap_or(approvals.policy);
+			if policy == ApprovalPolicy::RollingCap
+				&& token_override.cap.or(approvals.cap).is_none()
+			{
+				return Err(ConfigError::Validation(format!(
+					"Approval override for token {} on network {} needs a cap for rolling_cap policy",
+					token_override.token, token_override.chain_id
+				)));
+			}
+			if token_override.cap.is_some_and(|cap| cap <= Decimal::ZERO) {
+				return Err(ConfigError::Validation(format!(
+					"Approval override for token {} on network {} must have a positive cap",
+					token_override.token, token_override.chain_id
+				)));
+			}
+		}
+		if approvals.cap.is_some_and(|cap| cap <= Decimal::ZERO) {
+			return Err(ConfigError::Validation(
+				"approvals.cap must be positive".into(),
+			));
+		}
+
+		Ok(())
+	}
+
+	/// Validates that account roles and filler pools reference configured accounts.
+	fn validate_account_roles(&self) -> Result<(), ConfigError> {
+		let account = &self.account;
+		let roles = [
+			("filler", &account.roles.filler),
+			("claimer", &account.roles.claimer),
+			("opener", &account.roles.opener),
+			("approver", &account.roles.approver),
+		];
+		for (role, name) in roles {
+			if let Some(name) = name {
+				if !account.implementations.contains_key(name) {
+					return Err(ConfigError::Validation(format!(
+						"Account role '{}' references unknown account '{}'",
+						role, name
+					)));
+				}
+			}
+		}
+
+		for (chain_id, pool) in &account.filler_pools {
+			if !self.networks.contains_key(chain_id) {
+				return Err(ConfigError::Validation(format!(
+					"Filler pool references unknown network {}",
+					chain_id
+				)));
+			}
+			if pool.is_empty() {
+				return Err(ConfigError::Validation(format!(
+					"Filler pool for network {} cannot be empty",
+					chain_id
+				)));
+			}
+			for name in pool {
+				if !account.implementations.contains_key(name) {
+					return Err(ConfigError::Validation(format!(
+						"Filler pool for network {} references unknown account '{}'",
+						chain_id, name
+					)));
+				}
+			}
+		}
+
+		Ok(())
+	}
+
 	/// Validates settlement implementation coverage.
 	///
 	/// # Returns
@@ -1114,4 +2041,420 @@ network_ids = [1, 2]
 			.to_string()
 			.contains("Order standard 'eip9999' has no settlement implementations"));
 	}
+
+	fn config_with_accounts(account_section: &str) -> String {
+		format!(
+			r#"
+[solver]
+id = "test"
+min_profitability_pct = 1.0
+
+[networks.1]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.1.rpc_urls]]
+http = "http://localhost:8545"
+[[networks.1.tokens]]
+address = "0xabcdef1234567890abcdef1234567890abcdef12"
+symbol = "TEST"
+decimals = 18
+
+[networks.2]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.2.rpc_urls]]
+http = "http://localhost:8546"
+[[networks.2.tokens]]
+address = "0xabcdef1234567890abcdef1234567890abcdef12"
+symbol = "TEST"
+decimals = 18
+
+[storage]
+primary = "memory"
+cleanup_interval_seconds = 3600
+[storage.implementations.memory]
+
+[delivery]
+[delivery.implementations.test]
+
+{}
+
+[discovery]
+[discovery.implementations.test]
+
+[order]
+[order.implementations.test]
+[order.strategy]
+primary = "simple"
+[order.strategy.implementations.simple]
+
+[settlement]
+[settlement.implementations.test]
+order = "test"
+network_ids = [1, 2]
+"#,
+			account_section
+		)
+	}
+
+	#[test]
+	fn test_account_roles_and_filler_pools() {
+		let config: Config = config_with_accounts(
+			r#"
+[account]
+primary = "local"
+filler_pools = { "2" = ["filler_a", "filler_b"] }
+[account.roles]
+claimer = "claims"
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+[account.implementations.claims]
+implementation = "local"
+private_key = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d"
+[account.implementations.filler_a]
+implementation = "keystore"
+[account.implementations.filler_b]
+implementation = "remote"
+"#,
+		)
+		.parse()
+		.unwrap();
+
+		let account = &config.account;
+		assert_eq!(account.claimer(), "claims");
+		assert_eq!(account.filler(), "local");
+		assert_eq!(account.opener(), "local");
+		assert_eq!(account.implementation_of("claims"), "local");
+		assert_eq!(account.implementation_of("filler_b"), "remote");
+		assert_eq!(account.implementation_of("local"), "local");
+		assert_eq!(account.fillers_for(2), vec!["filler_a", "filler_b"]);
+		assert_eq!(account.fillers_for(1), vec!["local"]);
+	}
+
+	#[test]
+	fn test_account_role_must_reference_configured_account() {
+		let result = Config::from_str(&config_with_accounts(
+			r#"
+[account]
+primary = "local"
+[account.roles]
+approver = "missing"
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+"#,
+		));
+		let err = result.unwrap_err().to_string();
+		assert!(err.contains("Account role 'approver' references unknown account 'missing'"));
+
+		let result = Config::from_str(&config_with_accounts(
+			r#"
+[account]
+primary = "local"
+filler_pools = { "1" = ["local", "other"] }
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+"#,
+		));
+		let err = result.unwrap_err().to_string();
+		assert!(err.contains("Filler pool for network 1 references unknown account 'other'"));
+	}
+
+	#[test]
+	fn test_approval_policy_and_overrides() {
+		let config = Config::from_str(&config_with_accounts(
+			r#"
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+
+[approvals]
+policy = "rolling_cap"
+cap = "1000"
+refill_threshold_bps = 2500
+[[approvals.overrides]]
+chain_id = 1
+token = "0xABCDEF1234567890abcdef1234567890abcdef12"
+policy = "exact"
+reset_to_zero = true
+"#,
+		))
+		.unwrap();
+
+		let settings = config
+			.approvals
+			.settings_for(1, "0xabcdef1234567890abcdef1234567890abcdef12");
+		assert_eq!(settings.policy, ApprovalPolicy::Exact);
+		assert!(settings.reset_to_zero);
+		assert_eq!(settings.refill_threshold_bps, 2500);
+
+		let settings = config
+			.approvals
+			.settings_for(2, "0xabcdef1234567890abcdef1234567890abcdef12");
+		assert_eq!(settings.policy, ApprovalPolicy::RollingCap);
+		assert_eq!(settings.cap, Some(Decimal::from(1000)));
+		assert!(!settings.reset_to_zero);
+
+		// Unset approvals default to unlimited
+		let config = Config::from_str(&config_with_accounts(
+			r#"
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+"#,
+		))
+		.unwrap();
+		assert_eq!(config.approvals.policy, ApprovalPolicy::Unlimited);
+	}
+
+	#[test]
+	fn test_rolling_cap_requires_cap() {
+		let result = Config::from_str(&config_with_accounts(
+			r#"
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+
+[approvals]
+policy = "rolling_cap"
+"#,
+		));
+		let err = result.unwrap_err().to_string();
+		assert!(err.contains("approvals.cap is required for rolling_cap policy"));
+	}
+
+	#[test]
+	fn test_inventory_thresholds() {
+		let config = Config::from_str(&config_with_accounts(
+			r#"
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+
+[inventory]
+interval_seconds = 30
+[[inventory.thresholds]]
+chain_id = 2
+token = "0xABCDEF1234567890abcdef1234567890abcdef12"
+min_balance = "5000"
+typical_order = "1000"
+[[inventory.thresholds]]
+chain_id = 2
+min_balance = "0.5"
+"#,
+		))
+		.unwrap();
+
+		assert!(config.inventory.enabled);
+		assert_eq!(config.inventory.interval_seconds, 30);
+		let token = config
+			.inventory
+			.threshold_for(2, Some("abcdef1234567890abcdef1234567890abcdef12"))
+			.unwrap();
+		assert_eq!(token.typical_order, Some(Decimal::from(1000)));
+		let native = config.inventory.threshold_for(2, None).unwrap();
+		assert_eq!(native.min_balance, Some(Decimal::new(5, 1)));
+		assert!(native.typical_order.is_none());
+		assert!(config
+			.inventory
+			.threshold_for(1, Some("abcdef1234567890abcdef1234567890abcdef12"))
+			.is_none());
+
+		let result = Config::from_str(&config_with_accounts(
+			r#"
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+
+[[inventory.thresholds]]
+chain_id = 1
+token = "0xABCDEF1234567890abcdef1234567890abcdef12"
+"#,
+		));
+		let err = result.unwrap_err().to_string();
+		assert!(err.contains("needs min_balance or typical_order"));
+	}
+
+	#[test]
+	fn test_rebalance_allocations() {
+		let account = r#"
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+"#;
+		let config = Config::from_str(&config_with_accounts(&format!(
+			r#"{}
+[rebalance]
+enabled = true
+primary = "eip7683"
+[rebalance.implementations.mock]
+[rebalance.implementations.eip7683]
+[[rebalance.assets]]
+symbol = "TEST"
+min_transfer = "10"
+max_transfer = "1000"
+[[rebalance.assets.allocations]]
+chain_id = 1
+token = "0xabcdef1234567890abcdef1234567890abcdef12"
+target_bps = 4000
+[[rebalance.assets.allocations]]
+chain_id = 2
+token = "0xabcdef1234567890abcdef1234567890abcdef12"
+target_bps = 6000
+"#,
+			account
+		)))
+		.unwrap();
+
+		assert!(config.rebalance.enabled);
+		assert_eq!(config.rebalance.interval_seconds, 300);
+		assert_eq!(config.rebalance.bridge_order(), vec!["eip7683", "mock"]);
+		let asset = &config.rebalance.assets[0];
+		assert_eq!(asset.cooldown_seconds, 3600);
+		assert_eq!(asset.tolerance_bps, 500);
+		assert_eq!(asset.allocations[1].target_bps, 6000);
+
+		let result = Config::from_str(&config_with_accounts(&format!(
+			r#"{}
+[rebalance]
+[rebalance.implementations.mock]
+[[rebalance.assets]]
+symbol = "TEST"
+min_transfer = "10"
+max_transfer = "1000"
+[[rebalance.assets.allocations]]
+chain_id = 1
+token = "0xabcdef1234567890abcdef1234567890abcdef12"
+target_bps = 4000
+[[rebalance.assets.allocations]]
+chain_id = 2
+token = "0xabcdef1234567890abcdef1234567890abcdef12"
+target_bps = 5000
+"#,
+			account
+		)));
+		let err = result.unwrap_err().to_string();
+		assert!(err.contains("must sum to 10000 bps"));
+	}
+
+	#[test]
+	fn test_learned_gas_config() {
+		let account = r#"
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+"#;
+		let config = Config::from_str(&config_with_accounts(&format!(
+			r#"{}
+[gas.flows.permit2_escrow]
+fill = 76068
+[gas.learned]
+percentile = 75
+"#,
+			account
+		)))
+		.unwrap();
+
+		let learned = &config.gas.as_ref().unwrap().learned;
+		assert!(learned.enabled);
+		assert_eq!(learned.percentile, 75);
+		assert_eq!(learned.min_samples, 20);
+		assert_eq!(learned.window, 200);
+
+		let result = Config::from_str(&config_with_accounts(&format!(
+			r#"{}
+[gas.flows.permit2_escrow]
+fill = 76068
+[gas.learned]
+min_samples = 50
+window = 10
+"#,
+			account
+		)));
+		let err = result.unwrap_err().to_string();
+		assert!(err.contains("gas.learned.window cannot be smaller"));
+	}
+
+	#[test]
+	fn test_fee_strategy_config() {
+		let account = r#"
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+"#;
+		let config = Config::from_str(&config_with_accounts(account)).unwrap();
+		let fees = config.fee_strategy();
+		assert!(fees.enabled);
+		assert_eq!(fees.urgency(TransactionType::Fill), FeeUrgency::High);
+		assert_eq!(fees.urgency(TransactionType::Claim), FeeUrgency::Low);
+
+		let config = Config::from_str(&config_with_accounts(&format!(
+			r#"{}
+[gas.flows.permit2_escrow]
+fill = 76068
+[gas.fees]
+fill = "medium"
+"#,
+			account
+		)))
+		.unwrap();
+		let fees = config.fee_strategy();
+		assert_eq!(fees.urgency(TransactionType::PostFill), FeeUrgency::Medium);
+		assert_eq!(fees.urgency(TransactionType::Prepare), FeeUrgency::Medium);
+	}
+
+	#[test]
+	fn test_chain_state_config() {
+		let account = r#"
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+"#;
+		let config = Config::from_str(&config_with_accounts(account)).unwrap();
+		assert!(config.chain_state.enabled);
+		assert_eq!(config.chain_state.poll_interval_ms, 2000);
+		assert_eq!(config.chain_state.max_age_seconds, 30);
+
+		let result = Config::from_str(&config_with_accounts(&format!(
+			r#"{}
+[chain_state]
+poll_interval_ms = 0
+"#,
+			account
+		)));
+		let err = result.unwrap_err().to_string();
+		assert!(err.contains("chain_state.poll_interval_ms must be greater than 0"));
+	}
+
+	#[test]
+	fn test_simulation_config() {
+		let account = r#"
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+"#;
+		let config = Config::from_str(&config_with_accounts(account)).unwrap();
+		assert!(config.simulation.enabled);
+		assert_eq!(config.simulation.max_retries, 3);
+		assert_eq!(config.simulation.retry_delay_seconds, 30);
+
+		let result = Config::from_str(&config_with_accounts(&format!(
+			r#"{}
+[simulation]
+retry_delay_seconds = 0
+"#,
+			account
+		)));
+		let err = result.unwrap_err().to_string();
+		assert!(err.contains("simulation.retry_delay_seconds must be greater than 0"));
+	}
 }
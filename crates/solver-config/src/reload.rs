@@ -0,0 +1,318 @@
+//! Configuration diffing for hot reloads.
+//!
+//! When the configuration file changes while the solver is running, the new
+//! configuration is compared against the active one. Changes limited to
+//! hot-reloadable sections can be swapped in atomically; anything else needs a
+//! restart and is reported back as a diff so the operator can see why the
+//! reload was rejected.
+
+use crate::{Config, ConfigError};
+use serde_json::Value;
+use std::fmt;
+
+/// Configuration paths that can be swapped in without restarting the solver.
+///
+/// Paths are dot-separated and `*` matches a single segment. A change is hot
+/// when its path equals or is nested under one of these prefixes.
+///
+/// `api.rate_limiting` is not listed: the API server does not enforce it yet,
+/// so there is no running limiter to swap and changing it needs a restart.
+const HOT_RELOADABLE_PATHS: &[&str] = &[
+	"solver.min_profitability_pct",
+	"order.strategy",
+	"networks.*.tokens",
+	"settlement.implementations.*.oracles",
+	"settlement.implementations.*.routes",
+	"settlement.implementations.*.oracle_selection_strategy",
+	"api.quote",
+	"gas",
+	"approvals",
+	"inventory",
+	"rebalance.enabled",
+	"rebalance.interval_seconds",
+	"rebalance.assets",
+	"chain_state",
+	"simulation",
+];
+
+/// Kind of change detected at a configuration path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+	Added,
+	Removed,
+	Modified,
+}
+
+/// A single changed configuration path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChange {
+	/// Dot-separated path of the changed value (e.g. `networks.1.tokens`).
+	pub path: String,
+	/// Whether the value was added, removed or modified.
+	pub kind: ChangeKind,
+}
+
+impl fmt::Display for ConfigChange {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let marker = match self.kind {
+			ChangeKind::Added => '+',
+			ChangeKind::Removed => '-',
+			ChangeKind::Modified => '~',
+		};
+		write!(f, "{} {}", marker, self.path)
+	}
+}
+
+/// Differences between the active configuration and a reloaded one.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigDiff {
+	/// Changes that can be applied at runtime.
+	pub hot: Vec<ConfigChange>,
+	/// Changes that only take effect after a restart.
+	pub restart_required: Vec<ConfigChange>,
+}
+
+impl ConfigDiff {
+	/// Compares two configurations section by section.
+	///
+	/// Secrets serialize redacted, so changes to secret values are not
+	/// detected and always require a restart to take effect.
+	pub fn between(current: &Config, candidate: &Config) -> Result<Self, ConfigError> {
+		let current = serde_json::to_value(current)
+			.map_err(|e| ConfigError::Parse(format!("Failed to serialize config: {}", e)))?;
+		let candidate = serde_json::to_value(candidate)
+			.map_err(|e| ConfigError::Parse(format!("Failed to serialize config: {}", e)))?;
+
+		let mut changes = Vec::new();
+		collect_changes("", &current, &candidate, &mut changes);
+
+		let (hot, restart_required) = changes.into_iter().partition(|c| is_hot(&c.path));
+		Ok(Self {
+			hot,
+			restart_required,
+		})
+	}
+
+	/// Returns true when the configurations are equivalent.
+	pub fn is_empty(&self) -> bool {
+		self.hot.is_empty() && self.restart_required.is_empty()
+	}
+
+	/// Returns true when any change needs a restart to take effect.
+	pub fn requires_restart(&self) -> bool {
+		!self.restart_required.is_empty()
+	}
+
+	/// Renders a diff-style report of all changes, one path per line.
+	///
+	/// Values are left out so secrets never end up in logs.
+	pub fn report(&self) -> String {
+		let mut lines = Vec::new();
+		if !self.restart_required.is_empty() {
+			lines.push("restart required:".to_string());
+			lines.extend(self.restart_required.iter().map(|c| format!("  {}", c)));
+		}
+		if !self.hot.is_empty() {
+			lines.push("hot-reloadable:".to_string());
+			lines.extend(self.hot.iter().map(|c| format!("  {}", c)));
+		}
+		lines.join("\n")
+	}
+}
+
+/// Recursively records changed leaf paths between two JSON values.
+///
+/// Arrays are compared as a whole since element positions carry no stable identity.
+fn collect_changes(path: &str, current: &Value, candidate: &Value, out: &mut Vec<ConfigChange>) {
+	match (current, candidate) {
+		(Value::Object(a), Value::Object(b)) => {
+			let mut keys: Vec<&String> = a.keys().chain(b.keys()).collect();
+			keys.sort();
+			keys.dedup();
+			for key in keys {
+				let child = if path.is_empty() {
+					key.clone()
+				} else {
+					format!("{}.{}", path, key)
+				};
+				match (a.get(key), b.get(key)) {
+					(Some(x), Some(y)) => collect_changes(&child, x, y, out),
+					(None, Some(_)) => out.push(ConfigChange {
+						path: child,
+						kind: ChangeKind::Added,
+					}),
+					(Some(_), None) => out.push(ConfigChange {
+						path: child,
+						kind: ChangeKind::Removed,
+					}),
+					(None, None) => {},
+				}
+			}
+		},
+		(Value::Null, Value::Null) => {},
+		(Value::Null, _) => out.push(ConfigChange {
+			path: path.to_string(),
+			kind: ChangeKind::Added,
+		}),
+		(_, Value::Null) => out.push(ConfigChange {
+			path: path.to_string(),
+			kind: ChangeKind::Removed,
+		}),
+		(a, b) if a != b => out.push(ConfigChange {
+			path: path.to_string(),
+			kind: ChangeKind::Modified,
+		}),
+		_ => {},
+	}
+}
+
+/// Checks whether a changed path falls under a hot-reloadable section.
+fn is_hot(path: &str) -> bool {
+	let segments: Vec<&str> = path.split('.').collect();
+	HOT_RELOADABLE_PATHS.iter().any(|pattern| {
+		let pattern: Vec<&str> = pattern.split('.').collect();
+		segments.len() >= pattern.len()
+			&& pattern
+				.iter()
+				.zip(&segments)
+				.all(|(p, s)| *p == "*" || p == s)
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::str::FromStr;
+
+	fn base_config() -> String {
+		r#"
+[solver]
+id = "test-solver"
+monitoring_timeout_minutes = 5
+min_profitability_pct = 1.0
+
+[storage]
+primary = "memory"
+cleanup_interval_seconds = 3600
+[storage.implementations.memory]
+
+[delivery]
+[delivery.implementations.test]
+
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "0x1234567890123456789012345678901234567890123456789012345678901234"
+
+[discovery]
+[discovery.implementations.test]
+
+[order]
+[order.implementations.test]
+[order.strategy]
+primary = "simple"
+[order.strategy.implementations.simple]
+max_gas_price_gwei = 100
+
+[settlement]
+[settlement.implementations.test]
+order = "test"
+network_ids = [1, 2]
+oracles = { input = { 1 = ["0x1111111111111111111111111111111111111111"] }, output = { 2 = ["0x2222222222222222222222222222222222222222"] } }
+routes = { 1 = [2] }
+
+[networks.1]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.1.rpc_urls]]
+http = "http://localhost:8545"
+[[networks.1.tokens]]
+address = "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+symbol = "TOKA"
+decimals = 18
+
+[networks.2]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.2.rpc_urls]]
+http = "http://localhost:8546"
+[[networks.2.tokens]]
+address = "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"
+symbol = "TOKB"
+decimals = 18
+"#
+		.to_string()
+	}
+
+	#[test]
+	fn test_identical_configs_have_no_diff() {
+		let current = Config::from_str(&base_config()).unwrap();
+		let candidate = Config::from_str(&base_config()).unwrap();
+
+		let diff = ConfigDiff::between(&current, &candidate).unwrap();
+		assert!(diff.is_empty());
+		assert!(!diff.requires_restart());
+	}
+
+	#[test]
+	fn test_hot_reloadable_changes() {
+		let current = Config::from_str(&base_config()).unwrap();
+		let candidate = Config::from_str(
+			&base_config()
+				.replace("max_gas_price_gwei = 100", "max_gas_price_gwei = 50")
+				.replace("routes = { 1 = [2] }", "routes = { 1 = [2], 2 = [1] }")
+				.replace("symbol = \"TOKB\"", "symbol = \"TOKC\""),
+		)
+		.unwrap();
+
+		let diff = ConfigDiff::between(&current, &candidate).unwrap();
+		assert!(!diff.requires_restart());
+		let paths: Vec<_> = diff.hot.iter().map(|c| c.path.as_str()).collect();
+		assert_eq!(
+			paths,
+			vec![
+				"networks.2.tokens",
+				"order.strategy.implementations.simple.max_gas_price_gwei",
+				"settlement.implementations.test.routes.2",
+			]
+		);
+		assert_eq!(diff.hot[2].kind, ChangeKind::Added);
+	}
+
+	#[test]
+	fn test_restart_required_changes_are_reported() {
+		let current = Config::from_str(&base_config()).unwrap();
+		let candidate = Config::from_str(
+			&base_config()
+				.replace("http://localhost:8546", "http://localhost:9999")
+				.replace("max_gas_price_gwei = 100", "max_gas_price_gwei = 50"),
+		)
+		.unwrap();
+
+		let diff = ConfigDiff::between(&current, &candidate).unwrap();
+		assert!(diff.requires_restart());
+		assert_eq!(diff.restart_required.len(), 1);
+		assert_eq!(diff.restart_required[0].path, "networks.2.rpc_urls");
+
+		let report = diff.report();
+		assert!(report.contains("restart required:\n  ~ networks.2.rpc_urls"));
+		assert!(report.contains(
+			"hot-reloadable:\n  ~ order.strategy.implementations.simple.max_gas_price_gwei"
+		));
+	}
+
+	#[test]
+	fn test_is_hot_matches_wildcards_and_prefixes() {
+		assert!(is_hot("networks.10.tokens"));
+		assert!(is_hot("gas.flows.compact_resource_lock"));
+		assert!(is_hot(
+			"settlement.implementations.hyperlane.oracles.input.1"
+		));
+		assert!(!is_hot("networks.10.rpc_urls"));
+		assert!(!is_hot(
+			"settlement.implementations.hyperlane.mailbox_addresses"
+		));
+		assert!(!is_hot("solver.id"));
+		assert!(!is_hot("gas_price"));
+	}
+}
@@ -2,18 +2,24 @@
 //!
 //! Provides a flexible way to compose a SolverEngine from various service
 //! implementations using factory functions. Supports pluggable storage,
-//! account, delivery, discovery, order implementations and
-//! settlement and execution strategies.
-
-use crate::engine::{event_bus::EventBus, SolverEngine};
+//! account, delivery, discovery, order implementations,
+//! settlement and execution strategies and rebalancing bridges.
+
+use crate::engine::{
+	accounts::{AccountRoles, SolverAccounts},
+	event_bus::EventBus,
+	SolverEngine,
+};
 use solver_account::{AccountError, AccountInterface, AccountService};
 use solver_config::Config;
 use solver_delivery::{DeliveryError, DeliveryInterface, DeliveryService};
 use solver_discovery::{DiscoveryError, DiscoveryInterface, DiscoveryService};
 use solver_order::{ExecutionStrategy, OrderError, OrderInterface, OrderService, StrategyError};
-use solver_pricing::PricingService;
+use solver_pricing::{PricingConfig, PricingService};
+use solver_rebalance::{BridgeError, BridgeInterface, RebalanceService};
 use solver_settlement::{SettlementError, SettlementInterface, SettlementService};
 use solver_storage::{StorageError, StorageInterface, StorageService};
+use solver_types::ExposureLimits;
 use std::collections::HashMap;
 use std::sync::Arc;
 use thiserror::Error;
@@ -35,7 +41,7 @@ pub enum BuilderError {
 /// This struct holds factory functions for creating implementations of each
 /// service type required by the solver engine. Each factory function takes
 /// a TOML configuration value and returns the corresponding service implementation.
-pub struct SolverFactories<SF, AF, DF, DIF, OF, PF, SEF, STF> {
+pub struct SolverFactories<SF, AF, DF, DIF, OF, PF, SEF, STF, BF> {
 	pub storage_factories: HashMap<String, SF>,
 	pub account_factories: HashMap<String, AF>,
 	pub delivery_factories: HashMap<String, DF>,
@@ -44,6 +50,35 @@ pub struct SolverFactories<SF, AF, DF, DIF, OF, PF, SEF, STF> {
 	pub pricing_factories: HashMap<String, PF>,
 	pub settlement_factories: HashMap<String, SEF>,
 	pub strategy_factories: HashMap<String, STF>,
+	pub bridge_factories: HashMap<String, BF>,
+}
+
+/// Derives pricing settings from the primary strategy configuration.
+///
+/// Commission and buffer settings live next to the strategy limits, so they
+/// are reloaded together with them. Missing keys fall back to defaults.
+pub fn pricing_config_for(config: &Config) -> PricingConfig {
+	config
+		.order
+		.strategy
+		.implementations
+		.get(&config.order.strategy.primary)
+		.map(PricingConfig::from_table)
+		.unwrap_or_else(PricingConfig::default_values)
+}
+
+/// Derives exposure limits from the primary strategy configuration.
+///
+/// The quote API uses the same limits the strategy enforces, so quotes are
+/// refused for orders the strategy would skip. Missing keys leave limits unset.
+pub fn exposure_limits_for(config: &Config) -> ExposureLimits {
+	config
+		.order
+		.strategy
+		.implementations
+		.get(&config.order.strategy.primary)
+		.map(ExposureLimits::from_table)
+		.unwrap_or_default()
 }
 
 /// Builder for constructing a SolverEngine with pluggable implementations.
@@ -58,9 +93,9 @@ impl SolverBuilder {
 	}
 
 	/// Builds the SolverEngine using factories for each component type.
-	pub async fn build<SF, AF, DF, DIF, OF, PF, SEF, STF>(
+	pub async fn build<SF, AF, DF, DIF, OF, PF, SEF, STF, BF>(
 		self,
-		factories: SolverFactories<SF, AF, DF, DIF, OF, PF, SEF, STF>,
+		factories: SolverFactories<SF, AF, DF, DIF, OF, PF, SEF, STF, BF>,
 	) -> Result<SolverEngine, BuilderError>
 	where
 		SF: Fn(&toml::Value) -> Result<Box<dyn StorageInterface>, StorageError>,
@@ -68,8 +103,9 @@ impl SolverBuilder {
 		DF: Fn(
 			&toml::Value,
 			&solver_types::NetworksConfig,
-			&solver_types::SecretString,
-			&std::collections::HashMap<u64, solver_types::SecretString>,
+			&solver_account::AccountSigner,
+			&std::collections::HashMap<u64, solver_account::AccountSigner>,
+			&[solver_account::AccountSigner],
 		) -> Result<Box<dyn DeliveryInterface>, DeliveryError>,
 		DIF: Fn(
 			&toml::Value,
@@ -89,6 +125,11 @@ impl SolverBuilder {
 			Arc<StorageService>,
 		) -> Result<Box<dyn SettlementInterface>, SettlementError>,
 		STF: Fn(&toml::Value) -> Result<Box<dyn ExecutionStrategy>, StrategyError>,
+		BF: Fn(
+			&toml::Value,
+			&solver_types::NetworksConfig,
+			&solver_types::oracle::OracleRoutes,
+		) -> Result<Box<dyn BridgeInterface>, BridgeError>,
 	{
 		// Create storage implementations
 		let mut storage_impls = HashMap::new();
@@ -134,6 +175,14 @@ impl SolverBuilder {
 
 		let storage = Arc::new(StorageService::new(storage_backend));
 
+		// Refuse to start on data written by a newer schema
+		storage.ensure_schema_compatible().await.map_err(|e| {
+			BuilderError::Config(format!(
+				"Storage '{}' is not compatible with this version: {}",
+				primary_storage, e
+			))
+		})?;
+
 		// Create account implementations
 		let mut account_impls = HashMap::new();
 		for (name, config) in &self.config.account.implementations {
@@ -184,39 +233,73 @@ impl SolverBuilder {
 			})?
 			.clone();
 
-		// Fetch the solver address once during initialization
-		let solver_address = match account.get_address().await {
-			Ok(address) => address,
-			Err(e) => {
-				tracing::error!(
-					component = "account",
-					error = %e,
-					"Failed to get solver address"
-				);
-				return Err(BuilderError::Config(format!(
-					"Failed to get solver address: {}",
-					e
-				)));
-			},
+		// Fetch every account address once during initialization
+		let mut account_addresses = HashMap::new();
+		for (name, account_service) in &account_services {
+			match account_service.get_address().await {
+				Ok(address) => {
+					account_addresses.insert(name.clone(), address);
+				},
+				Err(e) => {
+					tracing::error!(
+						component = "account",
+						implementation = %name,
+						error = %e,
+						"Failed to get account address"
+					);
+					return Err(BuilderError::Config(format!(
+						"Failed to get address of account '{}': {}",
+						name, e
+					)));
+				},
+			}
+		}
+		let address_of = |name: &str| {
+			account_addresses.get(name).cloned().ok_or_else(|| {
+				BuilderError::Config(format!("Account '{}' not found in loaded accounts", name))
+			})
 		};
 
+		// The claimer is the solver of record named in fills and claims
+		let account_config = &self.config.account;
+		let roles = AccountRoles {
+			filler: address_of(account_config.filler())?,
+			claimer: address_of(account_config.claimer())?,
+			opener: address_of(account_config.opener())?,
+			approver: address_of(account_config.approver())?,
+		};
+		let solver_address = roles.claimer.clone();
+		let mut filler_pools = HashMap::new();
+		for (chain_id, names) in &account_config.filler_pools {
+			let members = names
+				.iter()
+				.map(|name| address_of(name))
+				.collect::<Result<Vec<_>, _>>()?;
+			filler_pools.insert(*chain_id, members);
+		}
+
 		// Create delivery implementations
 		let mut delivery_implementations = std::collections::HashMap::new();
 
-		// Get the default private key from the primary account
-		let default_private_key = account.get_private_key();
+		// Get the default signer from the primary account; the other accounts
+		// sign transactions that name them in `from`
+		let default_signer = account.signer();
+		let additional_signers: Vec<_> = account_services
+			.iter()
+			.filter(|(name, _)| name.as_str() != primary_account)
+			.map(|(_, account_service)| account_service.signer())
+			.collect();
 
 		for (name, config) in &self.config.delivery.implementations {
 			if let Some(factory) = factories.delivery_factories.get(name) {
 				// Parse per-network account mappings from config
-				let mut network_private_keys = HashMap::new();
+				let mut network_signers = HashMap::new();
 				if let Some(accounts_table) = config.get("accounts").and_then(|v| v.as_table()) {
 					for (network_id_str, account_name_value) in accounts_table {
 						if let Ok(network_id) = network_id_str.parse::<u64>() {
 							if let Some(account_name) = account_name_value.as_str() {
 								if let Some(account_service) = account_services.get(account_name) {
-									let private_key = account_service.get_private_key();
-									network_private_keys.insert(network_id, private_key);
+									network_signers.insert(network_id, account_service.signer());
 								} else {
 									tracing::warn!(
 										"Account '{}' not found, skipping",
@@ -231,8 +314,9 @@ impl SolverBuilder {
 				match factory(
 					config,
 					&self.config.networks,
-					&default_private_key,
-					&network_private_keys,
+					&default_signer,
+					&network_signers,
+					&additional_signers,
 				) {
 					Ok(implementation) => {
 						// Extract network_ids from config to create the mapping
@@ -287,6 +371,8 @@ impl SolverBuilder {
 			self.config.delivery.transaction_poll_interval_seconds,
 		));
 
+		let accounts = Arc::new(SolverAccounts::new(roles, filler_pools, delivery.clone()));
+
 		// Create discovery implementations
 		let mut discovery_implementations = HashMap::new();
 		for (name, config) in &self.config.discovery.implementations {
@@ -395,7 +481,10 @@ impl SolverBuilder {
 				primary_pricing
 			))
 		})?;
-		let pricing = Arc::new(PricingService::new(pricing_impl));
+		let pricing = Arc::new(PricingService::new_with_config(
+			pricing_impl,
+			pricing_config_for(&self.config),
+		));
 
 		// Build oracle routes from settlement implementations
 		let oracle_routes = settlement.build_oracle_routes();
@@ -434,6 +523,41 @@ impl SolverBuilder {
 			tracing::warn!("No order implementations available - solver will not be able to process any orders");
 		}
 
+		// Create bridge implementations, in the order they are tried
+		let mut bridge_impls = Vec::new();
+		for name in self.config.rebalance.bridge_order() {
+			let Some(factory) = factories.bridge_factories.get(&name) else {
+				continue;
+			};
+			let config = &self.config.rebalance.implementations[&name];
+			match factory(config, &self.config.networks, &oracle_routes) {
+				Ok(implementation) => {
+					tracing::info!(component = "bridge", implementation = %name, "Loaded");
+					bridge_impls.push((name, implementation));
+				},
+				Err(e) => {
+					tracing::error!(
+						component = "bridge",
+						implementation = %name,
+						error = %e,
+						"Failed to create bridge implementation"
+					);
+					return Err(BuilderError::Config(format!(
+						"Failed to create bridge implementation '{}': {}",
+						name, e
+					)));
+				},
+			}
+		}
+
+		if self.config.rebalance.enabled && bridge_impls.is_empty() {
+			tracing::warn!(
+				"No bridge implementations available - solver will not rebalance inventory"
+			);
+		}
+
+		let bridges = Arc::new(RebalanceService::new(bridge_impls));
+
 		// Create strategy implementations
 		let mut strategy_impls = HashMap::new();
 		for (name, config) in &self.config.order.strategy.implementations {
@@ -477,12 +601,28 @@ impl SolverBuilder {
 
 		let order = Arc::new(OrderService::new(order_impls, strategy));
 
-		// Create and initialize the TokenManager
-		let token_manager = Arc::new(crate::engine::token_manager::TokenManager::new(
-			self.config.networks.clone(),
-			delivery.clone(),
-			account.clone(),
-		));
+		// Create and initialize the TokenManager with the account owning the allowances
+		let approver = account_services
+			.get(self.config.account.approver())
+			.cloned()
+			.unwrap_or_else(|| account.clone());
+		// Filler accounts spend tokens through the output settler too, so their
+		// allowances are managed alongside the approver's
+		let allowance_owners = self
+			.config
+			.networks
+			.keys()
+			.map(|chain_id| (*chain_id, accounts.filler_pool.members(*chain_id)))
+			.collect();
+		let token_manager = Arc::new(
+			crate::engine::token_manager::TokenManager::new(
+				self.config.networks.clone(),
+				delivery.clone(),
+				approver,
+				self.config.approvals.clone(),
+			)
+			.with_allowance_owners(allowance_owners),
+		);
 
 		// Ensure all token approvals are set
 		match token_manager.ensure_approvals().await {
@@ -532,11 +672,18 @@ impl SolverBuilder {
 			},
 		}
 
+		// The engine signs as the solver of record
+		let claimer_account = account_services
+			.get(self.config.account.claimer())
+			.cloned()
+			.unwrap_or(account);
+
 		Ok(SolverEngine::new(
 			self.config,
 			storage,
-			account,
+			claimer_account,
 			solver_address,
+			accounts,
 			delivery,
 			discovery,
 			order,
@@ -544,6 +691,7 @@ impl SolverBuilder {
 			pricing,
 			EventBus::new(1000),
 			token_manager,
+			bridges,
 		))
 	}
 }
@@ -0,0 +1,373 @@
+//! Solver account roles and the filler account pool.
+//!
+//! The solver can split its duties across separate keys: a filler that sends
+//! fills on destination chains, a claimer that is the solver of record and
+//! sends claims, an opener for prepare transactions and an approver that owns
+//! token allowances. Fills on a chain can additionally be spread across a pool
+//! of filler accounts, which this module selects from based on tracked balances,
+//! nonces and in-flight transactions.
+
+use alloy_primitives::{hex, U256};
+use solver_delivery::DeliveryService;
+use solver_types::Address;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+/// Errors that can occur when selecting a filler account.
+#[derive(Debug, Error)]
+pub enum AccountPoolError {
+	/// No account in the pool can cover the required amounts.
+	#[error("No filler account on chain {0} has sufficient balance")]
+	InsufficientBalance(u64),
+}
+
+/// Addresses of the accounts assigned to each solver role.
+#[derive(Debug, Clone)]
+pub struct AccountRoles {
+	/// Default account for fill transactions when a chain has no pool.
+	pub filler: Address,
+	/// Solver of record; sends post-fill, pre-claim and claim transactions.
+	pub claimer: Address,
+	/// Sends prepare transactions for off-chain orders.
+	pub opener: Address,
+	/// Owns the token allowances granted to settlement contracts.
+	pub approver: Address,
+}
+
+impl AccountRoles {
+	/// Assigns every role to the same address.
+	pub fn single(address: Address) -> Self {
+		Self {
+			filler: address.clone(),
+			claimer: address.clone(),
+			opener: address.clone(),
+			approver: address,
+		}
+	}
+}
+
+/// Accounts the engine sends transactions from.
+pub struct SolverAccounts {
+	/// Role assignments.
+	pub roles: AccountRoles,
+	/// Filler accounts per chain.
+	pub filler_pool: FillerPool,
+}
+
+impl SolverAccounts {
+	/// Creates the account set from role assignments and per-chain filler pools.
+	pub fn new(
+		roles: AccountRoles,
+		filler_pools: HashMap<u64, Vec<Address>>,
+		delivery: Arc<DeliveryService>,
+	) -> Self {
+		let filler_pool = FillerPool::new(delivery, filler_pools, roles.filler.clone());
+		Self { roles, filler_pool }
+	}
+
+	/// Creates an account set where a single account takes every role.
+	pub fn single(address: Address, delivery: Arc<DeliveryService>) -> Self {
+		Self::new(AccountRoles::single(address), HashMap::new(), delivery)
+	}
+
+	/// Returns whether an address belongs to any role or filler pool.
+	pub fn contains(&self, address: &Address) -> bool {
+		let roles = &self.roles;
+		let role_addresses = [
+			&roles.filler,
+			&roles.claimer,
+			&roles.opener,
+			&roles.approver,
+		];
+		role_addresses.contains(&address) || self.filler_pool.all_members().contains(address)
+	}
+}
+
+/// Last known state of a filler account on one chain.
+#[derive(Debug, Clone, Default)]
+pub struct FillerAccountState {
+	/// Balances by token address (`None` for the native asset), as last fetched.
+	pub balances: HashMap<Option<Address>, U256>,
+	/// Amounts reserved by fills that have not completed yet.
+	pub reserved: HashMap<Option<Address>, U256>,
+	/// Transaction count of the account, as last fetched.
+	pub nonce: Option<u64>,
+	/// Number of fills submitted from this account that are not yet confirmed.
+	pub in_flight: usize,
+}
+
+impl FillerAccountState {
+	/// Returns the balance not yet reserved for a token, if it is known.
+	fn available(&self, token: &Option<Address>) -> Option<U256> {
+		let balance = self.balances.get(token)?;
+		let reserved = self.reserved.get(token).copied().unwrap_or_default();
+		Some(balance.saturating_sub(reserved))
+	}
+}
+
+/// Reservation held by an order between fill submission and completion.
+#[derive(Debug, Clone)]
+struct Reservation {
+	chain_id: u64,
+	account: Address,
+	amounts: Vec<(Option<Address>, U256)>,
+}
+
+/// Account state and reservations, guarded together so that selecting an
+/// account and reserving against it happen in one step.
+#[derive(Debug, Default)]
+struct PoolState {
+	/// Tracked state per (chain ID, account).
+	accounts: HashMap<(u64, Address), FillerAccountState>,
+	/// Active reservations by order ID.
+	reservations: HashMap<String, Reservation>,
+}
+
+impl PoolState {
+	/// Records the reservation and updates the account's tracked state.
+	fn reserve(
+		&mut self,
+		order_id: &str,
+		chain_id: u64,
+		account: &Address,
+		amounts: Vec<(Option<Address>, U256)>,
+	) {
+		let state = self
+			.accounts
+			.entry((chain_id, account.clone()))
+			.or_default();
+		state.in_flight += 1;
+		for (token, amount) in &amounts {
+			*state.reserved.entry(token.clone()).or_default() += *amount;
+		}
+
+		self.reservations.insert(
+			order_id.to_string(),
+			Reservation {
+				chain_id,
+				account: account.clone(),
+				amounts,
+			},
+		);
+	}
+}
+
+/// Pool of filler accounts per chain.
+///
+/// Each fill acquires an account that can cover its outputs, preferring the
+/// account with the fewest in-flight fills and then the lowest nonce. The
+/// required amounts stay reserved against that account until the fill is
+/// released, so concurrent fills do not spend the same inventory.
+pub struct FillerPool {
+	/// Delivery service used to refresh balances and nonces.
+	delivery: Arc<DeliveryService>,
+	/// Pool members per chain ID.
+	pools: HashMap<u64, Vec<Address>>,
+	/// Account used on chains without a pool.
+	default_filler: Address,
+	/// Tracked account state and active reservations.
+	state: Mutex<PoolState>,
+}
+
+impl FillerPool {
+	/// Creates a pool from the configured per-chain members.
+	pub fn new(
+		delivery: Arc<DeliveryService>,
+		pools: HashMap<u64, Vec<Address>>,
+		default_filler: Address,
+	) -> Self {
+		Self {
+			delivery,
+			pools,
+			default_filler,
+			state: Mutex::new(PoolState::default()),
+		}
+	}
+
+	/// Returns the accounts that may fill on a chain.
+	pub fn members(&self, chain_id: u64) -> Vec<Address> {
+		match self.pools.get(&chain_id) {
+			Some(pool) if !pool.is_empty() => pool.clone(),
+			_ => vec![self.default_filler.clone()],
+		}
+	}
+
+	/// Returns all distinct accounts across every pool and the default filler.
+	pub fn all_members(&self) -> Vec<Address> {
+		let mut members = vec![self.default_filler.clone()];
+		for address in self.pools.values().flatten() {
+			if !members.contains(address) {
+				members.push(address.clone());
+			}
+		}
+		members
+	}
+
+	/// Returns the tracked state of every pool account on a chain.
+	pub fn snapshot(&self, chain_id: u64) -> Vec<(Address, FillerAccountState)> {
+		let pool = self.state.lock().unwrap();
+		self.members(chain_id)
+			.into_iter()
+			.map(|address| {
+				let state = pool
+					.accounts
+					.get(&(chain_id, address.clone()))
+					.cloned()
+					.unwrap_or_default();
+				(address, state)
+			})
+			.collect()
+	}
+
+	/// Selects an account to fill an order and reserves the required amounts.
+	///
+	/// `amounts` lists the tokens the fill spends on `chain_id`, with `None` for
+	/// the native asset. Chains with a single account always return that account;
+	/// its balance is still tracked but not enforced, so the behaviour without
+	/// a pool stays unchanged.
+	pub async fn acquire(
+		&self,
+		order_id: &str,
+		chain_id: u64,
+		amounts: Vec<(Option<Address>, U256)>,
+	) -> Result<Address, AccountPoolError> {
+		// An order that is filled again keeps using the account it reserved.
+		if let Some(account) = self.reserved_account(order_id, chain_id) {
+			return Ok(account);
+		}
+
+		let members = self.members(chain_id);
+		for address in &members {
+			self.refresh(chain_id, address, &amounts).await;
+		}
+
+		// Selection and reservation share one lock so concurrent fills see each
+		// other's reservations
+		let account = {
+			let mut pool = self.state.lock().unwrap();
+			if let Some(existing) = pool.reservations.get(order_id) {
+				if existing.chain_id == chain_id {
+					return Ok(existing.account.clone());
+				}
+			}
+
+			let covers = |state: &FillerAccountState| {
+				amounts.iter().all(|(token, amount)| {
+					// Unknown balances do not exclude an account
+					state
+						.available(token)
+						.is_none_or(|available| available >= *amount)
+				})
+			};
+			let selected = members
+				.iter()
+				.filter_map(|address| {
+					let state = pool
+						.accounts
+						.get(&(chain_id, address.clone()))
+						.cloned()
+						.unwrap_or_default();
+					(members.len() == 1 || covers(&state)).then_some((address, state))
+				})
+				.min_by_key(|(_, state)| (state.in_flight, state.nonce.unwrap_or(u64::MAX)))
+				.map(|(address, _)| address.clone());
+
+			let account = selected.ok_or(AccountPoolError::InsufficientBalance(chain_id))?;
+			pool.reserve(order_id, chain_id, &account, amounts);
+			account
+		};
+
+		tracing::debug!(
+			order_id = %solver_types::truncate_id(order_id),
+			chain_id = chain_id,
+			account = %account,
+			"Selected filler account"
+		);
+
+		Ok(account)
+	}
+
+	/// Returns the amount of a token reserved by all unreleased fills of an
+	/// account on a chain.
+	pub fn reserved(&self, chain_id: u64, account: &Address, token: &Option<Address>) -> U256 {
+		let pool = self.state.lock().unwrap();
+		pool.accounts
+			.get(&(chain_id, account.clone()))
+			.and_then(|state| state.reserved.get(token))
+			.copied()
+			.unwrap_or_default()
+	}
+
+	/// Releases the reservation held by an order, if any.
+	pub fn release(&self, order_id: &str) {
+		let mut pool = self.state.lock().unwrap();
+		let Some(reservation) = pool.reservations.remove(order_id) else {
+			return;
+		};
+
+		if let Some(state) = pool
+			.accounts
+			.get_mut(&(reservation.chain_id, reservation.account))
+		{
+			state.in_flight = state.in_flight.saturating_sub(1);
+			for (token, amount) in reservation.amounts {
+				if let Some(reserved) = state.reserved.get_mut(&token) {
+					*reserved = reserved.saturating_sub(amount);
+				}
+			}
+		}
+	}
+
+	/// Returns the account an order already reserved on a chain, if any.
+	fn reserved_account(&self, order_id: &str, chain_id: u64) -> Option<Address> {
+		let pool = self.state.lock().unwrap();
+		pool.reservations
+			.get(order_id)
+			.filter(|reservation| reservation.chain_id == chain_id)
+			.map(|reservation| reservation.account.clone())
+	}
+
+	/// Refreshes the nonce and the relevant balances of an account.
+	///
+	/// Lookup failures keep the previous snapshot so a flaky RPC does not
+	/// take an account out of rotation.
+	async fn refresh(&self, chain_id: u64, address: &Address, amounts: &[(Option<Address>, U256)]) {
+		let owner = address.to_string();
+
+		let nonce = self.delivery.get_nonce(chain_id, &owner).await;
+		let mut balances = Vec::new();
+		for (token, _) in amounts {
+			let token_hex = token.as_ref().map(|t| hex::encode(&t.0));
+			match self
+				.delivery
+				.get_balance(chain_id, &owner, token_hex.as_deref())
+				.await
+			{
+				Ok(balance) => {
+					if let Ok(balance) = U256::from_str_radix(&balance, 10) {
+						balances.push((token.clone(), balance));
+					}
+				},
+				Err(e) => {
+					tracing::warn!(
+						chain_id = chain_id,
+						account = %address,
+						error = %e,
+						"Failed to refresh filler balance"
+					);
+				},
+			}
+		}
+
+		let mut pool = self.state.lock().unwrap();
+		let state = pool
+			.accounts
+			.entry((chain_id, address.clone()))
+			.or_default();
+		if let Ok(nonce) = nonce {
+			state.nonce = Some(nonce);
+		}
+		state.balances.extend(balances);
+	}
+}
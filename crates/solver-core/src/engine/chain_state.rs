@@ -0,0 +1,613 @@
+//! Background cache of chain state shared by quoting and execution.
+//!
+//! The `ChainStateService` keeps one snapshot per configured network holding
+//! the chain data, recent fee history and the solver balances that have been
+//! read through it. Each network's block number is polled every
+//! `chain_state.poll_interval_ms` and the snapshot is rebuilt when a new block
+//! arrives. Snapshots are replaced as a whole, so a reader sees chain data and
+//! balances taken at the same time, together with their age. The fee history
+//! is also turned into EIP-1559 fees per urgency level on the chain data, which
+//! are set on the solver's transactions before they are sent.
+//!
+//! A chain's snapshot is dropped once one of the solver's own transactions on
+//! it confirms or fails, since balances have changed. Order transactions are
+//! followed through their delivery events; approvals and rebalance transfers
+//! invalidate the chain directly once they settle. Reads of a missing or stale
+//! snapshot, or any read while the cache is disabled, go to the RPC, with
+//! concurrent readers of a chain sharing a single refresh.
+
+use alloy_primitives::hex;
+use solver_config::SharedConfig;
+use solver_delivery::{DeliveryError, DeliveryService};
+use solver_types::{
+	ChainData, DeliveryEvent, FeeEstimate, FeeHistory, SolverEvent, Transaction, TransactionType,
+	FEE_HISTORY_PERCENTILES,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tokio::time::{Interval, MissedTickBehavior};
+
+/// Number of recent blocks included in the fee history.
+const FEE_HISTORY_BLOCKS: u64 = 20;
+
+/// Identifies a balance by account and token; `None` is the native asset.
+///
+/// Addresses are lowercase hex without the 0x prefix.
+type BalanceKey = (String, Option<String>);
+
+/// Normalizes a hex address so differently formatted inputs share a key.
+fn normalize(address: &str) -> String {
+	address.trim_start_matches("0x").to_lowercase()
+}
+
+/// State of one chain read at a single point in time.
+#[derive(Debug, Clone)]
+pub struct ChainSnapshot {
+	/// Gas price and block number of the chain.
+	pub chain_data: ChainData,
+	/// Fee history of the most recent blocks, if the chain provides it.
+	pub fee_history: Option<FeeHistory>,
+	/// Balances of the watched accounts and tokens.
+	balances: HashMap<BalanceKey, String>,
+	/// When the snapshot was read.
+	fetched_at: Instant,
+}
+
+impl ChainSnapshot {
+	/// Returns the time since the snapshot was read.
+	pub fn age(&self) -> Duration {
+		self.fetched_at.elapsed()
+	}
+
+	/// Returns a balance held in the snapshot, in the asset's smallest unit.
+	pub fn balance(&self, account: &str, token: Option<&str>) -> Option<&String> {
+		self.balances
+			.get(&(normalize(account), token.map(normalize)))
+	}
+}
+
+/// Caches chain data, fee history and solver balances per chain.
+pub struct ChainStateService {
+	/// Configuration holding the cache settings, read on every use.
+	config: SharedConfig,
+	/// Delivery service used to read chain state.
+	delivery: Arc<DeliveryService>,
+	/// Latest snapshot of each chain.
+	snapshots: RwLock<HashMap<u64, Arc<ChainSnapshot>>>,
+	/// Balances refreshed with each chain's snapshot, with the addresses as
+	/// first requested.
+	watched: RwLock<HashMap<u64, HashMap<BalanceKey, (String, Option<String>)>>>,
+	/// Serializes refreshes of each chain so concurrent readers of a stale
+	/// snapshot wait for one read instead of each going to the RPC.
+	refresh_locks: Mutex<HashMap<u64, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+impl ChainStateService {
+	/// Creates a service with no snapshots.
+	pub fn new(config: SharedConfig, delivery: Arc<DeliveryService>) -> Self {
+		Self {
+			config,
+			delivery,
+			snapshots: RwLock::new(HashMap::new()),
+			watched: RwLock::new(HashMap::new()),
+			refresh_locks: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Returns the configured time between block number polls.
+	pub fn interval(&self) -> Duration {
+		Duration::from_millis(self.config.load().chain_state.poll_interval_ms.max(1))
+	}
+
+	/// Returns the snapshot of a chain if caching is enabled and it is fresh.
+	pub fn snapshot(&self, chain_id: u64) -> Option<Arc<ChainSnapshot>> {
+		let config = self.config.load();
+		if !config.chain_state.enabled {
+			return None;
+		}
+		let max_age = Duration::from_secs(config.chain_state.max_age_seconds);
+		self.snapshots
+			.read()
+			.unwrap()
+			.get(&chain_id)
+			.filter(|snapshot| snapshot.age() <= max_age)
+			.cloned()
+	}
+
+	/// Returns a fresh snapshot of a chain, reading a new one if needed.
+	async fn current(&self, chain_id: u64) -> Result<Arc<ChainSnapshot>, DeliveryError> {
+		if let Some(snapshot) = self.snapshot(chain_id) {
+			return Ok(snapshot);
+		}
+
+		let lock = self.refresh_lock(chain_id);
+		let _guard = lock.lock().await;
+		// Another reader may have refreshed the chain while this one waited
+		if let Some(snapshot) = self.snapshot(chain_id) {
+			return Ok(snapshot);
+		}
+		self.read_snapshot(chain_id).await
+	}
+
+	/// Returns the lock serializing refreshes of a chain.
+	fn refresh_lock(&self, chain_id: u64) -> Arc<tokio::sync::Mutex<()>> {
+		self.refresh_locks
+			.lock()
+			.unwrap()
+			.entry(chain_id)
+			.or_default()
+			.clone()
+	}
+
+	/// Returns the gas price, fees and block number of a chain.
+	pub async fn chain_data(&self, chain_id: u64) -> Result<ChainData, DeliveryError> {
+		if !self.config.load().chain_state.enabled {
+			let mut chain_data = self.delivery.get_chain_data(chain_id).await?;
+			chain_data.fees = self
+				.fee_history(chain_id)
+				.await
+				.as_ref()
+				.and_then(FeeEstimate::from_fee_history);
+			return Ok(chain_data);
+		}
+		Ok(self.current(chain_id).await?.chain_data.clone())
+	}
+
+	/// Returns the fee history of a chain, `None` if it cannot be read.
+	pub async fn fee_history(&self, chain_id: u64) -> Option<FeeHistory> {
+		if !self.config.load().chain_state.enabled {
+			return self
+				.delivery
+				.get_fee_history(chain_id, FEE_HISTORY_BLOCKS, &FEE_HISTORY_PERCENTILES)
+				.await
+				.ok();
+		}
+		self.current(chain_id).await.ok()?.fee_history.clone()
+	}
+
+	/// Returns the balance of an account in the asset's smallest unit.
+	///
+	/// `token` is `None` for the native asset. The first read of a balance
+	/// adds it to the chain's snapshot, so later reads are served from cache.
+	pub async fn balance(
+		&self,
+		chain_id: u64,
+		account: &str,
+		token: Option<&str>,
+	) -> Result<String, DeliveryError> {
+		if !self.config.load().chain_state.enabled {
+			return self.delivery.get_balance(chain_id, account, token).await;
+		}
+
+		if let Some(balance) = self
+			.snapshot(chain_id)
+			.and_then(|snapshot| snapshot.balance(account, token).cloned())
+		{
+			return Ok(balance);
+		}
+
+		let newly_watched = self
+			.watched
+			.write()
+			.unwrap()
+			.entry(chain_id)
+			.or_default()
+			.insert(
+				(normalize(account), token.map(normalize)),
+				(account.to_string(), token.map(str::to_string)),
+			)
+			.is_none();
+
+		if newly_watched {
+			// Rebuild the snapshot so the new balance is read with the rest
+			if let Some(balance) = self
+				.refresh(chain_id)
+				.await?
+				.balance(account, token)
+				.cloned()
+			{
+				return Ok(balance);
+			}
+		}
+
+		// The balance could not be read as part of the snapshot
+		self.delivery.get_balance(chain_id, account, token).await
+	}
+
+	/// Sets the fees of a transaction from the chain's fee estimate.
+	///
+	/// The transaction is priced at the urgency configured for its type, or at
+	/// the chain's gas price where EIP-1559 is unavailable. Transactions that
+	/// already carry fees, and all transactions while the fee strategy is
+	/// disabled or the chain cannot be read, are left for the provider to price.
+	pub async fn apply_fees(&self, tx: &mut Transaction, tx_type: TransactionType) {
+		let strategy = self.config.load().fee_strategy();
+		let has_fees = tx.gas_price.is_some()
+			|| tx.max_fee_per_gas.is_some()
+			|| tx.max_priority_fee_per_gas.is_some();
+		if !strategy.enabled || has_fees {
+			return;
+		}
+
+		let chain_data = match self.chain_data(tx.chain_id).await {
+			Ok(chain_data) => chain_data,
+			Err(e) => {
+				tracing::warn!(
+					chain_id = tx.chain_id,
+					error = %e,
+					"Failed to read chain fees, leaving them to the provider"
+				);
+				return;
+			},
+		};
+
+		let urgency = strategy.urgency(tx_type);
+		match &chain_data.fees {
+			Some(estimate) => {
+				let fees = estimate.fees(urgency);
+				tx.max_fee_per_gas = Some(fees.max_fee_per_gas);
+				tx.max_priority_fee_per_gas = Some(fees.max_priority_fee_per_gas);
+			},
+			None => tx.gas_price = chain_data.gas_price.parse().ok(),
+		}
+
+		tracing::debug!(
+			chain_id = tx.chain_id,
+			tx_type = ?tx_type,
+			urgency = ?urgency,
+			max_fee_per_gas = ?tx.max_fee_per_gas,
+			max_priority_fee_per_gas = ?tx.max_priority_fee_per_gas,
+			gas_price = ?tx.gas_price,
+			"Applied transaction fees"
+		);
+	}
+
+	/// Drops the snapshot of a chain so the next read fetches fresh state.
+	pub fn invalidate(&self, chain_id: u64) {
+		if self.snapshots.write().unwrap().remove(&chain_id).is_some() {
+			tracing::debug!(chain_id = chain_id, "Invalidated chain state");
+		}
+	}
+
+	/// Reads and stores a new snapshot of a chain.
+	///
+	/// Fails only when the chain data cannot be read. A chain without fee
+	/// history or an unreadable balance leaves those out of the snapshot.
+	pub async fn refresh(&self, chain_id: u64) -> Result<Arc<ChainSnapshot>, DeliveryError> {
+		let lock = self.refresh_lock(chain_id);
+		let _guard = lock.lock().await;
+		self.read_snapshot(chain_id).await
+	}
+
+	/// Reads and stores a new snapshot of a chain; callers hold its refresh lock.
+	async fn read_snapshot(&self, chain_id: u64) -> Result<Arc<ChainSnapshot>, DeliveryError> {
+		let fetched_at = Instant::now();
+		let mut chain_data = self.delivery.get_chain_data(chain_id).await?;
+
+		let fee_history = match self
+			.delivery
+			.get_fee_history(chain_id, FEE_HISTORY_BLOCKS, &FEE_HISTORY_PERCENTILES)
+			.await
+		{
+			Ok(history) => Some(history),
+			Err(e) => {
+				tracing::debug!(
+					chain_id = chain_id,
+					error = %e,
+					"Fee history unavailable"
+				);
+				None
+			},
+		};
+
+		chain_data.fees = fee_history.as_ref().and_then(FeeEstimate::from_fee_history);
+
+		let watched: Vec<(BalanceKey, (String, Option<String>))> = self
+			.watched
+			.read()
+			.unwrap()
+			.get(&chain_id)
+			.map(|keys| keys.clone().into_iter().collect())
+			.unwrap_or_default();
+
+		let mut balances = HashMap::new();
+		for (key, (account, token)) in watched {
+			match self
+				.delivery
+				.get_balance(chain_id, &account, token.as_deref())
+				.await
+			{
+				Ok(balance) => {
+					balances.insert(key, balance);
+				},
+				Err(e) => {
+					tracing::warn!(
+						chain_id = chain_id,
+						account = %account,
+						token = ?token,
+						error = %e,
+						"Failed to refresh balance"
+					);
+				},
+			}
+		}
+
+		let snapshot = Arc::new(ChainSnapshot {
+			chain_data,
+			fee_history,
+			balances,
+			fetched_at,
+		});
+		self.snapshots
+			.write()
+			.unwrap()
+			.insert(chain_id, snapshot.clone());
+		Ok(snapshot)
+	}
+
+	/// Refreshes every configured chain whose block advanced since its
+	/// snapshot, or which has no snapshot.
+	async fn poll(&self) {
+		let chain_ids: Vec<u64> = self.config.load().networks.keys().copied().collect();
+		for chain_id in chain_ids {
+			let block_number = match self.delivery.get_block_number(chain_id).await {
+				Ok(block_number) => block_number,
+				Err(e) => {
+					tracing::debug!(
+						chain_id = chain_id,
+						error = %e,
+						"Failed to poll block number"
+					);
+					continue;
+				},
+			};
+
+			let current_block = self
+				.snapshots
+				.read()
+				.unwrap()
+				.get(&chain_id)
+				.map(|snapshot| snapshot.chain_data.block_number);
+			if current_block.is_some_and(|block| block >= block_number) {
+				continue;
+			}
+
+			if let Err(e) = self.refresh(chain_id).await {
+				tracing::warn!(
+					chain_id = chain_id,
+					error = %e,
+					"Failed to refresh chain state"
+				);
+			}
+		}
+	}
+
+	/// Keeps the snapshots current until the task is aborted.
+	///
+	/// Polls for new blocks while caching is enabled and drops a chain's
+	/// snapshot when one of the solver's transactions on it settles.
+	pub async fn run(&self, mut events: broadcast::Receiver<SolverEvent>) {
+		// Chain of each pending transaction, keyed by hex hash
+		let mut pending: HashMap<String, u64> = HashMap::new();
+		let mut ticker = poll_ticker(self.interval());
+
+		loop {
+			tokio::select! {
+				_ = ticker.tick() => {
+					if self.config.load().chain_state.enabled {
+						self.poll().await;
+					}
+					// Pick up a poll interval changed on config reload
+					let interval = self.interval();
+					if interval != ticker.period() {
+						ticker = poll_ticker(interval);
+					}
+				}
+				event = events.recv() => match event {
+					Ok(SolverEvent::Delivery(DeliveryEvent::TransactionPending {
+						tx_hash,
+						tx_chain_id,
+						..
+					})) => {
+						pending.insert(hex::encode(&tx_hash.0), tx_chain_id);
+					},
+					Ok(SolverEvent::Delivery(
+						DeliveryEvent::TransactionConfirmed { tx_hash, .. }
+						| DeliveryEvent::TransactionFailed { tx_hash, .. },
+					)) => {
+						if let Some(chain_id) = pending.remove(&hex::encode(&tx_hash.0)) {
+							self.invalidate(chain_id);
+						}
+					},
+					Ok(_) => {},
+					Err(broadcast::error::RecvError::Lagged(skipped)) => {
+						// Settlements may have been missed, so nothing cached is trusted
+						tracing::warn!(skipped = skipped, "Chain state lagged behind events");
+						self.snapshots.write().unwrap().clear();
+					},
+					Err(broadcast::error::RecvError::Closed) => return,
+				},
+			}
+		}
+	}
+}
+
+/// Returns a ticker firing every period, starting one period from now.
+///
+/// A poll that overruns the period delays the next tick instead of causing a
+/// burst of catch-up polls.
+fn poll_ticker(period: Duration) -> Interval {
+	let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+	ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+	ticker
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use arc_swap::ArcSwap;
+	use solver_config::ConfigBuilder;
+	use solver_delivery::MockDeliveryInterface;
+
+	const CHAIN_ID: u64 = 1;
+	const ACCOUNT: &str = "0x00000000000000000000000000000000000000aa";
+
+	/// Mock chain answering reads, each of which must happen `reads` times.
+	fn mock_chain(reads: usize) -> MockDeliveryInterface {
+		let mut mock = MockDeliveryInterface::new();
+		mock.expect_get_gas_price()
+			.times(reads)
+			.returning(|_| Ok("1000000000".to_string()));
+		mock.expect_get_block_number()
+			.times(reads)
+			.returning(|_| Ok(100));
+		mock.expect_get_fee_history()
+			.times(reads)
+			.returning(|_, _, _| Err(DeliveryError::Network("unsupported".to_string())));
+		mock
+	}
+
+	fn service(mock: MockDeliveryInterface, max_age_seconds: u64) -> ChainStateService {
+		let mut config = ConfigBuilder::new().build();
+		config.chain_state.enabled = true;
+		config.chain_state.max_age_seconds = max_age_seconds;
+		let mut implementations: HashMap<u64, Arc<dyn solver_delivery::DeliveryInterface>> =
+			HashMap::new();
+		implementations.insert(CHAIN_ID, Arc::new(mock));
+		ChainStateService::new(
+			Arc::new(ArcSwap::from_pointee(config)),
+			Arc::new(DeliveryService::new(implementations, 1, 1)),
+		)
+	}
+
+	/// Ages the chain's snapshot by the given time.
+	fn age_snapshot(service: &ChainStateService, by: Duration) {
+		let mut snapshots = service.snapshots.write().unwrap();
+		let snapshot = snapshots.get_mut(&CHAIN_ID).unwrap();
+		let mut aged = (**snapshot).clone();
+		aged.fetched_at = Instant::now().checked_sub(by).unwrap();
+		*snapshot = Arc::new(aged);
+	}
+
+	#[tokio::test]
+	async fn test_fresh_snapshot_is_reused() {
+		let service = service(mock_chain(1), 30);
+		let first = service.chain_data(CHAIN_ID).await.unwrap();
+		let second = service.chain_data(CHAIN_ID).await.unwrap();
+		assert_eq!(first.block_number, 100);
+		assert_eq!(second.gas_price, first.gas_price);
+		assert!(service.snapshot(CHAIN_ID).is_some());
+	}
+
+	#[tokio::test]
+	async fn test_stale_snapshot_is_reread() {
+		let service = service(mock_chain(2), 30);
+		service.chain_data(CHAIN_ID).await.unwrap();
+
+		age_snapshot(&service, Duration::from_secs(31));
+		assert!(service.snapshot(CHAIN_ID).is_none());
+
+		service.chain_data(CHAIN_ID).await.unwrap();
+		assert!(service.snapshot(CHAIN_ID).unwrap().age() < Duration::from_secs(30));
+	}
+
+	#[tokio::test]
+	async fn test_invalidation_drops_cached_balances() {
+		let mut mock = mock_chain(2);
+		let mut balance = 0;
+		mock.expect_get_balance()
+			.times(2)
+			.returning(move |_, _, _| {
+				balance += 500;
+				Ok(balance.to_string())
+			});
+		let service = service(mock, 30);
+
+		assert_eq!(
+			service.balance(CHAIN_ID, ACCOUNT, None).await.unwrap(),
+			"500"
+		);
+		// Served from the snapshot, without another read
+		assert_eq!(
+			service
+				.balance(CHAIN_ID, &ACCOUNT.to_uppercase().replace("0X", "0x"), None)
+				.await
+				.unwrap(),
+			"500"
+		);
+
+		service.invalidate(CHAIN_ID);
+		assert!(service.snapshot(CHAIN_ID).is_none());
+		assert_eq!(
+			service.chain_data(CHAIN_ID).await.unwrap().block_number,
+			100
+		);
+		assert_eq!(
+			service.balance(CHAIN_ID, ACCOUNT, None).await.unwrap(),
+			"1000"
+		);
+	}
+
+	#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+	async fn test_concurrent_readers_share_one_refresh() {
+		let mut mock = MockDeliveryInterface::new();
+		mock.expect_get_gas_price().times(1).returning(|_| {
+			// Hold the read open long enough for every reader to arrive
+			std::thread::sleep(Duration::from_millis(100));
+			Ok("1000000000".to_string())
+		});
+		mock.expect_get_block_number()
+			.times(1)
+			.returning(|_| Ok(100));
+		mock.expect_get_fee_history()
+			.times(1)
+			.returning(|_, _, _| Err(DeliveryError::Network("unsupported".to_string())));
+		let service = Arc::new(service(mock, 30));
+
+		let readers: Vec<_> = (0..8)
+			.map(|_| {
+				let service = service.clone();
+				tokio::spawn(async move { service.chain_data(CHAIN_ID).await })
+			})
+			.collect();
+		for reader in readers {
+			assert_eq!(reader.await.unwrap().unwrap().block_number, 100);
+		}
+	}
+
+	#[tokio::test]
+	async fn test_settled_transaction_invalidates_its_chain() {
+		let service = Arc::new(service(mock_chain(1), 30));
+		service.chain_data(CHAIN_ID).await.unwrap();
+
+		let (sender, receiver) = broadcast::channel(16);
+		let runner = {
+			let service = service.clone();
+			tokio::spawn(async move { service.run(receiver).await })
+		};
+
+		let tx_hash = solver_types::TransactionHash(vec![0xab; 32]);
+		sender
+			.send(SolverEvent::Delivery(DeliveryEvent::TransactionPending {
+				order_id: "order".to_string(),
+				tx_hash: tx_hash.clone(),
+				tx_type: TransactionType::Fill,
+				tx_chain_id: CHAIN_ID,
+			}))
+			.unwrap();
+		sender
+			.send(SolverEvent::Delivery(DeliveryEvent::TransactionFailed {
+				order_id: "order".to_string(),
+				tx_hash,
+				tx_type: TransactionType::Fill,
+				error: "reverted".to_string(),
+			}))
+			.unwrap();
+		drop(sender);
+		runner.await.unwrap();
+
+		assert!(service.snapshot(CHAIN_ID).is_none());
+	}
+}
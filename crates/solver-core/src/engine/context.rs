@@ -1,14 +1,13 @@
 //! Execution context utilities for the OIF solver system.
 //!
 //! This module provides utilities for building execution contexts by extracting
-//! chain information from intents and fetching real-time blockchain data such as
-//! gas prices and solver balances.
+//! chain information from intents and reading blockchain data such as gas prices
+//! and solver balances from the chain-state cache.
 
-use super::token_manager::TokenManager;
+use super::{chain_state::ChainStateService, token_manager::TokenManager};
 use crate::SolverError;
 use alloy_primitives::hex;
 use solver_config::Config;
-use solver_delivery::DeliveryService;
 use solver_types::{Address, ExecutionContext, Intent};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -17,27 +16,27 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 /// Execution context builder for the solver engine.
 ///
 /// This struct provides methods to build chain-aware execution contexts
-/// by extracting chain information from intents and fetching real-time data.
+/// by extracting chain information from intents and reading cached chain state.
 pub struct ContextBuilder {
-	delivery: Arc<DeliveryService>,
+	chain_state: Arc<ChainStateService>,
 	solver_address: Address,
 	token_manager: Arc<TokenManager>,
-	_config: Config,
+	config: Config,
 }
 
 impl ContextBuilder {
 	/// Creates a new context builder.
 	pub fn new(
-		delivery: Arc<DeliveryService>,
+		chain_state: Arc<ChainStateService>,
 		solver_address: Address,
 		token_manager: Arc<TokenManager>,
 		config: Config,
 	) -> Self {
 		Self {
-			delivery,
+			chain_state,
 			solver_address,
 			token_manager,
-			_config: config,
+			config,
 		}
 	}
 
@@ -69,7 +68,7 @@ impl ContextBuilder {
 		// 2. Fetch chain data for each relevant chain
 		let mut chain_data = HashMap::new();
 		for chain_id in &involved_chains {
-			if let Ok(data) = self.delivery.get_chain_data(*chain_id).await {
+			if let Ok(data) = self.chain_state.chain_data(*chain_id).await {
 				chain_data.insert(*chain_id, data);
 			} else {
 				tracing::warn!(
@@ -86,6 +85,11 @@ impl ContextBuilder {
 		Ok(ExecutionContext {
 			chain_data,
 			solver_balances,
+			in_flight: Default::default(),
+			order_exposure: None,
+			profit_margin_pct: None,
+			fill_urgency: self.config.fee_strategy().fill,
+			quoted: false,
 			timestamp,
 		})
 	}
@@ -248,8 +252,8 @@ impl ContextBuilder {
 		for &chain_id in chains {
 			// Get native token balance
 			match self
-				.delivery
-				.get_balance(chain_id, &solver_address, None)
+				.chain_state
+				.balance(chain_id, &solver_address, None)
 				.await
 			{
 				Ok(balance) => {
@@ -268,8 +272,8 @@ impl ContextBuilder {
 			let common_tokens = self.get_common_tokens_for_chain(chain_id);
 			for token_address in common_tokens {
 				match self
-					.delivery
-					.get_balance(chain_id, &solver_address, Some(&token_address))
+					.chain_state
+					.balance(chain_id, &solver_address, Some(&token_address))
 					.await
 				{
 					Ok(balance) => {
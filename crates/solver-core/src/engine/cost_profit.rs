@@ -5,21 +5,28 @@
 //! - Calculating profit margins for orders and validating profitability thresholds
 //! - Unified service combining cost estimation and profitability validation
 
+use crate::engine::chain_state::ChainStateService;
+use crate::engine::fee_model::L1FeeEstimator;
+use crate::engine::gas_model::{GasModel, GasUsageKey};
 use crate::engine::token_manager::{TokenManager, TokenManagerError};
 use alloy_primitives::U256;
 use rust_decimal::Decimal;
 use solver_config::Config;
 use solver_delivery::DeliveryService;
+use solver_order::OrderService;
 use solver_pricing::PricingService;
+use solver_settlement::SettlementService;
 use solver_types::{
 	costs::{CostComponent, CostEstimate},
 	current_timestamp, APIError, Address, ApiErrorType, AvailableInput, ExecutionParams, FillProof,
-	Order, Quote, RequestedOutput, Transaction, TransactionHash, DEFAULT_GAS_PRICE_WEI,
+	NativeCurrency, Order, Quote, RequestedOutput, Transaction, TransactionHash, TransactionType,
+	DEFAULT_GAS_PRICE_WEI,
 };
 use std::{str::FromStr, sync::Arc};
 use thiserror::Error;
 
-const HUNDRED: i64 = 10000;
+/// Basis points in one whole.
+const BPS_DENOMINATOR: i64 = 10_000;
 
 #[derive(Debug, Error)]
 pub enum CostProfitError {
@@ -29,6 +36,8 @@ pub enum CostProfitError {
 	Calculation(String),
 	#[error("Configuration error: {0}")]
 	Config(String),
+	#[error("Pricing error: {0}")]
+	Pricing(String),
 	#[error("Token manager error: {0}")]
 	TokenManager(#[from] TokenManagerError),
 }
@@ -46,6 +55,19 @@ struct GasUnits {
 	claim_units: u64,
 }
 
+/// L1 data fees of the fill and claim, in the native smallest unit of their chains
+struct L1Fees {
+	fill: U256,
+	claim: U256,
+}
+
+/// Components priced from the value of the inputs and outputs, in USD
+struct PricingComponents {
+	base_price_usd: Decimal,
+	min_profit_usd: Decimal,
+	buffer_rates_usd: Decimal,
+}
+
 /// Unified service for cost estimation and profitability calculation.
 pub struct CostProfitService {
 	/// Pricing service for USD conversions and asset pricing
@@ -54,6 +76,16 @@ pub struct CostProfitService {
 	delivery_service: Arc<DeliveryService>,
 	/// Token manager for token configuration lookups
 	token_manager: Arc<TokenManager>,
+	/// Settlement service for messaging fee estimates
+	settlement_service: Arc<SettlementService>,
+	/// Order service for building the fill and claim calldata rollups charge for
+	order_service: Arc<OrderService>,
+	/// Estimator for rollup L1 data fees
+	l1_fees: L1FeeEstimator,
+	/// Gas units learned from confirmed transactions
+	gas_model: Arc<GasModel>,
+	/// Cached chain data for gas prices
+	chain_state: Arc<ChainStateService>,
 }
 
 impl CostProfitService {
@@ -62,11 +94,20 @@ impl CostProfitService {
 		pricing_service: Arc<PricingService>,
 		delivery_service: Arc<DeliveryService>,
 		token_manager: Arc<TokenManager>,
+		settlement_service: Arc<SettlementService>,
+		order_service: Arc<OrderService>,
+		gas_model: Arc<GasModel>,
+		chain_state: Arc<ChainStateService>,
 	) -> Self {
 		Self {
 			pricing_service,
+			l1_fees: L1FeeEstimator::new(delivery_service.clone()),
 			delivery_service,
 			token_manager,
+			settlement_service,
+			order_service,
+			gas_model,
+			chain_state,
 		}
 	}
 
@@ -127,15 +168,28 @@ impl CostProfitService {
 				config,
 				chain_params.origin_chain_id,
 				chain_params.dest_chain_id,
+				quote.details.requested_outputs.len(),
 			)
 			.await?;
 
+		// Estimate rollup L1 data fees
+		let l1_fees = self.estimate_l1_fees(&order, &chain_params, config).await?;
+
+		// Prices may move until the quote expires and the order is filled
+		let horizon_seconds = quote
+			.valid_until
+			.map(|valid_until| valid_until.saturating_sub(current_timestamp()))
+			.unwrap_or(0)
+			+ quote.eta.unwrap_or(0);
+
 		// Calculate cost components
 		self.calculate_cost_components(
 			chain_params,
 			gas_units,
+			l1_fees,
 			&quote.details.available_inputs,
 			&quote.details.requested_outputs,
+			Some(horizon_seconds),
 			config,
 		)
 		.await
@@ -175,6 +229,8 @@ impl CostProfitService {
 
 		// Extract flow key (lock_type) for gas config lookup
 		let flow_key = order_parsed.parse_lock_type();
+		let available_inputs = order_parsed.parse_available_inputs();
+		let requested_outputs = order_parsed.parse_requested_outputs();
 
 		// Estimate gas units
 		let gas_units = self
@@ -184,17 +240,21 @@ impl CostProfitService {
 				config,
 				chain_params.origin_chain_id,
 				chain_params.dest_chain_id,
+				requested_outputs.len(),
 			)
 			.await?;
 
-		// Calculate cost components
-		let available_inputs = order_parsed.parse_available_inputs();
-		let requested_outputs = order_parsed.parse_requested_outputs();
+		// Estimate rollup L1 data fees
+		let l1_fees = self.estimate_l1_fees(order, &chain_params, config).await?;
+
+		// Calculate cost components; the order's price risk was taken when it was quoted
 		self.calculate_cost_components(
 			chain_params,
 			gas_units,
+			l1_fees,
 			&available_inputs,
 			&requested_outputs,
+			None,
 			config,
 		)
 		.await
@@ -384,6 +444,11 @@ impl CostProfitService {
 				.await
 				.map_err(|e| match e {
 					CostProfitError::Api(api_error) => api_error,
+					CostProfitError::Pricing(message) => APIError::ServiceUnavailable {
+						error_type: ApiErrorType::ServiceError,
+						message: format!("Cost estimation failed: {}", message),
+						retry_after: None,
+					},
 					other => APIError::InternalServerError {
 						error_type: ApiErrorType::InternalError,
 						message: format!("Cost estimation failed: {}", other),
@@ -406,6 +471,9 @@ impl CostProfitService {
 	}
 
 	/// Estimate gas units with optional live estimation
+	///
+	/// Units learned from confirmed transactions of the same flow, chain and
+	/// number of outputs replace the configured ones once they have enough samples.
 	async fn estimate_gas_units(
 		&self,
 		order: &Order,
@@ -413,15 +481,44 @@ impl CostProfitService {
 		config: &Config,
 		origin_chain_id: u64,
 		dest_chain_id: u64,
+		outputs: usize,
 	) -> Result<GasUnits, CostProfitError> {
 		// TODO: For now, we'll use a simple check for live gas estimation and pass it as a parameter
 		// in the future we should use the config.gas.enable_live_gas_estimate
 		let enable_live_gas_estimate = false;
 
 		// Get base units from config
-		let (open_units, mut fill_units, mut claim_units) =
+		let (mut open_units, mut fill_units, mut claim_units) =
 			estimate_gas_units_from_config(flow_key, config, 0, 0, 0);
 
+		// Prefer units learned from our own receipts
+		if let Some(flow) = flow_key {
+			let learned = |tx_type, chain_id| {
+				self.gas_model.estimate(&GasUsageKey {
+					flow: flow.clone(),
+					chain_id,
+					tx_type,
+					outputs,
+				})
+			};
+			if let Some(units) = learned(TransactionType::Prepare, origin_chain_id) {
+				open_units = units;
+			}
+			if let Some(units) = learned(TransactionType::Fill, dest_chain_id) {
+				fill_units = units;
+			}
+			if let Some(units) = learned(TransactionType::Claim, origin_chain_id) {
+				claim_units = units;
+			}
+			tracing::debug!(
+				flow = %flow,
+				open = open_units,
+				fill = fill_units,
+				claim = claim_units,
+				"Gas units after applying learned estimates"
+			);
+		}
+
 		// Live estimation if enabled
 		if enable_live_gas_estimate {
 			// Estimate fill gas
@@ -486,6 +583,83 @@ impl CostProfitService {
 		})
 	}
 
+	/// Estimates the L1 data fees of the fill and claim under each chain's fee model.
+	///
+	/// The calldata is generated by the order's standard, with a placeholder
+	/// fill proof for the claim, so the claim fee is approximate. Chains
+	/// without an L1 fee are skipped.
+	async fn estimate_l1_fees(
+		&self,
+		order: &Order,
+		chain_params: &ChainParams,
+		config: &Config,
+	) -> Result<L1Fees, CostProfitError> {
+		let fee_model = |chain_id: u64| {
+			config
+				.networks
+				.get(&chain_id)
+				.map(|network| &network.fee_model)
+				.ok_or_else(|| {
+					CostProfitError::Config(format!("Network {} not configured", chain_id))
+				})
+		};
+		let dest_model = fee_model(chain_params.dest_chain_id)?;
+		let origin_model = fee_model(chain_params.origin_chain_id)?;
+
+		let mut fees = L1Fees {
+			fill: U256::ZERO,
+			claim: U256::ZERO,
+		};
+		if dest_model.has_l1_fee() {
+			let params = ExecutionParams {
+				gas_price: U256::from(DEFAULT_GAS_PRICE_WEI),
+				priority_fee: None,
+			};
+			let fill_tx = self
+				.order_service
+				.generate_fill_transaction(order, &params)
+				.await
+				.map_err(|e| {
+					CostProfitError::Calculation(format!("Failed to build fill calldata: {}", e))
+				})?;
+			fees.fill = self
+				.l1_fees
+				.estimate(dest_model, &fill_tx)
+				.await
+				.map_err(|e| {
+					CostProfitError::Calculation(format!("Failed to estimate fill L1 fee: {}", e))
+				})?;
+		}
+		if origin_model.has_l1_fee() {
+			// The real proof only exists once the fill is mined, so the claim
+			// calldata is built from a dummy one. Attestation data and other
+			// proof-dependent fields may differ in length from the real claim,
+			// making the claim L1 fee an approximation.
+			let proof = FillProof {
+				oracle_address: "0x0000000000000000000000000000000000000000".to_string(),
+				filled_timestamp: current_timestamp(),
+				block_number: 1,
+				tx_hash: TransactionHash(vec![0u8; 32]),
+				attestation_data: Some(vec![]),
+			};
+			let claim_tx = self
+				.order_service
+				.generate_claim_transaction(order, &proof)
+				.await
+				.map_err(|e| {
+					CostProfitError::Calculation(format!("Failed to build claim calldata: {}", e))
+				})?;
+			fees.claim = self
+				.l1_fees
+				.estimate(origin_model, &claim_tx)
+				.await
+				.map_err(|e| {
+					CostProfitError::Calculation(format!("Failed to estimate claim L1 fee: {}", e))
+				})?;
+		}
+		Ok(fees)
+	}
+
 	/// Build fill transaction for gas estimation
 	async fn build_fill_tx_for_estimation(&self, order: &Order) -> Result<Transaction, APIError> {
 		// Create execution params for estimation
@@ -505,6 +679,7 @@ impl CostProfitService {
 
 		Ok(Transaction {
 			chain_id,
+			from: None,
 			to: None,     // Will be filled by actual implementation
 			data: vec![], // Minimal data for estimation
 			gas_price: Some(params.gas_price.try_into().unwrap_or(u128::MAX)),
@@ -540,6 +715,7 @@ impl CostProfitService {
 
 		Ok(Transaction {
 			chain_id,
+			from: None,
 			to: None,     // Will be filled by actual implementation
 			data: vec![], // Minimal data for estimation
 			gas_price: Some(DEFAULT_GAS_PRICE_WEI as u128),
@@ -553,12 +729,18 @@ impl CostProfitService {
 	}
 
 	/// Calculate cost components for the order
+	///
+	/// `horizon_seconds` is how long prices may move before the order is
+	/// filled; without one the rate buffer is not scaled by volatility.
+	#[allow(clippy::too_many_arguments)]
 	async fn calculate_cost_components(
 		&self,
 		chain_params: ChainParams,
 		gas_units: GasUnits,
+		l1_fees: L1Fees,
 		available_inputs: &[AvailableInput],
 		requested_outputs: &[RequestedOutput],
+		horizon_seconds: Option<u64>,
 		config: &Config,
 	) -> Result<CostEstimate, CostProfitError> {
 		let pricing = self.pricing_service.config();
@@ -569,71 +751,103 @@ impl CostProfitService {
 			.await?;
 		let dest_gp = self.get_chain_gas_price(chain_params.dest_chain_id).await?;
 
-		// Calculate gas costs in wei
+		// Each leg pays gas in its own chain's native asset
+		let origin_native = Self::native_currency(config, chain_params.origin_chain_id)?;
+		let dest_native = Self::native_currency(config, chain_params.dest_chain_id)?;
+
+		// Calculate gas costs in the smallest unit of each chain's native asset
 		let open_cost_wei = origin_gp.saturating_mul(U256::from(gas_units.open_units));
 		let fill_cost_wei = dest_gp.saturating_mul(U256::from(gas_units.fill_units));
 		let claim_cost_wei = origin_gp.saturating_mul(U256::from(gas_units.claim_units));
 
-		// Convert wei amounts to display currency
+		// Rollup L1 data fees are paid alongside execution gas on the same chain
+		let fill_cost_wei = fill_cost_wei.saturating_add(l1_fees.fill);
+		let claim_cost_wei = claim_cost_wei.saturating_add(l1_fees.claim);
+
+		// Convert native amounts to display currency
 		let open_cost_currency = self
-			.pricing_service
-			.wei_to_currency(&open_cost_wei.to_string(), &pricing.currency)
-			.await
-			.unwrap_or_else(|_| "0".to_string());
+			.native_to_currency(&open_cost_wei, origin_native, &pricing.currency)
+			.await?;
 		let fill_cost_currency = self
-			.pricing_service
-			.wei_to_currency(&fill_cost_wei.to_string(), &pricing.currency)
-			.await
-			.unwrap_or_else(|_| "0".to_string());
+			.native_to_currency(&fill_cost_wei, dest_native, &pricing.currency)
+			.await?;
 		let claim_cost_currency = self
-			.pricing_service
-			.wei_to_currency(&claim_cost_wei.to_string(), &pricing.currency)
-			.await
-			.unwrap_or_else(|_| "0".to_string());
-
-		// Calculate gas buffer using Decimal arithmetic
-		let gas_subtotal_wei = open_cost_wei + fill_cost_wei + claim_cost_wei;
-		let bps_decimal = Decimal::new(pricing.gas_buffer_bps as i64, 0);
-		let bps_divisor = Decimal::new(10000, 0); // 10000 basis points = 100%
-
-		let gas_subtotal_decimal =
-			Decimal::from_str(&gas_subtotal_wei.to_string()).unwrap_or(Decimal::ZERO);
-		let buffer_gas_decimal = (gas_subtotal_decimal * bps_decimal) / bps_divisor;
-		let buffer_gas_wei = buffer_gas_decimal.to_string();
+			.native_to_currency(&claim_cost_wei, origin_native, &pricing.currency)
+			.await?;
 
-		let buffer_gas_currency = self
-			.pricing_service
-			.wei_to_currency(&buffer_gas_wei, &pricing.currency)
+		// Messaging fee paid on the destination chain (e.g. Hyperlane IGP payments)
+		let settlement_fee_wei = self
+			.settlement_service
+			.estimate_settlement_fee(chain_params.origin_chain_id, chain_params.dest_chain_id)
 			.await
-			.unwrap_or_else(|_| "0".to_string());
+			.map_err(|e| {
+				CostProfitError::Calculation(format!("Failed to estimate settlement fee: {}", e))
+			})?
+			.unwrap_or(U256::ZERO);
+		let settlement_fee_currency = if settlement_fee_wei.is_zero() {
+			"0".to_string()
+		} else {
+			self.native_to_currency(&settlement_fee_wei, dest_native, &pricing.currency)
+				.await?
+		};
 
-		// Calculate base price and minimum profit requirement in USD
-		let (base_price_usd, min_profit_usd) = self
-			.calculate_pricing_components(available_inputs, requested_outputs, config)
+		// Calculate gas buffer per chain, as the legs may pay gas in different assets
+		let bps = U256::from(pricing.gas_buffer_bps);
+		let bps_divisor = U256::from(10000); // 10000 basis points = 100%
+		let origin_buffer_wei = open_cost_wei
+			.saturating_add(claim_cost_wei)
+			.saturating_mul(bps)
+			/ bps_divisor;
+		let dest_buffer_wei = fill_cost_wei.saturating_mul(bps) / bps_divisor;
+
+		let origin_buffer_currency = self
+			.native_to_currency(&origin_buffer_wei, origin_native, &pricing.currency)
+			.await?;
+		let dest_buffer_currency = self
+			.native_to_currency(&dest_buffer_wei, dest_native, &pricing.currency)
+			.await?;
+		let buffer_gas_currency = (parse_amount("buffer-gas", &origin_buffer_currency)?
+			+ parse_amount("buffer-gas", &dest_buffer_currency)?)
+		.to_string();
+		// A single native amount is only meaningful when both legs pay in the same asset
+		let buffer_gas_wei = (origin_native == dest_native).then(|| {
+			origin_buffer_wei
+				.saturating_add(dest_buffer_wei)
+				.to_string()
+		});
+
+		// Calculate base price, minimum profit requirement and rate buffer in USD
+		let PricingComponents {
+			base_price_usd,
+			min_profit_usd,
+			buffer_rates_usd,
+		} = self
+			.calculate_pricing_components(
+				available_inputs,
+				requested_outputs,
+				horizon_seconds,
+				config,
+			)
 			.await
-			.unwrap_or_else(|e| {
-				tracing::warn!("Failed to calculate pricing components: {}", e);
-				("0".to_string(), "0".to_string())
-			});
-
-		// Calculate buffer rates (currently 0 since we don't have rate buffers implemented)
-		let buffer_rates = "0".to_string();
+			.map_err(|e| {
+				CostProfitError::Pricing(format!("Failed to calculate pricing components: {}", e))
+			})?;
 
-		// Calculate operational costs (gas + buffers only) in USD
-		let operational_cost_usd = Decimal::from_str(&open_cost_currency).unwrap_or(Decimal::ZERO)
-			+ Decimal::from_str(&fill_cost_currency).unwrap_or(Decimal::ZERO)
-			+ Decimal::from_str(&claim_cost_currency).unwrap_or(Decimal::ZERO)
-			+ Decimal::from_str(&buffer_gas_currency).unwrap_or(Decimal::ZERO);
+		// Calculate operational costs (gas + settlement fees + buffers) in USD
+		let operational_cost_usd = parse_amount("gas-open", &open_cost_currency)?
+			+ parse_amount("gas-fill", &fill_cost_currency)?
+			+ parse_amount("gas-claim", &claim_cost_currency)?
+			+ parse_amount("settlement-fee", &settlement_fee_currency)?
+			+ parse_amount("buffer-gas", &buffer_gas_currency)?
+			+ buffer_rates_usd;
 
 		// Calculate subtotal: all cost components before commission
-		let subtotal_usd = operational_cost_usd
-			+ Decimal::from_str(&base_price_usd).unwrap_or(Decimal::ZERO)
-			+ Decimal::from_str(&min_profit_usd).unwrap_or(Decimal::ZERO);
+		let subtotal_usd = operational_cost_usd + base_price_usd + min_profit_usd;
 
 		// Calculate commission on the subtotal
 		let commission_amount_usd = if pricing.commission_bps > 0 {
 			let commission_bps = Decimal::new(pricing.commission_bps as i64, 0);
-			let commission_divisor = Decimal::new(HUNDRED, 0);
+			let commission_divisor = Decimal::new(BPS_DENOMINATOR, 0);
 			(subtotal_usd * commission_bps) / commission_divisor
 		} else {
 			Decimal::ZERO
@@ -665,15 +879,20 @@ impl CostProfitService {
 					amount: claim_cost_currency,
 					amount_wei: Some(claim_cost_wei.to_string()),
 				},
+				CostComponent {
+					name: "settlement-fee".into(),
+					amount: settlement_fee_currency,
+					amount_wei: Some(settlement_fee_wei.to_string()),
+				},
 				CostComponent {
 					name: "buffer-gas".into(),
 					amount: buffer_gas_currency,
-					amount_wei: Some(buffer_gas_wei),
+					amount_wei: buffer_gas_wei,
 				},
 				CostComponent {
 					name: "buffer-rates".into(),
-					amount: buffer_rates.clone(),
-					amount_wei: Some(buffer_rates),
+					amount: buffer_rates_usd.to_string(),
+					amount_wei: None,
 				},
 				CostComponent {
 					name: "min-profit".into(),
@@ -693,15 +912,20 @@ impl CostProfitService {
 		})
 	}
 
-	/// Calculate the base price and minimum profit requirement in USD
+	/// Calculate the base price, minimum profit requirement and rate buffer in USD
+	///
+	/// The rate buffer is a share of the output value that protects swaps
+	/// between different assets against price moves over `horizon_seconds`.
 	async fn calculate_pricing_components(
 		&self,
 		available_inputs: &[AvailableInput],
 		requested_outputs: &[RequestedOutput],
+		horizon_seconds: Option<u64>,
 		config: &Config,
-	) -> Result<(String, String), Box<dyn std::error::Error>> {
+	) -> Result<PricingComponents, Box<dyn std::error::Error>> {
 		// Calculate total input value in USD
 		let mut total_input_value_usd = Decimal::ZERO;
+		let mut input_symbols = Vec::new();
 		for input in available_inputs.iter() {
 			let chain_id = input
 				.asset
@@ -717,6 +941,7 @@ impl CostProfitService {
 				.token_manager
 				.get_token_info(chain_id, &token_address)
 				.map_err(|e| format!("Failed to get input token info: {}", e))?;
+			input_symbols.push(token_info.symbol.clone());
 
 			let usd_amount = Self::convert_raw_token_to_usd(
 				&input.amount,
@@ -731,6 +956,7 @@ impl CostProfitService {
 
 		// Calculate total output value in USD
 		let mut total_output_value_usd = Decimal::ZERO;
+		let mut output_symbols = Vec::new();
 		for output in requested_outputs.iter() {
 			let chain_id = output
 				.asset
@@ -746,6 +972,7 @@ impl CostProfitService {
 				.token_manager
 				.get_token_info(chain_id, &token_address)
 				.map_err(|e| format!("Failed to get output token info: {}", e))?;
+			output_symbols.push(token_info.symbol.clone());
 
 			let usd_amount = Self::convert_raw_token_to_usd(
 				&output.amount,
@@ -774,28 +1001,69 @@ impl CostProfitService {
 		let min_required_profit =
 			(transaction_value * config.solver.min_profitability_pct) / hundred;
 
+		// Buffer the conversion between input and output assets
+		let buffer_rates_usd = if input_symbols != output_symbols {
+			let buffer_bps = self
+				.pricing_service
+				.rate_buffer_bps(&input_symbols, &output_symbols, horizon_seconds)
+				.await;
+			rate_buffer_usd(total_output_value_usd, buffer_bps)
+		} else {
+			Decimal::ZERO
+		};
+
 		tracing::info!(
-            "Pricing components: input_value={}, output_value={}, spread={}, base_price={}, min_profit={}",
+            "Pricing components: input_value={}, output_value={}, spread={}, base_price={}, min_profit={}, buffer_rates={}",
             total_input_value_usd,
             total_output_value_usd,
             spread,
             base_price_usd,
-            min_required_profit
+            min_required_profit,
+            buffer_rates_usd
         );
 
-		Ok((base_price_usd.to_string(), min_required_profit.to_string()))
+		Ok(PricingComponents {
+			base_price_usd,
+			min_profit_usd: min_required_profit,
+			buffer_rates_usd,
+		})
+	}
+
+	/// Returns the native asset gas is paid in on a chain.
+	fn native_currency(config: &Config, chain_id: u64) -> Result<&NativeCurrency, CostProfitError> {
+		config
+			.networks
+			.get(&chain_id)
+			.map(|network| &network.native_currency)
+			.ok_or_else(|| CostProfitError::Config(format!("Network {} not configured", chain_id)))
+	}
+
+	/// Converts a native asset amount to the display currency, failing when no price is available.
+	async fn native_to_currency(
+		&self,
+		amount: &U256,
+		native: &NativeCurrency,
+		currency: &str,
+	) -> Result<String, CostProfitError> {
+		self.pricing_service
+			.native_to_currency(&amount.to_string(), native, currency)
+			.await
+			.map_err(|e| {
+				CostProfitError::Pricing(format!(
+					"Failed to convert {} {} (smallest unit) to {}: {}",
+					amount, native.symbol, currency, e
+				))
+			})
 	}
 
 	/// Gets the gas price for a specific chain
 	async fn get_chain_gas_price(&self, chain_id: u64) -> Result<U256, APIError> {
-		let chain_data = self
-			.delivery_service
-			.get_chain_data(chain_id)
-			.await
-			.map_err(|e| APIError::InternalServerError {
+		let chain_data = self.chain_state.chain_data(chain_id).await.map_err(|e| {
+			APIError::InternalServerError {
 				error_type: ApiErrorType::ServiceError,
 				message: format!("Failed to get chain data: {}", e),
-			})?;
+			}
+		})?;
 
 		match U256::from_str_radix(&chain_data.gas_price, 10) {
 			Ok(gas_price) => Ok(gas_price),
@@ -804,7 +1072,7 @@ impl CostProfitService {
 	}
 
 	/// Converts a raw token amount to USD, handling decimals normalization.
-	async fn convert_raw_token_to_usd(
+	pub(crate) async fn convert_raw_token_to_usd(
 		raw_amount: &U256,
 		token_symbol: &str,
 		token_decimals: u8,
@@ -839,11 +1107,29 @@ impl CostProfitService {
 			.await
 			.map_err(|e| format!("Failed to convert {} to USD: {}", token_symbol, e))?;
 
-		Decimal::from_str(&usd_amount_str)
-			.map_err(|e| format!("Failed to parse USD amount {}: {}", usd_amount_str, e).into())
+		let usd_amount = Decimal::from_str(&usd_amount_str)
+			.map_err(|e| format!("Failed to parse USD amount {}: {}", usd_amount_str, e))?;
+
+		// Fetched prices feed the history the volatility is measured from
+		if normalized_amount > Decimal::ZERO {
+			pricing_service.record_price(token_symbol, usd_amount / normalized_amount);
+		}
+		Ok(usd_amount)
 	}
 }
 
+/// Returns the rate buffer in USD for an output value and a buffer in basis points.
+fn rate_buffer_usd(output_value_usd: Decimal, buffer_bps: u32) -> Decimal {
+	(output_value_usd * Decimal::from(buffer_bps)) / Decimal::new(BPS_DENOMINATOR, 0)
+}
+
+/// Parses a cost component amount.
+fn parse_amount(component: &str, amount: &str) -> Result<Decimal, CostProfitError> {
+	Decimal::from_str(amount).map_err(|e| {
+		CostProfitError::Calculation(format!("Invalid {} amount {}: {}", component, amount, e))
+	})
+}
+
 /// Estimates gas units using configuration flows with fallback estimates.
 pub fn estimate_gas_units_from_config(
 	flow_key: &Option<String>,
@@ -878,3 +1164,76 @@ pub fn estimate_gas_units_from_config(
 
 	(fallback_open, fallback_fill, fallback_claim)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_pricing::implementations::mock;
+	use solver_types::MOCK_ETH_USD_PRICE;
+
+	fn pricing_service() -> PricingService {
+		let config = toml::Value::Table(toml::map::Map::new());
+		PricingService::new(mock::create_mock_pricing(&config).unwrap())
+	}
+
+	#[test]
+	fn test_rate_buffer_usd() {
+		assert_eq!(
+			rate_buffer_usd(Decimal::from(10_000), 14),
+			Decimal::from(14)
+		);
+		assert_eq!(
+			rate_buffer_usd(Decimal::from_str("1234.56").unwrap(), 25),
+			Decimal::from_str("3.0864").unwrap()
+		);
+		assert_eq!(rate_buffer_usd(Decimal::from(10_000), 0), Decimal::ZERO);
+		assert_eq!(
+			rate_buffer_usd(Decimal::from(500), BPS_DENOMINATOR as u32),
+			Decimal::from(500)
+		);
+	}
+
+	#[tokio::test]
+	async fn test_rate_buffer_applies_to_output_value() {
+		let pricing = pricing_service();
+		let output_value = Decimal::from(2_500);
+
+		let swap_bps = pricing
+			.rate_buffer_bps(&["ETH".to_string()], &["USDC".to_string()], Some(60))
+			.await;
+		assert_eq!(swap_bps, pricing.config().rate_buffer_bps);
+		assert_eq!(
+			rate_buffer_usd(output_value, swap_bps),
+			Decimal::from_str("3.5").unwrap()
+		);
+
+		let same_bps = pricing
+			.rate_buffer_bps(&["USDC".to_string()], &["USDC".to_string()], Some(60))
+			.await;
+		assert_eq!(rate_buffer_usd(output_value, same_bps), Decimal::ZERO);
+	}
+
+	#[tokio::test]
+	async fn test_convert_raw_token_to_usd() {
+		let pricing = pricing_service();
+		let eth_usd = Decimal::from_str(MOCK_ETH_USD_PRICE).unwrap();
+
+		let two_eth = U256::from(2_000_000_000_000_000_000u128);
+		let value = CostProfitService::convert_raw_token_to_usd(&two_eth, "ETH", 18, &pricing)
+			.await
+			.unwrap();
+		assert_eq!(value, eth_usd * Decimal::TWO);
+
+		// A zero amount has no unit price to sample
+		let value = CostProfitService::convert_raw_token_to_usd(&U256::ZERO, "ETH", 18, &pricing)
+			.await
+			.unwrap();
+		assert_eq!(value, Decimal::ZERO);
+
+		assert!(
+			CostProfitService::convert_raw_token_to_usd(&U256::from(1), "ETH", 29, &pricing)
+				.await
+				.is_err()
+		);
+	}
+}
@@ -0,0 +1,221 @@
+//! Tracking of capital fronted for orders that are not yet claimed.
+//!
+//! The `ExposureTracker` reserves the value of each order's outputs while the
+//! strategy weighs it and, if the order is executed, keeps that value
+//! outstanding until the claim confirms and the inputs are returned. A skipped
+//! or deferred order, or a failed prepare or fill, releases it, since nothing
+//! was paid out. The totals per chain, token and counterparty are handed to the
+//! execution strategy and the quote API so both can enforce exposure limits.
+
+use super::{cost_profit::CostProfitService, token_manager::TokenManager};
+use alloy_primitives::{hex, U256};
+use solver_pricing::PricingService;
+use solver_storage::{QueryFilter, StorageService};
+use solver_types::{
+	Address, ExposureLeg, ExposureSnapshot, Order, OrderExposure, OrderStatus, StorageKey,
+	TransactionType,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use thiserror::Error;
+
+/// Errors that can occur while tracking exposure.
+#[derive(Debug, Error)]
+pub enum ExposureError {
+	/// The order could not be valued.
+	#[error("Valuation error: {0}")]
+	Valuation(String),
+	/// Orders could not be loaded from storage.
+	#[error("Storage error: {0}")]
+	Storage(String),
+}
+
+/// Keeps the fronted capital of executing orders until their claims confirm.
+pub struct ExposureTracker {
+	storage: Arc<StorageService>,
+	pricing: Arc<PricingService>,
+	token_manager: Arc<TokenManager>,
+	/// Outstanding exposure by order ID.
+	orders: RwLock<HashMap<String, OrderExposure>>,
+}
+
+impl ExposureTracker {
+	/// Creates a new tracker with no outstanding exposure.
+	pub fn new(
+		storage: Arc<StorageService>,
+		pricing: Arc<PricingService>,
+		token_manager: Arc<TokenManager>,
+	) -> Self {
+		Self {
+			storage,
+			pricing,
+			token_manager,
+			orders: RwLock::new(HashMap::new()),
+		}
+	}
+
+	/// Values the outputs the solver would pay to fill an order.
+	///
+	/// The counterparty is the user of the order's first input.
+	pub async fn value_order(&self, order: &Order) -> Result<OrderExposure, ExposureError> {
+		let parsed = order
+			.parse_order_data()
+			.map_err(|e| ExposureError::Valuation(e.to_string()))?;
+		let counterparty = parsed
+			.parse_available_inputs()
+			.first()
+			.and_then(|input| input.user.ethereum_address().ok())
+			.map(|user| hex::encode(user.as_slice()))
+			.unwrap_or_default();
+
+		let mut outputs = Vec::new();
+		for output in parsed.parse_requested_outputs() {
+			let chain_id = output
+				.asset
+				.ethereum_chain_id()
+				.map_err(|e| ExposureError::Valuation(e.to_string()))?;
+			let token = output
+				.asset
+				.ethereum_address()
+				.map_err(|e| ExposureError::Valuation(e.to_string()))?;
+			outputs.push((chain_id, Address(token.to_vec()), output.amount));
+		}
+
+		Ok(OrderExposure {
+			order_id: order.id.clone(),
+			counterparty,
+			legs: self.value_outputs(&outputs).await?,
+		})
+	}
+
+	/// Values token amounts paid on destination chains in USD.
+	pub async fn value_outputs(
+		&self,
+		outputs: &[(u64, Address, U256)],
+	) -> Result<Vec<ExposureLeg>, ExposureError> {
+		let mut legs = Vec::with_capacity(outputs.len());
+		for (chain_id, token, amount) in outputs {
+			let token_info = self
+				.token_manager
+				.get_token_info(*chain_id, token)
+				.map_err(|e| ExposureError::Valuation(e.to_string()))?;
+			let value_usd = CostProfitService::convert_raw_token_to_usd(
+				amount,
+				&token_info.symbol,
+				token_info.decimals,
+				&self.pricing,
+			)
+			.await
+			.map_err(|e| ExposureError::Valuation(e.to_string()))?;
+			legs.push(ExposureLeg {
+				chain_id: *chain_id,
+				token: hex::encode(&token.0),
+				amount: *amount,
+				value_usd,
+			});
+		}
+		Ok(legs)
+	}
+
+	/// Records the exposure of an order the solver is about to fill.
+	pub fn record(&self, exposure: OrderExposure) {
+		tracing::debug!(
+			order_id = %exposure.order_id,
+			value_usd = %exposure.value_usd(),
+			"Recording order exposure"
+		);
+		self.orders
+			.write()
+			.unwrap()
+			.insert(exposure.order_id.clone(), exposure);
+	}
+
+	/// Reserves the exposure of an order before the strategy weighs it.
+	///
+	/// Returns the outstanding exposure of all other orders, read under the
+	/// same lock as the reservation, so orders evaluated concurrently each see
+	/// the others' capital. The reservation must be released if the order is
+	/// not executed.
+	pub fn reserve(&self, exposure: OrderExposure) -> ExposureSnapshot {
+		let mut orders = self.orders.write().unwrap();
+		let mut in_flight = ExposureSnapshot::default();
+		for (order_id, other) in orders.iter() {
+			if *order_id != exposure.order_id {
+				in_flight.add(other);
+			}
+		}
+
+		tracing::debug!(
+			order_id = %exposure.order_id,
+			value_usd = %exposure.value_usd(),
+			"Reserving order exposure"
+		);
+		orders.insert(exposure.order_id.clone(), exposure);
+		in_flight
+	}
+
+	/// Releases the exposure of an order once its capital is back or was never spent.
+	pub fn release(&self, order_id: &str) {
+		if let Some(exposure) = self.orders.write().unwrap().remove(order_id) {
+			tracing::debug!(
+				order_id = %order_id,
+				value_usd = %exposure.value_usd(),
+				"Released order exposure"
+			);
+		}
+	}
+
+	/// Returns the outstanding exposure summed per chain, token and counterparty.
+	pub fn snapshot(&self) -> ExposureSnapshot {
+		let mut snapshot = ExposureSnapshot::default();
+		for exposure in self.orders.read().unwrap().values() {
+			snapshot.add(exposure);
+		}
+		snapshot
+	}
+
+	/// Rebuilds outstanding exposure from stored orders after a restart.
+	///
+	/// Orders that were prepared or filled but never claimed are valued again
+	/// at current prices. Orders that could not be valued are skipped.
+	pub async fn restore(&self) -> Result<usize, ExposureError> {
+		let settled_statuses = [
+			OrderStatus::Created,
+			OrderStatus::Finalized,
+			OrderStatus::Failed(TransactionType::Prepare),
+			OrderStatus::Failed(TransactionType::Fill),
+		]
+		.iter()
+		.map(|status| {
+			serde_json::to_value(status).expect("OrderStatus serialization should not fail")
+		})
+		.collect();
+
+		let orders = self
+			.storage
+			.query::<Order>(
+				StorageKey::Orders.as_str(),
+				QueryFilter::NotIn("status".to_string(), settled_statuses),
+			)
+			.await
+			.map_err(|e| ExposureError::Storage(e.to_string()))?;
+
+		let mut restored = 0;
+		for (_, order) in orders {
+			match self.value_order(&order).await {
+				Ok(exposure) => {
+					self.record(exposure);
+					restored += 1;
+				},
+				Err(e) => {
+					tracing::warn!(
+						order_id = %order.id,
+						error = %e,
+						"Failed to value exposure of recovered order"
+					);
+				},
+			}
+		}
+		Ok(restored)
+	}
+}
@@ -0,0 +1,303 @@
+//! Estimation of the L1 data fee rollups charge on top of execution gas.
+//!
+//! On OP-stack and Arbitrum chains a transaction also pays for posting its
+//! data to the parent chain, often more than its execution gas. The fee is
+//! read from the chain's own oracle for the calldata of the transaction, as
+//! selected by the `fee_model` of the network.
+
+use alloy_primitives::U256;
+use alloy_sol_types::{sol, SolCall};
+use solver_delivery::DeliveryService;
+use solver_types::{Address, FeeModel, Transaction};
+use std::sync::Arc;
+use thiserror::Error;
+
+sol! {
+	/// OP-stack GasPriceOracle predeploy.
+	interface IGasPriceOracle {
+		function getL1Fee(bytes memory data) external view returns (uint256);
+	}
+
+	/// Arbitrum NodeInterface precompile.
+	interface INodeInterface {
+		function gasEstimateL1Component(address to, bool contractCreation, bytes calldata data)
+			external
+			payable
+			returns (uint64 gasEstimateForL1, uint256 baseFee, uint256 l1BaseFeeEstimate);
+	}
+}
+
+/// Errors that can occur while estimating L1 fees.
+#[derive(Debug, Error)]
+pub enum FeeModelError {
+	/// The fee oracle could not be called.
+	#[error("Delivery error: {0}")]
+	Delivery(String),
+	/// The fee oracle returned data that could not be decoded.
+	#[error("Decode error: {0}")]
+	Decode(String),
+}
+
+/// Estimates L1 data fees through each chain's fee oracle.
+pub struct L1FeeEstimator {
+	delivery: Arc<DeliveryService>,
+}
+
+impl L1FeeEstimator {
+	/// Creates a new estimator that calls fee oracles through the delivery service.
+	pub fn new(delivery: Arc<DeliveryService>) -> Self {
+		Self { delivery }
+	}
+
+	/// Returns the L1 data fee of a transaction, in the chain's native smallest unit.
+	///
+	/// The oracles are given the calldata alone; the signature and transaction
+	/// envelope add about a hundred bytes that are left to the gas buffer.
+	pub async fn estimate(
+		&self,
+		model: &FeeModel,
+		tx: &Transaction,
+	) -> Result<U256, FeeModelError> {
+		match model {
+			FeeModel::L1 => Ok(U256::ZERO),
+			FeeModel::OpStack { gas_price_oracle } => {
+				let data = IGasPriceOracle::getL1FeeCall {
+					data: tx.data.clone().into(),
+				}
+				.abi_encode();
+				let result = self.call(tx.chain_id, gas_price_oracle, data).await?;
+				IGasPriceOracle::getL1FeeCall::abi_decode_returns(&result)
+					.map_err(|e| FeeModelError::Decode(format!("getL1Fee: {}", e)))
+			},
+			FeeModel::Arbitrum { node_interface } => {
+				let to = tx
+					.to
+					.as_ref()
+					.map(|to| alloy_primitives::Address::from_slice(&to.0))
+					.unwrap_or_default();
+				let data = INodeInterface::gasEstimateL1ComponentCall {
+					to,
+					contractCreation: tx.to.is_none(),
+					data: tx.data.clone().into(),
+				}
+				.abi_encode();
+				let result = self.call(tx.chain_id, node_interface, data).await?;
+				let estimate =
+					INodeInterface::gasEstimateL1ComponentCall::abi_decode_returns(&result)
+						.map_err(|e| {
+							FeeModelError::Decode(format!("gasEstimateL1Component: {}", e))
+						})?;
+				// The L1 component is charged as extra L2 gas at the L2 base fee
+				Ok(U256::from(estimate.gasEstimateForL1).saturating_mul(estimate.baseFee))
+			},
+		}
+	}
+
+	/// Calls a fee oracle on a chain.
+	async fn call(
+		&self,
+		chain_id: u64,
+		oracle: &Address,
+		data: Vec<u8>,
+	) -> Result<alloy_primitives::Bytes, FeeModelError> {
+		let tx = Transaction {
+			from: None,
+			to: Some(oracle.clone()),
+			data,
+			value: U256::ZERO,
+			chain_id,
+			nonce: None,
+			gas_limit: None,
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			metadata: None,
+		};
+		self.delivery
+			.contract_call(chain_id, tx)
+			.await
+			.map_err(|e| FeeModelError::Delivery(e.to_string()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_delivery::MockDeliveryInterface;
+	use std::collections::HashMap;
+
+	const CHAIN_ID: u64 = 10;
+
+	fn address(byte: u8) -> Address {
+		Address(vec![byte; 20])
+	}
+
+	fn transaction(to: Option<Address>) -> Transaction {
+		Transaction {
+			from: None,
+			to,
+			data: vec![0xde, 0xad, 0xbe, 0xef],
+			value: U256::ZERO,
+			chain_id: CHAIN_ID,
+			nonce: None,
+			gas_limit: None,
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			metadata: None,
+		}
+	}
+
+	fn estimator(mock: MockDeliveryInterface) -> L1FeeEstimator {
+		let mut implementations: HashMap<u64, Arc<dyn solver_delivery::DeliveryInterface>> =
+			HashMap::new();
+		implementations.insert(CHAIN_ID, Arc::new(mock));
+		L1FeeEstimator::new(Arc::new(DeliveryService::new(implementations, 1, 1)))
+	}
+
+	#[tokio::test]
+	async fn test_l1_chain_has_no_fee() {
+		let estimator = estimator(MockDeliveryInterface::new());
+		let fee = estimator
+			.estimate(&FeeModel::L1, &transaction(Some(address(0x22))))
+			.await
+			.unwrap();
+		assert_eq!(fee, U256::ZERO);
+	}
+
+	#[tokio::test]
+	async fn test_op_stack_calls_get_l1_fee_with_calldata() {
+		let oracle = address(0x42);
+		let tx = transaction(Some(address(0x22)));
+		let expected_data = IGasPriceOracle::getL1FeeCall {
+			data: tx.data.clone().into(),
+		}
+		.abi_encode();
+
+		let mut mock = MockDeliveryInterface::new();
+		let expected_oracle = oracle.clone();
+		mock.expect_eth_call()
+			.withf(move |call| {
+				call.to.as_ref() == Some(&expected_oracle)
+					&& call.chain_id == CHAIN_ID
+					&& call.data == expected_data
+			})
+			.times(1)
+			.returning(|_| {
+				Ok(
+					IGasPriceOracle::getL1FeeCall::abi_encode_returns(&U256::from(123_456u64))
+						.into(),
+				)
+			});
+
+		let fee = estimator(mock)
+			.estimate(
+				&FeeModel::OpStack {
+					gas_price_oracle: oracle,
+				},
+				&tx,
+			)
+			.await
+			.unwrap();
+		assert_eq!(fee, U256::from(123_456u64));
+	}
+
+	#[test]
+	fn test_op_stack_call_starts_with_selector() {
+		let data = IGasPriceOracle::getL1FeeCall {
+			data: vec![0x01].into(),
+		}
+		.abi_encode();
+		assert_eq!(&data[..4], &IGasPriceOracle::getL1FeeCall::SELECTOR);
+		// Selector, offset, length and one padded word
+		assert_eq!(data.len(), 4 + 32 * 3);
+	}
+
+	#[tokio::test]
+	async fn test_arbitrum_charges_l1_gas_at_base_fee() {
+		let node_interface = address(0xc8);
+		let target = address(0x22);
+		let tx = transaction(Some(target.clone()));
+		let expected_data = INodeInterface::gasEstimateL1ComponentCall {
+			to: alloy_primitives::Address::from_slice(&target.0),
+			contractCreation: false,
+			data: tx.data.clone().into(),
+		}
+		.abi_encode();
+
+		let mut mock = MockDeliveryInterface::new();
+		mock.expect_eth_call()
+			.withf(move |call| call.data == expected_data)
+			.times(1)
+			.returning(|_| {
+				Ok(
+					INodeInterface::gasEstimateL1ComponentCall::abi_encode_returns(
+						&INodeInterface::gasEstimateL1ComponentReturn {
+							gasEstimateForL1: 1_500,
+							baseFee: U256::from(100_000_000u64),
+							l1BaseFeeEstimate: U256::from(30_000_000_000u64),
+						},
+					)
+					.into(),
+				)
+			});
+
+		let fee = estimator(mock)
+			.estimate(&FeeModel::Arbitrum { node_interface }, &tx)
+			.await
+			.unwrap();
+		// 1,500 L1 gas units at the 0.1 gwei L2 base fee
+		assert_eq!(fee, U256::from(150_000_000_000u64));
+	}
+
+	#[tokio::test]
+	async fn test_arbitrum_flags_contract_creation() {
+		let mut mock = MockDeliveryInterface::new();
+		mock.expect_eth_call()
+			.withf(|call| {
+				let decoded =
+					INodeInterface::gasEstimateL1ComponentCall::abi_decode(&call.data).unwrap();
+				decoded.contractCreation && decoded.to == alloy_primitives::Address::ZERO
+			})
+			.returning(|_| {
+				Ok(
+					INodeInterface::gasEstimateL1ComponentCall::abi_encode_returns(
+						&INodeInterface::gasEstimateL1ComponentReturn {
+							gasEstimateForL1: 0,
+							baseFee: U256::from(100_000_000u64),
+							l1BaseFeeEstimate: U256::ZERO,
+						},
+					)
+					.into(),
+				)
+			});
+
+		let fee = estimator(mock)
+			.estimate(
+				&FeeModel::Arbitrum {
+					node_interface: address(0xc8),
+				},
+				&transaction(None),
+			)
+			.await
+			.unwrap();
+		assert_eq!(fee, U256::ZERO);
+	}
+
+	#[tokio::test]
+	async fn test_undecodable_answer_is_an_error() {
+		let mut mock = MockDeliveryInterface::new();
+		mock.expect_eth_call()
+			.returning(|_| Ok(alloy_primitives::Bytes::from(vec![0x01])));
+
+		let result = estimator(mock)
+			.estimate(
+				&FeeModel::OpStack {
+					gas_price_oracle: address(0x42),
+				},
+				&transaction(Some(address(0x22))),
+			)
+			.await;
+		assert!(matches!(result, Err(FeeModelError::Decode(_))));
+	}
+}
@@ -0,0 +1,332 @@
+//! Gas units learned from the receipts of confirmed transactions.
+//!
+//! The `GasModel` records the gas used by each confirmed transaction of the
+//! solver, keyed by flow, chain, transaction type and number of outputs. Once a
+//! key has collected `gas.learned.min_samples` samples, the configured
+//! percentile of its most recent samples is used for quoting and profitability
+//! instead of the static `[gas.flows]` units. Samples are written through to
+//! storage so the model survives restarts.
+
+use serde::{Deserialize, Serialize};
+use solver_config::{LearnedGasConfig, SharedConfig};
+use solver_storage::{StorageIndexes, StorageService};
+use solver_types::{current_timestamp, StorageKey, TransactionType};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use thiserror::Error;
+
+/// Errors that can occur while loading learned gas units.
+#[derive(Debug, Error)]
+pub enum GasModelError {
+	/// Samples could not be loaded from storage.
+	#[error("Storage error: {0}")]
+	Storage(String),
+}
+
+/// Identifies the transactions whose gas use is learned together.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GasUsageKey {
+	/// Lock flow of the order (e.g. "permit2_escrow")
+	pub flow: String,
+	/// Chain the transaction was sent on
+	pub chain_id: u64,
+	/// Step of the order the transaction performed
+	pub tx_type: TransactionType,
+	/// Number of outputs of the order
+	pub outputs: usize,
+}
+
+impl GasUsageKey {
+	/// Generate storage id for a key
+	fn storage_id(&self) -> String {
+		format!(
+			"{}-{}-{:?}-{}",
+			self.flow, self.chain_id, self.tx_type, self.outputs
+		)
+	}
+}
+
+/// Recorded gas samples of one key, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GasUsageRecord {
+	/// Which transactions the samples belong to
+	#[serde(flatten)]
+	pub key: GasUsageKey,
+	/// Gas used by the most recent transactions, oldest first
+	pub samples: Vec<u64>,
+	/// Timestamp of the most recent sample
+	pub updated_at: u64,
+}
+
+/// Learned gas units of one key, as reported by the API.
+#[derive(Debug, Clone, Serialize)]
+pub struct GasUsageStats {
+	/// Which transactions the statistics describe
+	#[serde(flatten)]
+	pub key: GasUsageKey,
+	/// Number of samples kept
+	pub samples: usize,
+	/// Median gas used
+	pub p50: u64,
+	/// 90th percentile of gas used
+	pub p90: u64,
+	/// Highest gas used
+	pub max: u64,
+	/// Gas units used for estimates, if the key has enough samples
+	pub estimate: Option<u64>,
+	/// Timestamp of the most recent sample
+	pub updated_at: u64,
+}
+
+/// Returns the nearest-rank percentile of the samples, `None` when empty.
+fn percentile(samples: &[u64], pct: u8) -> Option<u64> {
+	if samples.is_empty() {
+		return None;
+	}
+	let mut sorted = samples.to_vec();
+	sorted.sort_unstable();
+	let rank = (pct.min(100) as usize * sorted.len()).div_ceil(100);
+	Some(sorted[rank.saturating_sub(1)])
+}
+
+/// Storage-backed model of the gas used by the solver's own transactions.
+pub struct GasModel {
+	config: SharedConfig,
+	storage: Arc<StorageService>,
+	records: RwLock<HashMap<GasUsageKey, GasUsageRecord>>,
+}
+
+impl GasModel {
+	/// Creates a model with no samples.
+	pub fn new(config: SharedConfig, storage: Arc<StorageService>) -> Self {
+		Self {
+			config,
+			storage,
+			records: RwLock::new(HashMap::new()),
+		}
+	}
+
+	/// Loads previously recorded samples from storage.
+	pub async fn restore(&self) -> Result<usize, GasModelError> {
+		let stored = self
+			.storage
+			.retrieve_all::<GasUsageRecord>(StorageKey::GasUsage.as_str())
+			.await
+			.map_err(|e| GasModelError::Storage(e.to_string()))?;
+
+		let mut records = self.records.write().unwrap();
+		for (_, record) in stored {
+			records.insert(record.key.clone(), record);
+		}
+		Ok(records.len())
+	}
+
+	/// Records the gas used by a confirmed transaction.
+	///
+	/// Only the most recent `gas.learned.window` samples of a key are kept.
+	pub async fn record(&self, key: GasUsageKey, gas_used: u64) {
+		let window = self.config.load().learned_gas().window.max(1);
+		let record = {
+			let mut records = self.records.write().unwrap();
+			let record = records
+				.entry(key.clone())
+				.or_insert_with(|| GasUsageRecord {
+					key: key.clone(),
+					samples: Vec::new(),
+					updated_at: 0,
+				});
+			record.samples.push(gas_used);
+			if record.samples.len() > window {
+				let excess = record.samples.len() - window;
+				record.samples.drain(..excess);
+			}
+			record.updated_at = current_timestamp();
+			record.clone()
+		};
+
+		tracing::debug!(
+			flow = %key.flow,
+			chain_id = key.chain_id,
+			tx_type = ?key.tx_type,
+			outputs = key.outputs,
+			gas_used = gas_used,
+			samples = record.samples.len(),
+			"Recorded gas usage"
+		);
+
+		if let Err(e) = self
+			.storage
+			.store(
+				StorageKey::GasUsage.as_str(),
+				&key.storage_id(),
+				&record,
+				// Indexed so backends that list by index return it on reload
+				Some(StorageIndexes::new().with_field("chain_id", key.chain_id)),
+			)
+			.await
+		{
+			tracing::warn!(
+				flow = %key.flow,
+				chain_id = key.chain_id,
+				error = %e,
+				"Failed to persist gas usage"
+			);
+		}
+	}
+
+	/// Returns the learned gas units of a key.
+	///
+	/// `None` when learning is disabled or the key has fewer than
+	/// `gas.learned.min_samples` samples, in which case callers fall back to
+	/// the configured flow units.
+	pub fn estimate(&self, key: &GasUsageKey) -> Option<u64> {
+		let learned = self.config.load().learned_gas();
+		let records = self.records.read().unwrap();
+		Self::learned_units(records.get(key)?, &learned)
+	}
+
+	/// Applies the learned gas settings to the samples of a key.
+	fn learned_units(record: &GasUsageRecord, learned: &LearnedGasConfig) -> Option<u64> {
+		if !learned.enabled || record.samples.len() < learned.min_samples {
+			return None;
+		}
+		percentile(&record.samples, learned.percentile)
+	}
+
+	/// Returns the statistics of every key with samples, ordered by key.
+	pub fn table(&self) -> Vec<GasUsageStats> {
+		let learned = self.config.load().learned_gas();
+		let mut table: Vec<GasUsageStats> = self
+			.records
+			.read()
+			.unwrap()
+			.values()
+			.filter(|record| !record.samples.is_empty())
+			.map(|record| GasUsageStats {
+				key: record.key.clone(),
+				samples: record.samples.len(),
+				p50: percentile(&record.samples, 50).unwrap_or_default(),
+				p90: percentile(&record.samples, 90).unwrap_or_default(),
+				max: record.samples.iter().copied().max().unwrap_or_default(),
+				estimate: Self::learned_units(record, &learned),
+				updated_at: record.updated_at,
+			})
+			.collect();
+		table.sort_by_key(|stats| {
+			let key = &stats.key;
+			(
+				key.flow.clone(),
+				key.chain_id,
+				key.outputs,
+				key.tx_type as u8,
+			)
+		});
+		table
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use arc_swap::ArcSwap;
+	use solver_config::{ConfigBuilder, GasConfig};
+	use solver_storage::implementations::memory::MemoryStorage;
+
+	fn model(learned: LearnedGasConfig) -> GasModel {
+		let mut config = ConfigBuilder::new().build();
+		config.gas = Some(GasConfig {
+			flows: HashMap::new(),
+			learned,
+			fees: Default::default(),
+		});
+		GasModel::new(
+			Arc::new(ArcSwap::from_pointee(config)),
+			Arc::new(StorageService::new(Box::new(MemoryStorage::new()))),
+		)
+	}
+
+	fn learned(min_samples: usize, window: usize) -> LearnedGasConfig {
+		LearnedGasConfig {
+			enabled: true,
+			percentile: 90,
+			min_samples,
+			window,
+		}
+	}
+
+	fn fill_key() -> GasUsageKey {
+		GasUsageKey {
+			flow: "permit2_escrow".to_string(),
+			chain_id: 1,
+			tx_type: TransactionType::Fill,
+			outputs: 1,
+		}
+	}
+
+	#[test]
+	fn test_percentile_boundaries() {
+		let samples = [500, 100, 300];
+		assert_eq!(percentile(&samples, 0), Some(100));
+		assert_eq!(percentile(&samples, 100), Some(500));
+		assert_eq!(percentile(&samples, 255), Some(500));
+		assert_eq!(percentile(&[700], 0), Some(700));
+		assert_eq!(percentile(&[700], 50), Some(700));
+		assert_eq!(percentile(&[700], 100), Some(700));
+		assert_eq!(percentile(&[], 50), None);
+	}
+
+	#[test]
+	fn test_percentile_nearest_rank() {
+		let samples: Vec<u64> = (1..=10).collect();
+		assert_eq!(percentile(&samples, 50), Some(5));
+		assert_eq!(percentile(&samples, 90), Some(9));
+		assert_eq!(percentile(&samples, 91), Some(10));
+		assert_eq!(percentile(&samples, 1), Some(1));
+	}
+
+	#[tokio::test]
+	async fn test_window_evicts_oldest_samples() {
+		let model = model(learned(1, 3));
+		for gas_used in [100, 200, 300, 400, 500] {
+			model.record(fill_key(), gas_used).await;
+		}
+
+		let record: GasUsageRecord = model
+			.storage
+			.retrieve(StorageKey::GasUsage.as_str(), &fill_key().storage_id())
+			.await
+			.unwrap();
+		assert_eq!(record.samples, vec![300, 400, 500]);
+		assert_eq!(model.table()[0].samples, 3);
+		assert_eq!(model.table()[0].max, 500);
+	}
+
+	#[tokio::test]
+	async fn test_falls_back_below_min_samples() {
+		let model = model(learned(3, 10));
+		model.record(fill_key(), 100_000).await;
+		model.record(fill_key(), 120_000).await;
+		assert_eq!(model.estimate(&fill_key()), None);
+		assert_eq!(model.table()[0].estimate, None);
+
+		model.record(fill_key(), 110_000).await;
+		assert_eq!(model.estimate(&fill_key()), Some(120_000));
+
+		// Other keys have no samples and keep using configured units
+		let other = GasUsageKey {
+			outputs: 2,
+			..fill_key()
+		};
+		assert_eq!(model.estimate(&other), None);
+	}
+
+	#[tokio::test]
+	async fn test_disabled_learning_keeps_configured_units() {
+		let model = model(LearnedGasConfig {
+			enabled: false,
+			..learned(1, 10)
+		});
+		model.record(fill_key(), 100_000).await;
+		assert_eq!(model.estimate(&fill_key()), None);
+	}
+}
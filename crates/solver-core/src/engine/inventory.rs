@@ -0,0 +1,412 @@
+//! Background inventory monitoring for the solver's filler accounts.
+//!
+//! The `InventoryMonitor` periodically snapshots the native and token balances
+//! the filler accounts hold on every configured chain and keeps the history in
+//! storage. Balances are compared against the per-token thresholds from the
+//! `[inventory]` config section:
+//!
+//! - A total balance below `min_balance` publishes a low-balance event once,
+//!   and a recovery event when it is topped up again.
+//! - When no single filler account holds a `typical_order` of an asset, routes
+//!   delivering that asset are paused for quoting and filling until inventory
+//!   is restored. A native balance below its threshold pauses every route to
+//!   the chain, since fills there could not pay for gas.
+
+use super::{accounts::SolverAccounts, event_bus::EventBus, token_manager::TokenManager};
+use alloy_primitives::{hex, U256};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use solver_config::SharedConfig;
+use solver_delivery::DeliveryService;
+use solver_storage::{StorageIndexes, StorageService};
+use solver_types::{
+	current_timestamp, format_token_amount, with_0x_prefix, Address, InventoryEvent, SolverEvent,
+	StorageKey,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Errors that can occur while monitoring inventory.
+#[derive(Debug, Error)]
+pub enum InventoryError {
+	/// A snapshot could not be persisted.
+	#[error("Storage error: {0}")]
+	Storage(String),
+}
+
+/// Identifies an asset on a chain; `None` is the native asset.
+type AssetKey = (u64, Option<Address>);
+
+/// Balance of one account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountBalance {
+	/// Account address, 0x-prefixed hex.
+	pub address: String,
+	/// Balance in the asset's smallest unit.
+	pub balance: String,
+}
+
+/// Balances of one asset across the filler accounts of a chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetBalance {
+	/// Token address, 0x-prefixed hex, or `None` for the native asset.
+	pub token: Option<String>,
+	/// Asset symbol.
+	pub symbol: String,
+	/// Asset decimals.
+	pub decimals: u8,
+	/// Sum of all account balances in the smallest unit.
+	pub total: String,
+	/// Balance of each filler account.
+	pub accounts: Vec<AccountBalance>,
+}
+
+/// Inventory of a chain at a point in time, as kept in storage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventorySnapshot {
+	/// Chain the balances were read on.
+	pub chain_id: u64,
+	/// Unix timestamp of the snapshot.
+	pub timestamp: u64,
+	/// Balances per asset.
+	pub balances: Vec<AssetBalance>,
+}
+
+/// Snapshots balances, raises low-balance alerts and pauses routes whose
+/// destination inventory cannot cover typical orders.
+pub struct InventoryMonitor {
+	/// Configuration holding the inventory thresholds, read on every refresh.
+	config: SharedConfig,
+	/// Storage for snapshot history.
+	storage: Arc<StorageService>,
+	/// Delivery service used to read balances.
+	delivery: Arc<DeliveryService>,
+	/// Accounts whose balances make up the inventory.
+	accounts: Arc<SolverAccounts>,
+	/// Token registry listing the assets per chain.
+	token_manager: Arc<TokenManager>,
+	/// Event bus for low-balance and route events.
+	event_bus: EventBus,
+	/// Assets currently below their minimum balance.
+	low_balances: Mutex<HashSet<AssetKey>>,
+	/// Paused destination assets with the reason they were paused.
+	paused_routes: RwLock<HashMap<AssetKey, String>>,
+}
+
+impl InventoryMonitor {
+	/// Creates a new inventory monitor.
+	pub fn new(
+		config: SharedConfig,
+		storage: Arc<StorageService>,
+		delivery: Arc<DeliveryService>,
+		accounts: Arc<SolverAccounts>,
+		token_manager: Arc<TokenManager>,
+		event_bus: EventBus,
+	) -> Self {
+		Self {
+			config,
+			storage,
+			delivery,
+			accounts,
+			token_manager,
+			event_bus,
+			low_balances: Mutex::new(HashSet::new()),
+			paused_routes: RwLock::new(HashMap::new()),
+		}
+	}
+
+	/// Returns the configured time between refreshes.
+	pub fn interval(&self) -> Duration {
+		Duration::from_secs(self.config.load().inventory.interval_seconds)
+	}
+
+	/// Returns why routes delivering `token` on `chain_id` are paused, if they are.
+	///
+	/// `token` is `None` for the native asset. Routes are also paused when the
+	/// chain's native balance cannot pay for fills.
+	pub fn route_pause_reason(&self, chain_id: u64, token: Option<&Address>) -> Option<String> {
+		let paused = self.paused_routes.read().unwrap();
+		paused
+			.get(&(chain_id, token.cloned()))
+			.or_else(|| paused.get(&(chain_id, None)))
+			.cloned()
+	}
+
+	/// Returns whether routes delivering `token` on `chain_id` are paused.
+	pub fn is_route_paused(&self, chain_id: u64, token: Option<&Address>) -> bool {
+		self.route_pause_reason(chain_id, token).is_some()
+	}
+
+	/// Takes a snapshot of every chain, stores it and re-evaluates thresholds.
+	///
+	/// Assets whose balance cannot be read keep their previous state, so an
+	/// RPC outage neither pauses nor resumes routes. When monitoring is
+	/// disabled, all pauses and low-balance states are cleared.
+	pub async fn refresh(&self) -> Result<(), InventoryError> {
+		let config = self.config.load_full();
+		if !config.inventory.enabled {
+			self.clear();
+			return Ok(());
+		}
+
+		let retention = Duration::from_secs(config.inventory.history_retention_seconds);
+		let mut monitored = HashSet::new();
+		let networks = self.token_manager.get_networks();
+		for (chain_id, network) in networks.iter() {
+			let members = self.accounts.filler_pool.members(*chain_id);
+
+			let mut assets = vec![(
+				None,
+				network.native_currency.symbol.clone(),
+				network.native_currency.decimals,
+			)];
+			assets.extend(
+				network
+					.tokens
+					.iter()
+					.map(|t| (Some(t.address.clone()), t.symbol.clone(), t.decimals)),
+			);
+
+			let mut snapshot = InventorySnapshot {
+				chain_id: *chain_id,
+				timestamp: current_timestamp(),
+				balances: Vec::new(),
+			};
+			for (token, symbol, decimals) in assets {
+				let Some(balance) = self.read_balance(*chain_id, token.as_ref(), &members).await
+				else {
+					monitored.insert((*chain_id, token));
+					continue;
+				};
+
+				let token_hex = token.as_ref().map(|t| with_0x_prefix(&hex::encode(&t.0)));
+				let threshold = config
+					.inventory
+					.threshold_for(*chain_id, token_hex.as_deref());
+				let key = (*chain_id, token);
+				let total = balance
+					.iter()
+					.fold(U256::ZERO, |sum, (_, b)| sum.saturating_add(*b));
+				let largest = balance.iter().map(|(_, b)| *b).max().unwrap_or_default();
+
+				let min_balance = threshold
+					.and_then(|t| t.min_balance)
+					.and_then(|v| to_base_units(v, decimals));
+				self.check_min_balance(&key, token_hex.clone(), total, min_balance);
+
+				let typical_order = threshold
+					.and_then(|t| t.typical_order)
+					.and_then(|v| to_base_units(v, decimals));
+				let shortfall = match typical_order {
+					Some(required) if largest < required => Some(format!(
+						"No filler account on chain {} holds a typical order of {} {}",
+						chain_id,
+						format_token_amount(&required.to_string(), decimals),
+						symbol
+					)),
+					_ => None,
+				};
+				self.update_route(&key, token_hex.clone(), shortfall);
+				monitored.insert(key);
+
+				snapshot.balances.push(AssetBalance {
+					token: token_hex,
+					symbol,
+					decimals,
+					total: total.to_string(),
+					accounts: balance
+						.into_iter()
+						.map(|(address, balance)| AccountBalance {
+							address: with_0x_prefix(&hex::encode(&address.0)),
+							balance: balance.to_string(),
+						})
+						.collect(),
+				});
+			}
+
+			self.store_snapshot(&snapshot, retention).await?;
+		}
+
+		// Forget assets that are no longer configured
+		self.retain(&monitored);
+		Ok(())
+	}
+
+	/// Reads the balance of an asset for every account, or `None` if any read fails.
+	pub(crate) async fn read_balance(
+		&self,
+		chain_id: u64,
+		token: Option<&Address>,
+		members: &[Address],
+	) -> Option<Vec<(Address, U256)>> {
+		let token_hex = token.map(|t| hex::encode(&t.0));
+		let mut balances = Vec::with_capacity(members.len());
+		for member in members {
+			let result = self
+				.delivery
+				.get_balance(chain_id, &hex::encode(&member.0), token_hex.as_deref())
+				.await
+				.map_err(|e| e.to_string())
+				.and_then(|b| U256::from_str_radix(&b, 10).map_err(|e| e.to_string()));
+			match result {
+				Ok(balance) => balances.push((member.clone(), balance)),
+				Err(e) => {
+					tracing::warn!(
+						chain_id = chain_id,
+						account = %member,
+						token = token_hex.as_deref().unwrap_or("native"),
+						error = %e,
+						"Failed to read inventory balance"
+					);
+					return None;
+				},
+			}
+		}
+		Some(balances)
+	}
+
+	/// Publishes a low-balance event when an asset first drops below its
+	/// minimum, and a recovery event when it is back above it.
+	fn check_min_balance(
+		&self,
+		key: &AssetKey,
+		token: Option<String>,
+		total: U256,
+		min_balance: Option<U256>,
+	) {
+		let mut low_balances = self.low_balances.lock().unwrap();
+		match min_balance {
+			Some(min) if total < min => {
+				if low_balances.insert(key.clone()) {
+					tracing::warn!(
+						chain_id = key.0,
+						token = token.as_deref().unwrap_or("native"),
+						balance = %total,
+						threshold = %min,
+						"Inventory below minimum balance"
+					);
+					self.event_bus
+						.publish(SolverEvent::Inventory(InventoryEvent::LowBalance {
+							chain_id: key.0,
+							token,
+							balance: total.to_string(),
+							threshold: min.to_string(),
+						}))
+						.ok();
+				}
+			},
+			_ => {
+				if low_balances.remove(key) {
+					tracing::info!(
+						chain_id = key.0,
+						token = token.as_deref().unwrap_or("native"),
+						balance = %total,
+						"Inventory recovered above minimum balance"
+					);
+					self.event_bus
+						.publish(SolverEvent::Inventory(InventoryEvent::BalanceRecovered {
+							chain_id: key.0,
+							token,
+							balance: total.to_string(),
+						}))
+						.ok();
+				}
+			},
+		}
+	}
+
+	/// Pauses routes delivering an asset when there is a shortfall and
+	/// resumes them once it is gone.
+	fn update_route(&self, key: &AssetKey, token: Option<String>, shortfall: Option<String>) {
+		let mut paused = self.paused_routes.write().unwrap();
+		match shortfall {
+			Some(reason) => {
+				if paused.insert(key.clone(), reason.clone()).is_none() {
+					tracing::warn!(reason = %reason, "Pausing route");
+					self.event_bus
+						.publish(SolverEvent::Inventory(InventoryEvent::RoutePaused {
+							chain_id: key.0,
+							token,
+							reason,
+						}))
+						.ok();
+				}
+			},
+			None => {
+				if paused.remove(key).is_some() {
+					self.publish_resumed(key);
+				}
+			},
+		}
+	}
+
+	/// Drops state for assets that were not part of the last refresh.
+	fn retain(&self, monitored: &HashSet<AssetKey>) {
+		self.low_balances
+			.lock()
+			.unwrap()
+			.retain(|key| monitored.contains(key));
+		let mut paused = self.paused_routes.write().unwrap();
+		let removed: Vec<AssetKey> = paused
+			.keys()
+			.filter(|key| !monitored.contains(*key))
+			.cloned()
+			.collect();
+		for key in removed {
+			paused.remove(&key);
+			self.publish_resumed(&key);
+		}
+	}
+
+	/// Clears all low-balance states and resumes every paused route.
+	fn clear(&self) {
+		self.retain(&HashSet::new());
+	}
+
+	/// Publishes that routes delivering an asset were resumed.
+	fn publish_resumed(&self, key: &AssetKey) {
+		let token = key.1.as_ref().map(|t| with_0x_prefix(&hex::encode(&t.0)));
+		tracing::info!(
+			chain_id = key.0,
+			token = token.as_deref().unwrap_or("native"),
+			"Resuming route"
+		);
+		self.event_bus
+			.publish(SolverEvent::Inventory(InventoryEvent::RouteResumed {
+				chain_id: key.0,
+				token,
+			}))
+			.ok();
+	}
+
+	/// Persists a snapshot, indexed by chain, for the retention period.
+	async fn store_snapshot(
+		&self,
+		snapshot: &InventorySnapshot,
+		retention: Duration,
+	) -> Result<(), InventoryError> {
+		let id = format!("{}-{}", snapshot.chain_id, snapshot.timestamp);
+		let indexes = StorageIndexes::new()
+			.with_field("chain_id", snapshot.chain_id)
+			.with_field("timestamp", snapshot.timestamp);
+		self.storage
+			.store_with_ttl(
+				StorageKey::Inventory.as_str(),
+				&id,
+				snapshot,
+				Some(indexes),
+				Some(retention),
+			)
+			.await
+			.map_err(|e| InventoryError::Storage(e.to_string()))
+	}
+}
+
+/// Converts an amount in whole units to the asset's smallest unit.
+pub(crate) fn to_base_units(amount: Decimal, decimals: u8) -> Option<U256> {
+	let mantissa = u128::try_from(amount.mantissa()).ok()?;
+	let ten = U256::from(10u64);
+	Some(U256::from(mantissa) * ten.pow(U256::from(decimals)) / ten.pow(U256::from(amount.scale())))
+}
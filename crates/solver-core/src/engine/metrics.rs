@@ -0,0 +1,67 @@
+//! Counters for orders the solver decided not to execute.
+//!
+//! Every skip and deferral carries a reason. Reasons often embed amounts or
+//! addresses, so they are counted by their label: the text before the first
+//! colon, such as the name of the strategy rule that matched.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// Skip and defer counts by reason label.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DecisionCounts {
+	/// Orders skipped, by reason label.
+	pub skipped: BTreeMap<String, u64>,
+	/// Orders deferred, by reason label.
+	pub deferred: BTreeMap<String, u64>,
+}
+
+/// Counts skip and defer decisions since the solver started.
+#[derive(Debug, Default)]
+pub struct DecisionMetrics {
+	counts: Mutex<DecisionCounts>,
+}
+
+impl DecisionMetrics {
+	/// Creates empty counters.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Counts a skipped order.
+	pub fn record_skip(&self, reason: &str) {
+		*self
+			.counts
+			.lock()
+			.unwrap()
+			.skipped
+			.entry(reason_label(reason))
+			.or_default() += 1;
+	}
+
+	/// Counts a deferred order.
+	pub fn record_defer(&self, reason: &str) {
+		*self
+			.counts
+			.lock()
+			.unwrap()
+			.deferred
+			.entry(reason_label(reason))
+			.or_default() += 1;
+	}
+
+	/// Returns the current counts.
+	pub fn snapshot(&self) -> DecisionCounts {
+		self.counts.lock().unwrap().clone()
+	}
+}
+
+/// Returns the part of a reason before its first colon.
+fn reason_label(reason: &str) -> String {
+	reason
+		.split_once(':')
+		.map_or(reason, |(label, _)| label)
+		.trim()
+		.to_string()
+}
@@ -4,22 +4,37 @@
 //! all services (discovery, order processing, delivery, settlement) and manages
 //! the main event loop for processing intents and orders.
 
+pub mod accounts;
+pub mod chain_state;
 pub mod context;
 pub mod cost_profit;
 pub mod event_bus;
+pub mod exposure;
+pub mod fee_model;
+pub mod gas_model;
+pub mod inventory;
 pub mod lifecycle;
+pub mod metrics;
+pub mod preflight;
+pub mod rebalancer;
 pub mod token_manager;
 
-use self::{cost_profit::CostProfitService, token_manager::TokenManager};
+use self::{
+	accounts::SolverAccounts, chain_state::ChainStateService, cost_profit::CostProfitService,
+	exposure::ExposureTracker, gas_model::GasModel, inventory::InventoryMonitor,
+	metrics::DecisionMetrics, preflight::PreflightService, rebalancer::Rebalancer,
+	token_manager::TokenManager,
+};
 use crate::handlers::{IntentHandler, OrderHandler, SettlementHandler, TransactionHandler};
 use crate::recovery::RecoveryService;
 use crate::state::OrderStateMachine;
 use solver_account::AccountService;
-use solver_config::Config;
+use solver_config::{Config, SharedConfig};
 use solver_delivery::DeliveryService;
 use solver_discovery::DiscoveryService;
 use solver_order::OrderService;
 use solver_pricing::PricingService;
+use solver_rebalance::RebalanceService;
 use solver_settlement::SettlementService;
 use solver_storage::StorageService;
 use solver_types::{
@@ -49,13 +64,16 @@ pub enum EngineError {
 /// Main solver engine that orchestrates the order execution lifecycle.
 #[derive(Clone)]
 pub struct SolverEngine {
-	/// Solver configuration.
-	pub(crate) config: Config,
+	/// Solver configuration, swapped in place on hot reload.
+	pub(crate) config: SharedConfig,
 	/// Storage service for persisting state.
 	pub(crate) storage: Arc<StorageService>,
-	/// Account service for address and signing operations.
+	/// Account service of the solver of record (the claimer role).
 	#[allow(dead_code)]
 	pub(crate) account: Arc<AccountService>,
+	/// Role accounts and filler pools transactions are sent from.
+	#[allow(dead_code)]
+	pub(crate) accounts: Arc<SolverAccounts>,
 	/// Delivery service for blockchain transactions.
 	#[allow(dead_code)]
 	pub(crate) delivery: Arc<DeliveryService>,
@@ -73,6 +91,18 @@ pub struct SolverEngine {
 	/// Token manager for token approvals and validation.
 	#[allow(dead_code)]
 	pub(crate) token_manager: Arc<TokenManager>,
+	/// Inventory monitor that pauses routes lacking destination inventory.
+	pub(crate) inventory: Arc<InventoryMonitor>,
+	/// Rebalancer that moves inventory between chains.
+	pub(crate) rebalancer: Arc<Rebalancer>,
+	/// Tracker of capital fronted for orders that are not yet claimed.
+	pub(crate) exposure: Arc<ExposureTracker>,
+	/// Gas units learned from confirmed transactions.
+	pub(crate) gas_model: Arc<GasModel>,
+	/// Cached chain data, fee history and solver balances.
+	pub(crate) chain_state: Arc<ChainStateService>,
+	/// Counters of skipped and deferred orders by reason.
+	pub(crate) decisions: Arc<DecisionMetrics>,
 	/// Event bus for inter-service communication.
 	pub(crate) event_bus: event_bus::EventBus,
 	/// Order state machine
@@ -105,8 +135,9 @@ impl SolverEngine {
 	///
 	/// * `config` - Solver configuration settings
 	/// * `storage` - Storage service for persisting state
-	/// * `account` - Account service for address and signing operations
+	/// * `account` - Account service of the solver of record
 	/// * `solver_address` - The solver's Ethereum address
+	/// * `accounts` - Role accounts and filler pools used to send transactions
 	/// * `delivery` - Service for submitting blockchain transactions
 	/// * `discovery` - Service for discovering new intents
 	/// * `order` - Service for order validation and execution
@@ -114,12 +145,14 @@ impl SolverEngine {
 	/// * `pricing` - Service for asset price conversion
 	/// * `event_bus` - Event bus for inter-service communication
 	/// * `token_manager` - Manager for token approvals and validation
+	/// * `bridges` - Bridge adapters used to rebalance inventory
 	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		config: Config,
 		storage: Arc<StorageService>,
 		account: Arc<AccountService>,
 		solver_address: Address,
+		accounts: Arc<SolverAccounts>,
 		delivery: Arc<DeliveryService>,
 		discovery: Arc<DiscoveryService>,
 		order: Arc<OrderService>,
@@ -127,14 +160,62 @@ impl SolverEngine {
 		pricing: Arc<PricingService>,
 		event_bus: event_bus::EventBus,
 		token_manager: Arc<TokenManager>,
+		bridges: Arc<RebalanceService>,
 	) -> Self {
+		let config = config.into_shared();
 		let state_machine = Arc::new(OrderStateMachine::new(storage.clone()));
+		let gas_model = Arc::new(GasModel::new(config.clone(), storage.clone()));
+		let chain_state = Arc::new(ChainStateService::new(config.clone(), delivery.clone()));
+		token_manager.set_chain_state(chain_state.clone());
 
 		// Create CostProfitService for cost estimation and profitability validation
 		let cost_profit_service = Arc::new(CostProfitService::new(
 			pricing.clone(),
 			delivery.clone(),
 			token_manager.clone(),
+			settlement.clone(),
+			order.clone(),
+			gas_model.clone(),
+			chain_state.clone(),
+		));
+
+		let inventory = Arc::new(InventoryMonitor::new(
+			config.clone(),
+			storage.clone(),
+			delivery.clone(),
+			accounts.clone(),
+			token_manager.clone(),
+			event_bus.clone(),
+		));
+
+		let rebalancer = Arc::new(Rebalancer::new(
+			config.clone(),
+			storage.clone(),
+			delivery.clone(),
+			accounts.clone(),
+			token_manager.clone(),
+			inventory.clone(),
+			chain_state.clone(),
+			bridges,
+			event_bus.clone(),
+		));
+
+		let exposure = Arc::new(ExposureTracker::new(
+			storage.clone(),
+			pricing.clone(),
+			token_manager.clone(),
+		));
+
+		let decisions = Arc::new(DecisionMetrics::new());
+
+		let preflight = Arc::new(PreflightService::new(
+			delivery.clone(),
+			state_machine.clone(),
+			event_bus.clone(),
+			accounts.clone(),
+			exposure.clone(),
+			decisions.clone(),
+			config.clone(),
 		));
 
 		let intent_handler = Arc::new(IntentHandler::new(
@@ -142,10 +223,14 @@ impl SolverEngine {
 			storage.clone(),
 			state_machine.clone(),
 			event_bus.clone(),
-			delivery.clone(),
+			chain_state.clone(),
 			solver_address,
 			token_manager.clone(),
 			cost_profit_service,
+			inventory.clone(),
+			exposure.clone(),
+			decisions.clone(),
+			accounts.clone(),
 			config.clone(),
 		));
 
@@ -155,6 +240,11 @@ impl SolverEngine {
 			storage.clone(),
 			state_machine.clone(),
 			event_bus.clone(),
+			accounts.clone(),
+			token_manager.clone(),
+			chain_state.clone(),
+			preflight.clone(),
+			exposure.clone(),
 		));
 
 		let transaction_handler = Arc::new(TransactionHandler::new(
@@ -163,7 +253,10 @@ impl SolverEngine {
 			state_machine.clone(),
 			settlement.clone(),
 			event_bus.clone(),
-			config.solver.monitoring_timeout_minutes,
+			accounts.clone(),
+			exposure.clone(),
+			gas_model.clone(),
+			config.load().solver.monitoring_timeout_minutes,
 		));
 
 		let settlement_handler = Arc::new(SettlementHandler::new(
@@ -173,19 +266,29 @@ impl SolverEngine {
 			storage.clone(),
 			state_machine.clone(),
 			event_bus.clone(),
-			config.solver.monitoring_timeout_minutes,
+			accounts.clone(),
+			chain_state.clone(),
+			preflight,
+			config.load().solver.monitoring_timeout_minutes,
 		));
 
 		Self {
 			config,
 			storage,
 			account,
+			accounts,
 			delivery,
 			discovery,
 			order,
 			settlement,
 			pricing,
 			token_manager,
+			inventory,
+			rebalancer,
+			exposure,
+			gas_model,
+			chain_state,
+			decisions,
 			event_bus,
 			state_machine,
 			intent_handler,
@@ -202,6 +305,8 @@ impl SolverEngine {
 	/// 2. Reconciling order states with blockchain state
 	/// 3. Recovering orphaned intents that weren't processed
 	/// 4. Publishing appropriate events to resume processing
+	/// 5. Restoring the exposure of orders that are filled but not yet claimed
+	/// 6. Loading the gas units learned from earlier confirmed transactions
 	///
 	/// # Returns
 	///
@@ -219,6 +324,26 @@ impl SolverEngine {
 			self.event_bus.clone(),
 		);
 
+		// Restore fronted capital before new orders are evaluated against it
+		match self.exposure.restore().await {
+			Ok(restored) => {
+				tracing::info!("Restored exposure of {} unclaimed orders", restored);
+			},
+			Err(e) => {
+				tracing::warn!("Failed to restore order exposure: {}", e);
+			},
+		}
+
+		// Load learned gas units before orders are costed against them
+		match self.gas_model.restore().await {
+			Ok(keys) => {
+				tracing::info!("Restored learned gas units for {} keys", keys);
+			},
+			Err(e) => {
+				tracing::warn!("Failed to restore learned gas units: {}", e);
+			},
+		}
+
 		// Perform recovery
 		match recovery_service.recover_state().await {
 			Ok((report, orphaned_intents)) => {
@@ -250,6 +375,9 @@ impl SolverEngine {
 	/// 5. Manages transaction monitoring and error handling
 	/// 6. Batches settlement claims for efficiency
 	/// 7. Runs storage cleanup tasks
+	/// 8. Monitors inventory and pauses routes it cannot cover
+	/// 9. Rebalances inventory between chains
+	/// 10. Keeps cached chain state current on new blocks
 	///
 	/// The loop uses semaphores to control concurrency - transaction events
 	/// are serialized to avoid nonce conflicts, while other events can run
@@ -286,7 +414,7 @@ impl SolverEngine {
 
 		// Start storage cleanup task
 		let storage = self.storage.clone();
-		let cleanup_interval_seconds = self.config.storage.cleanup_interval_seconds;
+		let cleanup_interval_seconds = self.config.load().storage.cleanup_interval_seconds;
 		let cleanup_interval = tokio::time::interval(Duration::from_secs(cleanup_interval_seconds));
 		tracing::info!(
 			"Starting storage cleanup service, will run every {} seconds",
@@ -310,6 +438,37 @@ impl SolverEngine {
 			}
 		});
 
+		// Start inventory monitor, re-reading the interval so reloads apply
+		let inventory = self.inventory.clone();
+		tracing::info!(
+			"Starting inventory monitor, will run every {} seconds",
+			inventory.interval().as_secs()
+		);
+		let inventory_handle = tokio::spawn(async move {
+			loop {
+				if let Err(e) = inventory.refresh().await {
+					tracing::warn!("Inventory refresh failed: {}", e);
+				}
+				tokio::time::sleep(inventory.interval()).await;
+			}
+		});
+
+		// Start rebalancer; it idles while rebalancing is disabled
+		let rebalancer = self.rebalancer.clone();
+		let rebalancer_handle = tokio::spawn(async move {
+			loop {
+				tokio::time::sleep(rebalancer.interval()).await;
+				rebalancer.run().await;
+			}
+		});
+
+		// Start chain-state refresher; it idles while caching is disabled
+		let chain_state = self.chain_state.clone();
+		let chain_state_events = self.event_bus.subscribe();
+		let chain_state_handle = tokio::spawn(async move {
+			chain_state.run(chain_state_events).await;
+		});
+
 		// Create separate semaphores for different event types
 		// Transaction events need to be serialized to avoid nonce conflicts
 		let transaction_semaphore = Arc::new(Semaphore::new(1)); // Serialize transaction submissions
@@ -334,7 +493,7 @@ impl SolverEngine {
 						SolverEvent::Order(OrderEvent::Preparing { intent, order, params }) => {
 							// Preparing sends a prepare transaction - use transaction semaphore
 							self.spawn_handler(&transaction_semaphore, move |engine| async move {
-								if let Err(e) = engine.order_handler.handle_preparation(intent.source, order, params).await {
+								if let Err(e) = engine.order_handler.handle_preparation(intent, order, params).await {
 									return Err(EngineError::Service(format!("Failed to handle order preparation: {}", e)));
 								}
 								Ok(())
@@ -454,6 +613,9 @@ impl SolverEngine {
 
 		// Cleanup
 		cleanup_handle.abort(); // Stop the cleanup task
+		inventory_handle.abort(); // Stop the inventory monitor
+		rebalancer_handle.abort(); // Stop the rebalancer
+		chain_state_handle.abort(); // Stop the chain-state refresher
 
 		self.discovery
 			.stop_all()
@@ -471,14 +633,36 @@ impl SolverEngine {
 		&self.event_bus
 	}
 
-	/// Returns a reference to the solver configuration.
+	/// Returns a snapshot of the current solver configuration.
 	///
 	/// Provides access to all configuration settings incl체uding network
-	/// parameters, timeouts, and service-specific settings.
-	pub fn config(&self) -> &Config {
+	/// parameters, timeouts, and service-specific settings. The snapshot
+	/// does not change if the configuration is reloaded afterwards.
+	pub fn config(&self) -> Arc<Config> {
+		self.config.load_full()
+	}
+
+	/// Returns the shared configuration handle that observes hot reloads.
+	pub fn shared_config(&self) -> &SharedConfig {
 		&self.config
 	}
 
+	/// Swaps in a reloaded configuration.
+	///
+	/// Updates the settings that services read directly from config (pricing
+	/// buffers, token lists and approval policy) and publishes the new config
+	/// to all readers.
+	/// Components built from config, such as the execution strategy and
+	/// settlement implementations, must be replaced by the caller beforehand.
+	pub fn apply_config(&self, config: Config) {
+		self.pricing
+			.update_config(crate::builder::pricing_config_for(&config));
+		self.token_manager.update_networks(config.networks.clone());
+		self.token_manager
+			.update_approvals(config.approvals.clone());
+		self.config.store(Arc::new(config));
+	}
+
 	/// Returns a reference to the storage service.
 	///
 	/// Provides access to the persistent storage layer for orders,
@@ -495,6 +679,45 @@ impl SolverEngine {
 		&self.token_manager
 	}
 
+	/// Returns a reference to the inventory monitor.
+	///
+	/// Reports which routes are paused because the destination inventory
+	/// cannot cover typical orders.
+	pub fn inventory(&self) -> &Arc<InventoryMonitor> {
+		&self.inventory
+	}
+
+	/// Returns a reference to the exposure tracker.
+	///
+	/// Reports the capital fronted for orders that are filled but not yet
+	/// claimed.
+	pub fn exposure(&self) -> &Arc<ExposureTracker> {
+		&self.exposure
+	}
+
+	/// Returns a reference to the learned gas model.
+	///
+	/// Reports the gas used by confirmed transactions per flow, chain,
+	/// transaction type and number of outputs.
+	pub fn gas_model(&self) -> &Arc<GasModel> {
+		&self.gas_model
+	}
+
+	/// Returns a reference to the chain-state cache.
+	///
+	/// Serves chain data, fee history and solver balances refreshed on new
+	/// blocks, shared by quoting and execution.
+	pub fn chain_state(&self) -> &Arc<ChainStateService> {
+		&self.chain_state
+	}
+
+	/// Returns a reference to the decision metrics.
+	///
+	/// Counts skipped and deferred orders by reason since startup.
+	pub fn decision_metrics(&self) -> &Arc<DecisionMetrics> {
+		&self.decisions
+	}
+
 	/// Returns a reference to the settlement service.
 	pub fn settlement(&self) -> &Arc<SettlementService> {
 		&self.settlement
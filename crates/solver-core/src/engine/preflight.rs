@@ -0,0 +1,289 @@
+//! Pre-flight simulation of the solver's transactions.
+//!
+//! Prepare, fill and claim transactions are simulated against the pending
+//! block before they are sent, so a transaction that would revert costs no
+//! gas. The decoded revert reason is recorded on the order and decides what
+//! happens next:
+//!
+//! - reverts showing the order can no longer be executed, such as a fill by
+//!   another solver or a passed deadline, skip it;
+//! - reverts that may clear up, such as a missing allowance or balance, retry
+//!   the step after `simulation.retry_delay_seconds`, up to
+//!   `simulation.max_retries` times;
+//! - any other revert aborts the order.
+//!
+//! Skipped and aborted orders are failed at the step that reverted. Claims are
+//! the exception: the fill has already paid out, so a claim is never aborted.
+//! Its reverts are retried with a delay that doubles each time, and once the
+//! retries are used up the order is left for the operator instead of failed.
+//! When the simulation itself cannot be run, the transaction is sent as before.
+
+use crate::engine::{
+	accounts::SolverAccounts, event_bus::EventBus, exposure::ExposureTracker,
+	metrics::DecisionMetrics,
+};
+use crate::state::OrderStateMachine;
+use solver_config::SharedConfig;
+use solver_delivery::{DeliveryError, DeliveryService};
+use solver_types::{
+	truncate_id, OrderEvent, OrderStatus, RevertAction, RevertReason, SolverEvent, Transaction,
+	TransactionType,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Largest factor by which the delay between claim retries grows.
+const MAX_CLAIM_BACKOFF_FACTOR: u32 = 64;
+
+/// Simulates transactions before they are sent and acts on their reverts.
+pub struct PreflightService {
+	delivery: Arc<DeliveryService>,
+	state_machine: Arc<OrderStateMachine>,
+	event_bus: EventBus,
+	accounts: Arc<SolverAccounts>,
+	exposure: Arc<ExposureTracker>,
+	metrics: Arc<DecisionMetrics>,
+	config: SharedConfig,
+	/// Retries so far per order and step.
+	retries: Mutex<HashMap<(String, TransactionType), u32>>,
+}
+
+impl PreflightService {
+	#[allow(clippy::too_many_arguments)]
+	pub fn new(
+		delivery: Arc<DeliveryService>,
+		state_machine: Arc<OrderStateMachine>,
+		event_bus: EventBus,
+		accounts: Arc<SolverAccounts>,
+		exposure: Arc<ExposureTracker>,
+		metrics: Arc<DecisionMetrics>,
+		config: SharedConfig,
+	) -> Self {
+		Self {
+			delivery,
+			state_machine,
+			event_bus,
+			accounts,
+			exposure,
+			metrics,
+			config,
+			retries: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Simulates a transaction of an order and decides whether to send it.
+	///
+	/// When the simulation reverts, the reason is recorded on the order and it
+	/// is either failed or `retry` is published again after the retry delay.
+	///
+	/// # Returns
+	///
+	/// Whether the transaction should be sent.
+	pub async fn check(
+		&self,
+		order_id: &str,
+		tx: &Transaction,
+		tx_type: TransactionType,
+		retry: SolverEvent,
+	) -> bool {
+		let config = self.config.load().simulation.clone();
+		if !config.enabled {
+			return true;
+		}
+
+		let reason = match self.delivery.simulate(tx.clone()).await {
+			Ok(gas) => {
+				tracing::debug!(
+					order_id = %truncate_id(order_id),
+					tx_type = ?tx_type,
+					gas,
+					"Simulation succeeded"
+				);
+				self.retries
+					.lock()
+					.unwrap()
+					.remove(&(order_id.to_string(), tx_type));
+				return true;
+			},
+			Err(DeliveryError::Reverted(reason)) => reason,
+			Err(e) => {
+				tracing::warn!(
+					order_id = %truncate_id(order_id),
+					tx_type = ?tx_type,
+					error = %e,
+					"Simulation unavailable, sending transaction unsimulated"
+				);
+				return true;
+			},
+		};
+
+		let description = format!("simulation: {:?} reverted with {}", tx_type, reason);
+		let action = reason.action_for(tx_type);
+		let attempt = match action {
+			RevertAction::Retry => self.next_attempt(order_id, tx_type, config.max_retries),
+			_ => None,
+		};
+		match (action, attempt) {
+			(RevertAction::Retry, Some(attempt)) => {
+				let delay = retry_delay(tx_type, config.retry_delay_seconds, attempt);
+				tracing::info!(
+					order_id = %truncate_id(order_id),
+					reason = %reason,
+					retry_after_secs = delay.as_secs(),
+					"Simulation reverted, retrying later"
+				);
+				self.metrics.record_defer(&description);
+				self.record_reason(order_id, reason).await;
+				// The filler is picked again when the fill is retried
+				if tx_type == TransactionType::Fill {
+					self.accounts.filler_pool.release(order_id);
+				}
+				self.retry_later(delay, retry);
+			},
+			(RevertAction::Retry, None) if tx_type == TransactionType::Claim => {
+				tracing::error!(
+					order_id = %truncate_id(order_id),
+					reason = %reason,
+					"Claim simulation keeps reverting, leaving the order for the operator"
+				);
+				self.record_reason(order_id, reason).await;
+			},
+			(RevertAction::Skip, _) => {
+				tracing::info!(
+					order_id = %truncate_id(order_id),
+					reason = %reason,
+					"Simulation reverted, skipping order"
+				);
+				self.metrics.record_skip(&description);
+				self.event_bus
+					.publish(SolverEvent::Order(OrderEvent::Skipped {
+						order_id: order_id.to_string(),
+						reason: description,
+					}))
+					.ok();
+				self.fail_order(order_id, tx_type, reason).await;
+			},
+			(RevertAction::Retry | RevertAction::Abort, _) => {
+				tracing::error!(
+					order_id = %truncate_id(order_id),
+					reason = %reason,
+					"Simulation reverted, aborting order"
+				);
+				self.fail_order(order_id, tx_type, reason).await;
+			},
+		}
+		false
+	}
+
+	/// Counts a retry of a step and returns its number, unless its retries are
+	/// used up.
+	fn next_attempt(
+		&self,
+		order_id: &str,
+		tx_type: TransactionType,
+		max_retries: u32,
+	) -> Option<u32> {
+		let mut retries = self.retries.lock().unwrap();
+		let key = (order_id.to_string(), tx_type);
+		let count = retries.entry(key.clone()).or_default();
+		if *count >= max_retries {
+			retries.remove(&key);
+			return None;
+		}
+		*count += 1;
+		Some(*count)
+	}
+
+	/// Publishes an event again once the delay has passed.
+	fn retry_later(&self, delay: Duration, event: SolverEvent) {
+		let event_bus = self.event_bus.clone();
+		tokio::spawn(async move {
+			tokio::time::sleep(delay).await;
+			event_bus.publish(event).ok();
+		});
+	}
+
+	/// Records the revert reason on an order.
+	async fn record_reason(&self, order_id: &str, reason: RevertReason) {
+		if let Err(e) = self
+			.state_machine
+			.update_order_with(order_id, |o| o.revert_reason = Some(reason))
+			.await
+		{
+			tracing::warn!(
+				order_id = %truncate_id(order_id),
+				error = %e,
+				"Failed to record revert reason"
+			);
+		}
+	}
+
+	/// Fails an order at the step whose simulation reverted.
+	async fn fail_order(&self, order_id: &str, tx_type: TransactionType, reason: RevertReason) {
+		// The step will not be retried, so its retry count is dropped
+		self.retries
+			.lock()
+			.unwrap()
+			.remove(&(order_id.to_string(), tx_type));
+		if tx_type == TransactionType::Fill {
+			self.accounts.filler_pool.release(order_id);
+		}
+		// Nothing was paid out unless the fill went through
+		if matches!(tx_type, TransactionType::Prepare | TransactionType::Fill) {
+			self.exposure.release(order_id);
+		}
+
+		self.record_reason(order_id, reason).await;
+		if let Err(e) = self
+			.state_machine
+			.transition_order_status(order_id, OrderStatus::Failed(tx_type))
+			.await
+		{
+			tracing::warn!(
+				order_id = %truncate_id(order_id),
+				error = %e,
+				"Failed to mark order as failed"
+			);
+		}
+	}
+}
+
+/// Returns the delay before retrying a step whose simulation reverted.
+///
+/// Claims back off, doubling the delay with each retry; other steps retry at
+/// the configured delay.
+fn retry_delay(tx_type: TransactionType, delay_seconds: u64, attempt: u32) -> Duration {
+	let factor = match tx_type {
+		TransactionType::Claim => 2u32
+			.saturating_pow(attempt.saturating_sub(1))
+			.min(MAX_CLAIM_BACKOFF_FACTOR),
+		_ => 1,
+	};
+	Duration::from_secs(delay_seconds.saturating_mul(u64::from(factor)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_claim_retries_back_off() {
+		assert_eq!(
+			retry_delay(TransactionType::Fill, 30, 3),
+			Duration::from_secs(30)
+		);
+		assert_eq!(
+			retry_delay(TransactionType::Claim, 30, 1),
+			Duration::from_secs(30)
+		);
+		assert_eq!(
+			retry_delay(TransactionType::Claim, 30, 3),
+			Duration::from_secs(120)
+		);
+		assert_eq!(
+			retry_delay(TransactionType::Claim, 30, 40),
+			Duration::from_secs(30 * u64::from(MAX_CLAIM_BACKOFF_FACTOR))
+		);
+	}
+}
@@ -0,0 +1,418 @@
+//! Cross-chain rebalancing of the filler accounts' inventory.
+//!
+//! The `Rebalancer` periodically compares the balances of every asset in the
+//! `[rebalance]` config section against its target allocation and moves
+//! surplus to chains that fall short. Transfers are planned by
+//! `solver_rebalance::plan_transfers` and sent through the first bridge that
+//! supports the route:
+//!
+//! - Funds are taken from the filler account holding the most on the surplus
+//!   chain and delivered to the one holding the least on the deficit chain.
+//! - After a transfer on a route, the route rests for the asset's cooldown.
+//!   The last transfer is kept in storage so restarts honour the cooldown.
+//! - Transfers are sent one at a time, each confirmed before the next. Funds
+//!   in transit are not counted until they arrive, so a route's cooldown
+//!   should cover the bridge's delivery time.
+
+use super::{
+	accounts::SolverAccounts,
+	chain_state::ChainStateService,
+	event_bus::EventBus,
+	inventory::{to_base_units, InventoryMonitor},
+	token_manager::TokenManager,
+};
+use alloy_primitives::{hex, U256};
+use serde::{Deserialize, Serialize};
+use solver_config::{RebalanceAsset, SharedConfig};
+use solver_delivery::DeliveryService;
+use solver_rebalance::{plan_transfers, RebalanceLimits, RebalanceService, TransferRequest};
+use solver_storage::StorageService;
+use solver_types::{
+	current_timestamp, parse_address, with_0x_prefix, Address, InventoryEvent, SolverEvent,
+	StorageKey,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Errors that can occur while rebalancing inventory.
+#[derive(Debug, Error)]
+pub enum RebalanceError {
+	/// The asset configuration does not match the configured networks.
+	#[error("Configuration error: {0}")]
+	Config(String),
+	/// A bridge could not build a transfer.
+	#[error("Bridge error: {0}")]
+	Bridge(String),
+	/// The bridge could not be allowed to spend the source token.
+	#[error("Allowance error: {0}")]
+	Allowance(String),
+	/// A transfer transaction failed to submit or reverted.
+	#[error("Delivery error: {0}")]
+	Delivery(String),
+	/// The transfer record could not be persisted or read.
+	#[error("Storage error: {0}")]
+	Storage(String),
+}
+
+/// Last transfer on a route, as kept in storage for the cooldown.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebalanceRecord {
+	/// Asset symbol from the rebalance config.
+	pub asset: String,
+	/// Chain the funds were taken from.
+	pub from_chain: u64,
+	/// Chain the funds were sent to.
+	pub to_chain: u64,
+	/// Amount taken from the sender, in the token's smallest unit.
+	pub amount: String,
+	/// Amount expected to arrive, in the token's smallest unit.
+	pub expected_amount: String,
+	/// Bridge implementation used.
+	pub bridge: String,
+	/// Hashes of the transfer transactions, 0x-prefixed hex.
+	pub tx_hashes: Vec<String>,
+	/// Unix timestamp of the transfer.
+	pub timestamp: u64,
+}
+
+/// Allocation of an asset on one chain, resolved against the network config.
+struct ResolvedAllocation {
+	chain_id: u64,
+	token: Address,
+	target_bps: u32,
+}
+
+/// Moves inventory between chains to keep assets at their target allocation.
+pub struct Rebalancer {
+	/// Configuration holding the rebalance targets, read on every run.
+	config: SharedConfig,
+	/// Storage for transfer records.
+	storage: Arc<StorageService>,
+	/// Delivery service used to send transfers.
+	delivery: Arc<DeliveryService>,
+	/// Accounts holding the inventory.
+	accounts: Arc<SolverAccounts>,
+	/// Token manager used for token lookups and bridge allowances.
+	token_manager: Arc<TokenManager>,
+	/// Inventory monitor used to read balances.
+	inventory: Arc<InventoryMonitor>,
+	/// Chain state cache invalidated once a transfer settles.
+	chain_state: Arc<ChainStateService>,
+	/// Bridge adapters, in order of preference.
+	bridges: Arc<RebalanceService>,
+	/// Event bus for rebalancing events.
+	event_bus: EventBus,
+}
+
+impl Rebalancer {
+	/// Creates a new rebalancer.
+	#[allow(clippy::too_many_arguments)]
+	pub fn new(
+		config: SharedConfig,
+		storage: Arc<StorageService>,
+		delivery: Arc<DeliveryService>,
+		accounts: Arc<SolverAccounts>,
+		token_manager: Arc<TokenManager>,
+		inventory: Arc<InventoryMonitor>,
+		chain_state: Arc<ChainStateService>,
+		bridges: Arc<RebalanceService>,
+		event_bus: EventBus,
+	) -> Self {
+		Self {
+			config,
+			storage,
+			delivery,
+			accounts,
+			token_manager,
+			inventory,
+			chain_state,
+			bridges,
+			event_bus,
+		}
+	}
+
+	/// Returns the configured time between runs.
+	pub fn interval(&self) -> Duration {
+		Duration::from_secs(self.config.load().rebalance.interval_seconds)
+	}
+
+	/// Rebalances every configured asset once.
+	///
+	/// Does nothing while rebalancing is disabled or no bridge is loaded. A
+	/// failure on one asset is logged and does not stop the others.
+	pub async fn run(&self) {
+		let config = self.config.load_full();
+		if !config.rebalance.enabled || self.bridges.is_empty() {
+			return;
+		}
+
+		for asset in &config.rebalance.assets {
+			if let Err(e) = self.rebalance_asset(asset).await {
+				tracing::warn!(asset = %asset.symbol, error = %e, "Rebalancing failed");
+			}
+		}
+	}
+
+	/// Plans and sends the transfers for one asset.
+	async fn rebalance_asset(&self, asset: &RebalanceAsset) -> Result<(), RebalanceError> {
+		let (allocations, decimals) = self.resolve(asset)?;
+		let limits = RebalanceLimits {
+			min_transfer: to_base_units(asset.min_transfer, decimals).ok_or_else(|| {
+				RebalanceError::Config(format!("Invalid min_transfer for {}", asset.symbol))
+			})?,
+			max_transfer: to_base_units(asset.max_transfer, decimals).ok_or_else(|| {
+				RebalanceError::Config(format!("Invalid max_transfer for {}", asset.symbol))
+			})?,
+			tolerance_bps: asset.tolerance_bps,
+		};
+
+		// Skip the run if any balance cannot be read, since a missing chain
+		// would look like an empty one
+		let mut accounts = HashMap::new();
+		let mut totals = HashMap::new();
+		for allocation in &allocations {
+			let members = self.accounts.filler_pool.members(allocation.chain_id);
+			let Some(balances) = self
+				.inventory
+				.read_balance(allocation.chain_id, Some(&allocation.token), &members)
+				.await
+			else {
+				return Ok(());
+			};
+			let total = balances
+				.iter()
+				.fold(U256::ZERO, |sum, (_, b)| sum.saturating_add(*b));
+			totals.insert(allocation.chain_id, total);
+			accounts.insert(allocation.chain_id, balances);
+		}
+
+		let targets: Vec<(u64, u32)> = allocations
+			.iter()
+			.map(|a| (a.chain_id, a.target_bps))
+			.collect();
+		for planned in plan_transfers(&totals, &targets, &limits) {
+			if self
+				.in_cooldown(asset, planned.from_chain, planned.to_chain)
+				.await?
+			{
+				tracing::debug!(
+					asset = %asset.symbol,
+					from_chain = planned.from_chain,
+					to_chain = planned.to_chain,
+					"Rebalancing route in cooldown"
+				);
+				continue;
+			}
+
+			// Send from the richest account on the source chain to the
+			// poorest account on the destination chain
+			let Some((sender, available)) = accounts
+				.get(&planned.from_chain)
+				.and_then(|balances| balances.iter().max_by_key(|(_, b)| *b).cloned())
+			else {
+				continue;
+			};
+			let Some((recipient, _)) = accounts
+				.get(&planned.to_chain)
+				.and_then(|balances| balances.iter().min_by_key(|(_, b)| *b).cloned())
+			else {
+				continue;
+			};
+			let amount = planned.amount.min(available);
+			if amount < limits.min_transfer {
+				continue;
+			}
+
+			let token_of = |chain_id: u64| {
+				allocations
+					.iter()
+					.find(|a| a.chain_id == chain_id)
+					.map(|a| a.token.clone())
+			};
+			let (Some(from_token), Some(to_token)) =
+				(token_of(planned.from_chain), token_of(planned.to_chain))
+			else {
+				continue;
+			};
+			let request = TransferRequest {
+				asset: asset.symbol.clone(),
+				from_chain: planned.from_chain,
+				to_chain: planned.to_chain,
+				from_token,
+				to_token,
+				amount,
+				sender,
+				recipient,
+			};
+			self.transfer(&request).await?;
+
+			// The sender may fund another transfer in this run
+			if let Some((_, balance)) = accounts
+				.get_mut(&request.from_chain)
+				.and_then(|balances| balances.iter_mut().find(|(a, _)| *a == request.sender))
+			{
+				*balance = balance.saturating_sub(amount);
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Sends one transfer through the first bridge supporting its route.
+	async fn transfer(&self, request: &TransferRequest) -> Result<(), RebalanceError> {
+		let Some((bridge_name, bridge)) = self
+			.bridges
+			.bridge_for(request.from_chain, request.to_chain)
+		else {
+			tracing::warn!(
+				asset = %request.asset,
+				from_chain = request.from_chain,
+				to_chain = request.to_chain,
+				"No bridge supports rebalancing route"
+			);
+			return Ok(());
+		};
+
+		let transfer = bridge
+			.build_transfer(request)
+			.await
+			.map_err(|e| RebalanceError::Bridge(e.to_string()))?;
+
+		if let Some(spender) = &transfer.spender {
+			self.token_manager
+				.ensure_spender_allowance(
+					request.from_chain,
+					&request.from_token,
+					spender,
+					&request.sender,
+					request.amount,
+				)
+				.await
+				.map_err(|e| RebalanceError::Allowance(e.to_string()))?;
+		}
+
+		tracing::info!(
+			asset = %request.asset,
+			from_chain = request.from_chain,
+			to_chain = request.to_chain,
+			amount = %request.amount,
+			expected_amount = %transfer.expected_amount,
+			bridge = %bridge_name,
+			"Rebalancing inventory"
+		);
+
+		let mut tx_hashes = Vec::with_capacity(transfer.transactions.len());
+		for mut tx in transfer.transactions {
+			tx.from = Some(request.sender.clone());
+			let tx_hash = self
+				.delivery
+				.deliver(tx)
+				.await
+				.map_err(|e| RebalanceError::Delivery(e.to_string()))?;
+			let tx_hash_hex = with_0x_prefix(&hex::encode(&tx_hash.0));
+			let receipt = self
+				.delivery
+				.confirm_with_default(&tx_hash, request.from_chain)
+				.await;
+			// The transfer moved funds and spent gas, whatever its outcome
+			self.chain_state.invalidate(request.from_chain);
+			let receipt = receipt.map_err(|e| RebalanceError::Delivery(e.to_string()))?;
+			if !receipt.success {
+				return Err(RebalanceError::Delivery(format!(
+					"Transfer transaction {} reverted on chain {}",
+					tx_hash_hex, request.from_chain
+				)));
+			}
+			tx_hashes.push(tx_hash_hex);
+		}
+
+		let record = RebalanceRecord {
+			asset: request.asset.clone(),
+			from_chain: request.from_chain,
+			to_chain: request.to_chain,
+			amount: request.amount.to_string(),
+			expected_amount: transfer.expected_amount.to_string(),
+			bridge: bridge_name.to_string(),
+			tx_hashes,
+			timestamp: current_timestamp(),
+		};
+		self.storage
+			.store(
+				StorageKey::Rebalances.as_str(),
+				&route_id(&request.asset, request.from_chain, request.to_chain),
+				&record,
+				None,
+			)
+			.await
+			.map_err(|e| RebalanceError::Storage(e.to_string()))?;
+
+		self.event_bus
+			.publish(SolverEvent::Inventory(InventoryEvent::Rebalanced {
+				asset: record.asset,
+				from_chain: record.from_chain,
+				to_chain: record.to_chain,
+				amount: record.amount,
+				bridge: record.bridge,
+			}))
+			.ok();
+		Ok(())
+	}
+
+	/// Returns whether a route has seen a transfer within the asset's cooldown.
+	async fn in_cooldown(
+		&self,
+		asset: &RebalanceAsset,
+		from_chain: u64,
+		to_chain: u64,
+	) -> Result<bool, RebalanceError> {
+		let id = route_id(&asset.symbol, from_chain, to_chain);
+		let namespace = StorageKey::Rebalances.as_str();
+		if !self
+			.storage
+			.exists(namespace, &id)
+			.await
+			.map_err(|e| RebalanceError::Storage(e.to_string()))?
+		{
+			return Ok(false);
+		}
+		let record: RebalanceRecord = self
+			.storage
+			.retrieve(namespace, &id)
+			.await
+			.map_err(|e| RebalanceError::Storage(e.to_string()))?;
+		Ok(current_timestamp() < record.timestamp.saturating_add(asset.cooldown_seconds))
+	}
+
+	/// Resolves the token addresses and decimals of an asset's allocations.
+	fn resolve(
+		&self,
+		asset: &RebalanceAsset,
+	) -> Result<(Vec<ResolvedAllocation>, u8), RebalanceError> {
+		let mut allocations = Vec::with_capacity(asset.allocations.len());
+		let mut decimals = None;
+		for allocation in &asset.allocations {
+			let token = parse_address(&allocation.token).map_err(RebalanceError::Config)?;
+			let info = self
+				.token_manager
+				.get_token_info(allocation.chain_id, &token)
+				.map_err(|e| RebalanceError::Config(e.to_string()))?;
+			decimals = Some(info.decimals);
+			allocations.push(ResolvedAllocation {
+				chain_id: allocation.chain_id,
+				token,
+				target_bps: allocation.target_bps,
+			});
+		}
+		let decimals = decimals.ok_or_else(|| {
+			RebalanceError::Config(format!("Asset {} has no allocations", asset.symbol))
+		})?;
+		Ok((allocations, decimals))
+	}
+}
+
+/// Returns the storage ID of the last transfer on a route.
+fn route_id(asset: &str, from_chain: u64, to_chain: u64) -> String {
+	format!("{}-{}-{}", asset, from_chain, to_chain)
+}
@@ -15,18 +15,28 @@
 //!
 //! # Token Approvals
 //!
-//! The manager automatically sets MAX_UINT256 approvals for all configured tokens
-//! to their respective input and output settler contracts. This eliminates the need
-//! for per-transaction approvals and reduces gas costs during order execution.
-
+//! Allowances for the input and output settler contracts follow the configured
+//! `ApprovalPolicy`: unlimited approvals, a rolling cap that is topped up when it
+//! runs low, or exact approvals sized to the account's unconfirmed fills.
+//! Allowances are checked at startup and again before every fill, so the
+//! process is idempotent and repairs allowances that have been spent down
+//! below the refill threshold.
+//! Tokens that only accept a change from a zero allowance (such as USDT) can be
+//! configured to be reset to zero first.
+
+use crate::engine::chain_state::ChainStateService;
 use alloy_primitives::{hex, U256};
+use arc_swap::ArcSwap;
+use rust_decimal::Decimal;
 use solver_account::AccountService;
+use solver_config::{ApprovalConfig, ApprovalPolicy};
 use solver_delivery::DeliveryService;
 use solver_types::{
 	with_0x_prefix, Address, NetworksConfig, TokenConfig, Transaction, TransactionHash,
+	TransactionType,
 };
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, OnceLock};
 use thiserror::Error;
 
 /// Errors that can occur during token management operations.
@@ -51,8 +61,15 @@ pub enum TokenManagerError {
 	/// Failed to parse a value.
 	#[error("Failed to parse value: {0}")]
 	ParseError(String),
+
+	/// An approval could not be set.
+	#[error("Approval failed: {0}")]
+	ApprovalFailed(String),
 }
 
+/// Key identifying a single allowance: (chain ID, token, spender, owner).
+type AllowanceKey = (u64, Address, Address, Address);
+
 /// Manages token configurations and approvals across multiple blockchain networks.
 ///
 /// The `TokenManager` is responsible for:
@@ -65,11 +82,23 @@ pub enum TokenManagerError {
 /// across all components that need token information.
 pub struct TokenManager {
 	/// Network configurations mapping chain IDs to their token and settler information.
-	networks: NetworksConfig,
+	/// Swappable so token list changes can be applied on config reload.
+	networks: ArcSwap<NetworksConfig>,
 	/// Service for delivering transactions to various blockchain networks.
 	delivery: Arc<DeliveryService>,
-	/// Service for managing the solver's account and signatures.
+	/// Account that owns the managed allowances (the approver role).
 	account: Arc<AccountService>,
+	/// Approval policy, swappable on config reload.
+	approvals: ArcSwap<ApprovalConfig>,
+	/// Further accounts per chain that spend tokens through the settlers and
+	/// therefore need their own allowances, such as filler pool members.
+	allowance_owners: HashMap<u64, Vec<Address>>,
+	/// Serializes approvals of the same allowance so concurrent fills do not
+	/// submit duplicate approval transactions.
+	approval_locks: Mutex<HashMap<AllowanceKey, Arc<tokio::sync::Mutex<()>>>>,
+	/// Chain state cache invalidated once an approval settles, since the gas
+	/// it spent changed the approver's balance.
+	chain_state: OnceLock<Arc<ChainStateService>>,
 }
 
 impl TokenManager {
@@ -79,24 +108,58 @@ impl TokenManager {
 	///
 	/// * `networks` - Configuration for all supported networks and their tokens
 	/// * `delivery` - Service for delivering transactions to blockchain networks
-	/// * `account` - Service for managing the solver's account
+	/// * `account` - Account that owns the token allowances (the approver role)
+	/// * `approvals` - Policy for the allowances granted to settler contracts
 	pub fn new(
 		networks: NetworksConfig,
 		delivery: Arc<DeliveryService>,
 		account: Arc<AccountService>,
+		approvals: ApprovalConfig,
 	) -> Self {
 		Self {
-			networks,
+			networks: ArcSwap::from_pointee(networks),
 			delivery,
 			account,
+			approvals: ArcSwap::from_pointee(approvals),
+			allowance_owners: HashMap::new(),
+			approval_locks: Mutex::new(HashMap::new()),
+			chain_state: OnceLock::new(),
 		}
 	}
 
-	/// Ensures all configured tokens have MAX_UINT256 approval for their respective settlers.
+	/// Sets the further accounts per chain whose allowances are managed
+	/// alongside the approver's, such as the filler pool members.
+	pub fn with_allowance_owners(mut self, owners: HashMap<u64, Vec<Address>>) -> Self {
+		self.allowance_owners = owners;
+		self
+	}
+
+	/// Sets the chain state cache to invalidate when an approval settles.
+	///
+	/// The engine creates the cache after the token manager, so it attaches
+	/// it here once built. Later calls are ignored.
+	pub fn set_chain_state(&self, chain_state: Arc<ChainStateService>) {
+		let _ = self.chain_state.set(chain_state);
+	}
+
+	/// Replaces the network configurations, e.g. after a token list change.
+	pub fn update_networks(&self, networks: NetworksConfig) {
+		self.networks.store(Arc::new(networks));
+	}
+
+	/// Replaces the approval policy, e.g. after a config reload.
+	pub fn update_approvals(&self, approvals: ApprovalConfig) {
+		self.approvals.store(Arc::new(approvals));
+	}
+
+	/// Ensures all configured tokens have the allowances required by the approval policy.
 	///
 	/// This method iterates through all configured tokens on all networks and checks
-	/// if the solver has already approved the maximum amount for both input and output
-	/// settlers. If not, it submits approval transactions.
+	/// the allowance each managed account has granted to the input and output
+	/// settlers. Allowances below the policy's refill threshold are topped up and
+	/// the approval transactions are awaited before returning. Running it again is
+	/// a no-op while allowances stay above the threshold. Under the `exact` policy
+	/// nothing is approved here; approvals are made per fill instead.
 	///
 	/// # Returns
 	///
@@ -108,89 +171,246 @@ impl TokenManager {
 	/// This method should be called during solver initialization to ensure all
 	/// necessary approvals are in place before processing orders.
 	pub async fn ensure_approvals(&self) -> Result<(), TokenManagerError> {
-		// TODO: Should re-enable. Temporarily disable
-
-		// let solver_address = self.account.get_address().await?;
-		// let solver_address_str = with_0x_prefix(&hex::encode(&solver_address.0));
-		// let max_uint256 = U256::MAX;
-		// let max_uint256_str = max_uint256.to_string();
-
-		// for (chain_id, network) in &self.networks {
-		// 	for token in &network.tokens {
-		// 		// Process input settler if not zero address
-		// 		if network.input_settler_address.0 != [0u8; 20] {
-		// 			// Check allowance for input settler
-		// 			let current_allowance_input = self
-		// 				.delivery
-		// 				.get_allowance(
-		// 					*chain_id,
-		// 					&solver_address_str,
-		// 					&with_0x_prefix(&hex::encode(&network.input_settler_address.0)),
-		// 					&with_0x_prefix(&hex::encode(&token.address.0)),
-		// 				)
-		// 				.await?;
-
-		// 			if current_allowance_input != max_uint256_str {
-		// 				tracing::info!(
-		// 					"Setting approval for token {} on chain {} for input settler",
-		// 					token.symbol,
-		// 					chain_id
-		// 				);
-		// 				self.submit_approval(
-		// 					*chain_id,
-		// 					&token.address,
-		// 					&network.input_settler_address,
-		// 					max_uint256,
-		// 				)
-		// 				.await?;
-		// 			}
-		// 		}
-
-		// 		// Process output settler if not zero address
-		// 		if network.output_settler_address.0 != [0u8; 20] {
-		// 			// Check allowance for output settler
-		// 			let current_allowance_output = self
-		// 				.delivery
-		// 				.get_allowance(
-		// 					*chain_id,
-		// 					&solver_address_str,
-		// 					&with_0x_prefix(&hex::encode(&network.output_settler_address.0)),
-		// 					&with_0x_prefix(&hex::encode(&token.address.0)),
-		// 				)
-		// 				.await?;
-
-		// 			if current_allowance_output != max_uint256_str {
-		// 				tracing::info!(
-		// 					"Setting approval for token {} on chain {} for output settler",
-		// 					token.symbol,
-		// 					chain_id
-		// 				);
-		// 				self.submit_approval(
-		// 					*chain_id,
-		// 					&token.address,
-		// 					&network.output_settler_address,
-		// 					max_uint256,
-		// 				)
-		// 				.await?;
-		// 			}
-		// 		}
-		// 	}
-		// }
+		let networks = self.networks.load_full();
+		for (chain_id, network) in networks.iter() {
+			let owners = self.allowance_owners_for(*chain_id).await?;
+			let spenders = [
+				&network.input_settler_address,
+				&network.output_settler_address,
+			];
+			for token in &network.tokens {
+				for spender in spenders {
+					// Skip settlers that are not deployed on this chain
+					if spender.0.iter().all(|b| *b == 0) {
+						continue;
+					}
+					for owner in &owners {
+						self.ensure_allowance(*chain_id, token, spender, owner, U256::ZERO)
+							.await?;
+					}
+				}
+			}
+		}
 
 		Ok(())
 	}
 
+	/// Ensures an account can spend `amount` of a token through the output settler.
+	///
+	/// Called before each fill with the total the account has reserved for its
+	/// unconfirmed fills, so that an exact approval made for one fill is not
+	/// consumed by another fill sent concurrently. Depending on the policy this
+	/// approves that amount, tops up a rolling cap or repairs a depleted
+	/// unlimited approval. Tokens that are not configured on the chain are left
+	/// alone.
+	pub async fn ensure_fill_allowance(
+		&self,
+		chain_id: u64,
+		token_address: &Address,
+		owner: &Address,
+		amount: U256,
+	) -> Result<(), TokenManagerError> {
+		let networks = self.networks.load_full();
+		let network = networks
+			.get(&chain_id)
+			.ok_or(TokenManagerError::NetworkNotConfigured(chain_id))?;
+
+		self.ensure_spender_allowance(
+			chain_id,
+			token_address,
+			&network.output_settler_address,
+			owner,
+			amount,
+		)
+		.await
+	}
+
+	/// Ensures an account can spend `amount` of a token through any contract.
+	///
+	/// Applies the same policy as fill allowances; used for contracts other
+	/// than the output settler, such as bridges moving inventory. Tokens that
+	/// are not configured on the chain are left alone.
+	pub async fn ensure_spender_allowance(
+		&self,
+		chain_id: u64,
+		token_address: &Address,
+		spender: &Address,
+		owner: &Address,
+		amount: U256,
+	) -> Result<(), TokenManagerError> {
+		let networks = self.networks.load_full();
+		let network = networks
+			.get(&chain_id)
+			.ok_or(TokenManagerError::NetworkNotConfigured(chain_id))?;
+		let Some(token) = network.tokens.iter().find(|t| t.address == *token_address) else {
+			tracing::debug!(
+				chain_id = chain_id,
+				token = %token_address,
+				"Token not configured, skipping allowance check"
+			);
+			return Ok(());
+		};
+
+		self.ensure_allowance(chain_id, token, spender, owner, amount)
+			.await?;
+
+		Ok(())
+	}
+
+	/// Checks one allowance and approves the policy target if it is too low.
+	///
+	/// Returns the hash of the approval transaction if one was sent.
+	async fn ensure_allowance(
+		&self,
+		chain_id: u64,
+		token: &TokenConfig,
+		spender: &Address,
+		owner: &Address,
+		required: U256,
+	) -> Result<Option<TransactionHash>, TokenManagerError> {
+		let settings = self
+			.approvals
+			.load()
+			.settings_for(chain_id, &token.address.to_string());
+
+		let target = match settings.policy {
+			ApprovalPolicy::Unlimited => U256::MAX,
+			ApprovalPolicy::RollingCap => {
+				cap_in_base_units(settings.cap, token.decimals)?.max(required)
+			},
+			ApprovalPolicy::Exact => required,
+		};
+		if target.is_zero() {
+			return Ok(None);
+		}
+		let threshold = match settings.policy {
+			ApprovalPolicy::Exact => required,
+			_ => (target / U256::from(10_000u64) * U256::from(settings.refill_threshold_bps))
+				.max(required),
+		};
+
+		// Hold the lock across the check and the approval so that concurrent
+		// callers see the updated allowance instead of approving again
+		let lock = self.approval_lock((
+			chain_id,
+			token.address.clone(),
+			spender.clone(),
+			owner.clone(),
+		));
+		let _guard = lock.lock().await;
+
+		let current = self
+			.get_allowance(chain_id, &token.address, spender, owner)
+			.await?;
+		if current >= threshold {
+			return Ok(None);
+		}
+
+		tracing::info!(
+			chain_id = chain_id,
+			token = %token.symbol,
+			spender = %spender,
+			owner = %owner,
+			policy = ?settings.policy,
+			current = %current,
+			target = %target,
+			"Setting token approval"
+		);
+
+		if settings.reset_to_zero && !current.is_zero() {
+			let reset_hash = self
+				.submit_approval(chain_id, &token.address, spender, owner, U256::ZERO)
+				.await?;
+			self.confirm_approval(chain_id, &reset_hash).await?;
+		}
+
+		let tx_hash = self
+			.submit_approval(chain_id, &token.address, spender, owner, target)
+			.await?;
+		self.confirm_approval(chain_id, &tx_hash).await?;
+
+		Ok(Some(tx_hash))
+	}
+
+	/// Returns the accounts whose allowances are managed on a chain, approver first.
+	async fn allowance_owners_for(&self, chain_id: u64) -> Result<Vec<Address>, TokenManagerError> {
+		let mut owners = vec![self.account.get_address().await?];
+		for owner in self.allowance_owners.get(&chain_id).into_iter().flatten() {
+			if !owners.contains(owner) {
+				owners.push(owner.clone());
+			}
+		}
+		Ok(owners)
+	}
+
+	/// Returns the lock guarding approvals of a single allowance.
+	fn approval_lock(&self, key: AllowanceKey) -> Arc<tokio::sync::Mutex<()>> {
+		self.approval_locks
+			.lock()
+			.unwrap()
+			.entry(key)
+			.or_default()
+			.clone()
+	}
+
+	/// Reads the current allowance of `owner` for `spender` on a token.
+	async fn get_allowance(
+		&self,
+		chain_id: u64,
+		token_address: &Address,
+		spender: &Address,
+		owner: &Address,
+	) -> Result<U256, TokenManagerError> {
+		let allowance = self
+			.delivery
+			.get_allowance(
+				chain_id,
+				&with_0x_prefix(&hex::encode(&owner.0)),
+				&with_0x_prefix(&hex::encode(&spender.0)),
+				&with_0x_prefix(&hex::encode(&token_address.0)),
+			)
+			.await?;
+
+		U256::from_str_radix(&allowance, 10)
+			.map_err(|e| TokenManagerError::ParseError(format!("Invalid allowance: {}", e)))
+	}
+
+	/// Waits for an approval transaction to be included and checks that it succeeded.
+	///
+	/// A single confirmation is enough since the allowance is usable as soon as the
+	/// approval is included.
+	async fn confirm_approval(
+		&self,
+		chain_id: u64,
+		tx_hash: &TransactionHash,
+	) -> Result<(), TokenManagerError> {
+		let receipt = self.delivery.confirm(tx_hash, chain_id, 1).await;
+		if let Some(chain_state) = self.chain_state.get() {
+			chain_state.invalidate(chain_id);
+		}
+		let receipt = receipt?;
+		if !receipt.success {
+			return Err(TokenManagerError::ApprovalFailed(format!(
+				"Approval transaction {} reverted on chain {}",
+				with_0x_prefix(&hex::encode(&tx_hash.0)),
+				chain_id
+			)));
+		}
+		Ok(())
+	}
+
 	/// Submits an ERC20 approval transaction.
 	///
 	/// Creates and submits a transaction to approve the specified spender to transfer
-	/// the given amount of tokens on behalf of the solver.
+	/// the given amount of tokens on behalf of the owner.
 	///
 	/// # Arguments
 	///
 	/// * `chain_id` - The blockchain network ID
 	/// * `token_address` - The ERC20 token contract address
 	/// * `spender` - The address being granted approval (settler contract)
-	/// * `amount` - The amount to approve (typically MAX_UINT256)
+	/// * `owner` - The account granting the approval, which sends the transaction
+	/// * `amount` - The amount to approve
 	///
 	/// # Returns
 	///
@@ -200,6 +420,7 @@ impl TokenManager {
 		chain_id: u64,
 		token_address: &Address,
 		spender: &Address,
+		owner: &Address,
 		amount: U256,
 	) -> Result<TransactionHash, TokenManagerError> {
 		// Create approval transaction data
@@ -217,12 +438,14 @@ impl TokenManager {
 		let amount_bytes = amount.to_be_bytes::<32>();
 		call_data.extend_from_slice(&amount_bytes);
 
-		let tx = Transaction {
+		// The gas limit is left for the provider to estimate
+		let mut tx = Transaction {
 			chain_id,
+			from: Some(owner.clone()),
 			to: Some(token_address.clone()),
 			data: call_data,
 			value: U256::ZERO,
-			gas_limit: Some(100000),
+			gas_limit: None,
 			gas_price: None,
 			max_fee_per_gas: None,
 			max_priority_fee_per_gas: None,
@@ -230,6 +453,11 @@ impl TokenManager {
 			metadata: None,
 		};
 
+		// Approvals gate fills, so they are priced at fill urgency
+		if let Some(chain_state) = self.chain_state.get() {
+			chain_state.apply_fees(&mut tx, TransactionType::Fill).await;
+		}
+
 		let tx_hash = self.delivery.deliver(tx).await?;
 
 		Ok(tx_hash)
@@ -252,7 +480,8 @@ impl TokenManager {
 		let solver_address_str = hex::encode(&solver_address.0);
 		let mut balances = HashMap::new();
 
-		for (chain_id, network) in &self.networks {
+		let networks = self.networks.load_full();
+		for (chain_id, network) in networks.iter() {
 			for token in &network.tokens {
 				let balance = self
 					.delivery
@@ -314,7 +543,7 @@ impl TokenManager {
 	///
 	/// Returns `true` if the token is configured for the specified chain, `false` otherwise.
 	pub fn is_supported(&self, chain_id: u64, token_address: &Address) -> bool {
-		if let Some(network) = self.networks.get(&chain_id) {
+		if let Some(network) = self.networks.load().get(&chain_id) {
 			network.tokens.iter().any(|t| t.address == *token_address)
 		} else {
 			false
@@ -337,8 +566,8 @@ impl TokenManager {
 		chain_id: u64,
 		token_address: &Address,
 	) -> Result<TokenConfig, TokenManagerError> {
-		let network = self
-			.networks
+		let networks = self.networks.load();
+		let network = networks
 			.get(&chain_id)
 			.ok_or(TokenManagerError::NetworkNotConfigured(chain_id))?;
 
@@ -364,6 +593,7 @@ impl TokenManager {
 	/// Returns an empty vector if the chain is not configured.
 	pub fn get_tokens_for_chain(&self, chain_id: u64) -> Vec<TokenConfig> {
 		self.networks
+			.load()
 			.get(&chain_id)
 			.map(|n| n.tokens.clone())
 			.unwrap_or_default()
@@ -373,8 +603,20 @@ impl TokenManager {
 	///
 	/// # Returns
 	///
-	/// Returns a reference to the `NetworksConfig` containing all network and token configurations.
-	pub fn get_networks(&self) -> &NetworksConfig {
-		&self.networks
+	/// Returns a snapshot of the `NetworksConfig` containing all network and token configurations.
+	pub fn get_networks(&self) -> Arc<NetworksConfig> {
+		self.networks.load_full()
 	}
 }
+
+/// Converts a cap in whole token units to base units.
+fn cap_in_base_units(cap: Option<Decimal>, decimals: u8) -> Result<U256, TokenManagerError> {
+	let cap = cap.ok_or_else(|| {
+		TokenManagerError::ApprovalFailed("rolling_cap policy requires a cap".to_string())
+	})?;
+	let mantissa = u128::try_from(cap.mantissa())
+		.map_err(|_| TokenManagerError::ParseError(format!("Invalid approval cap: {}", cap)))?;
+
+	let ten = U256::from(10u64);
+	Ok(U256::from(mantissa) * ten.pow(U256::from(decimals)) / ten.pow(U256::from(cap.scale())))
+}
@@ -4,22 +4,29 @@
 //! and determining execution strategy through the order service.
 
 use crate::engine::{
-	context::ContextBuilder, cost_profit::CostProfitService, event_bus::EventBus,
-	token_manager::TokenManager,
+	accounts::SolverAccounts, chain_state::ChainStateService, context::ContextBuilder,
+	cost_profit::CostProfitService, event_bus::EventBus, exposure::ExposureTracker,
+	inventory::InventoryMonitor, metrics::DecisionMetrics, token_manager::TokenManager,
 };
 use crate::state::OrderStateMachine;
-use solver_config::Config;
-use solver_delivery::DeliveryService;
+use solver_config::SharedConfig;
 use solver_order::OrderService;
 use solver_storage::StorageService;
 use solver_types::{
-	truncate_id, Address, DiscoveryEvent, ExecutionDecision, Intent, OrderEvent, SolverEvent,
-	StorageKey,
+	current_timestamp, truncate_id, Address, DiscoveryEvent, Eip7683OrderData, ExecutionDecision,
+	Intent, Order, OrderEvent, Quote, SolverEvent, StorageKey,
 };
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
+use tokio::sync::Mutex;
 use tracing::instrument;
 
+/// Time a quote's acceptance is remembered after the quote expires, in seconds.
+///
+/// Covers orders for the quote that are still in flight when it expires.
+const QUOTE_USAGE_GRACE_SECONDS: u64 = 3_600;
+
 /// Errors that can occur during intent processing.
 ///
 /// These errors represent failures in validating intents,
@@ -44,11 +51,17 @@ pub struct IntentHandler {
 	storage: Arc<StorageService>,
 	state_machine: Arc<OrderStateMachine>,
 	event_bus: EventBus,
-	delivery: Arc<DeliveryService>,
+	chain_state: Arc<ChainStateService>,
 	solver_address: Address,
 	token_manager: Arc<TokenManager>,
 	cost_profit_service: Arc<CostProfitService>,
-	config: Config,
+	inventory: Arc<InventoryMonitor>,
+	exposure: Arc<ExposureTracker>,
+	metrics: Arc<DecisionMetrics>,
+	accounts: Arc<SolverAccounts>,
+	config: SharedConfig,
+	/// Serializes quote acceptance so each quote binds to a single order.
+	quote_lock: Mutex<()>,
 }
 
 impl IntentHandler {
@@ -58,22 +71,31 @@ impl IntentHandler {
 		storage: Arc<StorageService>,
 		state_machine: Arc<OrderStateMachine>,
 		event_bus: EventBus,
-		delivery: Arc<DeliveryService>,
+		chain_state: Arc<ChainStateService>,
 		solver_address: Address,
 		token_manager: Arc<TokenManager>,
 		cost_profit_service: Arc<CostProfitService>,
-		config: Config,
+		inventory: Arc<InventoryMonitor>,
+		exposure: Arc<ExposureTracker>,
+		metrics: Arc<DecisionMetrics>,
+		accounts: Arc<SolverAccounts>,
+		config: SharedConfig,
 	) -> Self {
 		Self {
 			order_service,
 			storage,
 			state_machine,
 			event_bus,
-			delivery,
+			chain_state,
 			solver_address,
 			token_manager,
 			cost_profit_service,
+			inventory,
+			exposure,
+			metrics,
+			accounts,
 			config,
+			quote_lock: Mutex::new(()),
 		}
 	}
 
@@ -142,11 +164,54 @@ impl IntentHandler {
 			)
 			.await
 		{
-			Ok(order) => {
+			Ok(mut order) => {
+				order.quote_id = intent.quote_id.clone();
+
+				// Skip orders opened by our own accounts, such as rebalancing transfers
+				if self.is_own_order(&order) {
+					tracing::info!("Skipping order opened by a solver account");
+					self.skip_order(&intent, &order, "Order opened by solver".to_string())
+						.await;
+					return Ok(());
+				}
+
+				// Skip orders whose destination inventory is paused
+				if let Some(reason) = self.paused_route_reason(&order) {
+					tracing::warn!(reason = %reason, "Skipping order on paused route");
+					self.skip_order(&intent, &order, format!("Route paused: {}", reason))
+						.await;
+					return Ok(());
+				}
+
+				// Check the order against the quote it accepts, if any
+				let quoted = match order.quote_id.clone() {
+					Some(quote_id) => match self.bind_quote(&quote_id, &order).await {
+						Ok(quoted) => quoted,
+						Err(reason) => {
+							tracing::warn!(
+								quote_id = %quote_id,
+								reason = %reason,
+								"Order rejected against its quote"
+							);
+							self.skip_order(
+								&intent,
+								&order,
+								format!("Quote {} rejected: {}", quote_id, reason),
+							)
+							.await;
+							return Ok(());
+						},
+					},
+					None => false,
+				};
+
+				// Snapshot the config so a concurrent reload can't change it mid-decision
+				let config = self.config.load_full();
+
 				// Calculate cost estimation and validate profitability
 				let cost_estimate = match self
 					.cost_profit_service
-					.estimate_cost_for_order(&order, &self.config)
+					.estimate_cost_for_order(&order, &config)
 					.await
 				{
 					Ok(estimate) => {
@@ -167,13 +232,14 @@ impl IntentHandler {
 				};
 
 				// Validate profitability (skip for Signet testnet orders)
+				let mut profit_margin_pct = None;
 				if order.standard != "signet" {
 					match self
 						.cost_profit_service
 						.validate_profitability(
 							&order,
 							&cost_estimate,
-							self.config.solver.min_profitability_pct,
+							config.solver.min_profitability_pct,
 						)
 						.await
 					{
@@ -181,29 +247,24 @@ impl IntentHandler {
 							tracing::info!(
 								"Order passed profitability validation: {:.2}% (min required: {:.2}%)",
 								actual_profit_margin,
-								self.config.solver.min_profitability_pct
+								config.solver.min_profitability_pct
+							);
+							profit_margin_pct = Some(actual_profit_margin);
+						},
+						Err(e) if quoted => {
+							tracing::info!(
+								"Honouring quoted terms despite current profitability: {}",
+								e
 							);
 						},
 						Err(e) => {
 							tracing::warn!("Order failed profitability validation: {}", e);
-							self.event_bus
-								.publish(SolverEvent::Order(OrderEvent::Skipped {
-									order_id: order.id.clone(),
-									reason: format!("Insufficient profitability: {}", e),
-								}))
-								.ok();
-							// Remove intent to prevent duplicate processing
-							if let Err(remove_err) = self
-								.storage
-								.remove(StorageKey::Intents.as_str(), &intent.id)
-								.await
-							{
-								tracing::warn!(
-									intent_id = %intent.id,
-									error = %remove_err,
-									"Failed to remove intent after profitability skip"
-								);
-							}
+							self.skip_order(
+								&intent,
+								&order,
+								format!("Insufficient profitability: {}", e),
+							)
+							.await;
 							return Ok(());
 						},
 					}
@@ -228,15 +289,34 @@ impl IntentHandler {
 
 				// Check execution strategy
 				let builder = ContextBuilder::new(
-					self.delivery.clone(),
+					self.chain_state.clone(),
 					self.solver_address.clone(),
 					self.token_manager.clone(),
-					self.config.clone(),
+					(*config).clone(),
 				);
-				let context = builder
+				let mut context = builder
 					.build_execution_context(&intent)
 					.await
 					.map_err(|e| IntentError::Service(e.to_string()))?;
+
+				// Let the strategy weigh the order against capital already in flight.
+				// The order's exposure is reserved first, so orders evaluated
+				// concurrently cannot together exceed the limits.
+				let order_exposure = match self.exposure.value_order(&order).await {
+					Ok(exposure) => Some(exposure),
+					Err(e) => {
+						tracing::warn!(error = %e, "Failed to value order exposure");
+						None
+					},
+				};
+				context.in_flight = match &order_exposure {
+					Some(exposure) => self.exposure.reserve(exposure.clone()),
+					None => self.exposure.snapshot(),
+				};
+				context.order_exposure = order_exposure;
+				context.profit_margin_pct = profit_margin_pct;
+				context.quoted = quoted;
+
 				match self.order_service.should_execute(&order, &context).await {
 					ExecutionDecision::Execute(params) => {
 						self.event_bus
@@ -248,6 +328,9 @@ impl IntentHandler {
 							.ok();
 					},
 					ExecutionDecision::Skip(reason) => {
+						tracing::info!(reason = %reason, "Strategy skipped order");
+						self.metrics.record_skip(&reason);
+						self.exposure.release(&order.id);
 						self.event_bus
 							.publish(SolverEvent::Order(OrderEvent::Skipped {
 								order_id: order.id,
@@ -255,11 +338,19 @@ impl IntentHandler {
 							}))
 							.ok();
 					},
-					ExecutionDecision::Defer(duration) => {
+					ExecutionDecision::Defer(duration, reason) => {
+						tracing::info!(
+							reason = %reason,
+							retry_after_secs = duration.as_secs(),
+							"Strategy deferred order"
+						);
+						self.metrics.record_defer(&reason);
+						self.exposure.release(&order.id);
 						self.event_bus
 							.publish(SolverEvent::Order(OrderEvent::Deferred {
 								order_id: order.id,
 								retry_after: duration,
+								reason,
 							}))
 							.ok();
 					},
@@ -281,4 +372,154 @@ impl IntentHandler {
 
 		Ok(())
 	}
+
+	/// Returns whether any input of the order is locked by one of the solver's accounts.
+	fn is_own_order(&self, order: &Order) -> bool {
+		let Ok(parsed) = order.parse_order_data() else {
+			return false;
+		};
+		parsed.parse_available_inputs().iter().any(|input| {
+			input
+				.user
+				.ethereum_address()
+				.is_ok_and(|user| self.accounts.contains(&Address(user.to_vec())))
+		})
+	}
+
+	/// Returns why the order cannot be filled because a destination route is paused.
+	fn paused_route_reason(&self, order: &Order) -> Option<String> {
+		let parsed = order.parse_order_data().ok()?;
+		parsed.parse_requested_outputs().iter().find_map(|output| {
+			let chain_id = output.asset.ethereum_chain_id().ok()?;
+			let token = output.asset.ethereum_address().ok()?;
+			// The zero address stands for the native asset
+			let token = (!token.is_zero()).then(|| Address(token.to_vec()));
+			self.inventory.route_pause_reason(chain_id, token.as_ref())
+		})
+	}
+
+	/// Binds an order to the quote it accepts.
+	///
+	/// The order's terms must match the stored quote field by field, and each
+	/// quote can only be accepted by one order. Quotes that expired or are no
+	/// longer stored are not honoured, leaving the order to be evaluated at
+	/// current prices.
+	///
+	/// # Returns
+	///
+	/// Whether the quoted terms are honoured, or why the order is rejected.
+	async fn bind_quote(&self, quote_id: &str, order: &Order) -> Result<bool, String> {
+		let _guard = self.quote_lock.lock().await;
+
+		if let Ok(accepted_by) = self
+			.storage
+			.retrieve::<String>(StorageKey::QuoteUsage.as_str(), quote_id)
+			.await
+		{
+			if accepted_by != order.id {
+				return Err(format!(
+					"already accepted by order {}",
+					truncate_id(&accepted_by)
+				));
+			}
+		}
+
+		let quote = match self
+			.storage
+			.retrieve::<Quote>(StorageKey::Quotes.as_str(), quote_id)
+			.await
+		{
+			Ok(quote) => quote,
+			Err(e) => {
+				tracing::warn!(
+					quote_id = %quote_id,
+					error = %e,
+					"Quote unavailable, evaluating order at current prices"
+				);
+				return Ok(false);
+			},
+		};
+
+		let submitted = serde_json::from_value::<Eip7683OrderData>(order.data.clone())
+			.map_err(|e| format!("unable to parse order: {}", e))?;
+		let quoted = Eip7683OrderData::from_quote(&quote)
+			.map_err(|e| format!("unable to decode quoted order: {}", e))?;
+		if let Some(mismatch) = submitted.quote_mismatch(&quoted) {
+			return Err(mismatch);
+		}
+
+		self.storage
+			.store_with_ttl(
+				StorageKey::QuoteUsage.as_str(),
+				quote_id,
+				&order.id,
+				None,
+				Some(quote_usage_ttl(quote.valid_until, current_timestamp())),
+			)
+			.await
+			.map_err(|e| format!("failed to record quote acceptance: {}", e))?;
+
+		let expired = quote
+			.valid_until
+			.is_some_and(|valid_until| current_timestamp() > valid_until);
+		if expired {
+			tracing::info!(
+				quote_id = %quote_id,
+				"Quote expired, evaluating order at current prices"
+			);
+		}
+		Ok(!expired)
+	}
+
+	/// Publishes a skip for an order and releases its intent.
+	async fn skip_order(&self, intent: &Intent, order: &Order, reason: String) {
+		self.metrics.record_skip(&reason);
+		self.event_bus
+			.publish(SolverEvent::Order(OrderEvent::Skipped {
+				order_id: order.id.clone(),
+				reason,
+			}))
+			.ok();
+		// Remove intent to prevent duplicate processing
+		if let Err(remove_err) = self
+			.storage
+			.remove(StorageKey::Intents.as_str(), &intent.id)
+			.await
+		{
+			tracing::warn!(
+				intent_id = %intent.id,
+				error = %remove_err,
+				"Failed to remove intent after skip"
+			);
+		}
+	}
+}
+
+/// Returns how long the acceptance of a quote valid until `valid_until` is kept.
+fn quote_usage_ttl(valid_until: Option<u64>, now: u64) -> Duration {
+	let remaining = valid_until.map_or(0, |valid_until| valid_until.saturating_sub(now));
+	Duration::from_secs(remaining + QUOTE_USAGE_GRACE_SECONDS)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_quote_usage_ttl() {
+		let now = 1_700_000_000;
+		assert_eq!(
+			quote_usage_ttl(Some(now + 300), now),
+			Duration::from_secs(300 + QUOTE_USAGE_GRACE_SECONDS)
+		);
+		// Expired or open-ended quotes are remembered for the grace period
+		assert_eq!(
+			quote_usage_ttl(Some(now - 60), now),
+			Duration::from_secs(QUOTE_USAGE_GRACE_SECONDS)
+		);
+		assert_eq!(
+			quote_usage_ttl(None, now),
+			Duration::from_secs(QUOTE_USAGE_GRACE_SECONDS)
+		);
+	}
 }
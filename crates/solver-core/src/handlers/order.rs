@@ -3,15 +3,18 @@
 //! Manages the generation and submission of prepare transactions (for off-chain orders)
 //! and fill transactions, updating order state and publishing appropriate events.
 
-use crate::engine::event_bus::EventBus;
+use crate::engine::{
+	accounts::SolverAccounts, chain_state::ChainStateService, event_bus::EventBus,
+	exposure::ExposureTracker, preflight::PreflightService, token_manager::TokenManager,
+};
 use crate::state::OrderStateMachine;
-use alloy_primitives::hex;
+use alloy_primitives::{hex, U256};
 use solver_delivery::DeliveryService;
 use solver_order::OrderService;
 use solver_storage::StorageService;
 use solver_types::{
-	truncate_id, DeliveryEvent, ExecutionParams, Order, OrderEvent, OrderStatus, SolverEvent,
-	StorageKey, TransactionType,
+	truncate_id, Address, DeliveryEvent, ExecutionParams, Intent, Order, OrderEvent, OrderStatus,
+	SolverEvent, StorageKey, TransactionType,
 };
 use std::sync::Arc;
 use thiserror::Error;
@@ -42,15 +45,26 @@ pub struct OrderHandler {
 	storage: Arc<StorageService>,
 	state_machine: Arc<OrderStateMachine>,
 	event_bus: EventBus,
+	accounts: Arc<SolverAccounts>,
+	token_manager: Arc<TokenManager>,
+	chain_state: Arc<ChainStateService>,
+	preflight: Arc<PreflightService>,
+	exposure: Arc<ExposureTracker>,
 }
 
 impl OrderHandler {
+	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		order_service: Arc<OrderService>,
 		delivery: Arc<DeliveryService>,
 		storage: Arc<StorageService>,
 		state_machine: Arc<OrderStateMachine>,
 		event_bus: EventBus,
+		accounts: Arc<SolverAccounts>,
+		token_manager: Arc<TokenManager>,
+		chain_state: Arc<ChainStateService>,
+		preflight: Arc<PreflightService>,
+		exposure: Arc<ExposureTracker>,
 	) -> Self {
 		Self {
 			order_service,
@@ -58,30 +72,126 @@ impl OrderHandler {
 			storage,
 			state_machine,
 			event_bus,
+			accounts,
+			token_manager,
+			chain_state,
+			preflight,
+			exposure,
+		}
+	}
+
+	/// Fails an order at a step whose transaction could not be sent.
+	///
+	/// Nothing was paid out, so the filler and the order's exposure are
+	/// released and the order is marked failed rather than left in flight,
+	/// where exposure recovery would count it again after a restart.
+	/// Returns `error` for the caller to pass on.
+	async fn abandon(
+		&self,
+		order_id: &str,
+		tx_type: TransactionType,
+		error: OrderError,
+	) -> OrderError {
+		if tx_type == TransactionType::Fill {
+			self.accounts.filler_pool.release(order_id);
+		}
+		self.exposure.release(order_id);
+
+		if let Err(e) = self
+			.state_machine
+			.transition_order_status(order_id, OrderStatus::Failed(tx_type))
+			.await
+		{
+			tracing::warn!(
+				order_id = %truncate_id(order_id),
+				error = %e,
+				"Failed to mark order as failed"
+			);
 		}
+		error
+	}
+
+	/// Sums the outputs the solver must provide on a chain, per token.
+	///
+	/// The zero address stands for the chain's native asset and maps to `None`.
+	fn fill_amounts(order: &Order, chain_id: u64) -> Vec<(Option<Address>, U256)> {
+		let Ok(parsed) = order.parse_order_data() else {
+			return Vec::new();
+		};
+
+		let mut amounts: Vec<(Option<Address>, U256)> = Vec::new();
+		for output in parsed.parse_requested_outputs() {
+			let (Ok(output_chain), Ok(token)) = (
+				output.asset.ethereum_chain_id(),
+				output.asset.ethereum_address(),
+			) else {
+				continue;
+			};
+			if output_chain != chain_id {
+				continue;
+			}
+			let token = (!token.is_zero()).then(|| Address(token.to_vec()));
+			match amounts.iter_mut().find(|(t, _)| *t == token) {
+				Some((_, total)) => *total = total.saturating_add(output.amount),
+				None => amounts.push((token, output.amount)),
+			}
+		}
+		amounts
 	}
 
 	/// Handles order preparation for off-chain orders.
 	#[instrument(skip_all, fields(order_id = %truncate_id(&order.id)))]
 	pub async fn handle_preparation(
 		&self,
-		source: String,
+		intent: Intent,
 		order: Order,
 		params: ExecutionParams,
 	) -> Result<(), OrderError> {
 		// Generate prepare transaction
-		if let Some(prepare_tx) = self
+		let prepare_tx = match self
 			.order_service
-			.generate_prepare_transaction(&source, &order, &params)
+			.generate_prepare_transaction(&intent.source, &order, &params)
 			.await
-			.map_err(|e| OrderError::Service(e.to_string()))?
 		{
-			// Submit prepare transaction
-			let prepare_tx_hash = self
-				.delivery
-				.deliver(prepare_tx.clone())
+			Ok(prepare_tx) => prepare_tx,
+			Err(e) => {
+				let error = OrderError::Service(e.to_string());
+				return Err(self
+					.abandon(&order.id, TransactionType::Prepare, error)
+					.await);
+			},
+		};
+		if let Some(mut prepare_tx) = prepare_tx {
+			prepare_tx.from = Some(self.accounts.roles.opener.clone());
+			self.chain_state
+				.apply_fees(&mut prepare_tx, TransactionType::Prepare)
+				.await;
+
+			// Simulate before sending; a revert skips, retries or fails the order
+			let retry = SolverEvent::Order(OrderEvent::Preparing {
+				intent,
+				order: order.clone(),
+				params: params.clone(),
+			});
+			if !self
+				.preflight
+				.check(&order.id, &prepare_tx, TransactionType::Prepare, retry)
 				.await
-				.map_err(|e| OrderError::Service(e.to_string()))?;
+			{
+				return Ok(());
+			}
+
+			// Submit prepare transaction; once sent, its outcome is handled by
+			// the transaction monitor
+			let prepare_tx_hash = match self.delivery.deliver(prepare_tx.clone()).await {
+				Ok(tx_hash) => tx_hash,
+				Err(e) => {
+					let error = OrderError::Service(e.to_string());
+					return Err(self
+						.abandon(&order.id, TransactionType::Prepare, error)
+						.await);
+				},
+			};
 
 			self.event_bus
 				.publish(SolverEvent::Delivery(DeliveryEvent::TransactionPending {
@@ -114,13 +224,19 @@ impl OrderHandler {
 				.map_err(|e| OrderError::State(e.to_string()))?;
 		} else {
 			// No preparation needed (on-chain intent), go directly to Executing
-			self.state_machine
+			if let Err(e) = self
+				.state_machine
 				.update_order_with(&order.id, |o| {
 					o.execution_params = Some(params.clone());
 					o.status = OrderStatus::Executing;
 				})
 				.await
-				.map_err(|e| OrderError::State(e.to_string()))?;
+			{
+				let error = OrderError::State(e.to_string());
+				return Err(self
+					.abandon(&order.id, TransactionType::Prepare, error)
+					.await);
+			}
 
 			self.event_bus
 				.publish(SolverEvent::Order(OrderEvent::Executing {
@@ -156,6 +272,7 @@ impl OrderHandler {
 					.first()
 					.map(|c| c.chain_id)
 					.unwrap_or(14174),
+				from: None,
 				to: order
 					.input_chains
 					.first()
@@ -177,11 +294,13 @@ impl OrderHandler {
 			};
 
 			// Submit via bundle delivery (which will create SignedFill and submit bundle)
-			let tx_hash = self
-				.delivery
-				.deliver(tx.clone())
-				.await
-				.map_err(|e| OrderError::Service(e.to_string()))?;
+			let tx_hash = match self.delivery.deliver(tx.clone()).await {
+				Ok(tx_hash) => tx_hash,
+				Err(e) => {
+					let error = OrderError::Service(e.to_string());
+					return Err(self.abandon(&order.id, TransactionType::Fill, error).await);
+				},
+			};
 
 			self.event_bus
 				.publish(SolverEvent::Delivery(DeliveryEvent::TransactionPending {
@@ -234,11 +353,17 @@ impl OrderHandler {
 
 		// For non-Signet orders, use traditional fill transaction generation
 		// Generate fill transaction
-		let mut tx = self
+		let mut tx = match self
 			.order_service
 			.generate_fill_transaction(&order, &params)
 			.await
-			.map_err(|e| OrderError::Service(e.to_string()))?;
+		{
+			Ok(tx) => tx,
+			Err(e) => {
+				let error = OrderError::Service(e.to_string());
+				return Err(self.abandon(&order.id, TransactionType::Fill, error).await);
+			},
+		};
 
 		// For EIP-7683 orders, attach order data as metadata
 		// This allows delivery to reconstruct the order for settlement
@@ -247,12 +372,78 @@ impl OrderHandler {
 			tx.metadata = Some(order.data.clone());
 		}
 
-		// Submit transaction
-		let tx_hash = self
-			.delivery
-			.deliver(tx.clone())
+		// Pick the filler account and record it before submitting, so the claim
+		// pays out to the account whose inventory was spent
+		let fill_amounts = Self::fill_amounts(&order, tx.chain_id);
+		let filler = match self
+			.accounts
+			.filler_pool
+			.acquire(&order.id, tx.chain_id, fill_amounts.clone())
 			.await
-			.map_err(|e| OrderError::Service(e.to_string()))?;
+		{
+			Ok(filler) => filler,
+			Err(e) => {
+				let error = OrderError::Service(e.to_string());
+				return Err(self.abandon(&order.id, TransactionType::Fill, error).await);
+			},
+		};
+		tx.from = Some(filler.clone());
+
+		// Make sure the filler's allowances cover the outputs it is about to send.
+		// Other unconfirmed fills from the same account spend the same allowance,
+		// so the check covers everything the account has reserved for the token.
+		for (token, _) in &fill_amounts {
+			let Some(token_address) = token else {
+				continue;
+			};
+			let reserved = self
+				.accounts
+				.filler_pool
+				.reserved(tx.chain_id, &filler, token);
+			if let Err(e) = self
+				.token_manager
+				.ensure_fill_allowance(tx.chain_id, token_address, &filler, reserved)
+				.await
+			{
+				let error = OrderError::Service(e.to_string());
+				return Err(self.abandon(&order.id, TransactionType::Fill, error).await);
+			}
+		}
+		if let Err(e) = self
+			.state_machine
+			.update_order_with(&order.id, |o| {
+				o.filler_address = Some(filler.clone());
+			})
+			.await
+		{
+			let error = OrderError::State(e.to_string());
+			return Err(self.abandon(&order.id, TransactionType::Fill, error).await);
+		}
+
+		// Price the fill at the configured urgency and simulate it before
+		// submitting; a revert releases the filler and skips, retries or fails
+		// the order
+		self.chain_state
+			.apply_fees(&mut tx, TransactionType::Fill)
+			.await;
+		let retry = SolverEvent::Order(OrderEvent::Executing {
+			order: order.clone(),
+			params: params.clone(),
+		});
+		if !self
+			.preflight
+			.check(&order.id, &tx, TransactionType::Fill, retry)
+			.await
+		{
+			return Ok(());
+		}
+		let tx_hash = match self.delivery.deliver(tx.clone()).await {
+			Ok(tx_hash) => tx_hash,
+			Err(e) => {
+				let error = OrderError::Service(e.to_string());
+				return Err(self.abandon(&order.id, TransactionType::Fill, error).await);
+			},
+		};
 
 		self.event_bus
 			.publish(SolverEvent::Delivery(DeliveryEvent::TransactionPending {
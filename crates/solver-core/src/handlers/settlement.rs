@@ -4,7 +4,10 @@
 //! Handles the complete settlement lifecycle including optional oracle interactions
 //! and proof generation through the settlement service.
 
-use crate::engine::event_bus::EventBus;
+use crate::engine::{
+	accounts::SolverAccounts, chain_state::ChainStateService, event_bus::EventBus,
+	preflight::PreflightService,
+};
 use crate::monitoring::SettlementMonitor;
 use crate::state::OrderStateMachine;
 use alloy_primitives::hex;
@@ -48,10 +51,14 @@ pub struct SettlementHandler {
 	storage: Arc<StorageService>,
 	state_machine: Arc<OrderStateMachine>,
 	event_bus: EventBus,
+	accounts: Arc<SolverAccounts>,
+	chain_state: Arc<ChainStateService>,
+	preflight: Arc<PreflightService>,
 	monitoring_timeout_minutes: u64,
 }
 
 impl SettlementHandler {
+	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		settlement: Arc<SettlementService>,
 		order_service: Arc<OrderService>,
@@ -59,6 +66,9 @@ impl SettlementHandler {
 		storage: Arc<StorageService>,
 		state_machine: Arc<OrderStateMachine>,
 		event_bus: EventBus,
+		accounts: Arc<SolverAccounts>,
+		chain_state: Arc<ChainStateService>,
+		preflight: Arc<PreflightService>,
 		monitoring_timeout_minutes: u64,
 	) -> Self {
 		Self {
@@ -68,6 +78,9 @@ impl SettlementHandler {
 			storage,
 			state_machine,
 			event_bus,
+			accounts,
+			chain_state,
+			preflight,
 			monitoring_timeout_minutes,
 		}
 	}
@@ -76,8 +89,10 @@ impl SettlementHandler {
 	pub fn spawn_settlement_monitor(&self, order: Order, fill_tx_hash: TransactionHash) {
 		let monitor = SettlementMonitor::new(
 			self.settlement.clone(),
+			self.delivery.clone(),
 			self.state_machine.clone(),
 			self.event_bus.clone(),
+			self.chain_state.clone(),
 			self.monitoring_timeout_minutes,
 		);
 
@@ -123,7 +138,12 @@ impl SettlementHandler {
 			.map_err(|e| SettlementError::Service(e.to_string()))?;
 
 		match post_fill_tx {
-			Some(post_fill_tx) => {
+			Some(mut post_fill_tx) => {
+				post_fill_tx.from = Some(self.accounts.roles.claimer.clone());
+				self.chain_state
+					.apply_fees(&mut post_fill_tx, TransactionType::PostFill)
+					.await;
+
 				let tx_hash = self
 					.delivery
 					.deliver(post_fill_tx.clone())
@@ -212,7 +232,12 @@ impl SettlementHandler {
 			.map_err(|e| SettlementError::Service(e.to_string()))?;
 
 		match pre_claim_tx {
-			Some(pre_claim_tx) => {
+			Some(mut pre_claim_tx) => {
+				pre_claim_tx.from = Some(self.accounts.roles.claimer.clone());
+				self.chain_state
+					.apply_fees(&mut pre_claim_tx, TransactionType::PreClaim)
+					.await;
+
 				let tx_hash = self
 					.delivery
 					.deliver(pre_claim_tx.clone())
@@ -283,12 +308,28 @@ impl SettlementHandler {
 				.clone()
 				.ok_or_else(|| SettlementError::Service("Order missing fill proof".to_string()))?;
 
-			// Generate claim transaction
-			let claim_tx = self
+			// Generate claim transaction, sent by the solver of record
+			let mut claim_tx = self
 				.order_service
 				.generate_claim_transaction(&order, &fill_proof)
 				.await
 				.map_err(|e| SettlementError::Service(e.to_string()))?;
+			claim_tx.from = Some(self.accounts.roles.claimer.clone());
+			self.chain_state
+				.apply_fees(&mut claim_tx, TransactionType::Claim)
+				.await;
+
+			// Simulate before sending; a reverting claim is retried later or failed
+			let retry = SolverEvent::Settlement(SettlementEvent::ClaimReady {
+				order_id: order.id.clone(),
+			});
+			if !self
+				.preflight
+				.check(&order.id, &claim_tx, TransactionType::Claim, retry)
+				.await
+			{
+				continue;
+			}
 
 			// Submit claim transaction through delivery service
 			let claim_tx_hash = self
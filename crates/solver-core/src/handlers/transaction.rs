@@ -4,7 +4,12 @@
 //! transaction type (prepare, fill, post-fill, pre-claim, claim). Spawns monitoring
 //! tasks for pending transactions and emits events for settlement processing.
 
-use crate::engine::event_bus::EventBus;
+use crate::engine::{
+	accounts::SolverAccounts,
+	event_bus::EventBus,
+	exposure::ExposureTracker,
+	gas_model::{GasModel, GasUsageKey},
+};
 use crate::monitoring::TransactionMonitor;
 use crate::state::OrderStateMachine;
 use alloy_primitives::hex;
@@ -46,16 +51,23 @@ pub struct TransactionHandler {
 	state_machine: Arc<OrderStateMachine>,
 	settlement: Arc<SettlementService>,
 	event_bus: EventBus,
+	accounts: Arc<SolverAccounts>,
+	exposure: Arc<ExposureTracker>,
+	gas_model: Arc<GasModel>,
 	monitoring_timeout_minutes: u64,
 }
 
 impl TransactionHandler {
+	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		delivery: Arc<DeliveryService>,
 		storage: Arc<StorageService>,
 		state_machine: Arc<OrderStateMachine>,
 		settlement: Arc<SettlementService>,
 		event_bus: EventBus,
+		accounts: Arc<SolverAccounts>,
+		exposure: Arc<ExposureTracker>,
+		gas_model: Arc<GasModel>,
 		monitoring_timeout_minutes: u64,
 	) -> Self {
 		Self {
@@ -64,6 +76,9 @@ impl TransactionHandler {
 			state_machine,
 			settlement,
 			event_bus,
+			accounts,
+			exposure,
+			gas_model,
 			monitoring_timeout_minutes,
 		}
 	}
@@ -97,6 +112,7 @@ impl TransactionHandler {
 	/// - PostFill: Updates status to PostFilled and emits SettlementEvent::StartMonitoring
 	/// - PreClaim: Updates status to PreClaimed and emits SettlementEvent::ClaimReady
 	/// - Claim: Updates status to Finalized and emits SettlementEvent::Completed
+	/// - Relay: Nothing; the settlement monitor sees the delivered message
 	#[instrument(skip_all, fields(order_id = %truncate_id(&order_id), tx_type = ?tx_type))]
 	pub async fn handle_confirmed(
 		&self,
@@ -118,6 +134,8 @@ impl TransactionHandler {
 			return Ok(());
 		}
 
+		self.record_gas_usage(&order_id, tx_type, &receipt).await;
+
 		// For PostFill and PreClaim, call settlement callback BEFORE other processing
 		// This allows Hyperlane to extract and store message IDs from receipts
 		if matches!(
@@ -148,6 +166,8 @@ impl TransactionHandler {
 				self.handle_prepare_confirmed(tx_hash).await?;
 			},
 			TransactionType::Fill => {
+				// The fill has spent its inventory, free the filler's reservation
+				self.accounts.filler_pool.release(&order_id);
 				self.handle_fill_confirmed(tx_hash).await?;
 			},
 			TransactionType::PostFill => {
@@ -159,11 +179,64 @@ impl TransactionHandler {
 			TransactionType::Claim => {
 				self.handle_claim_confirmed(tx_hash).await?;
 			},
+			TransactionType::Relay => {
+				tracing::info!("Settlement relay transaction confirmed");
+			},
 		}
 
 		Ok(())
 	}
 
+	/// Records the gas used by a confirmed transaction in the learned gas model.
+	///
+	/// Transactions whose receipt does not report gas used, or whose order has
+	/// no known lock flow, are not recorded. This never fails the confirmation.
+	async fn record_gas_usage(
+		&self,
+		order_id: &str,
+		tx_type: TransactionType,
+		receipt: &TransactionReceipt,
+	) {
+		let Some(gas_used) = receipt.gas_used else {
+			return;
+		};
+		let order: Order = match self
+			.storage
+			.retrieve(StorageKey::Orders.as_str(), order_id)
+			.await
+		{
+			Ok(order) => order,
+			Err(e) => {
+				tracing::debug!(error = %e, "Skipping gas usage of unknown order");
+				return;
+			},
+		};
+		let Ok(parsed) = order.parse_order_data() else {
+			return;
+		};
+		let Some(flow) = parsed.parse_lock_type() else {
+			return;
+		};
+		// Fills run on the destination chain, every other step on the origin
+		let chain_id = match tx_type {
+			TransactionType::Fill | TransactionType::PostFill => {
+				parsed.destination_chain_ids().first().copied()
+			},
+			_ => Some(parsed.origin_chain_id()),
+		};
+		let Some(chain_id) = chain_id else {
+			return;
+		};
+
+		let key = GasUsageKey {
+			flow,
+			chain_id,
+			tx_type,
+			outputs: parsed.parse_requested_outputs().len(),
+		};
+		self.gas_model.record(key, gas_used).await;
+	}
+
 	/// Handles failed transactions.
 	#[instrument(skip_all, fields(order_id = %truncate_id(&order_id), tx_hash = %truncate_id(&hex::encode(&tx_hash.0)), tx_type = ?tx_type))]
 	pub async fn handle_failed(
@@ -175,6 +248,29 @@ impl TransactionHandler {
 	) -> Result<(), TransactionError> {
 		tracing::error!("Transaction failed: {}", error);
 
+		// A failed relay leaves the order waiting on the public relayer; allow
+		// the settlement monitor to relay again instead of failing the order
+		if tx_type == TransactionType::Relay {
+			let order: Order = self
+				.storage
+				.retrieve(StorageKey::Orders.as_str(), &order_id)
+				.await
+				.map_err(|e| TransactionError::Storage(e.to_string()))?;
+			self.settlement
+				.record_relay_failed(&order)
+				.await
+				.map_err(|e| TransactionError::Service(e.to_string()))?;
+			return Ok(());
+		}
+
+		if tx_type == TransactionType::Fill {
+			self.accounts.filler_pool.release(&order_id);
+		}
+		// Nothing was paid out unless the fill went through
+		if matches!(tx_type, TransactionType::Prepare | TransactionType::Fill) {
+			self.exposure.release(&order_id);
+		}
+
 		// Update order status with specific failure type
 		self.state_machine
 			.transition_order_status(&order_id, OrderStatus::Failed(tx_type))
@@ -362,6 +458,9 @@ impl TransactionHandler {
 			.await
 			.map_err(|e| TransactionError::State(e.to_string()))?;
 
+		// The inputs are claimed, so the fronted capital is back
+		self.exposure.release(&order_id);
+
 		// Publish completed event
 		self.event_bus
 			.publish(SolverEvent::Settlement(
@@ -4,11 +4,13 @@
 //! to determine when they are ready for claiming, retrieving attestations and
 //! checking claim conditions before initiating the claim process.
 
-use crate::engine::event_bus::EventBus;
+use crate::engine::{chain_state::ChainStateService, event_bus::EventBus};
 use crate::state::OrderStateMachine;
+use solver_delivery::DeliveryService;
 use solver_settlement::SettlementService;
 use solver_types::{
-	truncate_id, Order, OrderStatus, SettlementEvent, SolverEvent, TransactionHash,
+	truncate_id, DeliveryEvent, Order, OrderStatus, SettlementEvent, SolverEvent, TransactionHash,
+	TransactionType,
 };
 use std::sync::Arc;
 
@@ -19,22 +21,28 @@ use std::sync::Arc;
 /// until the order is claimable or a timeout is reached.
 pub struct SettlementMonitor {
 	settlement: Arc<SettlementService>,
+	delivery: Arc<DeliveryService>,
 	state_machine: Arc<OrderStateMachine>,
 	event_bus: EventBus,
+	chain_state: Arc<ChainStateService>,
 	timeout_minutes: u64,
 }
 
 impl SettlementMonitor {
 	pub fn new(
 		settlement: Arc<SettlementService>,
+		delivery: Arc<DeliveryService>,
 		state_machine: Arc<OrderStateMachine>,
 		event_bus: EventBus,
+		chain_state: Arc<ChainStateService>,
 		timeout_minutes: u64,
 	) -> Self {
 		Self {
 			settlement,
+			delivery,
 			state_machine,
 			event_bus,
+			chain_state,
 			timeout_minutes,
 		}
 	}
@@ -92,11 +100,18 @@ impl SettlementMonitor {
 					"Claim readiness monitoring timeout reached after {} minutes",
 					self.timeout_minutes
 				);
+				settlement
+					.record_attestation_outcome(&order, &fill_proof, None)
+					.await;
 				break;
 			}
 
 			// Check if we can claim
 			if settlement.can_claim(&order, &fill_proof).await {
+				settlement
+					.record_attestation_outcome(&order, &fill_proof, Some(start_time.elapsed()))
+					.await;
+
 				// Update status to Settled
 				self.state_machine
 					.transition_order_status(&order.id, OrderStatus::Settled)
@@ -112,8 +127,70 @@ impl SettlementMonitor {
 				break;
 			}
 
+			// Relay the settlement message ourselves if it has stalled
+			self.relay_if_stalled(&order).await;
+
 			// Wait before next check
 			tokio::time::sleep(check_interval).await;
 		}
 	}
+
+	/// Submits a relay transaction when the settlement reports a stalled message.
+	///
+	/// The relay is recorded as attempted only once it has been sent, and its
+	/// confirmation is watched like any other transaction. A send failure, a
+	/// revert or a dropped relay lets a later poll relay again.
+	async fn relay_if_stalled(&self, order: &Order) {
+		let mut relay_tx = match self.settlement.generate_relay_transaction(order).await {
+			Ok(Some(tx)) => tx,
+			Ok(None) => return,
+			Err(e) => {
+				tracing::warn!(
+					order_id = %truncate_id(&order.id),
+					error = %e,
+					"Failed to generate settlement relay transaction"
+				);
+				return;
+			},
+		};
+
+		self.chain_state
+			.apply_fees(&mut relay_tx, TransactionType::Relay)
+			.await;
+
+		let tx_hash = match self.delivery.deliver(relay_tx.clone()).await {
+			Ok(tx_hash) => tx_hash,
+			Err(e) => {
+				tracing::warn!(
+					order_id = %truncate_id(&order.id),
+					error = %e,
+					"Failed to submit settlement relay transaction"
+				);
+				return;
+			},
+		};
+		tracing::info!(
+			order_id = %truncate_id(&order.id),
+			tx_hash = %truncate_id(&alloy_primitives::hex::encode(&tx_hash.0)),
+			chain_id = relay_tx.chain_id,
+			"Submitted settlement relay transaction"
+		);
+
+		if let Err(e) = self.settlement.record_relay_submitted(order).await {
+			tracing::warn!(
+				order_id = %truncate_id(&order.id),
+				error = %e,
+				"Failed to record settlement relay attempt"
+			);
+		}
+
+		self.event_bus
+			.publish(SolverEvent::Delivery(DeliveryEvent::TransactionPending {
+				order_id: order.id.clone(),
+				tx_hash,
+				tx_type: TransactionType::Relay,
+				tx_chain_id: relay_tx.chain_id,
+			}))
+			.ok();
+	}
 }
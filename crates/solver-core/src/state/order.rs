@@ -218,6 +218,8 @@ impl OrderStateMachine {
 			TransactionType::PostFill => order.post_fill_tx_hash = Some(tx_hash),
 			TransactionType::PreClaim => order.pre_claim_tx_hash = Some(tx_hash),
 			TransactionType::Claim => order.claim_tx_hash = Some(tx_hash),
+			// Relays deliver a settlement message and are not tracked on the order
+			TransactionType::Relay => {},
 		})
 		.await
 	}
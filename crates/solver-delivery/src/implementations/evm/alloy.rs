@@ -3,21 +3,22 @@
 //! This module provides concrete implementations of the DeliveryInterface trait,
 //! supporting blockchain transaction submission and monitoring using the Alloy library.
 
-use crate::{DeliveryError, DeliveryInterface};
+use crate::{revert::decode_revert, DeliveryError, DeliveryInterface};
 use alloy_network::EthereumWallet;
 use alloy_primitives::{Address, Bytes, FixedBytes, U256};
 use alloy_provider::{
 	fillers::{CachedNonceManager, ChainIdFiller, GasFiller, NonceFiller},
 	Provider, ProviderBuilder,
 };
-use alloy_rpc_types::TransactionRequest;
+use alloy_rpc_types::{BlockId, BlockNumberOrTag, TransactionRequest};
 use alloy_signer::Signer;
-use alloy_signer_local::PrivateKeySigner;
+use alloy_transport::TransportError;
 use alloy_transport_http::Http;
 use async_trait::async_trait;
+use solver_account::AccountSigner;
 use solver_types::{
-	with_0x_prefix, ConfigSchema, Field, FieldType, NetworksConfig, Schema,
-	Transaction as SolverTransaction, TransactionHash, TransactionReceipt,
+	with_0x_prefix, ConfigSchema, FeeHistory, Field, FieldType, NetworksConfig, RevertReason,
+	Schema, Transaction as SolverTransaction, TransactionHash, TransactionReceipt,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -37,12 +38,14 @@ impl AlloyDelivery {
 	///
 	/// Configures Alloy providers for multiple networks with the specified
 	/// RPC URLs and signers for transaction submission. The default_signer is used
-	/// for networks that don't have a specific signer configured.
+	/// for networks that don't have a specific signer configured. Additional signers
+	/// are registered with every wallet and sign transactions whose `from` matches them.
 	pub async fn new(
 		network_ids: Vec<u64>,
 		networks: &NetworksConfig,
-		signers: HashMap<u64, PrivateKeySigner>,
-		default_signer: PrivateKeySigner,
+		signers: HashMap<u64, AccountSigner>,
+		default_signer: AccountSigner,
+		additional_signers: Vec<AccountSigner>,
 	) -> Result<Self, DeliveryError> {
 		// Validate at least one network
 		if network_ids.is_empty() {
@@ -77,7 +80,10 @@ impl AlloyDelivery {
 
 			// Create signer with chain ID
 			let chain_signer = signer.clone().with_chain_id(Some(*network_id));
-			let wallet = EthereumWallet::from(chain_signer);
+			let mut wallet = EthereumWallet::from(chain_signer);
+			for additional in &additional_signers {
+				wallet.register_signer(additional.clone().with_chain_id(Some(*network_id)));
+			}
 
 			// Create provider with explicit cached nonce management to avoid race conditions
 			let provider = ProviderBuilder::new()
@@ -330,6 +336,7 @@ impl DeliveryInterface for AlloyDelivery {
 					success: receipt.status(),
 					logs,
 					block_timestamp,
+					gas_used: Some(receipt.gas_used),
 				});
 			}
 
@@ -384,6 +391,7 @@ impl DeliveryInterface for AlloyDelivery {
 					success: receipt.status(),
 					logs,
 					block_timestamp,
+					gas_used: Some(receipt.gas_used),
 				})
 			},
 			Ok(None) => Err(DeliveryError::Network(format!(
@@ -541,6 +549,27 @@ impl DeliveryInterface for AlloyDelivery {
 			.await
 			.map_err(|e| DeliveryError::Network(format!("Failed to get block number: {}", e)))
 	}
+
+	async fn get_fee_history(
+		&self,
+		chain_id: u64,
+		block_count: u64,
+		reward_percentiles: &[f64],
+	) -> Result<FeeHistory, DeliveryError> {
+		let provider = self.get_provider(chain_id)?;
+
+		let history = provider
+			.get_fee_history(block_count, BlockNumberOrTag::Latest, reward_percentiles)
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to get fee history: {}", e)))?;
+
+		Ok(FeeHistory {
+			oldest_block: history.oldest_block,
+			base_fee_per_gas: history.base_fee_per_gas,
+			gas_used_ratio: history.gas_used_ratio,
+			reward: history.reward.unwrap_or_default(),
+		})
+	}
 	async fn estimate_gas(&self, tx: SolverTransaction) -> Result<u64, DeliveryError> {
 		// Get the chain ID from the transaction
 		let chain_id = tx.chain_id;
@@ -578,6 +607,34 @@ impl DeliveryInterface for AlloyDelivery {
 
 		Ok(result)
 	}
+
+	async fn simulate(&self, tx: SolverTransaction) -> Result<u64, DeliveryError> {
+		let provider = self.get_provider(tx.chain_id)?;
+		let request: TransactionRequest = tx.into();
+
+		// Estimating gas executes the transaction and surfaces its revert data
+		provider
+			.estimate_gas(request)
+			.block(BlockId::pending())
+			.await
+			.map_err(|e| match revert_reason(&e) {
+				Some(reason) => DeliveryError::Reverted(reason),
+				None => DeliveryError::Network(format!("Failed to simulate transaction: {}", e)),
+			})
+	}
+}
+
+/// Returns the decoded reason of an RPC error that reports a revert.
+fn revert_reason(error: &TransportError) -> Option<RevertReason> {
+	let payload = error.as_error_resp()?;
+	match payload.as_revert_data() {
+		Some(data) => Some(decode_revert(&data)),
+		// Some nodes report reverts without the data
+		None => payload
+			.message
+			.contains("revert")
+			.then(|| RevertReason::Message(payload.message.to_string())),
+	}
 }
 
 /// Factory function to create an HTTP-based delivery provider from configuration.
@@ -590,16 +647,18 @@ impl DeliveryInterface for AlloyDelivery {
 ///   - `network_ids` (required): Array of network IDs to support
 ///   - `accounts` (optional): Map of network IDs to account names for per-network signing
 /// - `networks`: Network configuration containing RPC URLs and contract addresses
-/// - `default_private_key`: Default private key for signing transactions
-/// - `network_private_keys`: Map of network IDs to private keys for per-network signing
+/// - `default_signer`: Default signer for transactions
+/// - `network_signers`: Map of network IDs to signers for per-network signing
+/// - `additional_signers`: Signers for role and pool accounts, selected by `Transaction::from`
 ///
 /// # Returns
 /// A boxed implementation of DeliveryInterface configured for the specified networks
 pub fn create_http_delivery(
 	config: &toml::Value,
 	networks: &NetworksConfig,
-	default_private_key: &solver_types::SecretString,
-	network_private_keys: &HashMap<u64, solver_types::SecretString>,
+	default_signer: &AccountSigner,
+	network_signers: &HashMap<u64, AccountSigner>,
+	additional_signers: &[AccountSigner],
 ) -> Result<Box<dyn DeliveryInterface>, DeliveryError> {
 	// Validate configuration first
 	AlloyDeliverySchema::validate_config(config)
@@ -622,30 +681,17 @@ pub fn create_http_delivery(
 		));
 	}
 
-	// Parse the default signer
-	let default_signer: PrivateKeySigner = default_private_key.with_exposed(|key| {
-		key.parse()
-			.map_err(|_| DeliveryError::Network("Invalid default private key format".to_string()))
-	})?;
-
-	// Parse network-specific signers
-	let mut network_signers = HashMap::new();
-	for (network_id, private_key) in network_private_keys {
-		let signer: PrivateKeySigner = private_key.with_exposed(|key| {
-			key.parse().map_err(|_| {
-				DeliveryError::Network(format!(
-					"Invalid private key format for network {}",
-					network_id
-				))
-			})
-		})?;
-		network_signers.insert(*network_id, signer);
-	}
-
 	// Create delivery service synchronously, but the actual connection happens async
 	let delivery = tokio::task::block_in_place(|| {
 		tokio::runtime::Handle::current().block_on(async {
-			AlloyDelivery::new(network_ids, networks, network_signers, default_signer).await
+			AlloyDelivery::new(
+				network_ids,
+				networks,
+				network_signers.clone(),
+				default_signer.clone(),
+				additional_signers.to_vec(),
+			)
+			.await
 		})
 	})?;
 
@@ -662,6 +708,10 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_http_delivery
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(AlloyDeliverySchema)
+	}
 }
 
 impl crate::DeliveryRegistry for Registry {}
@@ -669,10 +719,8 @@ impl crate::DeliveryRegistry for Registry {}
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use solver_types::{
-		utils::tests::builders::{NetworkConfigBuilder, NetworksConfigBuilder},
-		SecretString,
-	};
+	use solver_account::{implementations::local::LocalWallet, AccountInterface};
+	use solver_types::utils::tests::builders::{NetworkConfigBuilder, NetworksConfigBuilder};
 	use std::collections::HashMap;
 
 	fn create_test_networks() -> NetworksConfig {
@@ -682,10 +730,10 @@ mod tests {
 			.build()
 	}
 
-	fn create_test_signer() -> PrivateKeySigner {
-		"0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
-			.parse()
+	fn create_test_signer() -> AccountSigner {
+		LocalWallet::new("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
 			.unwrap()
+			.signer()
 	}
 
 	#[tokio::test]
@@ -693,7 +741,7 @@ mod tests {
 		let networks = create_test_networks();
 		let signer = create_test_signer();
 
-		let result = AlloyDelivery::new(vec![1], &networks, HashMap::new(), signer).await;
+		let result = AlloyDelivery::new(vec![1], &networks, HashMap::new(), signer, vec![]).await;
 
 		assert!(result.is_ok());
 		let delivery = result.unwrap();
@@ -705,7 +753,7 @@ mod tests {
 		let networks = NetworksConfigBuilder::new().build();
 		let signer = create_test_signer();
 
-		let result = AlloyDelivery::new(vec![], &networks, HashMap::new(), signer).await;
+		let result = AlloyDelivery::new(vec![], &networks, HashMap::new(), signer, vec![]).await;
 
 		assert!(matches!(result, Err(DeliveryError::Network(_))));
 		if let Err(DeliveryError::Network(msg)) = result {
@@ -758,12 +806,11 @@ mod tests {
 		});
 
 		let networks = create_test_networks();
-		let default_key = SecretString::from(
-			"0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
-		);
-		let network_keys = HashMap::new();
+		let default_signer = create_test_signer();
+		let network_signers = HashMap::new();
 
-		let result = create_http_delivery(&config, &networks, &default_key, &network_keys);
+		let result =
+			create_http_delivery(&config, &networks, &default_signer, &network_signers, &[]);
 		assert!(result.is_ok());
 	}
 
@@ -17,14 +17,14 @@ use alloy_network::EthereumWallet;
 use alloy_primitives::Bytes;
 use alloy_provider::{Provider, ProviderBuilder};
 use alloy_rpc_types::mev::EthSendBundle;
-use alloy_signer_local::PrivateKeySigner;
 use async_trait::async_trait;
 use signet_bundle::SignetEthBundle;
 use signet_tx_cache::client::TxCache;
 use signet_types::SignedFill;
+use solver_account::AccountSigner;
 use solver_types::{
-	ConfigSchema, Field, FieldType, NetworksConfig, Schema, Transaction as SolverTransaction,
-	TransactionHash, TransactionReceipt,
+	ConfigSchema, FeeHistory, Field, FieldType, NetworksConfig, Schema,
+	Transaction as SolverTransaction, TransactionHash, TransactionReceipt,
 };
 use std::sync::Arc;
 use tokio::time::{sleep, Duration};
@@ -69,7 +69,7 @@ pub struct SignetBundleDelivery {
 	/// Signet cache client
 	cache_client: Arc<TxCache>,
 	/// Solver's signer for creating SignedFills
-	signer: PrivateKeySigner,
+	signer: AccountSigner,
 }
 
 impl SignetBundleDelivery {
@@ -93,7 +93,7 @@ impl SignetBundleDelivery {
 	pub fn new(
 		config: SignetBundleConfig,
 		networks: NetworksConfig,
-		signer: PrivateKeySigner,
+		signer: AccountSigner,
 	) -> Result<Self, DeliveryError> {
 		// Validate chain name
 		if config.chain_name.is_empty() {
@@ -571,11 +571,30 @@ impl DeliveryInterface for SignetBundleDelivery {
 		Ok(self.config.target_block.unwrap_or(DEFAULT_BLOCK_NUMBER))
 	}
 
+	async fn get_fee_history(
+		&self,
+		_chain_id: u64,
+		_block_count: u64,
+		_reward_percentiles: &[f64],
+	) -> Result<FeeHistory, DeliveryError> {
+		// Signet bundles don't use traditional gas pricing
+		Err(DeliveryError::Network(
+			"Fee history not available for Signet".to_string(),
+		))
+	}
+
 	async fn estimate_gas(&self, _tx: SolverTransaction) -> Result<u64, DeliveryError> {
 		// Signet bundles don't use traditional gas estimation
 		Ok(0)
 	}
 
+	async fn simulate(&self, _tx: SolverTransaction) -> Result<u64, DeliveryError> {
+		// Bundles are simulated by the builder before inclusion, not here
+		Err(DeliveryError::Unsupported(
+			"Simulation is not supported for Signet bundles".to_string(),
+		))
+	}
+
 	async fn eth_call(&self, _tx: SolverTransaction) -> Result<Bytes, DeliveryError> {
 		// TODO: Implement contract calls if needed
 		Err(DeliveryError::Network(
@@ -588,8 +607,9 @@ impl DeliveryInterface for SignetBundleDelivery {
 pub fn create_delivery(
 	config: &toml::Value,
 	networks: &NetworksConfig,
-	default_private_key: &solver_types::SecretString,
-	network_private_keys: &std::collections::HashMap<u64, solver_types::SecretString>,
+	default_signer: &AccountSigner,
+	network_signers: &std::collections::HashMap<u64, AccountSigner>,
+	_additional_signers: &[AccountSigner],
 ) -> Result<Box<dyn DeliveryInterface>, DeliveryError> {
 	// Validate configuration first
 	SignetBundleDeliverySchema::validate_config(config).map_err(|e| {
@@ -650,16 +670,21 @@ pub fn create_delivery(
 		.parse::<alloy_primitives::Address>()
 		.map_err(|e| DeliveryError::Network(format!("Invalid filler_recipient: {}", e)))?;
 
-	// Create signer from private key
-	// Use host chain specific key if available, otherwise use default
-	let private_key = network_private_keys
+	// Use host chain specific signer if available, otherwise use default
+	let signer = network_signers
 		.get(&host_chain_id)
-		.unwrap_or(default_private_key);
-
-	let signer = private_key
-		.expose_secret()
-		.parse::<PrivateKeySigner>()
-		.map_err(|e| DeliveryError::Network(format!("Invalid private key: {}", e)))?;
+		.unwrap_or(default_signer)
+		.clone();
+
+	// Fills are signed as Permit2 digests, which remote signing services refuse
+	if !signer.signs_digests() {
+		return Err(DeliveryError::Unsupported(format!(
+			"Signet bundle delivery signs fills as raw digests, which account {} cannot sign; \
+			 use a local or keystore account for host chain {}",
+			signer.address(),
+			host_chain_id
+		)));
+	}
 
 	let delivery_config = SignetBundleConfig {
 		chain_name,
@@ -685,6 +710,10 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_delivery
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(SignetBundleDeliverySchema)
+	}
 }
 
 impl crate::DeliveryRegistry for Registry {}
@@ -692,6 +721,7 @@ impl crate::DeliveryRegistry for Registry {}
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use solver_account::{implementations::local::LocalWallet, AccountInterface};
 	use solver_types::utils::tests::builders::NetworksConfigBuilder;
 	use std::collections::HashMap;
 
@@ -762,16 +792,50 @@ mod tests {
 		let config = toml::Value::try_from(create_test_config()).unwrap();
 
 		let networks = create_test_networks();
-		// Use a valid hex private key for testing
-		let default_key = solver_types::SecretString::from(
-			"0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
-		);
-		let network_keys = HashMap::new();
+		let default_signer =
+			LocalWallet::new("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+				.unwrap()
+				.signer();
+		let network_signers = HashMap::new();
 
-		let result = create_delivery(&config, &networks, &default_key, &network_keys);
+		let result = create_delivery(&config, &networks, &default_signer, &network_signers, &[]);
 		assert!(result.is_ok());
 	}
 
+	/// Signer that, like a remote signing service, refuses raw digests.
+	#[derive(Debug)]
+	struct DigestlessSigner;
+
+	#[async_trait]
+	impl solver_account::SolverSigner for DigestlessSigner {
+		fn address(&self) -> alloy_primitives::Address {
+			alloy_primitives::Address::repeat_byte(0x11)
+		}
+
+		async fn sign_hash(
+			&self,
+			_hash: &alloy_primitives::B256,
+		) -> Result<alloy_primitives::Signature, solver_account::AccountError> {
+			Err(solver_account::AccountError::SigningFailed(
+				"raw digests not supported".to_string(),
+			))
+		}
+
+		fn signs_digests(&self) -> bool {
+			false
+		}
+	}
+
+	#[test]
+	fn test_create_delivery_rejects_signer_without_digest_signing() {
+		let config = toml::Value::try_from(create_test_config()).unwrap();
+		let networks = create_test_networks();
+		let default_signer = AccountSigner::new(Arc::new(DigestlessSigner));
+
+		let result = create_delivery(&config, &networks, &default_signer, &HashMap::new(), &[]);
+		assert!(matches!(result, Err(DeliveryError::Unsupported(_))));
+	}
+
 	#[test]
 	fn test_registry_name() {
 		assert_eq!(
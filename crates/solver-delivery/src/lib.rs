@@ -6,9 +6,10 @@
 
 use alloy_primitives::Bytes;
 use async_trait::async_trait;
+use solver_account::AccountSigner;
 use solver_types::{
-	ChainData, ConfigSchema, ImplementationRegistry, NetworksConfig, Transaction, TransactionHash,
-	TransactionReceipt,
+	ChainData, ConfigSchema, FeeHistory, ImplementationRegistry, NetworksConfig, RevertReason,
+	Transaction, TransactionHash, TransactionReceipt,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -22,6 +23,8 @@ pub mod implementations {
 	pub mod signet;
 }
 
+pub mod revert;
+
 /// Errors that can occur during transaction delivery operations.
 #[derive(Debug, Error)]
 pub enum DeliveryError {
@@ -34,6 +37,12 @@ pub enum DeliveryError {
 	/// Error that occurs when no suitable implementation is available for the operation.
 	#[error("No implementation available")]
 	NoImplementationAvailable,
+	/// Error that occurs when a simulated transaction reverts.
+	#[error("Transaction reverted: {0}")]
+	Reverted(RevertReason),
+	/// Error that occurs when an implementation does not support the operation.
+	#[error("Unsupported operation: {0}")]
+	Unsupported(String),
 }
 
 /// Trait defining the interface for transaction delivery implementations.
@@ -42,6 +51,7 @@ pub enum DeliveryError {
 /// integrate with the solver system. It provides methods for submitting
 /// transactions and monitoring their confirmation status.
 #[async_trait]
+#[cfg_attr(feature = "testing", mockall::automock)]
 pub trait DeliveryInterface: Send + Sync {
 	/// Returns the configuration schema for this delivery implementation.
 	///
@@ -116,6 +126,17 @@ pub trait DeliveryInterface: Send + Sync {
 	/// Returns the latest block number on the network.
 	async fn get_block_number(&self, chain_id: u64) -> Result<u64, DeliveryError>;
 
+	/// Gets the fee history of the most recent blocks.
+	///
+	/// Returns base fees and the priority fees paid at each of the given
+	/// percentiles for the last `block_count` blocks, as `eth_feeHistory` does.
+	async fn get_fee_history(
+		&self,
+		chain_id: u64,
+		block_count: u64,
+		reward_percentiles: &[f64],
+	) -> Result<FeeHistory, DeliveryError>;
+
 	/// Estimates gas units for a transaction without submitting it.
 	/// Implementations should call the chain's estimateGas RPC with the provided transaction.
 	async fn estimate_gas(&self, tx: Transaction) -> Result<u64, DeliveryError>;
@@ -125,6 +146,13 @@ pub trait DeliveryInterface: Send + Sync {
 	/// This performs an eth_call RPC to read data from smart contracts
 	/// or simulate transaction execution without submitting to the blockchain.
 	async fn eth_call(&self, tx: Transaction) -> Result<Bytes, DeliveryError>;
+
+	/// Simulates a transaction against the pending block without submitting it.
+	///
+	/// Returns the gas the transaction would use, or `DeliveryError::Reverted`
+	/// with the decoded reason if it would revert. Implementations that cannot
+	/// simulate return `DeliveryError::Unsupported`.
+	async fn simulate(&self, tx: Transaction) -> Result<u64, DeliveryError>;
 }
 
 /// Type alias for delivery factory functions.
@@ -134,8 +162,9 @@ pub trait DeliveryInterface: Send + Sync {
 pub type DeliveryFactory = fn(
 	&toml::Value,
 	&NetworksConfig,
-	&solver_types::SecretString,               // Default/primary private key
-	&HashMap<u64, solver_types::SecretString>, // Per-network private keys
+	&AccountSigner,               // Default/primary signer
+	&HashMap<u64, AccountSigner>, // Per-network signers
+	&[AccountSigner],             // Additional signers selectable via `Transaction::from`
 ) -> Result<Box<dyn DeliveryInterface>, DeliveryError>;
 
 /// Registry trait for delivery implementations.
@@ -157,6 +186,22 @@ pub fn get_all_implementations() -> Vec<(&'static str, DeliveryFactory)> {
 	]
 }
 
+/// Get configuration schemas for all registered delivery implementations.
+///
+/// Returns a vector of (name, schema) tuples, used to validate configuration
+/// without constructing the implementations.
+pub fn get_all_schemas() -> Vec<(&'static str, Box<dyn ConfigSchema>)> {
+	use implementations::{evm::alloy, signet};
+
+	vec![
+		(alloy::Registry::NAME, alloy::Registry::config_schema()),
+		(
+			signet::bundle::Registry::NAME,
+			signet::bundle::Registry::config_schema(),
+		),
+	]
+}
+
 /// Service that manages transaction delivery across multiple blockchain networks.
 ///
 /// The DeliveryService coordinates between different delivery implementations based on
@@ -266,6 +311,7 @@ impl DeliveryService {
 	/// Gets chain-specific data for the given chain ID.
 	///
 	/// Returns gas price, block number, and other chain state information.
+	/// EIP-1559 fees are left unset; they are estimated from the fee history.
 	pub async fn get_chain_data(&self, chain_id: u64) -> Result<ChainData, DeliveryError> {
 		let implementation = self
 			.implementations
@@ -283,6 +329,7 @@ impl DeliveryService {
 				.duration_since(std::time::UNIX_EPOCH)
 				.unwrap_or_default()
 				.as_secs(),
+			fees: None,
 		})
 	}
 
@@ -359,6 +406,25 @@ impl DeliveryService {
 		implementation.get_block_number(chain_id).await
 	}
 
+	/// Gets the fee history of the most recent blocks on a specific chain.
+	///
+	/// Convenience method that routes to the appropriate implementation.
+	pub async fn get_fee_history(
+		&self,
+		chain_id: u64,
+		block_count: u64,
+		reward_percentiles: &[f64],
+	) -> Result<FeeHistory, DeliveryError> {
+		let implementation = self
+			.implementations
+			.get(&chain_id)
+			.ok_or(DeliveryError::NoImplementationAvailable)?;
+
+		implementation
+			.get_fee_history(chain_id, block_count, reward_percentiles)
+			.await
+	}
+
 	/// Estimates gas for a transaction on the specified chain.
 	pub async fn estimate_gas(&self, chain_id: u64, tx: Transaction) -> Result<u64, DeliveryError> {
 		let implementation = self
@@ -369,6 +435,19 @@ impl DeliveryService {
 		implementation.estimate_gas(tx).await
 	}
 
+	/// Simulates a transaction against the pending block of its chain.
+	///
+	/// Returns the gas the transaction would use, or `DeliveryError::Reverted`
+	/// with the decoded reason if it would revert.
+	pub async fn simulate(&self, tx: Transaction) -> Result<u64, DeliveryError> {
+		let implementation = self
+			.implementations
+			.get(&tx.chain_id)
+			.ok_or(DeliveryError::NoImplementationAvailable)?;
+
+		implementation.simulate(tx).await
+	}
+
 	/// Executes a contract call (eth_call) without sending a transaction.
 	///
 	/// This method is used to read data from smart contracts or simulate
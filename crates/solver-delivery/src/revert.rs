@@ -0,0 +1,146 @@
+//! Decoding of revert data returned by transaction simulation.
+//!
+//! Maps the custom errors of the OIF input and output settlers, and of the
+//! tokens they move, onto typed `RevertReason`s. Standard `Error(string)` and
+//! `Panic(uint256)` reverts are decoded as well; anything else is kept as hex.
+
+use alloy_primitives::hex;
+use alloy_sol_types::{Panic, Revert, SolError};
+use solver_types::RevertReason;
+
+mod errors {
+	alloy_sol_types::sol! {
+		// Output settler
+		error AlreadyFilled();
+		error FilledBySomeoneElse(bytes32 solver);
+		error FillDeadline();
+		error WrongChain(uint256 expected, uint256 actual);
+		error WrongOutputSettler(bytes32 addressThis, bytes32 expected);
+
+		// Input settler
+		error DeadlinePassed();
+		error TimestampPassed();
+		error InvalidOrderStatus();
+		error TimestampNotPassed();
+
+		// Input oracle, reached through the input settler's proof check
+		error NotProven();
+		error NotAllProven();
+
+		// Tokens
+		error ERC20InsufficientBalance(address sender, uint256 balance, uint256 needed);
+		error ERC20InsufficientAllowance(address spender, uint256 allowance, uint256 needed);
+		error TransferFailed();
+		error TransferFromFailed();
+	}
+}
+
+/// Decodes the data of a reverted call into a typed reason.
+pub fn decode_revert(data: &[u8]) -> RevertReason {
+	use errors::*;
+
+	let Some(selector) = data.get(..4).and_then(|s| <[u8; 4]>::try_from(s).ok()) else {
+		return RevertReason::Unknown(hex::encode_prefixed(data));
+	};
+
+	let known = [
+		(AlreadyFilled::SELECTOR, RevertReason::AlreadyFilled),
+		(FilledBySomeoneElse::SELECTOR, RevertReason::AlreadyFilled),
+		(FillDeadline::SELECTOR, RevertReason::DeadlinePassed),
+		(WrongChain::SELECTOR, RevertReason::WrongDestination),
+		(WrongOutputSettler::SELECTOR, RevertReason::WrongDestination),
+		(DeadlinePassed::SELECTOR, RevertReason::DeadlinePassed),
+		(TimestampPassed::SELECTOR, RevertReason::DeadlinePassed),
+		(
+			InvalidOrderStatus::SELECTOR,
+			RevertReason::InvalidOrderStatus,
+		),
+		(TimestampNotPassed::SELECTOR, RevertReason::NotYetClaimable),
+		(NotProven::SELECTOR, RevertReason::NotYetClaimable),
+		(NotAllProven::SELECTOR, RevertReason::NotYetClaimable),
+		(
+			ERC20InsufficientBalance::SELECTOR,
+			RevertReason::InsufficientBalance,
+		),
+		(
+			ERC20InsufficientAllowance::SELECTOR,
+			RevertReason::InsufficientAllowance,
+		),
+		(TransferFailed::SELECTOR, RevertReason::TransferFailed),
+		(TransferFromFailed::SELECTOR, RevertReason::TransferFailed),
+	];
+	if let Some((_, reason)) = known.into_iter().find(|(known, _)| *known == selector) {
+		return reason;
+	}
+
+	if selector == Revert::SELECTOR {
+		if let Ok(revert) = Revert::abi_decode(data) {
+			return RevertReason::Message(revert.reason);
+		}
+	}
+	if selector == Panic::SELECTOR {
+		if let Ok(panic) = Panic::abi_decode(data) {
+			return RevertReason::Panic(panic.code.saturating_to());
+		}
+	}
+	RevertReason::Unknown(hex::encode_prefixed(data))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_primitives::{Address, FixedBytes, U256};
+
+	#[test]
+	fn test_decode_settler_errors() {
+		let data = errors::FilledBySomeoneElse {
+			solver: FixedBytes::repeat_byte(0xab),
+		}
+		.abi_encode();
+		assert_eq!(decode_revert(&data), RevertReason::AlreadyFilled);
+
+		let data = errors::FillDeadline {}.abi_encode();
+		assert_eq!(decode_revert(&data), RevertReason::DeadlinePassed);
+
+		let data = errors::ERC20InsufficientAllowance {
+			spender: Address::ZERO,
+			allowance: U256::ZERO,
+			needed: U256::from(100),
+		}
+		.abi_encode();
+		assert_eq!(decode_revert(&data), RevertReason::InsufficientAllowance);
+
+		// A claim whose fill is not attested yet
+		let data = errors::NotProven {}.abi_encode();
+		assert_eq!(decode_revert(&data), RevertReason::NotYetClaimable);
+		let data = errors::NotAllProven {}.abi_encode();
+		assert_eq!(decode_revert(&data), RevertReason::NotYetClaimable);
+	}
+
+	#[test]
+	fn test_decode_standard_reverts() {
+		let data = Revert {
+			reason: "Ownable: caller is not the owner".to_string(),
+		}
+		.abi_encode();
+		assert_eq!(
+			decode_revert(&data),
+			RevertReason::Message("Ownable: caller is not the owner".to_string())
+		);
+
+		let data = Panic {
+			code: U256::from(0x11),
+		}
+		.abi_encode();
+		assert_eq!(decode_revert(&data), RevertReason::Panic(0x11));
+	}
+
+	#[test]
+	fn test_decode_unknown_revert() {
+		assert_eq!(
+			decode_revert(&[0xde, 0xad, 0xbe, 0xef, 0x01]),
+			RevertReason::Unknown("0xdeadbeef01".to_string())
+		);
+		assert_eq!(decode_revert(&[]), RevertReason::Unknown("0x".to_string()));
+	}
+}
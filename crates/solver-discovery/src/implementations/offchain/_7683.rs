@@ -397,6 +397,7 @@ impl Eip7683OffchainDiscovery {
 	/// * `lock_type` - The custody mechanism type (Permit2Escrow, Eip3009Escrow, or ResourceLock)
 	/// * `providers` - RPC providers for each supported network
 	/// * `networks` - Networks configuration for settler lookups
+	/// * `quote_id` - The solver quote the order accepts, if any
 	///
 	/// # Returns
 	///
@@ -408,6 +409,7 @@ impl Eip7683OffchainDiscovery {
 	/// - Order ID computation fails
 	/// - No outputs are present in the order
 	/// - Network configuration is missing for the origin chain
+	#[allow(clippy::too_many_arguments)]
 	async fn order_to_intent(
 		order: &StandardOrder,
 		order_bytes: &Bytes,
@@ -416,6 +418,7 @@ impl Eip7683OffchainDiscovery {
 		lock_type: LockType,
 		providers: &HashMap<u64, RootProvider>,
 		networks: &NetworksConfig,
+		quote_id: Option<String>,
 	) -> Result<Intent, DiscoveryError> {
 		// Get the input settler address for the order's origin chain
 		let origin_chain_id = order.originChainId.to::<u64>();
@@ -505,6 +508,7 @@ impl Eip7683OffchainDiscovery {
 			signature: Some(with_0x_prefix(&hex::encode(signature))),
 			sponsor: Some(sponsor.to_string()),
 			lock_type: Some(lock_type),
+			input_settler: Some(with_0x_prefix(&hex::encode(settler_address))),
 		};
 
 		Ok(Intent {
@@ -520,7 +524,7 @@ impl Eip7683OffchainDiscovery {
 				DiscoveryError::ParseError(format!("Failed to serialize order data: {}", e))
 			})?,
 			order_bytes: order_bytes.clone(),
-			quote_id: None, // TODO: add quote id to the intent
+			quote_id,
 			lock_type: lock_type.to_string(),
 		})
 	}
@@ -726,6 +730,7 @@ async fn handle_intent_submission(
 		request.lock_type,
 		&state.providers,
 		&state.networks,
+		request.quote_id.clone(),
 	)
 	.await
 	{
@@ -957,6 +962,10 @@ impl ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_discovery
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(Eip7683OffchainDiscoverySchema)
+	}
 }
 
 impl crate::DiscoveryRegistry for Registry {}
@@ -1323,6 +1332,7 @@ mod tests {
 			signature: Bytes::from_static(b"signature"),
 			sponsor: Address::from_slice(&[0xab; 20]),
 			lock_type: LockType::Permit2Escrow,
+			quote_id: None,
 		};
 
 		let response = handle_intent_submission(State(state), Json(invalid_request)).await;
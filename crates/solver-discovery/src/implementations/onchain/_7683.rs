@@ -257,6 +257,7 @@ impl Eip7683Discovery {
 			signature: None,
 			sponsor: None,
 			lock_type: Some(LockType::Permit2Escrow),
+			input_settler: None,
 		};
 
 		Ok(Intent {
@@ -651,6 +652,10 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_discovery
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(Eip7683DiscoverySchema)
+	}
 }
 
 impl crate::DiscoveryRegistry for Registry {}
@@ -1053,6 +1058,7 @@ mod tests {
 			signature: None,
 			sponsor: None,
 			lock_type: Some(LockType::Permit2Escrow),
+			input_settler: None,
 		};
 
 		// Test serialization to JSON
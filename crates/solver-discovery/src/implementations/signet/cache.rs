@@ -373,6 +373,10 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_discovery
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(SignetCacheDiscoverySchema)
+	}
 }
 
 impl crate::DiscoveryRegistry for Registry {}
@@ -116,6 +116,29 @@ pub fn get_all_implementations() -> Vec<(&'static str, DiscoveryFactory)> {
 	]
 }
 
+/// Get configuration schemas for all registered discovery implementations.
+///
+/// Returns a vector of (name, schema) tuples, used to validate configuration
+/// without constructing the implementations.
+pub fn get_all_schemas() -> Vec<(&'static str, Box<dyn ConfigSchema>)> {
+	use implementations::{offchain, onchain, signet};
+
+	vec![
+		(
+			onchain::_7683::Registry::NAME,
+			onchain::_7683::Registry::config_schema(),
+		),
+		(
+			offchain::_7683::Registry::NAME,
+			offchain::_7683::Registry::config_schema(),
+		),
+		(
+			signet::cache::Registry::NAME,
+			signet::cache::Registry::config_schema(),
+		),
+	]
+}
+
 /// Service that manages multiple intent discovery implementations.
 ///
 /// The DiscoveryService coordinates multiple discovery implementations, allowing
@@ -262,6 +262,7 @@ impl OrderInterface for Eip7683OrderImpl {
 			.ok_or_else(|| OrderError::ValidationFailed("No input chains in order".into()))?;
 
 		Ok(Some(Transaction {
+			from: None,
 			to: Some(input_chain.settler_address.clone()),
 			data: open_for_data,
 			value: U256::ZERO,
@@ -366,6 +367,7 @@ impl OrderInterface for Eip7683OrderImpl {
 		.abi_encode();
 
 		Ok(Transaction {
+			from: None,
 			to: Some(output_settler_address),
 			data: fill_data,
 			value: U256::ZERO,
@@ -514,9 +516,14 @@ impl OrderInterface for Eip7683OrderImpl {
 			solver: FixedBytes::<32>::from(solver_bytes32),
 		}];
 
-		// Create destination bytes32 (solver address for self-finalisation)
+		// Create destination bytes32. Inputs go to the account that filled the
+		// order so its inventory is replenished; fall back to the solver address.
+		let recipient = order
+			.filler_address
+			.as_ref()
+			.unwrap_or(&order.solver_address);
 		let mut destination_bytes32 = [0u8; 32];
-		destination_bytes32[12..32].copy_from_slice(&order.solver_address.0);
+		destination_bytes32[12..32].copy_from_slice(&recipient.0);
 		let destination = FixedBytes::<32>::from(destination_bytes32);
 
 		// Empty call data for simple finalisation
@@ -568,6 +575,7 @@ impl OrderInterface for Eip7683OrderImpl {
 			.ok_or_else(|| OrderError::ValidationFailed("No input chains in order".into()))?;
 
 		Ok(Transaction {
+			from: None,
 			to: Some(input_chain.settler_address.clone()),
 			data: call_data,
 			value: U256::ZERO,
@@ -797,6 +805,7 @@ impl OrderInterface for Eip7683OrderImpl {
 				OrderError::ValidationFailed(format!("Failed to serialize order: {}", e))
 			})?,
 			solver_address: solver_address.clone(),
+			filler_address: None,
 			quote_id: None,
 			input_chains,
 			output_chains,
@@ -807,6 +816,7 @@ impl OrderInterface for Eip7683OrderImpl {
 			post_fill_tx_hash: None,
 			pre_claim_tx_hash: None,
 			fill_proof: None,
+			revert_reason: None,
 		})
 	}
 }
@@ -855,6 +865,10 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_order_impl
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(Eip7683OrderSchema)
+	}
 }
 
 impl crate::OrderRegistry for Registry {}
@@ -1120,6 +1134,40 @@ mod tests {
 		assert!(!tx.data.is_empty());
 	}
 
+	#[tokio::test]
+	async fn test_generate_claim_transaction_pays_filler() {
+		let networks = create_test_networks();
+		let oracle_routes = create_test_oracle_routes();
+		let order_impl = Eip7683OrderImpl::new(networks, oracle_routes).unwrap();
+
+		let order = OrderBuilder::new()
+			.with_id("test-order".to_string())
+			.with_data(serde_json::to_value(create_test_order_data()).unwrap())
+			.with_solver_address(Address(vec![99u8; 20]))
+			.with_filler_address(Some(Address(vec![77u8; 20])))
+			.with_input_chain_ids(vec![1])
+			.with_output_chain_ids(vec![137])
+			.build();
+
+		let fill_proof = FillProof {
+			tx_hash: TransactionHash(hex::decode("abcd").unwrap()),
+			block_number: 12345,
+			attestation_data: None,
+			filled_timestamp: 123456789,
+			oracle_address: "0x0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B".to_string(),
+		};
+
+		let tx = order_impl
+			.generate_claim_transaction(&order, &fill_proof)
+			.await
+			.unwrap();
+		let call = IInputSettlerEscrow::finaliseCall::abi_decode(&tx.data).unwrap();
+
+		// The claimer stays the solver of record, the filler receives the inputs.
+		assert_eq!(call.solveParams[0].solver.as_slice()[12..], [99u8; 20]);
+		assert_eq!(call.destination.as_slice()[12..], [77u8; 20]);
+	}
+
 	#[test]
 	fn test_config_schema_validation() {
 		let schema = Eip7683OrderSchema;
@@ -156,6 +156,7 @@ impl OrderInterface for SignetOrderImpl {
 			status: OrderStatus::Created,
 			data: intent_data.clone().unwrap_or_default(),
 			solver_address: solver_address.clone(),
+			filler_address: None,
 			quote_id: None,
 			input_chains,
 			output_chains,
@@ -166,6 +167,7 @@ impl OrderInterface for SignetOrderImpl {
 			pre_claim_tx_hash: None,
 			claim_tx_hash: None,
 			fill_proof: None,
+			revert_reason: None,
 		})
 	}
 }
@@ -200,6 +202,10 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_order
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(SignetOrderSchema)
+	}
 }
 
 impl crate::OrderRegistry for Registry {}
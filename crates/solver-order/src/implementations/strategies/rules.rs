@@ -0,0 +1,872 @@
+//! Rule-based execution strategy.
+//!
+//! Policy is expressed as an ordered list of rules in the strategy config
+//! instead of Rust code. Rules are checked in order and the first one that
+//! matches decides: deny-style rules skip the order, timing rules defer it.
+//! The reason of a decision starts with the rule type, so skip and defer
+//! counts can be broken down by rule. Orders no rule stops are handed to the
+//! simple strategy, which still applies the gas, balance and exposure checks
+//! configured in the same table.
+//!
+//! ```toml
+//! [order.strategy.implementations.rules]
+//! max_gas_price_gwei = 100
+//!
+//! [[order.strategy.implementations.rules.rules]]
+//! type = "deny_users"
+//! users = ["0x1234567890123456789012345678901234567890"]
+//!
+//! [[order.strategy.implementations.rules.rules]]
+//! type = "gas_ceiling"
+//! chain_id = 1
+//! max_gas_price_gwei = 30
+//! ```
+
+use alloy_primitives::{hex, U256};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use solver_types::{
+	without_0x_prefix, ConfigSchema, ExecutionContext, ExecutionDecision, Field, FieldType,
+	InteropAddress, Order, Schema,
+};
+use std::time::Duration;
+
+use super::simple::{self, SimpleStrategySchema};
+use crate::{ExecutionStrategy, StrategyError};
+
+/// Seconds in a day, for time-of-day windows.
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// A token on a chain.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TokenMatch {
+	pub chain_id: u64,
+	pub address: String,
+}
+
+/// A route from an origin chain to a destination chain.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RouteMatch {
+	pub from: u64,
+	pub to: u64,
+}
+
+/// A single policy rule.
+///
+/// Addresses are hex strings with or without the 0x prefix. Token amounts are
+/// decimal strings in the token's smallest unit.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Rule {
+	/// Skips orders from users not on the list.
+	AllowUsers { users: Vec<String> },
+	/// Skips orders from users on the list.
+	DenyUsers { users: Vec<String> },
+	/// Skips orders with an input or output token not on the list.
+	AllowTokens { tokens: Vec<TokenMatch> },
+	/// Skips orders with an input or output token on the list.
+	DenyTokens { tokens: Vec<TokenMatch> },
+	/// Skips orders on a route not on the list.
+	AllowRoutes { routes: Vec<RouteMatch> },
+	/// Skips orders on a route on the list.
+	DenyRoutes { routes: Vec<RouteMatch> },
+	/// Skips orders paying a token outside the amount bounds.
+	OutputAmount {
+		chain_id: u64,
+		token: String,
+		min: Option<String>,
+		max: Option<String>,
+	},
+	/// Skips orders whose outputs are worth less or more than the USD bounds.
+	OrderValue {
+		min_usd: Option<Decimal>,
+		max_usd: Option<Decimal>,
+	},
+	/// Skips orders below a profit margin, on the listed routes or on all.
+	/// Orders accepting a live quote pass, since their terms were already priced.
+	MinMargin {
+		min_margin_pct: Decimal,
+		#[serde(default)]
+		routes: Vec<RouteMatch>,
+	},
+	/// Defers orders outside a daily UTC window given as "HH:MM".
+	TimeWindow { start: String, end: String },
+	/// Defers orders while gas on a chain is above a ceiling. Orders accepting a
+	/// live quote pass.
+	GasCeiling {
+		chain_id: u64,
+		max_gas_price_gwei: u64,
+		#[serde(default = "default_retry_after_seconds")]
+		retry_after_seconds: u64,
+	},
+}
+
+fn default_retry_after_seconds() -> u64 {
+	60
+}
+
+/// Assets, users and routes of an order, as the rules see them.
+struct OrderFacts {
+	/// Users of the inputs, lowercase hex.
+	users: Vec<String>,
+	/// Input (chain, token, amount), tokens as lowercase hex.
+	inputs: Vec<(u64, String, U256)>,
+	/// Output (chain, token, amount), tokens as lowercase hex.
+	outputs: Vec<(u64, String, U256)>,
+}
+
+impl OrderFacts {
+	fn from_order(order: &Order) -> Result<Self, String> {
+		let parsed = order.parse_order_data().map_err(|e| e.to_string())?;
+		let asset = |asset: &InteropAddress| -> Result<(u64, String), String> {
+			let chain_id = asset.ethereum_chain_id().map_err(|e| e.to_string())?;
+			let address = asset.ethereum_address().map_err(|e| e.to_string())?;
+			Ok((chain_id, hex::encode(address.as_slice())))
+		};
+
+		let mut users = Vec::new();
+		let mut inputs = Vec::new();
+		for input in parsed.parse_available_inputs() {
+			let user = input.user.ethereum_address().map_err(|e| e.to_string())?;
+			users.push(hex::encode(user.as_slice()));
+			let (chain_id, token) = asset(&input.asset)?;
+			inputs.push((chain_id, token, input.amount));
+		}
+		let mut outputs = Vec::new();
+		for output in parsed.parse_requested_outputs() {
+			let (chain_id, token) = asset(&output.asset)?;
+			outputs.push((chain_id, token, output.amount));
+		}
+		Ok(Self {
+			users,
+			inputs,
+			outputs,
+		})
+	}
+
+	fn tokens(&self) -> impl Iterator<Item = (u64, &str)> {
+		self.inputs
+			.iter()
+			.chain(&self.outputs)
+			.map(|(chain_id, token, _)| (*chain_id, token.as_str()))
+	}
+
+	fn routes(&self) -> Vec<(u64, u64)> {
+		let mut routes = Vec::new();
+		for (from, _, _) in &self.inputs {
+			for (to, _, _) in &self.outputs {
+				if !routes.contains(&(*from, *to)) {
+					routes.push((*from, *to));
+				}
+			}
+		}
+		routes
+	}
+}
+
+/// Normalizes a configured address to lowercase hex without the 0x prefix.
+fn normalize_address(address: &str) -> String {
+	without_0x_prefix(address).to_lowercase()
+}
+
+fn is_valid_address(address: &str) -> bool {
+	hex::decode(without_0x_prefix(address)).is_ok_and(|bytes| bytes.len() == 20)
+}
+
+/// Parses "HH:MM" into minutes after midnight.
+fn parse_time_of_day(time: &str) -> Option<u64> {
+	let (hours, minutes) = time.split_once(':')?;
+	let hours: u64 = hours.parse().ok()?;
+	let minutes: u64 = minutes.parse().ok()?;
+	(hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+fn route_listed(routes: &[RouteMatch], from: u64, to: u64) -> bool {
+	routes
+		.iter()
+		.any(|route| route.from == from && route.to == to)
+}
+
+fn token_listed(tokens: &[TokenMatch], chain_id: u64, token: &str) -> bool {
+	tokens
+		.iter()
+		.any(|t| t.chain_id == chain_id && normalize_address(&t.address) == token)
+}
+
+impl Rule {
+	/// Returns the rule type as written in the config.
+	pub fn name(&self) -> &'static str {
+		match self {
+			Rule::AllowUsers { .. } => "allow_users",
+			Rule::DenyUsers { .. } => "deny_users",
+			Rule::AllowTokens { .. } => "allow_tokens",
+			Rule::DenyTokens { .. } => "deny_tokens",
+			Rule::AllowRoutes { .. } => "allow_routes",
+			Rule::DenyRoutes { .. } => "deny_routes",
+			Rule::OutputAmount { .. } => "output_amount",
+			Rule::OrderValue { .. } => "order_value",
+			Rule::MinMargin { .. } => "min_margin",
+			Rule::TimeWindow { .. } => "time_window",
+			Rule::GasCeiling { .. } => "gas_ceiling",
+		}
+	}
+
+	/// Checks values serde cannot, such as address and time formats.
+	pub fn validate(&self) -> Result<(), String> {
+		let check_address = |address: &str| {
+			if is_valid_address(address) {
+				Ok(())
+			} else {
+				Err(format!("{}: invalid address {}", self.name(), address))
+			}
+		};
+		match self {
+			Rule::AllowUsers { users } | Rule::DenyUsers { users } => {
+				users.iter().try_for_each(|user| check_address(user))
+			},
+			Rule::AllowTokens { tokens } | Rule::DenyTokens { tokens } => tokens
+				.iter()
+				.try_for_each(|token| check_address(&token.address)),
+			Rule::AllowRoutes { .. } | Rule::DenyRoutes { .. } => Ok(()),
+			Rule::OutputAmount {
+				token, min, max, ..
+			} => {
+				check_address(token)?;
+				let parse = |amount: &Option<String>| {
+					amount
+						.as_deref()
+						.map(|amount| U256::from_str_radix(amount, 10))
+						.transpose()
+						.map_err(|_| format!("{}: amounts must be integers", self.name()))
+				};
+				match (parse(min)?, parse(max)?) {
+					(None, None) => Err(format!("{}: set min, max or both", self.name())),
+					(Some(min), Some(max)) if min > max => {
+						Err(format!("{}: min is above max", self.name()))
+					},
+					_ => Ok(()),
+				}
+			},
+			Rule::OrderValue { min_usd, max_usd } => match (min_usd, max_usd) {
+				(None, None) => Err(format!("{}: set min_usd, max_usd or both", self.name())),
+				(Some(min), Some(max)) if min > max => {
+					Err(format!("{}: min_usd is above max_usd", self.name()))
+				},
+				_ => Ok(()),
+			},
+			Rule::MinMargin { .. } => Ok(()),
+			Rule::TimeWindow { start, end } => {
+				if parse_time_of_day(start).is_some() && parse_time_of_day(end).is_some() {
+					Ok(())
+				} else {
+					Err(format!("{}: times must be HH:MM", self.name()))
+				}
+			},
+			Rule::GasCeiling {
+				max_gas_price_gwei, ..
+			} => {
+				if *max_gas_price_gwei == 0 {
+					Err(format!(
+						"{}: max_gas_price_gwei must be positive",
+						self.name()
+					))
+				} else {
+					Ok(())
+				}
+			},
+		}
+	}
+
+	/// Returns the decision of this rule, or `None` when the order passes it.
+	fn evaluate(
+		&self,
+		facts: &OrderFacts,
+		context: &ExecutionContext,
+	) -> Option<ExecutionDecision> {
+		let skip = |detail: String| {
+			Some(ExecutionDecision::Skip(format!(
+				"{}: {}",
+				self.name(),
+				detail
+			)))
+		};
+		match self {
+			Rule::AllowUsers { users } => {
+				let allowed: Vec<String> = users.iter().map(|u| normalize_address(u)).collect();
+				facts
+					.users
+					.iter()
+					.find(|user| !allowed.contains(user))
+					.and_then(|user| skip(format!("user 0x{} is not allowed", user)))
+			},
+			Rule::DenyUsers { users } => {
+				let denied: Vec<String> = users.iter().map(|u| normalize_address(u)).collect();
+				facts
+					.users
+					.iter()
+					.find(|user| denied.contains(user))
+					.and_then(|user| skip(format!("user 0x{} is denied", user)))
+			},
+			Rule::AllowTokens { tokens } => facts
+				.tokens()
+				.find(|(chain_id, token)| !token_listed(tokens, *chain_id, token))
+				.and_then(|(chain_id, token)| {
+					skip(format!(
+						"token 0x{} on chain {} is not allowed",
+						token, chain_id
+					))
+				}),
+			Rule::DenyTokens { tokens } => facts
+				.tokens()
+				.find(|(chain_id, token)| token_listed(tokens, *chain_id, token))
+				.and_then(|(chain_id, token)| {
+					skip(format!("token 0x{} on chain {} is denied", token, chain_id))
+				}),
+			Rule::AllowRoutes { routes } => facts
+				.routes()
+				.into_iter()
+				.find(|(from, to)| !route_listed(routes, *from, *to))
+				.and_then(|(from, to)| skip(format!("route {} -> {} is not allowed", from, to))),
+			Rule::DenyRoutes { routes } => facts
+				.routes()
+				.into_iter()
+				.find(|(from, to)| route_listed(routes, *from, *to))
+				.and_then(|(from, to)| skip(format!("route {} -> {} is denied", from, to))),
+			Rule::OutputAmount {
+				chain_id,
+				token,
+				min,
+				max,
+			} => {
+				let token = normalize_address(token);
+				let min = min
+					.as_deref()
+					.and_then(|v| U256::from_str_radix(v, 10).ok());
+				let max = max
+					.as_deref()
+					.and_then(|v| U256::from_str_radix(v, 10).ok());
+				facts
+					.outputs
+					.iter()
+					.filter(|(c, t, _)| c == chain_id && *t == token)
+					.find_map(|(_, _, amount)| {
+						if min.is_some_and(|min| *amount < min) {
+							skip(format!(
+								"output {} of token 0x{} is below the minimum of {}",
+								amount,
+								token,
+								min.unwrap_or_default()
+							))
+						} else if max.is_some_and(|max| *amount > max) {
+							skip(format!(
+								"output {} of token 0x{} is above the maximum of {}",
+								amount,
+								token,
+								max.unwrap_or_default()
+							))
+						} else {
+							None
+						}
+					})
+			},
+			Rule::OrderValue { min_usd, max_usd } => {
+				let Some(value) = context.order_exposure.as_ref().map(|e| e.value_usd()) else {
+					return skip("order value is unknown".to_string());
+				};
+				if let Some(min) = min_usd.filter(|min| value < *min) {
+					skip(format!(
+						"value {} USD is below the minimum of {} USD",
+						value, min
+					))
+				} else if let Some(max) = max_usd.filter(|max| value > *max) {
+					skip(format!(
+						"value {} USD is above the maximum of {} USD",
+						value, max
+					))
+				} else {
+					None
+				}
+			},
+			Rule::MinMargin {
+				min_margin_pct,
+				routes,
+			} => {
+				let applies = routes.is_empty()
+					|| facts
+						.routes()
+						.iter()
+						.any(|(from, to)| route_listed(routes, *from, *to));
+				if !applies || context.quoted {
+					return None;
+				}
+				match context.profit_margin_pct {
+					Some(margin) if margin >= *min_margin_pct => None,
+					Some(margin) => skip(format!(
+						"margin {}% is below the minimum of {}%",
+						margin.round_dp(2),
+						min_margin_pct
+					)),
+					None => skip("profit margin is unknown".to_string()),
+				}
+			},
+			Rule::TimeWindow { start, end } => {
+				let (Some(start), Some(end)) = (parse_time_of_day(start), parse_time_of_day(end))
+				else {
+					return None;
+				};
+				let second_of_day = context.timestamp % SECONDS_PER_DAY;
+				let minute = second_of_day / 60;
+				let open = match start.cmp(&end) {
+					std::cmp::Ordering::Less => minute >= start && minute < end,
+					std::cmp::Ordering::Greater => minute >= start || minute < end,
+					std::cmp::Ordering::Equal => true,
+				};
+				if open {
+					return None;
+				}
+				let wait = (start * 60 + SECONDS_PER_DAY - second_of_day) % SECONDS_PER_DAY;
+				Some(ExecutionDecision::Defer(
+					Duration::from_secs(wait),
+					format!("{}: outside the execution window", self.name()),
+				))
+			},
+			Rule::GasCeiling {
+				chain_id,
+				max_gas_price_gwei,
+				retry_after_seconds,
+			} => {
+				if context.quoted {
+					return None;
+				}
+				let gas_price = context
+					.chain_data
+					.get(chain_id)
+					.and_then(|data| data.gas_price_for(context.fill_urgency))
+					.map(U256::from)?;
+				let ceiling = U256::from(*max_gas_price_gwei) * U256::from(10u64.pow(9));
+				(gas_price > ceiling).then(|| {
+					ExecutionDecision::Defer(
+						Duration::from_secs(*retry_after_seconds),
+						format!(
+							"{}: gas price {} wei on chain {} is above {} gwei",
+							self.name(),
+							gas_price,
+							chain_id,
+							max_gas_price_gwei
+						),
+					)
+				})
+			},
+		}
+	}
+}
+
+/// Parses and validates the `rules` array of a strategy table.
+fn parse_rules(config: &toml::Value) -> Result<Vec<Rule>, String> {
+	let Some(rules) = config.get("rules") else {
+		return Ok(Vec::new());
+	};
+	let rules: Vec<Rule> = rules.clone().try_into().map_err(|e| e.to_string())?;
+	for rule in &rules {
+		rule.validate()?;
+	}
+	Ok(rules)
+}
+
+/// Execution strategy driven by ordered, configured rules.
+///
+/// The first rule that matches skips or defers the order. Orders that pass
+/// every rule are decided by the simple strategy.
+pub struct RulesStrategy {
+	/// Rules in the order they are checked.
+	rules: Vec<Rule>,
+	/// Strategy deciding orders that pass every rule.
+	base: Box<dyn ExecutionStrategy>,
+}
+
+impl RulesStrategy {
+	/// Creates a new RulesStrategy checking rules before the base strategy.
+	pub fn new(rules: Vec<Rule>, base: Box<dyn ExecutionStrategy>) -> Self {
+		Self { rules, base }
+	}
+}
+
+/// Configuration schema for RulesStrategy.
+///
+/// Accepts every simple strategy field plus an optional `rules` array of
+/// tables, each tagged with its rule `type`.
+pub struct RulesStrategySchema;
+
+impl ConfigSchema for RulesStrategySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		SimpleStrategySchema.validate(config)?;
+
+		let schema = Schema::new(
+			// Required fields
+			vec![],
+			// Optional fields
+			vec![Field::new(
+				"rules",
+				FieldType::Array(Box::new(FieldType::Table(Schema::new(
+					vec![Field::new("type", FieldType::String)],
+					vec![],
+				)))),
+			)],
+		);
+		schema.validate(config)?;
+
+		parse_rules(config).map_err(|message| solver_types::ValidationError::InvalidValue {
+			field: "rules".to_string(),
+			message,
+		})?;
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl ExecutionStrategy for RulesStrategy {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(RulesStrategySchema)
+	}
+
+	async fn should_execute(&self, order: &Order, context: &ExecutionContext) -> ExecutionDecision {
+		if !self.rules.is_empty() {
+			let facts = match OrderFacts::from_order(order) {
+				Ok(facts) => facts,
+				Err(e) => {
+					return ExecutionDecision::Skip(format!("rules: unable to parse order: {}", e));
+				},
+			};
+			for rule in &self.rules {
+				if let Some(decision) = rule.evaluate(&facts, context) {
+					tracing::debug!(
+						order_id = %order.id,
+						rule = rule.name(),
+						decision = ?decision,
+						"Rule matched order"
+					);
+					return decision;
+				}
+			}
+		}
+
+		self.base.should_execute(order, context).await
+	}
+}
+
+/// Factory function to create the rules strategy from configuration.
+///
+/// Configuration parameters:
+/// - `rules`: Ordered array of rule tables, each with a `type` (optional)
+/// - Any simple strategy parameter, applied to orders that pass every rule
+pub fn create_strategy(config: &toml::Value) -> Result<Box<dyn ExecutionStrategy>, StrategyError> {
+	RulesStrategySchema
+		.validate(config)
+		.map_err(|e| StrategyError::InvalidConfig(e.to_string()))?;
+
+	let rules = parse_rules(config).map_err(StrategyError::InvalidConfig)?;
+	let base = simple::create_strategy(config)?;
+
+	Ok(Box::new(RulesStrategy::new(rules, base)))
+}
+
+/// Registry for the rules strategy implementation.
+pub struct Registry;
+
+impl solver_types::ImplementationRegistry for Registry {
+	const NAME: &'static str = "rules";
+	type Factory = crate::StrategyFactory;
+
+	fn factory() -> Self::Factory {
+		create_strategy
+	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(RulesStrategySchema)
+	}
+}
+
+impl crate::StrategyRegistry for Registry {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_types::{
+		standards::eip7683::{Eip7683OrderData, GasLimitOverrides, MandateOutput},
+		utils::tests::builders::OrderBuilder,
+		ChainData, ExposureLeg, FeeUrgency, OrderExposure,
+	};
+	use std::collections::HashMap;
+
+	const USER: &str = "0x1234567890123456789012345678901234567890";
+	const INPUT_TOKEN: &str = "0x0000000000000000000000000000000000000064";
+	const OUTPUT_TOKEN: &str = "0x0202020202020202020202020202020202020202";
+
+	fn create_test_order() -> Order {
+		let mut token = [0u8; 32];
+		token[12..32].copy_from_slice(&[0x02; 20]);
+		let order_data = Eip7683OrderData {
+			user: USER.to_string(),
+			nonce: U256::from(1),
+			origin_chain_id: U256::from(1),
+			expires: u32::MAX,
+			fill_deadline: u32::MAX,
+			input_oracle: "0x0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A".to_string(),
+			inputs: vec![[U256::from(100), U256::from(1000)]],
+			order_id: [1u8; 32],
+			gas_limit_overrides: GasLimitOverrides::default(),
+			outputs: vec![MandateOutput {
+				oracle: [0u8; 32],
+				settler: [0u8; 32],
+				chain_id: U256::from(137),
+				token,
+				amount: U256::from(950),
+				recipient: [3u8; 32],
+				call: vec![],
+				context: vec![],
+			}],
+			raw_order_data: None,
+			signature: None,
+			sponsor: None,
+			lock_type: None,
+			input_settler: None,
+		};
+		OrderBuilder::new()
+			.with_data(serde_json::to_value(&order_data).unwrap())
+			.with_input_chain_ids(vec![1])
+			.with_output_chain_ids(vec![137])
+			.build()
+	}
+
+	fn create_test_context() -> ExecutionContext {
+		let mut chain_data = HashMap::new();
+		for (chain_id, gas_price) in [(1u64, "20000000000"), (137, "50000000000")] {
+			chain_data.insert(
+				chain_id,
+				ChainData {
+					chain_id,
+					gas_price: gas_price.to_string(),
+					block_number: 1,
+					timestamp: 0,
+					fees: None,
+				},
+			);
+		}
+		let mut solver_balances = HashMap::new();
+		solver_balances.insert(
+			(137, Some(without_0x_prefix(OUTPUT_TOKEN).to_string())),
+			"10000".to_string(),
+		);
+		ExecutionContext {
+			chain_data,
+			solver_balances,
+			in_flight: Default::default(),
+			order_exposure: Some(OrderExposure {
+				order_id: "order".to_string(),
+				counterparty: without_0x_prefix(USER).to_string(),
+				legs: vec![ExposureLeg {
+					chain_id: 137,
+					token: without_0x_prefix(OUTPUT_TOKEN).to_string(),
+					amount: U256::from(950),
+					value_usd: Decimal::from(950),
+				}],
+			}),
+			profit_margin_pct: Some(Decimal::new(15, 1)),
+			fill_urgency: FeeUrgency::High,
+			quoted: false,
+			// 12:00 UTC
+			timestamp: 43_200,
+		}
+	}
+
+	fn strategy(config: &str) -> Box<dyn ExecutionStrategy> {
+		create_strategy(&toml::from_str(config).unwrap()).unwrap()
+	}
+
+	async fn decide(config: &str) -> ExecutionDecision {
+		strategy(config)
+			.should_execute(&create_test_order(), &create_test_context())
+			.await
+	}
+
+	fn skip_reason(decision: ExecutionDecision) -> String {
+		match decision {
+			ExecutionDecision::Skip(reason) => reason,
+			other => panic!("Expected Skip, got {:?}", other),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_no_rules_falls_through_to_simple() {
+		assert!(matches!(decide("").await, ExecutionDecision::Execute(_)));
+	}
+
+	#[tokio::test]
+	async fn test_user_lists() {
+		let reason = skip_reason(
+			decide(&format!(
+				"[[rules]]\ntype = \"deny_users\"\nusers = [\"{}\"]",
+				USER
+			))
+			.await,
+		);
+		assert!(reason.starts_with("deny_users:"));
+
+		let reason = skip_reason(
+			decide("[[rules]]\ntype = \"allow_users\"\nusers = [\"0x9999999999999999999999999999999999999999\"]")
+				.await,
+		);
+		assert!(reason.starts_with("allow_users:"));
+	}
+
+	#[tokio::test]
+	async fn test_token_and_route_lists() {
+		let allowed = format!(
+			"[[rules]]\ntype = \"allow_tokens\"\ntokens = [{{ chain_id = 1, address = \"{}\" }}, {{ chain_id = 137, address = \"{}\" }}]",
+			INPUT_TOKEN, OUTPUT_TOKEN
+		);
+		assert!(matches!(
+			decide(&allowed).await,
+			ExecutionDecision::Execute(_)
+		));
+
+		let denied = format!(
+			"[[rules]]\ntype = \"deny_tokens\"\ntokens = [{{ chain_id = 137, address = \"{}\" }}]",
+			without_0x_prefix(OUTPUT_TOKEN)
+		);
+		assert!(skip_reason(decide(&denied).await).starts_with("deny_tokens:"));
+
+		let reason = skip_reason(
+			decide("[[rules]]\ntype = \"deny_routes\"\nroutes = [{ from = 1, to = 137 }]").await,
+		);
+		assert!(reason.contains("route 1 -> 137"));
+
+		let reason = skip_reason(
+			decide("[[rules]]\ntype = \"allow_routes\"\nroutes = [{ from = 1, to = 10 }]").await,
+		);
+		assert!(reason.starts_with("allow_routes:"));
+	}
+
+	#[tokio::test]
+	async fn test_amount_value_and_margin() {
+		let reason = skip_reason(
+			decide(&format!(
+				"[[rules]]\ntype = \"output_amount\"\nchain_id = 137\ntoken = \"{}\"\nmax = \"900\"",
+				OUTPUT_TOKEN
+			))
+			.await,
+		);
+		assert!(reason.contains("above the maximum of 900"));
+
+		let reason =
+			skip_reason(decide("[[rules]]\ntype = \"order_value\"\nmin_usd = \"1000\"").await);
+		assert!(reason.starts_with("order_value:"));
+
+		let reason = skip_reason(
+			decide("[[rules]]\ntype = \"min_margin\"\nmin_margin_pct = \"2\"\nroutes = [{ from = 1, to = 137 }]")
+				.await,
+		);
+		assert!(reason.contains("below the minimum of 2%"));
+
+		// Margin rules for other routes do not apply
+		let decision =
+			decide("[[rules]]\ntype = \"min_margin\"\nmin_margin_pct = \"2\"\nroutes = [{ from = 1, to = 10 }]")
+				.await;
+		assert!(matches!(decision, ExecutionDecision::Execute(_)));
+	}
+
+	#[tokio::test]
+	async fn test_time_window_and_gas_ceiling_defer() {
+		match decide("[[rules]]\ntype = \"time_window\"\nstart = \"14:00\"\nend = \"18:00\"").await
+		{
+			ExecutionDecision::Defer(duration, reason) => {
+				assert_eq!(duration, Duration::from_secs(2 * 3600));
+				assert!(reason.starts_with("time_window:"));
+			},
+			other => panic!("Expected Defer, got {:?}", other),
+		}
+		// Windows may wrap around midnight
+		let decision =
+			decide("[[rules]]\ntype = \"time_window\"\nstart = \"22:00\"\nend = \"13:00\"").await;
+		assert!(matches!(decision, ExecutionDecision::Execute(_)));
+
+		match decide("[[rules]]\ntype = \"gas_ceiling\"\nchain_id = 137\nmax_gas_price_gwei = 30")
+			.await
+		{
+			ExecutionDecision::Defer(duration, reason) => {
+				assert_eq!(duration, Duration::from_secs(60));
+				assert!(reason.contains("chain 137"));
+			},
+			other => panic!("Expected Defer, got {:?}", other),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_quoted_orders_pass_price_rules() {
+		let mut context = create_test_context();
+		context.quoted = true;
+		context.profit_margin_pct = None;
+
+		for config in [
+			"[[rules]]\ntype = \"min_margin\"\nmin_margin_pct = \"2\"",
+			"[[rules]]\ntype = \"gas_ceiling\"\nchain_id = 137\nmax_gas_price_gwei = 30",
+		] {
+			let decision = strategy(config)
+				.should_execute(&create_test_order(), &context)
+				.await;
+			assert!(matches!(decision, ExecutionDecision::Execute(_)));
+		}
+	}
+
+	#[tokio::test]
+	async fn test_first_matching_rule_wins() {
+		let config = "
+			[[rules]]
+			type = \"gas_ceiling\"
+			chain_id = 1
+			max_gas_price_gwei = 30
+
+			[[rules]]
+			type = \"deny_routes\"
+			routes = [{ from = 1, to = 137 }]
+
+			[[rules]]
+			type = \"gas_ceiling\"
+			chain_id = 137
+			max_gas_price_gwei = 30
+		";
+		assert!(skip_reason(decide(config).await).starts_with("deny_routes:"));
+	}
+
+	#[test]
+	fn test_schema_rejects_invalid_rules() {
+		let schema = RulesStrategySchema;
+		let parse = |config: &str| toml::from_str::<toml::Value>(config).unwrap();
+
+		assert!(schema
+			.validate(&parse(
+				"max_gas_price_gwei = 50\n[[rules]]\ntype = \"deny_routes\"\nroutes = []"
+			))
+			.is_ok());
+		assert!(schema
+			.validate(&parse("[[rules]]\ntype = \"unknown\""))
+			.is_err());
+		assert!(schema
+			.validate(&parse(
+				"[[rules]]\ntype = \"deny_users\"\nusers = [\"0x1234\"]"
+			))
+			.is_err());
+		assert!(schema
+			.validate(&parse(
+				"[[rules]]\ntype = \"time_window\"\nstart = \"25:00\"\nend = \"01:00\""
+			))
+			.is_err());
+		assert!(schema
+			.validate(&parse("[[rules]]\ntype = \"order_value\""))
+			.is_err());
+		assert!(schema.validate(&parse("max_gas_price_gwei = 0")).is_err());
+	}
+}
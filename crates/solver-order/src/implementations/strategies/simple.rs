@@ -4,20 +4,28 @@
 
 use alloy_primitives::{hex, U256};
 use async_trait::async_trait;
+use rust_decimal::Decimal;
 use solver_types::{
-	with_0x_prefix, ConfigSchema, ExecutionContext, ExecutionDecision, ExecutionParams, Field,
-	FieldType, Order, Schema,
+	with_0x_prefix, ConfigSchema, ExecutionContext, ExecutionDecision, ExecutionParams,
+	ExposureLimits, Field, FieldType, Order, Schema,
 };
+use std::str::FromStr;
 
 use crate::{ExecutionStrategy, StrategyError};
 
-/// Simple execution strategy that considers gas price limits.
+/// Simple execution strategy that considers gas price and exposure limits.
 ///
 /// This strategy executes orders when gas prices are below a configured
-/// maximum, deferring execution when prices are too high.
+/// maximum, deferring execution when prices are too high. On EIP-1559 chains
+/// the price compared is the next base fee plus the tip at the fill urgency.
+/// Orders accepting a live quote are not deferred on gas. Orders that would
+/// push the capital fronted for unclaimed orders past a configured limit are
+/// skipped.
 pub struct SimpleStrategy {
 	/// Maximum gas price the solver is willing to pay.
 	max_gas_price: U256,
+	/// Limits on single order size and in-flight capital.
+	exposure_limits: ExposureLimits,
 }
 
 impl SimpleStrategy {
@@ -25,29 +33,58 @@ impl SimpleStrategy {
 	pub fn new(max_gas_price_gwei: u64) -> Self {
 		Self {
 			max_gas_price: U256::from(max_gas_price_gwei) * U256::from(10u64.pow(9)),
+			exposure_limits: ExposureLimits::default(),
 		}
 	}
+
+	/// Sets the limits on single order size and in-flight capital.
+	pub fn with_exposure_limits(mut self, limits: ExposureLimits) -> Self {
+		self.exposure_limits = limits;
+		self
+	}
 }
 
+/// Priority fee used on chains without EIP-1559 fee estimates (2 gwei).
+const DEFAULT_PRIORITY_FEE_WEI: u64 = 2_000_000_000;
+
+/// Names of the optional USD limits on fronted capital.
+const EXPOSURE_LIMIT_FIELDS: [&str; 4] = [
+	"max_order_value_usd",
+	"max_in_flight_per_chain_usd",
+	"max_in_flight_per_token_usd",
+	"max_in_flight_per_counterparty_usd",
+];
+
 /// Configuration schema for SimpleStrategy.
 ///
 /// This schema validates the configuration for the simple execution strategy,
-/// ensuring the optional maximum gas price parameter is valid if provided.
+/// ensuring the optional maximum gas price and exposure limits are valid if
+/// provided. Exposure limits are positive decimal strings in USD.
 pub struct SimpleStrategySchema;
 
 impl ConfigSchema for SimpleStrategySchema {
 	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let mut optional = vec![Field::new(
+			"max_gas_price_gwei",
+			FieldType::Integer {
+				min: Some(1),
+				max: None,
+			},
+		)];
+		for name in EXPOSURE_LIMIT_FIELDS {
+			optional.push(Field::new(name, FieldType::String).with_validator(|value| {
+				match value.as_str().map(Decimal::from_str) {
+					Some(Ok(limit)) if limit > Decimal::ZERO => Ok(()),
+					_ => Err("must be a positive decimal amount in USD".to_string()),
+				}
+			}));
+		}
+
 		let schema = Schema::new(
 			// Required fields
 			vec![],
 			// Optional fields
-			vec![Field::new(
-				"max_gas_price_gwei",
-				FieldType::Integer {
-					min: Some(1),
-					max: None,
-				},
-			)],
+			optional,
 		);
 
 		schema.validate(config)
@@ -61,17 +98,29 @@ impl ExecutionStrategy for SimpleStrategy {
 	}
 
 	async fn should_execute(&self, order: &Order, context: &ExecutionContext) -> ExecutionDecision {
-		// Find the maximum gas price across all chains in the context
-		let max_gas_price = context
+		// Find the chain with the highest gas price at the fill urgency
+		let priciest_chain = context
 			.chain_data
 			.values()
-			.map(|chain_data| chain_data.gas_price.parse::<U256>().unwrap_or(U256::ZERO))
-			.max()
-			.unwrap_or(U256::ZERO);
-
-		// Check if any chain has gas price above our limit
-		if max_gas_price > self.max_gas_price {
-			return ExecutionDecision::Defer(std::time::Duration::from_secs(60));
+			.map(|chain_data| {
+				let price = chain_data
+					.gas_price_for(context.fill_urgency)
+					.unwrap_or_default();
+				(chain_data, U256::from(price))
+			})
+			.max_by_key(|(_, price)| *price);
+		let max_gas_price = priciest_chain.map(|(_, price)| price).unwrap_or(U256::ZERO);
+
+		// Check if any chain has gas price above our limit, unless the order
+		// accepts a live quote whose terms were priced at quote time
+		if max_gas_price > self.max_gas_price && !context.quoted {
+			return ExecutionDecision::Defer(
+				std::time::Duration::from_secs(60),
+				format!(
+					"Gas price {} wei above limit of {} wei",
+					max_gas_price, self.max_gas_price
+				),
+			);
 		}
 
 		// Parse order using OrderParsable trait for balance checking
@@ -172,12 +221,42 @@ impl ExecutionStrategy for SimpleStrategy {
 			},
 		}
 
-		// Use the maximum gas price for execution (could be made more sophisticated)
-		ExecutionDecision::Execute(ExecutionParams {
-			// TODO: gas_price should be determined with a chain_data
-			gas_price: U256::from(2) * U256::from(10u64.pow(9)),
-			priority_fee: Some(U256::from(2) * U256::from(10u64.pow(9))), // 2 gwei priority
-		})
+		// Check the order against the exposure limits
+		if !self.exposure_limits.is_empty() {
+			let Some(exposure) = &context.order_exposure else {
+				return ExecutionDecision::Skip(
+					"Unable to value order against exposure limits".to_string(),
+				);
+			};
+			if let Err(reason) = self.exposure_limits.check(&context.in_flight, exposure) {
+				tracing::warn!(
+					order_id = %order.id,
+					reason = %reason,
+					"Order exceeds exposure limits"
+				);
+				return ExecutionDecision::Skip(reason);
+			}
+		}
+
+		// Price execution for the most expensive chain involved
+		let params = match priciest_chain.and_then(|(chain_data, _)| chain_data.fees.as_ref()) {
+			Some(estimate) => {
+				let fees = estimate.fees(context.fill_urgency);
+				ExecutionParams {
+					gas_price: U256::from(fees.max_fee_per_gas),
+					priority_fee: Some(U256::from(fees.max_priority_fee_per_gas)),
+				}
+			},
+			None => ExecutionParams {
+				gas_price: if max_gas_price.is_zero() {
+					U256::from(DEFAULT_PRIORITY_FEE_WEI)
+				} else {
+					max_gas_price
+				},
+				priority_fee: Some(U256::from(DEFAULT_PRIORITY_FEE_WEI)),
+			},
+		};
+		ExecutionDecision::Execute(params)
 	}
 }
 
@@ -185,6 +264,10 @@ impl ExecutionStrategy for SimpleStrategy {
 ///
 /// Configuration parameters:
 /// - `max_gas_price_gwei`: Maximum gas price in gwei (default: 100)
+/// - `max_order_value_usd`: Largest USD value a single order may require (optional)
+/// - `max_in_flight_per_chain_usd`: Largest unclaimed USD value per chain (optional)
+/// - `max_in_flight_per_token_usd`: Largest unclaimed USD value per token (optional)
+/// - `max_in_flight_per_counterparty_usd`: Largest unclaimed USD value per user (optional)
 pub fn create_strategy(config: &toml::Value) -> Result<Box<dyn ExecutionStrategy>, StrategyError> {
 	// Validate configuration using the schema
 	let schema = SimpleStrategySchema;
@@ -197,7 +280,9 @@ pub fn create_strategy(config: &toml::Value) -> Result<Box<dyn ExecutionStrategy
 		.and_then(|v| v.as_integer())
 		.unwrap_or(100) as u64;
 
-	Ok(Box::new(SimpleStrategy::new(max_gas_price)))
+	Ok(Box::new(
+		SimpleStrategy::new(max_gas_price).with_exposure_limits(ExposureLimits::from_table(config)),
+	))
 }
 
 /// Registry for the simple strategy implementation.
@@ -210,6 +295,10 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_strategy
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(SimpleStrategySchema)
+	}
 }
 
 impl crate::StrategyRegistry for Registry {}
@@ -221,7 +310,8 @@ mod tests {
 	use solver_types::{
 		standards::eip7683::{Eip7683OrderData, GasLimitOverrides, MandateOutput},
 		utils::tests::builders::OrderBuilder,
-		ChainData, ExecutionContext, Order,
+		ChainData, Eip1559Fees, ExecutionContext, ExposureLeg, FeeEstimate, FeeUrgency, Order,
+		OrderExposure,
 	};
 	use std::collections::HashMap;
 
@@ -261,6 +351,7 @@ mod tests {
 			signature: None,
 			sponsor: None,
 			lock_type: None,
+			input_settler: None,
 		}
 	}
 
@@ -290,6 +381,7 @@ mod tests {
 					gas_price: gas_price.to_string(),
 					block_number: 1000000,
 					timestamp: 1234567890,
+					fees: None,
 				},
 			);
 		}
@@ -305,6 +397,11 @@ mod tests {
 		ExecutionContext {
 			chain_data,
 			solver_balances,
+			in_flight: Default::default(),
+			order_exposure: None,
+			profit_margin_pct: None,
+			fill_urgency: FeeUrgency::High,
+			quoted: false,
 			timestamp: 1234567890,
 		}
 	}
@@ -358,13 +455,78 @@ mod tests {
 		let decision = strategy.should_execute(&order, &context).await;
 
 		match decision {
-			ExecutionDecision::Defer(duration) => {
+			ExecutionDecision::Defer(duration, reason) => {
 				assert_eq!(duration, std::time::Duration::from_secs(60));
+				assert!(reason.contains("Gas price"));
 			},
 			_ => panic!("Expected Defer decision for high gas price"),
 		}
 	}
 
+	#[tokio::test]
+	async fn test_should_execute_quoted_order_despite_gas_price() {
+		let strategy = SimpleStrategy::new(50); // 50 gwei max
+		let order = create_test_order(create_test_order_data());
+
+		// Gas moved above the limit after the quote was given
+		let mut context = create_test_context(
+			vec![(1, "100000000000"), (137, "50000000000")],
+			vec![(
+				137,
+				"0202020202020202020202020202020202020202020202020202020202020202",
+				"1000",
+			)],
+		);
+		context.quoted = true;
+
+		let decision = strategy.should_execute(&order, &context).await;
+		assert!(matches!(decision, ExecutionDecision::Execute(_)));
+	}
+
+	#[tokio::test]
+	async fn test_should_execute_with_eip1559_fees() {
+		let strategy = SimpleStrategy::new(50); // 50 gwei max
+		let order = create_test_order(create_test_order_data());
+		let fees = |tip_gwei: u128| Eip1559Fees {
+			max_fee_per_gas: 60_000_000_000 + tip_gwei * 1_000_000_000,
+			max_priority_fee_per_gas: tip_gwei * 1_000_000_000,
+		};
+		let context_with_high_tip = |tip_gwei: u128| {
+			let mut context = create_test_context(
+				// Legacy prices above the limit are ignored once fees are known
+				vec![(137, "80000000000")],
+				vec![(137, "0202020202020202020202020202020202020202", "200")],
+			);
+			context.chain_data.get_mut(&137).unwrap().fees = Some(FeeEstimate {
+				base_fee_per_gas: 30_000_000_000,
+				low: fees(1),
+				medium: fees(5),
+				high: fees(tip_gwei),
+			});
+			context
+		};
+
+		// 30 gwei base fee + 10 gwei tip at high urgency
+		match strategy
+			.should_execute(&order, &context_with_high_tip(10))
+			.await
+		{
+			ExecutionDecision::Execute(params) => {
+				assert_eq!(params.gas_price, U256::from(70_000_000_000u64));
+				assert_eq!(params.priority_fee, Some(U256::from(10_000_000_000u64)));
+			},
+			_ => panic!("Expected Execute decision"),
+		}
+
+		// 30 gwei base fee + 25 gwei tip exceeds the limit
+		assert!(matches!(
+			strategy
+				.should_execute(&order, &context_with_high_tip(25))
+				.await,
+			ExecutionDecision::Defer(_, _)
+		));
+	}
+
 	#[tokio::test]
 	async fn test_should_execute_insufficient_balance() {
 		let strategy = SimpleStrategy::new(100); // 100 gwei max
@@ -443,7 +605,7 @@ mod tests {
 				println!("Skip reason: {}", reason);
 				panic!("Expected Execute but got Skip: {}", reason);
 			},
-			ExecutionDecision::Defer(duration) => {
+			ExecutionDecision::Defer(duration, _) => {
 				println!("Defer duration: {:?}", duration);
 				panic!("Expected Execute but got Defer");
 			},
@@ -516,7 +678,7 @@ mod tests {
 				println!("Skip reason: {}", reason);
 				panic!("Expected Execute but got Skip: {}", reason);
 			},
-			ExecutionDecision::Defer(duration) => {
+			ExecutionDecision::Defer(duration, _) => {
 				println!("Defer duration: {:?}", duration);
 				panic!("Expected Execute but got Defer");
 			},
@@ -566,6 +728,74 @@ mod tests {
 		}
 	}
 
+	fn create_test_exposure(order_id: &str, value_usd: i64) -> OrderExposure {
+		OrderExposure {
+			order_id: order_id.to_string(),
+			counterparty: "1234567890123456789012345678901234567890".to_string(),
+			legs: vec![ExposureLeg {
+				chain_id: 137,
+				token: "0202020202020202020202020202020202020202".to_string(),
+				amount: U256::from(95),
+				value_usd: Decimal::from(value_usd),
+			}],
+		}
+	}
+
+	#[tokio::test]
+	async fn test_should_execute_exposure_limits() {
+		let strategy = SimpleStrategy::new(100).with_exposure_limits(ExposureLimits {
+			max_in_flight_per_chain_usd: Some(Decimal::from(1000)),
+			..Default::default()
+		});
+		let order = create_test_order(create_test_order_data());
+		let mut context = create_test_context(
+			vec![(1, "50000000000"), (137, "30000000000")],
+			vec![(137, "0202020202020202020202020202020202020202", "200")],
+		);
+
+		// Orders that cannot be valued are skipped while limits are set
+		match strategy.should_execute(&order, &context).await {
+			ExecutionDecision::Skip(reason) => assert!(reason.contains("Unable to value")),
+			_ => panic!("Expected Skip decision for unvalued order"),
+		}
+
+		// Within the chain limit
+		context.in_flight.add(&create_test_exposure("other", 800));
+		context.order_exposure = Some(create_test_exposure("order", 150));
+		assert!(matches!(
+			strategy.should_execute(&order, &context).await,
+			ExecutionDecision::Execute(_)
+		));
+
+		// Past the chain limit
+		context.order_exposure = Some(create_test_exposure("order", 250));
+		match strategy.should_execute(&order, &context).await {
+			ExecutionDecision::Skip(reason) => assert!(reason.contains("chain 137")),
+			_ => panic!("Expected Skip decision for exceeded exposure limit"),
+		}
+	}
+
+	#[test]
+	fn test_config_schema_exposure_limits() {
+		let schema = SimpleStrategySchema;
+
+		let valid: toml::Value = toml::from_str(
+			r#"
+			max_order_value_usd = "5000"
+			max_in_flight_per_counterparty_usd = "2500.50"
+			"#,
+		)
+		.unwrap();
+		assert!(schema.validate(&valid).is_ok());
+
+		let negative: toml::Value = toml::from_str(r#"max_order_value_usd = "-1""#).unwrap();
+		assert!(schema.validate(&negative).is_err());
+
+		let malformed: toml::Value =
+			toml::from_str(r#"max_in_flight_per_chain_usd = "lots""#).unwrap();
+		assert!(schema.validate(&malformed).is_err());
+	}
+
 	#[test]
 	fn test_create_strategy_factory() {
 		// Test with default config
@@ -5,6 +5,7 @@
 //! standards and pluggable execution strategies.
 
 use alloy_primitives::Bytes;
+use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use solver_types::{
 	standards::eip7683::interfaces::StandardOrder, Address, ConfigSchema, ExecutionContext,
@@ -12,6 +13,7 @@ use solver_types::{
 	OrderIdCallback, Transaction,
 };
 use std::collections::HashMap;
+use std::sync::Arc;
 use thiserror::Error;
 
 /// Re-export implementations
@@ -21,6 +23,7 @@ pub mod implementations {
 		pub mod signet;
 	}
 	pub mod strategies {
+		pub mod rules;
 		pub mod simple;
 	}
 }
@@ -203,9 +206,39 @@ pub fn get_all_order_implementations() -> Vec<(&'static str, OrderFactory)> {
 /// Returns a vector of (name, factory) tuples for all available strategy implementations.
 /// This is used by the factory registry to automatically register all implementations.
 pub fn get_all_strategy_implementations() -> Vec<(&'static str, StrategyFactory)> {
-	use implementations::strategies::simple;
+	use implementations::strategies::{rules, simple};
 
-	vec![(simple::Registry::NAME, simple::Registry::factory())]
+	vec![
+		(rules::Registry::NAME, rules::Registry::factory()),
+		(simple::Registry::NAME, simple::Registry::factory()),
+	]
+}
+
+/// Get configuration schemas for all registered order implementations.
+///
+/// Returns a vector of (name, schema) tuples, used to validate configuration
+/// without constructing the implementations.
+pub fn get_all_order_schemas() -> Vec<(&'static str, Box<dyn ConfigSchema>)> {
+	use implementations::standards::_7683;
+	use implementations::standards::signet;
+
+	vec![
+		(_7683::Registry::NAME, _7683::Registry::config_schema()),
+		(signet::Registry::NAME, signet::Registry::config_schema()),
+	]
+}
+
+/// Get configuration schemas for all registered strategy implementations.
+///
+/// Returns a vector of (name, schema) tuples, used to validate configuration
+/// without constructing the implementations.
+pub fn get_all_strategy_schemas() -> Vec<(&'static str, Box<dyn ConfigSchema>)> {
+	use implementations::strategies::{rules, simple};
+
+	vec![
+		(rules::Registry::NAME, rules::Registry::config_schema()),
+		(simple::Registry::NAME, simple::Registry::config_schema()),
+	]
 }
 
 /// Service that manages order processing with multiple implementations and strategies.
@@ -214,9 +247,13 @@ pub fn get_all_strategy_implementations() -> Vec<(&'static str, StrategyFactory)
 /// and applies the configured execution strategy to make filling decisions.
 pub struct OrderService {
 	/// Map of standard names to their implementations.
-	implementations: HashMap<String, Box<dyn OrderInterface>>,
+	///
+	/// Swappable so oracle route changes can be applied on config reload.
+	implementations: ArcSwap<HashMap<String, Box<dyn OrderInterface>>>,
 	/// The execution strategy to use for making filling decisions.
-	strategy: Box<dyn ExecutionStrategy>,
+	///
+	/// Swappable so strategy limits can be changed on config reload.
+	strategy: ArcSwap<Box<dyn ExecutionStrategy>>,
 }
 
 impl OrderService {
@@ -226,18 +263,36 @@ impl OrderService {
 		strategy: Box<dyn ExecutionStrategy>,
 	) -> Self {
 		Self {
-			implementations,
-			strategy,
+			implementations: ArcSwap::from_pointee(implementations),
+			strategy: ArcSwap::from_pointee(strategy),
 		}
 	}
 
+	/// Replaces the execution strategy.
+	///
+	/// Decisions already in progress finish with the previous strategy.
+	pub fn replace_strategy(&self, strategy: Box<dyn ExecutionStrategy>) {
+		self.strategy.store(Arc::new(strategy));
+	}
+
+	/// Replaces the order standard implementations.
+	///
+	/// Operations already in progress finish with the previous implementations.
+	pub fn replace_implementations(
+		&self,
+		implementations: HashMap<String, Box<dyn OrderInterface>>,
+	) {
+		self.implementations.store(Arc::new(implementations));
+	}
+
 	/// Determines whether an order should be executed using the configured strategy.
 	pub async fn should_execute(
 		&self,
 		order: &Order,
 		context: &ExecutionContext,
 	) -> ExecutionDecision {
-		self.strategy.should_execute(order, context).await
+		let strategy = self.strategy.load_full();
+		strategy.should_execute(order, context).await
 	}
 
 	/// Generates a prepare transaction for the given order if needed.
@@ -249,8 +304,8 @@ impl OrderService {
 		order: &Order,
 		params: &ExecutionParams,
 	) -> Result<Option<Transaction>, OrderError> {
-		let implementation = self
-			.implementations
+		let implementations = self.implementations.load_full();
+		let implementation = implementations
 			.get(&order.standard)
 			.ok_or_else(|| OrderError::ValidationFailed("Unknown standard".into()))?;
 
@@ -267,8 +322,8 @@ impl OrderService {
 		order: &Order,
 		params: &ExecutionParams,
 	) -> Result<Transaction, OrderError> {
-		let implementation = self
-			.implementations
+		let implementations = self.implementations.load_full();
+		let implementation = implementations
 			.get(&order.standard)
 			.ok_or_else(|| OrderError::ValidationFailed("Unknown standard".into()))?;
 
@@ -285,8 +340,8 @@ impl OrderService {
 		order: &Order,
 		proof: &FillProof,
 	) -> Result<Transaction, OrderError> {
-		let implementation = self
-			.implementations
+		let implementations = self.implementations.load_full();
+		let implementation = implementations
 			.get(&order.standard)
 			.ok_or_else(|| OrderError::ValidationFailed("Unknown standard".into()))?;
 
@@ -301,7 +356,8 @@ impl OrderService {
 		standard: &str,
 		order_bytes: &Bytes,
 	) -> Result<StandardOrder, OrderError> {
-		let implementation = self.implementations.get(standard).ok_or_else(|| {
+		let implementations = self.implementations.load_full();
+		let implementation = implementations.get(standard).ok_or_else(|| {
 			OrderError::ValidationFailed(format!("Unknown standard: {}", standard))
 		})?;
 
@@ -321,7 +377,8 @@ impl OrderService {
 		order_id_callback: OrderIdCallback,
 		solver_address: &Address,
 	) -> Result<Order, OrderError> {
-		let implementation = self.implementations.get(standard).ok_or_else(|| {
+		let implementations = self.implementations.load_full();
+		let implementation = implementations.get(standard).ok_or_else(|| {
 			OrderError::ValidationFailed(format!("Unknown standard: {}", standard))
 		})?;
 
@@ -0,0 +1,188 @@
+//! Recent price observations used to measure volatility.
+//!
+//! Prices are recorded per asset as they are fetched. The realized volatility
+//! is the standard deviation of log returns per second of elapsed time, so it
+//! does not depend on how regularly prices were sampled.
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use solver_types::current_timestamp;
+use std::collections::{HashMap, VecDeque};
+use std::sync::RwLock;
+
+/// Minimum number of returns before a volatility is reported.
+const MIN_RETURNS: usize = 3;
+
+/// A single price observation.
+#[derive(Debug, Clone, Copy)]
+struct PriceSample {
+	timestamp: u64,
+	price: f64,
+}
+
+/// Recent unit prices of each asset, in USD.
+#[derive(Debug, Default)]
+pub struct PriceHistory {
+	samples: RwLock<HashMap<String, VecDeque<PriceSample>>>,
+}
+
+impl PriceHistory {
+	/// Creates an empty history.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Records a price of an asset, dropping samples older than the lookback.
+	///
+	/// A second price within the same second replaces the first.
+	pub fn record(&self, symbol: &str, price: Decimal, lookback_seconds: u64) {
+		self.record_at(symbol, price, current_timestamp(), lookback_seconds);
+	}
+
+	/// Records a price of an asset observed at a given time.
+	pub(crate) fn record_at(&self, symbol: &str, price: Decimal, now: u64, lookback_seconds: u64) {
+		let Some(price) = price.to_f64().filter(|p| *p > 0.0) else {
+			return;
+		};
+		let mut samples = self.samples.write().unwrap();
+		let series = samples.entry(symbol.to_uppercase()).or_default();
+		if series.back().is_some_and(|last| last.timestamp == now) {
+			series.pop_back();
+		}
+		series.push_back(PriceSample {
+			timestamp: now,
+			price,
+		});
+		let cutoff = now.saturating_sub(lookback_seconds);
+		while series.front().is_some_and(|first| first.timestamp < cutoff) {
+			series.pop_front();
+		}
+	}
+
+	/// Returns the volatility of an asset per square root of a second.
+	///
+	/// Scaling it by the square root of a horizon in seconds gives the
+	/// expected relative price move over that horizon. `None` until enough
+	/// samples were recorded within the lookback.
+	pub fn volatility(&self, symbol: &str, lookback_seconds: u64) -> Option<f64> {
+		self.volatility_at(symbol, current_timestamp(), lookback_seconds)
+	}
+
+	/// Returns the volatility of an asset as of a given time.
+	fn volatility_at(&self, symbol: &str, now: u64, lookback_seconds: u64) -> Option<f64> {
+		let samples = self.samples.read().unwrap();
+		let series = samples.get(&symbol.to_uppercase())?;
+		let cutoff = now.saturating_sub(lookback_seconds);
+
+		let mut squared_returns = 0.0;
+		let mut elapsed = 0u64;
+		let mut returns = 0;
+		let mut previous: Option<PriceSample> = None;
+		for sample in series.iter().filter(|s| s.timestamp >= cutoff) {
+			if let Some(prev) = previous {
+				let log_return = (sample.price / prev.price).ln();
+				squared_returns += log_return * log_return;
+				elapsed += sample.timestamp - prev.timestamp;
+				returns += 1;
+			}
+			previous = Some(*sample);
+		}
+
+		if returns < MIN_RETURNS || elapsed == 0 {
+			return None;
+		}
+		Some((squared_returns / elapsed as f64).sqrt())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const LOOKBACK: u64 = 3_600;
+	const START: u64 = 1_700_000_000;
+
+	fn history_of(samples: &[(u64, &str)]) -> PriceHistory {
+		let history = PriceHistory::new();
+		for (offset, price) in samples {
+			history.record_at("eth", price.parse().unwrap(), START + offset, LOOKBACK);
+		}
+		history
+	}
+
+	#[test]
+	fn test_volatility_is_per_second_of_elapsed_time() {
+		let up = 1.01f64.ln();
+		// Three 1% moves, 10 seconds apart
+		let history = history_of(&[(0, "100"), (10, "101"), (20, "102.01"), (30, "103.0301")]);
+		let volatility = history.volatility_at("ETH", START + 30, LOOKBACK).unwrap();
+		let expected = (3.0 * up * up / 30.0).sqrt();
+		assert!((volatility - expected).abs() < 1e-12);
+
+		// The same moves spread over ten times as long are less volatile per second
+		let slow = history_of(&[(0, "100"), (100, "101"), (200, "102.01"), (300, "103.0301")]);
+		let slow_volatility = slow.volatility_at("ETH", START + 300, LOOKBACK).unwrap();
+		assert!((slow_volatility * 10f64.sqrt() - volatility).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_volatility_of_flat_prices_is_zero() {
+		let history = history_of(&[(0, "100"), (5, "100"), (10, "100"), (15, "100")]);
+		assert_eq!(
+			history.volatility_at("eth", START + 15, LOOKBACK),
+			Some(0.0)
+		);
+	}
+
+	#[test]
+	fn test_too_few_samples_have_no_volatility() {
+		let history = history_of(&[(0, "100"), (10, "101"), (20, "102")]);
+		assert_eq!(history.volatility_at("ETH", START + 20, LOOKBACK), None);
+		assert_eq!(history.volatility_at("BTC", START + 20, LOOKBACK), None);
+	}
+
+	#[test]
+	fn test_price_within_same_second_replaces_previous() {
+		let history = history_of(&[
+			(0, "100"),
+			(10, "101"),
+			(20, "102"),
+			(20, "150"),
+			(30, "103"),
+		]);
+		let series = history.samples.read().unwrap()["ETH"].clone();
+		assert_eq!(series.len(), 4);
+		assert_eq!(series[2].price, 150.0);
+	}
+
+	#[test]
+	fn test_samples_outside_lookback_are_trimmed() {
+		let history = history_of(&[(0, "100"), (10, "200"), (20, "101")]);
+		// Recording past the lookback drops samples older than the cutoff
+		history.record_at(
+			"ETH",
+			"102".parse().unwrap(),
+			START + LOOKBACK + 15,
+			LOOKBACK,
+		);
+		let series = history.samples.read().unwrap()["ETH"].clone();
+		assert_eq!(
+			series
+				.iter()
+				.map(|s| s.timestamp - START)
+				.collect::<Vec<_>>(),
+			vec![20, LOOKBACK + 15]
+		);
+
+		// Samples are also ignored once they fall out of the window at read time
+		let history = history_of(&[(0, "100"), (10, "200"), (20, "100"), (30, "101")]);
+		assert!(history.volatility_at("ETH", START + 30, LOOKBACK).is_some());
+		assert_eq!(history.volatility_at("ETH", START + 30, 25), None);
+	}
+
+	#[test]
+	fn test_non_positive_prices_are_ignored() {
+		let history = history_of(&[(0, "0"), (10, "-5")]);
+		assert!(history.samples.read().unwrap().get("ETH").is_none());
+	}
+}
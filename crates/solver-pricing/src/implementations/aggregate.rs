@@ -0,0 +1,651 @@
+//! Aggregating pricing implementation combining several sources.
+//!
+//! Each rate is requested from every configured source and the median of the
+//! answers is used. Sources do not report when their price was last updated,
+//! so a live answer is taken as current; the freshness window only bounds how
+//! long a source's last answer may stand in after it fails or times out.
+//! Answers deviating from the median beyond a threshold are flagged and left
+//! out, and a rate is refused outright when too few sources agree.
+
+use alloy_primitives::utils::parse_ether;
+use async_trait::async_trait;
+use futures::future::join_all;
+use rust_decimal::Decimal;
+use solver_types::utils::wei_string_to_eth_string;
+use solver_types::{
+	ConfigSchema, ImplementationRegistry, PricingError, PricingFactory, PricingInterface,
+	PricingRegistry, TradingPair, ValidationError,
+};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+/// Default number of sources that must agree on a rate.
+const DEFAULT_MIN_SOURCES: usize = 2;
+
+/// Default largest tolerated deviation from the median, in basis points.
+const DEFAULT_MAX_DEVIATION_BPS: u64 = 200;
+
+/// Default age up to which a source's last answer may stand in, in seconds.
+const DEFAULT_FRESHNESS_SECONDS: u64 = 60;
+
+/// Default time a source is given to answer, in milliseconds.
+const DEFAULT_TIMEOUT_MS: u64 = 5_000;
+
+/// A pricing source with the name it was configured under.
+struct Source {
+	name: String,
+	implementation: Box<dyn PricingInterface>,
+}
+
+/// A rate answered by a source.
+#[derive(Debug, Clone)]
+struct Observation {
+	rate: Decimal,
+	observed_at: u64,
+}
+
+/// Pricing implementation taking the median of several sources.
+pub struct AggregatePricing {
+	/// Sources in configuration order
+	sources: Vec<Source>,
+	/// Number of agreeing fresh answers required
+	min_sources: usize,
+	/// Largest tolerated deviation from the median, in basis points
+	max_deviation_bps: u64,
+	/// Age up to which a stored answer may stand in for a failed source, in seconds
+	freshness_seconds: u64,
+	/// Time each source is given to answer
+	timeout: Duration,
+	/// Last answer by source name and pair
+	observations: RwLock<HashMap<(String, String), Observation>>,
+}
+
+impl AggregatePricing {
+	/// Creates a new AggregatePricing instance with configuration.
+	pub fn new(config: &toml::Value) -> Result<Self, PricingError> {
+		let schema = AggregateConfigSchema;
+		schema.validate(config).map_err(|e| {
+			PricingError::InvalidData(format!("Configuration validation failed: {}", e))
+		})?;
+
+		let factories: HashMap<&str, PricingFactory> =
+			crate::get_all_implementations().into_iter().collect();
+		let mut sources = Vec::new();
+		for (name, implementation, source_config) in source_configs(config)
+			.map_err(|e| PricingError::InvalidData(format!("Invalid source: {}", e)))?
+		{
+			let factory = factories.get(implementation.as_str()).ok_or_else(|| {
+				PricingError::InvalidData(format!(
+					"Unknown implementation '{}' for source '{}'",
+					implementation, name
+				))
+			})?;
+			let source = factory(&source_config).map_err(|e| {
+				PricingError::InvalidData(format!("Failed to create source '{}': {}", name, e))
+			})?;
+			sources.push(Source {
+				name,
+				implementation: source,
+			});
+		}
+
+		let read_u64 = |key: &str, default: u64| {
+			config
+				.get(key)
+				.and_then(|v| v.as_integer())
+				.map(|v| v as u64)
+				.unwrap_or(default)
+		};
+		let min_sources = read_u64("min_sources", DEFAULT_MIN_SOURCES as u64) as usize;
+
+		debug!(
+			"Aggregate pricing initialized - {} sources, {} required",
+			sources.len(),
+			min_sources
+		);
+
+		Ok(Self {
+			sources,
+			min_sources,
+			max_deviation_bps: read_u64("max_deviation_bps", DEFAULT_MAX_DEVIATION_BPS),
+			freshness_seconds: read_u64("freshness_seconds", DEFAULT_FRESHNESS_SECONDS),
+			timeout: Duration::from_millis(read_u64("timeout_ms", DEFAULT_TIMEOUT_MS)),
+			observations: RwLock::new(HashMap::new()),
+		})
+	}
+
+	/// Asks a source for the rate of a pair.
+	async fn query_source(&self, source: &Source, pair: &TradingPair) -> Result<Decimal, String> {
+		let answer = tokio::time::timeout(
+			self.timeout,
+			source
+				.implementation
+				.convert_asset(&pair.base, &pair.quote, "1"),
+		)
+		.await
+		.map_err(|_| format!("timed out after {}ms", self.timeout.as_millis()))?
+		.map_err(|e| e.to_string())?;
+
+		let rate: Decimal = answer
+			.parse()
+			.map_err(|e| format!("invalid rate {}: {}", answer, e))?;
+		if rate <= Decimal::ZERO {
+			return Err(format!("non-positive rate {}", rate));
+		}
+		Ok(rate)
+	}
+
+	/// Collects a rate for a pair from every source that answers, or whose
+	/// last answer is still within the freshness window.
+	async fn fresh_rates(&self, pair: &TradingPair) -> Vec<(String, Decimal)> {
+		let pair_key = pair.to_string();
+		let answers = join_all(
+			self.sources
+				.iter()
+				.map(|source| self.query_source(source, pair)),
+		)
+		.await;
+
+		let now = now_seconds();
+		let mut observations = self.observations.write().await;
+		let mut rates = Vec::new();
+		for (source, answer) in self.sources.iter().zip(answers) {
+			let key = (source.name.clone(), pair_key.clone());
+			match answer {
+				Ok(rate) => {
+					observations.insert(
+						key,
+						Observation {
+							rate,
+							observed_at: now,
+						},
+					);
+					rates.push((source.name.clone(), rate));
+				},
+				Err(e) => match observations.get(&key) {
+					Some(last)
+						if now.saturating_sub(last.observed_at) <= self.freshness_seconds =>
+					{
+						debug!(
+							"Source '{}' failed for {} ({}), using answer from {}s ago",
+							source.name,
+							pair,
+							e,
+							now.saturating_sub(last.observed_at)
+						);
+						rates.push((source.name.clone(), last.rate));
+					},
+					_ => {
+						warn!(
+							"Source '{}' has no fresh price for {}: {}",
+							source.name, pair, e
+						);
+					},
+				},
+			}
+		}
+		rates
+	}
+
+	/// Returns the median rate of a pair across sources.
+	async fn get_pair_price(&self, pair: &TradingPair) -> Result<Decimal, PricingError> {
+		if pair.base.eq_ignore_ascii_case(&pair.quote) {
+			return Ok(Decimal::ONE);
+		}
+
+		let rates = self.fresh_rates(pair).await;
+		if rates.len() < self.min_sources {
+			return Err(PricingError::PriceNotAvailable(format!(
+				"{}: {} of {} sources returned a fresh price, {} required",
+				pair,
+				rates.len(),
+				self.sources.len(),
+				self.min_sources
+			)));
+		}
+
+		let first_median = median(rates.iter().map(|(_, rate)| *rate).collect());
+		let limit = Decimal::from(self.max_deviation_bps);
+		let bps = Decimal::from(10_000);
+		let (agreeing, outliers): (Vec<_>, Vec<_>) = rates
+			.into_iter()
+			.partition(|(_, rate)| (*rate - first_median).abs() * bps / first_median <= limit);
+
+		for (name, rate) in &outliers {
+			warn!(
+				"Source '{}' price {} for {} deviates more than {} bps from median {}",
+				name, rate, pair, self.max_deviation_bps, first_median
+			);
+		}
+		if agreeing.len() < self.min_sources {
+			let answers = agreeing
+				.iter()
+				.chain(outliers.iter())
+				.map(|(name, rate)| format!("{}={}", name, rate))
+				.collect::<Vec<_>>()
+				.join(", ");
+			return Err(PricingError::InvalidData(format!(
+				"{}: sources disagree by more than {} bps ({})",
+				pair, self.max_deviation_bps, answers
+			)));
+		}
+
+		let rate = median(agreeing.iter().map(|(_, rate)| *rate).collect());
+		debug!(
+			"Aggregated {} from {} sources: {}",
+			pair,
+			agreeing.len(),
+			rate
+		);
+		Ok(rate)
+	}
+}
+
+#[async_trait]
+impl PricingInterface for AggregatePricing {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(AggregateConfigSchema)
+	}
+
+	async fn get_supported_pairs(&self) -> Vec<TradingPair> {
+		let mut seen = HashSet::new();
+		let mut pairs = Vec::new();
+		for source in &self.sources {
+			for pair in source.implementation.get_supported_pairs().await {
+				if seen.insert(pair.to_string()) {
+					pairs.push(pair);
+				}
+			}
+		}
+		pairs
+	}
+
+	async fn convert_asset(
+		&self,
+		from_asset: &str,
+		to_asset: &str,
+		amount: &str,
+	) -> Result<String, PricingError> {
+		if from_asset.eq_ignore_ascii_case(to_asset) {
+			return Ok(amount.to_string());
+		}
+
+		let amount_decimal: Decimal = amount
+			.parse()
+			.map_err(|e| PricingError::InvalidData(format!("Invalid amount: {}", e)))?;
+
+		let pair = TradingPair::new(&from_asset.to_uppercase(), &to_asset.to_uppercase());
+		let rate = self.get_pair_price(&pair).await?;
+
+		let result = amount_decimal
+			.checked_mul(rate)
+			.ok_or_else(|| PricingError::InvalidData(format!("Overflow converting {}", pair)))?;
+		Ok(result.to_string())
+	}
+
+	async fn wei_to_currency(
+		&self,
+		wei_amount: &str,
+		currency: &str,
+	) -> Result<String, PricingError> {
+		let eth_amount_str =
+			wei_string_to_eth_string(wei_amount).map_err(PricingError::InvalidData)?;
+		let eth_amount: Decimal = eth_amount_str
+			.parse()
+			.map_err(|e| PricingError::InvalidData(format!("Invalid ETH amount: {}", e)))?;
+
+		let rate = self
+			.get_pair_price(&TradingPair::new("ETH", &currency.to_uppercase()))
+			.await?;
+
+		Ok((eth_amount * rate).round_dp(2).to_string())
+	}
+
+	async fn currency_to_wei(
+		&self,
+		currency_amount: &str,
+		currency: &str,
+	) -> Result<String, PricingError> {
+		let currency_amount_decimal: Decimal = currency_amount
+			.parse()
+			.map_err(|e| PricingError::InvalidData(format!("Invalid currency amount: {}", e)))?;
+
+		let rate = self
+			.get_pair_price(&TradingPair::new("ETH", &currency.to_uppercase()))
+			.await?;
+
+		let eth_amount = currency_amount_decimal / rate;
+		let wei_amount = parse_ether(&eth_amount.round_dp(18).to_string()).map_err(|e| {
+			PricingError::InvalidData(format!("Failed to convert ETH to wei: {}", e))
+		})?;
+
+		Ok(wei_amount.to_string())
+	}
+}
+
+/// Returns the current Unix time in seconds.
+fn now_seconds() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.unwrap()
+		.as_secs()
+}
+
+/// Returns the median of a non-empty list of rates.
+fn median(mut rates: Vec<Decimal>) -> Decimal {
+	rates.sort();
+	let middle = rates.len() / 2;
+	if rates.len() % 2 == 0 {
+		(rates[middle - 1] + rates[middle]) / Decimal::TWO
+	} else {
+		rates[middle]
+	}
+}
+
+/// Reads the `sources` table as (name, implementation, config) entries.
+///
+/// A source is created by the implementation of the same name unless it sets
+/// `implementation`, which allows one implementation to be used twice.
+fn source_configs(
+	config: &toml::Value,
+) -> Result<Vec<(String, String, toml::Value)>, ValidationError> {
+	let table = config
+		.get("sources")
+		.ok_or_else(|| ValidationError::MissingField("sources".to_string()))?
+		.as_table()
+		.ok_or_else(|| ValidationError::TypeMismatch {
+			field: "sources".to_string(),
+			expected: "table".to_string(),
+			actual: format!("{:?}", config.get("sources")),
+		})?;
+
+	let mut sources = Vec::new();
+	for (name, source) in table {
+		let mut source = source
+			.as_table()
+			.ok_or_else(|| ValidationError::TypeMismatch {
+				field: format!("sources.{}", name),
+				expected: "table".to_string(),
+				actual: format!("{:?}", source),
+			})?
+			.clone();
+		let implementation = match source.remove("implementation") {
+			None => name.clone(),
+			Some(value) => value
+				.as_str()
+				.ok_or_else(|| ValidationError::TypeMismatch {
+					field: format!("sources.{}.implementation", name),
+					expected: "string".to_string(),
+					actual: format!("{:?}", value),
+				})?
+				.to_string(),
+		};
+		sources.push((name.clone(), implementation, toml::Value::Table(source)));
+	}
+	Ok(sources)
+}
+
+/// Configuration schema for aggregate pricing implementation.
+pub struct AggregateConfigSchema;
+
+impl ConfigSchema for AggregateConfigSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), ValidationError> {
+		for key in [
+			"min_sources",
+			"max_deviation_bps",
+			"freshness_seconds",
+			"timeout_ms",
+		] {
+			if let Some(value) = config.get(key) {
+				match value.as_integer() {
+					Some(v) if v >= 0 => {},
+					_ => {
+						return Err(ValidationError::InvalidValue {
+							field: key.to_string(),
+							message: "must be a non-negative integer".to_string(),
+						})
+					},
+				}
+			}
+		}
+
+		let sources = source_configs(config)?;
+		let min_sources = config
+			.get("min_sources")
+			.and_then(|v| v.as_integer())
+			.map(|v| v as usize)
+			.unwrap_or(DEFAULT_MIN_SOURCES);
+		if min_sources == 0 || min_sources > sources.len() {
+			return Err(ValidationError::InvalidValue {
+				field: "min_sources".to_string(),
+				message: format!(
+					"must be between 1 and the number of sources ({})",
+					sources.len()
+				),
+			});
+		}
+
+		let schemas: HashMap<&str, Box<dyn ConfigSchema>> =
+			crate::get_all_schemas().into_iter().collect();
+		for (name, implementation, source_config) in &sources {
+			if implementation == AggregatePricingRegistry::NAME {
+				return Err(ValidationError::InvalidValue {
+					field: format!("sources.{}", name),
+					message: "an aggregate cannot be a source of itself".to_string(),
+				});
+			}
+			let schema = schemas.get(implementation.as_str()).ok_or_else(|| {
+				ValidationError::InvalidValue {
+					field: format!("sources.{}", name),
+					message: format!("unknown pricing implementation '{}'", implementation),
+				}
+			})?;
+			schema
+				.validate(source_config)
+				.map_err(|e| ValidationError::InvalidValue {
+					field: format!("sources.{}", name),
+					message: e.to_string(),
+				})?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Registry for aggregate pricing implementation.
+pub struct AggregatePricingRegistry;
+
+impl ImplementationRegistry for AggregatePricingRegistry {
+	const NAME: &'static str = "aggregate";
+	type Factory = PricingFactory;
+
+	fn factory() -> Self::Factory {
+		create_aggregate_pricing
+	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(AggregateConfigSchema)
+	}
+}
+
+impl PricingRegistry for AggregatePricingRegistry {}
+
+/// Factory function for creating AggregatePricing instances.
+pub fn create_aggregate_pricing(
+	config: &toml::Value,
+) -> Result<Box<dyn PricingInterface>, PricingError> {
+	Ok(Box::new(AggregatePricing::new(config)?))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::str::FromStr;
+	use std::sync::{Arc, Mutex};
+
+	/// Source answering a settable rate, or failing while it is unset.
+	struct StubSource {
+		rate: Arc<Mutex<Option<Decimal>>>,
+	}
+
+	#[async_trait]
+	impl PricingInterface for StubSource {
+		fn config_schema(&self) -> Box<dyn ConfigSchema> {
+			Box::new(AggregateConfigSchema)
+		}
+
+		async fn get_supported_pairs(&self) -> Vec<TradingPair> {
+			vec![TradingPair::new("ETH", "USD")]
+		}
+
+		async fn convert_asset(
+			&self,
+			_from_asset: &str,
+			_to_asset: &str,
+			amount: &str,
+		) -> Result<String, PricingError> {
+			let rate = self
+				.rate
+				.lock()
+				.unwrap()
+				.ok_or_else(|| PricingError::Network("source down".to_string()))?;
+			let amount = Decimal::from_str(amount).unwrap();
+			Ok((amount * rate).to_string())
+		}
+
+		async fn wei_to_currency(
+			&self,
+			_wei_amount: &str,
+			_currency: &str,
+		) -> Result<String, PricingError> {
+			Err(PricingError::PriceNotAvailable(
+				"stub source only converts assets".to_string(),
+			))
+		}
+
+		async fn currency_to_wei(
+			&self,
+			_currency_amount: &str,
+			_currency: &str,
+		) -> Result<String, PricingError> {
+			Err(PricingError::PriceNotAvailable(
+				"stub source only converts assets".to_string(),
+			))
+		}
+	}
+
+	fn dec(value: &str) -> Decimal {
+		Decimal::from_str(value).unwrap()
+	}
+
+	/// Builds an aggregate over stub sources, returning handles to their rates.
+	fn aggregate(
+		rates: &[&str],
+		min_sources: usize,
+	) -> (AggregatePricing, Vec<Arc<Mutex<Option<Decimal>>>>) {
+		let handles: Vec<_> = rates
+			.iter()
+			.map(|rate| Arc::new(Mutex::new(Some(dec(rate)))))
+			.collect();
+		let sources = handles
+			.iter()
+			.enumerate()
+			.map(|(i, rate)| Source {
+				name: format!("source{}", i),
+				implementation: Box::new(StubSource { rate: rate.clone() }),
+			})
+			.collect();
+		let pricing = AggregatePricing {
+			sources,
+			min_sources,
+			max_deviation_bps: 200,
+			freshness_seconds: 60,
+			timeout: Duration::from_millis(1_000),
+			observations: RwLock::new(HashMap::new()),
+		};
+		(pricing, handles)
+	}
+
+	fn eth_usd() -> TradingPair {
+		TradingPair::new("ETH", "USD")
+	}
+
+	#[test]
+	fn test_median_odd_and_even_counts() {
+		assert_eq!(median(vec![dec("3"), dec("1"), dec("2")]), dec("2"));
+		assert_eq!(median(vec![dec("5")]), dec("5"));
+		assert_eq!(
+			median(vec![dec("4"), dec("1"), dec("3"), dec("2")]),
+			dec("2.5")
+		);
+		assert_eq!(median(vec![dec("10"), dec("11")]), dec("10.5"));
+	}
+
+	#[tokio::test]
+	async fn test_outlier_is_left_out() {
+		let (pricing, _) = aggregate(&["2000", "2010", "2500"], 2);
+
+		// 2500 deviates 24% from the median 2010 and is dropped
+		let rate = pricing.get_pair_price(&eth_usd()).await.unwrap();
+		assert_eq!(rate, dec("2005"));
+	}
+
+	#[tokio::test]
+	async fn test_disagreeing_sources_are_refused() {
+		let (pricing, _) = aggregate(&["2000", "2500", "3000"], 2);
+
+		let result = pricing.get_pair_price(&eth_usd()).await;
+		assert!(matches!(result, Err(PricingError::InvalidData(_))));
+	}
+
+	#[tokio::test]
+	async fn test_too_few_sources_is_an_error() {
+		let (pricing, handles) = aggregate(&["2000", "2001", "2002"], 3);
+		*handles[0].lock().unwrap() = None;
+		*handles[1].lock().unwrap() = None;
+
+		let result = pricing.get_pair_price(&eth_usd()).await;
+		assert!(matches!(result, Err(PricingError::PriceNotAvailable(_))));
+	}
+
+	#[tokio::test]
+	async fn test_cached_answer_stands_in_only_while_fresh() {
+		let (pricing, handles) = aggregate(&["2000", "2020"], 2);
+		assert_eq!(
+			pricing.get_pair_price(&eth_usd()).await.unwrap(),
+			dec("2010")
+		);
+
+		// The failing source's last answer is reused within the window
+		*handles[1].lock().unwrap() = None;
+		*handles[0].lock().unwrap() = Some(dec("2004"));
+		assert_eq!(
+			pricing.get_pair_price(&eth_usd()).await.unwrap(),
+			dec("2012")
+		);
+
+		// Once it is older than freshness_seconds it no longer counts
+		let key = ("source1".to_string(), eth_usd().to_string());
+		pricing
+			.observations
+			.write()
+			.await
+			.get_mut(&key)
+			.unwrap()
+			.observed_at -= 61;
+		let result = pricing.get_pair_price(&eth_usd()).await;
+		assert!(matches!(result, Err(PricingError::PriceNotAvailable(_))));
+	}
+
+	#[tokio::test]
+	async fn test_same_asset_needs_no_sources() {
+		let (pricing, _) = aggregate(&["2000"], 1);
+		let rate = pricing
+			.get_pair_price(&TradingPair::new("ETH", "eth"))
+			.await
+			.unwrap();
+		assert_eq!(rate, Decimal::ONE);
+	}
+}
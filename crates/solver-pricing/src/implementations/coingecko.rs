@@ -633,6 +633,10 @@ impl ImplementationRegistry for CoinGeckoPricingRegistry {
 	fn factory() -> Self::Factory {
 		create_coingecko_pricing
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(CoinGeckoConfigSchema)
+	}
 }
 
 impl PricingRegistry for CoinGeckoPricingRegistry {}
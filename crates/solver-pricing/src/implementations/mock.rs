@@ -256,6 +256,10 @@ impl ImplementationRegistry for MockPricingRegistry {
 	fn factory() -> Self::Factory {
 		create_mock_pricing
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(MockPricingSchema)
+	}
 }
 
 impl PricingRegistry for MockPricingRegistry {}
@@ -0,0 +1,896 @@
+//! On-chain price feed implementation for production use.
+//!
+//! This implementation reads Chainlink-style AggregatorV3 feeds through
+//! `eth_call`. Each configured symbol maps to a SYMBOL/USD feed on one chain.
+//! Answers are normalised by the feed's decimals and rejected when they are
+//! older than the feed's heartbeat allows. Prices between two non-USD assets
+//! are derived through USD.
+
+use alloy_primitives::{utils::parse_ether, Address, Bytes, I256};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types::TransactionRequest;
+use alloy_sol_types::{sol, SolCall};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use solver_types::utils::wei_string_to_eth_string;
+use solver_types::{
+	ConfigSchema, ImplementationRegistry, PricingError, PricingFactory, PricingInterface,
+	PricingRegistry, TradingPair, ValidationError,
+};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::RwLock;
+use tracing::debug;
+
+sol! {
+	/// Chainlink AggregatorV3 read interface.
+	interface AggregatorV3Interface {
+		function decimals() external view returns (uint8);
+		function latestRoundData() external view returns (
+			uint80 roundId,
+			int256 answer,
+			uint256 startedAt,
+			uint256 updatedAt,
+			uint80 answeredInRound
+		);
+	}
+}
+
+/// Default staleness cap applied to every feed, in seconds.
+const DEFAULT_MAX_STALENESS_SECONDS: u64 = 86_400;
+
+/// Default tolerance on top of a feed's heartbeat, in seconds.
+const DEFAULT_HEARTBEAT_GRACE_SECONDS: u64 = 300;
+
+/// Default duration a round is reused before the feed is read again, in seconds.
+const DEFAULT_CACHE_DURATION_SECONDS: u64 = 30;
+
+/// A configured SYMBOL/USD feed.
+#[derive(Debug, Clone)]
+struct FeedConfig {
+	/// Chain the aggregator is deployed on.
+	chain_id: u64,
+	/// Aggregator address.
+	address: Address,
+	/// Expected maximum interval between updates, in seconds.
+	heartbeat_seconds: Option<u64>,
+	/// Answer decimals; read from the aggregator when not configured.
+	decimals: Option<u8>,
+}
+
+/// A normalised feed answer.
+#[derive(Debug, Clone)]
+struct FeedPrice {
+	/// USD price of one unit of the asset.
+	price: Decimal,
+	/// Timestamp the round was updated on-chain.
+	updated_at: u64,
+	/// Local timestamp the round was read.
+	fetched_at: u64,
+}
+
+/// On-chain pricing implementation reading AggregatorV3 feeds.
+pub struct OnchainFeedsPricing {
+	/// HTTP providers by chain ID
+	providers: HashMap<u64, RootProvider>,
+	/// Feeds by uppercase asset symbol
+	feeds: HashMap<String, FeedConfig>,
+	/// Upper bound on the age of any answer, in seconds
+	max_staleness_seconds: u64,
+	/// Tolerance added to each feed's heartbeat, in seconds
+	heartbeat_grace_seconds: u64,
+	/// Duration a read round is reused, in seconds
+	cache_duration: u64,
+	/// Decimals read from aggregators by symbol
+	decimals_cache: RwLock<HashMap<String, u8>>,
+	/// Latest rounds by symbol
+	price_cache: RwLock<HashMap<String, FeedPrice>>,
+}
+
+impl OnchainFeedsPricing {
+	/// Creates a new OnchainFeedsPricing instance with configuration.
+	pub fn new(config: &toml::Value) -> Result<Self, PricingError> {
+		let schema = OnchainFeedsConfigSchema;
+		schema.validate(config).map_err(|e| {
+			PricingError::InvalidData(format!("Configuration validation failed: {}", e))
+		})?;
+
+		let feeds = parse_feeds(config)
+			.map_err(|e| PricingError::InvalidData(format!("Invalid feed: {}", e)))?;
+
+		let mut providers = HashMap::new();
+		for (chain_id, url) in parse_rpc_urls(config)
+			.map_err(|e| PricingError::InvalidData(format!("Invalid RPC URL: {}", e)))?
+		{
+			let url = url.parse().map_err(|e| {
+				PricingError::InvalidData(format!("Invalid RPC URL for chain {}: {}", chain_id, e))
+			})?;
+			providers.insert(chain_id, RootProvider::new_http(url));
+		}
+
+		let read_u64 = |key: &str, default: u64| {
+			config
+				.get(key)
+				.and_then(|v| v.as_integer())
+				.map(|v| v as u64)
+				.unwrap_or(default)
+		};
+
+		debug!(
+			"On-chain feed pricing initialized - {} feeds on {} chains",
+			feeds.len(),
+			providers.len()
+		);
+
+		Ok(Self {
+			providers,
+			feeds,
+			max_staleness_seconds: read_u64("max_staleness_seconds", DEFAULT_MAX_STALENESS_SECONDS),
+			heartbeat_grace_seconds: read_u64(
+				"heartbeat_grace_seconds",
+				DEFAULT_HEARTBEAT_GRACE_SECONDS,
+			),
+			cache_duration: read_u64("cache_duration_seconds", DEFAULT_CACHE_DURATION_SECONDS),
+			decimals_cache: RwLock::new(HashMap::new()),
+			price_cache: RwLock::new(HashMap::new()),
+		})
+	}
+
+	/// Check if currency is USD
+	fn is_usd(&self, currency: &str) -> bool {
+		currency.eq_ignore_ascii_case("USD")
+	}
+
+	/// Returns the oldest age an answer of a feed may have.
+	fn staleness_limit(&self, feed: &FeedConfig) -> u64 {
+		feed.heartbeat_seconds
+			.map(|heartbeat| heartbeat.saturating_add(self.heartbeat_grace_seconds))
+			.unwrap_or(self.max_staleness_seconds)
+			.min(self.max_staleness_seconds)
+	}
+
+	/// Executes a read-only call against an aggregator.
+	async fn call(&self, feed: &FeedConfig, data: Vec<u8>) -> Result<Bytes, PricingError> {
+		let provider = self.providers.get(&feed.chain_id).ok_or_else(|| {
+			PricingError::Network(format!("No RPC URL configured for chain {}", feed.chain_id))
+		})?;
+		let request = TransactionRequest::default()
+			.to(feed.address)
+			.input(Bytes::from(data).into());
+		provider.call(request).await.map_err(|e| {
+			PricingError::Network(format!(
+				"Failed to call feed {} on chain {}: {}",
+				feed.address, feed.chain_id, e
+			))
+		})
+	}
+
+	/// Returns the answer decimals of a feed.
+	async fn feed_decimals(&self, symbol: &str, feed: &FeedConfig) -> Result<u8, PricingError> {
+		if let Some(decimals) = feed.decimals {
+			return Ok(decimals);
+		}
+		if let Some(decimals) = self.decimals_cache.read().await.get(symbol) {
+			return Ok(*decimals);
+		}
+
+		let result = self
+			.call(feed, AggregatorV3Interface::decimalsCall {}.abi_encode())
+			.await?;
+		let decimals =
+			AggregatorV3Interface::decimalsCall::abi_decode_returns(&result).map_err(|e| {
+				PricingError::InvalidData(format!(
+					"Failed to decode {} feed decimals: {}",
+					symbol, e
+				))
+			})?;
+
+		self.decimals_cache
+			.write()
+			.await
+			.insert(symbol.to_string(), decimals);
+		Ok(decimals)
+	}
+
+	/// Reads the latest round of a feed and normalises its answer.
+	async fn read_feed(&self, symbol: &str, feed: &FeedConfig) -> Result<FeedPrice, PricingError> {
+		let decimals = self.feed_decimals(symbol, feed).await?;
+		let result = self
+			.call(
+				feed,
+				AggregatorV3Interface::latestRoundDataCall {}.abi_encode(),
+			)
+			.await?;
+		let round = AggregatorV3Interface::latestRoundDataCall::abi_decode_returns(&result)
+			.map_err(|e| {
+				PricingError::InvalidData(format!("Failed to decode {} feed round: {}", symbol, e))
+			})?;
+
+		if round.updatedAt.is_zero() {
+			return Err(PricingError::PriceNotAvailable(format!(
+				"{}/USD feed round {} is incomplete",
+				symbol, round.roundId
+			)));
+		}
+		if round.answeredInRound < round.roundId {
+			return Err(PricingError::PriceNotAvailable(format!(
+				"{}/USD feed answer is carried over from round {}",
+				symbol, round.answeredInRound
+			)));
+		}
+		let price = normalise_answer(symbol, round.answer, decimals)?;
+		let updated_at = u64::try_from(round.updatedAt).unwrap_or(u64::MAX);
+
+		Ok(FeedPrice {
+			price,
+			updated_at,
+			fetched_at: now_seconds(),
+		})
+	}
+
+	/// Checks that a round is recent enough for its feed.
+	fn ensure_fresh(
+		&self,
+		symbol: &str,
+		feed: &FeedConfig,
+		price: &FeedPrice,
+	) -> Result<(), PricingError> {
+		let age = now_seconds().saturating_sub(price.updated_at);
+		let limit = self.staleness_limit(feed);
+		if age > limit {
+			return Err(PricingError::PriceNotAvailable(format!(
+				"{}/USD feed is stale: last updated {}s ago, limit is {}s",
+				symbol, age, limit
+			)));
+		}
+		Ok(())
+	}
+
+	/// Returns the USD price of one unit of an asset.
+	async fn get_usd_price(&self, symbol: &str) -> Result<Decimal, PricingError> {
+		if self.is_usd(symbol) {
+			return Ok(Decimal::ONE);
+		}
+
+		let symbol = symbol.to_uppercase();
+		let feed = self
+			.feeds
+			.get(&symbol)
+			.ok_or_else(|| PricingError::PriceNotAvailable(format!("{}/USD", symbol)))?;
+
+		let cached = self.price_cache.read().await.get(&symbol).cloned();
+		let price = match cached {
+			Some(entry) if now_seconds().saturating_sub(entry.fetched_at) < self.cache_duration => {
+				debug!(
+					"Using cached feed price for {}/USD: ${}",
+					symbol, entry.price
+				);
+				entry
+			},
+			_ => {
+				let entry = self.read_feed(&symbol, feed).await?;
+				debug!(
+					"Read {}/USD feed on chain {}: ${} (updated at {})",
+					symbol, feed.chain_id, entry.price, entry.updated_at
+				);
+				self.price_cache
+					.write()
+					.await
+					.insert(symbol.clone(), entry.clone());
+				entry
+			},
+		};
+
+		// Checked on every use so a cached round cannot outlive its heartbeat
+		self.ensure_fresh(&symbol, feed, &price)?;
+		Ok(price.price)
+	}
+
+	/// Get price for a trading pair, crossing through USD when neither side is USD
+	async fn get_pair_price(&self, pair: &TradingPair) -> Result<Decimal, PricingError> {
+		if pair.base.eq_ignore_ascii_case(&pair.quote) {
+			return Ok(Decimal::ONE);
+		}
+
+		let base_price = self.get_usd_price(&pair.base).await?;
+		let quote_price = self.get_usd_price(&pair.quote).await?;
+		if quote_price.is_zero() {
+			return Err(PricingError::InvalidData(format!(
+				"{} price is zero",
+				pair.quote
+			)));
+		}
+		base_price
+			.checked_div(quote_price)
+			.ok_or_else(|| PricingError::InvalidData(format!("Cannot derive {} rate", pair)))
+	}
+}
+
+#[async_trait]
+impl PricingInterface for OnchainFeedsPricing {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(OnchainFeedsConfigSchema)
+	}
+
+	async fn get_supported_pairs(&self) -> Vec<TradingPair> {
+		self.feeds
+			.keys()
+			.map(|symbol| TradingPair::new(symbol, "USD"))
+			.collect()
+	}
+
+	async fn convert_asset(
+		&self,
+		from_asset: &str,
+		to_asset: &str,
+		amount: &str,
+	) -> Result<String, PricingError> {
+		if from_asset.eq_ignore_ascii_case(to_asset) {
+			return Ok(amount.to_string());
+		}
+
+		let amount_decimal: Decimal = amount
+			.parse()
+			.map_err(|e| PricingError::InvalidData(format!("Invalid amount: {}", e)))?;
+
+		let pair = TradingPair::new(&from_asset.to_uppercase(), &to_asset.to_uppercase());
+		let rate = self.get_pair_price(&pair).await?;
+
+		let result = amount_decimal
+			.checked_mul(rate)
+			.ok_or_else(|| PricingError::InvalidData(format!("Overflow converting {}", pair)))?;
+		Ok(result.to_string())
+	}
+
+	async fn wei_to_currency(
+		&self,
+		wei_amount: &str,
+		currency: &str,
+	) -> Result<String, PricingError> {
+		let eth_amount_str =
+			wei_string_to_eth_string(wei_amount).map_err(PricingError::InvalidData)?;
+		let eth_amount: Decimal = eth_amount_str
+			.parse()
+			.map_err(|e| PricingError::InvalidData(format!("Invalid ETH amount: {}", e)))?;
+
+		let rate = self
+			.get_pair_price(&TradingPair::new("ETH", &currency.to_uppercase()))
+			.await?;
+
+		let result = eth_amount * rate;
+		debug!(
+			"Converted gas cost: {} wei = {} ETH = {} {} (rate: {})",
+			wei_amount,
+			eth_amount_str,
+			result.round_dp(2),
+			currency,
+			rate
+		);
+		Ok(result.round_dp(2).to_string())
+	}
+
+	async fn currency_to_wei(
+		&self,
+		currency_amount: &str,
+		currency: &str,
+	) -> Result<String, PricingError> {
+		let currency_amount_decimal: Decimal = currency_amount
+			.parse()
+			.map_err(|e| PricingError::InvalidData(format!("Invalid currency amount: {}", e)))?;
+
+		let rate = self
+			.get_pair_price(&TradingPair::new("ETH", &currency.to_uppercase()))
+			.await?;
+		if rate.is_zero() {
+			return Err(PricingError::InvalidData(
+				"ETH price cannot be zero".to_string(),
+			));
+		}
+
+		let eth_amount = currency_amount_decimal / rate;
+		let wei_amount = parse_ether(&eth_amount.round_dp(18).to_string()).map_err(|e| {
+			PricingError::InvalidData(format!("Failed to convert ETH to wei: {}", e))
+		})?;
+
+		Ok(wei_amount.to_string())
+	}
+}
+
+/// Returns the current Unix time in seconds.
+fn now_seconds() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.unwrap()
+		.as_secs()
+}
+
+/// Scales a raw feed answer by the feed's decimals.
+fn normalise_answer(symbol: &str, answer: I256, decimals: u8) -> Result<Decimal, PricingError> {
+	if !answer.is_positive() {
+		return Err(PricingError::InvalidData(format!(
+			"{}/USD feed returned non-positive answer {}",
+			symbol, answer
+		)));
+	}
+	let answer = i128::try_from(answer).map_err(|_| {
+		PricingError::InvalidData(format!("{}/USD feed answer is out of range", symbol))
+	})?;
+	Decimal::try_from_i128_with_scale(answer, decimals as u32).map_err(|e| {
+		PricingError::InvalidData(format!(
+			"{}/USD feed answer {} with {} decimals cannot be represented: {}",
+			symbol, answer, decimals, e
+		))
+	})
+}
+
+/// Reads the `rpc_urls` table, keyed by chain ID.
+fn parse_rpc_urls(config: &toml::Value) -> Result<HashMap<u64, String>, ValidationError> {
+	let table = config
+		.get("rpc_urls")
+		.ok_or_else(|| ValidationError::MissingField("rpc_urls".to_string()))?
+		.as_table()
+		.ok_or_else(|| ValidationError::TypeMismatch {
+			field: "rpc_urls".to_string(),
+			expected: "table".to_string(),
+			actual: format!("{:?}", config.get("rpc_urls")),
+		})?;
+
+	let mut urls = HashMap::new();
+	for (chain_id, url) in table {
+		let field = format!("rpc_urls.{}", chain_id);
+		let chain_id = chain_id
+			.parse::<u64>()
+			.map_err(|_| ValidationError::InvalidValue {
+				field: field.clone(),
+				message: "key must be a chain ID".to_string(),
+			})?;
+		let url = url.as_str().ok_or_else(|| ValidationError::TypeMismatch {
+			field,
+			expected: "string".to_string(),
+			actual: format!("{:?}", url),
+		})?;
+		urls.insert(chain_id, url.to_string());
+	}
+	Ok(urls)
+}
+
+/// Reads the `feeds` table, keyed by uppercase asset symbol.
+fn parse_feeds(config: &toml::Value) -> Result<HashMap<String, FeedConfig>, ValidationError> {
+	let table = config
+		.get("feeds")
+		.ok_or_else(|| ValidationError::MissingField("feeds".to_string()))?
+		.as_table()
+		.ok_or_else(|| ValidationError::TypeMismatch {
+			field: "feeds".to_string(),
+			expected: "table".to_string(),
+			actual: format!("{:?}", config.get("feeds")),
+		})?;
+
+	let mut feeds = HashMap::new();
+	for (symbol, feed) in table {
+		let field = |name: &str| format!("feeds.{}.{}", symbol, name);
+		let positive_integer = |name: &str| -> Result<Option<i64>, ValidationError> {
+			match feed.get(name) {
+				None => Ok(None),
+				Some(value) => match value.as_integer() {
+					Some(v) if v > 0 => Ok(Some(v)),
+					_ => Err(ValidationError::InvalidValue {
+						field: field(name),
+						message: "must be a positive integer".to_string(),
+					}),
+				},
+			}
+		};
+
+		let chain_id = positive_integer("chain_id")?
+			.ok_or_else(|| ValidationError::MissingField(field("chain_id")))?;
+		let address = feed
+			.get("address")
+			.and_then(|v| v.as_str())
+			.ok_or_else(|| ValidationError::MissingField(field("address")))?
+			.parse::<Address>()
+			.map_err(|e| ValidationError::InvalidValue {
+				field: field("address"),
+				message: e.to_string(),
+			})?;
+		let heartbeat_seconds = positive_integer("heartbeat_seconds")?.map(|v| v as u64);
+		let decimals = match feed.get("decimals") {
+			None => None,
+			Some(value) => match value.as_integer() {
+				Some(v) if (0..=28).contains(&v) => Some(v as u8),
+				_ => {
+					return Err(ValidationError::InvalidValue {
+						field: field("decimals"),
+						message: "must be an integer between 0 and 28".to_string(),
+					})
+				},
+			},
+		};
+
+		feeds.insert(
+			symbol.to_uppercase(),
+			FeedConfig {
+				chain_id: chain_id as u64,
+				address,
+				heartbeat_seconds,
+				decimals,
+			},
+		);
+	}
+	Ok(feeds)
+}
+
+/// Configuration schema for on-chain feed pricing implementation.
+pub struct OnchainFeedsConfigSchema;
+
+impl ConfigSchema for OnchainFeedsConfigSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), ValidationError> {
+		for key in [
+			"max_staleness_seconds",
+			"heartbeat_grace_seconds",
+			"cache_duration_seconds",
+		] {
+			if let Some(value) = config.get(key) {
+				match value.as_integer() {
+					Some(v) if v >= 0 => {},
+					_ => {
+						return Err(ValidationError::InvalidValue {
+							field: key.to_string(),
+							message: "must be a non-negative integer".to_string(),
+						})
+					},
+				}
+			}
+		}
+
+		let rpc_urls = parse_rpc_urls(config)?;
+		let feeds = parse_feeds(config)?;
+		if feeds.is_empty() {
+			return Err(ValidationError::InvalidValue {
+				field: "feeds".to_string(),
+				message: "at least one feed is required".to_string(),
+			});
+		}
+		for (symbol, feed) in &feeds {
+			if !rpc_urls.contains_key(&feed.chain_id) {
+				return Err(ValidationError::InvalidValue {
+					field: format!("feeds.{}.chain_id", symbol),
+					message: format!("no RPC URL configured for chain {}", feed.chain_id),
+				});
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// Registry for on-chain feed pricing implementation.
+pub struct OnchainFeedsPricingRegistry;
+
+impl ImplementationRegistry for OnchainFeedsPricingRegistry {
+	const NAME: &'static str = "onchain_feeds";
+	type Factory = PricingFactory;
+
+	fn factory() -> Self::Factory {
+		create_onchain_feeds_pricing
+	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(OnchainFeedsConfigSchema)
+	}
+}
+
+impl PricingRegistry for OnchainFeedsPricingRegistry {}
+
+/// Factory function for creating OnchainFeedsPricing instances.
+pub fn create_onchain_feeds_pricing(
+	config: &toml::Value,
+) -> Result<Box<dyn PricingInterface>, PricingError> {
+	Ok(Box::new(OnchainFeedsPricing::new(config)?))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_primitives::{hex, U256};
+	use axum::{extract::State, routing::post, Json};
+	use serde_json::{json, Value};
+	use std::str::FromStr;
+	use std::sync::Arc;
+
+	const ETH_FEED: &str = "0x1111111111111111111111111111111111111111";
+	const USDC_FEED: &str = "0x2222222222222222222222222222222222222222";
+	const STALE_FEED: &str = "0x3333333333333333333333333333333333333333";
+	const CARRIED_FEED: &str = "0x4444444444444444444444444444444444444444";
+
+	/// Latest round served by a mock aggregator.
+	#[derive(Clone)]
+	struct MockRound {
+		decimals: u8,
+		round_id: u64,
+		answer: i128,
+		age_seconds: u64,
+		answered_in_round: u64,
+	}
+
+	impl MockRound {
+		fn fresh(decimals: u8, answer: i128) -> Self {
+			Self {
+				decimals,
+				round_id: 10,
+				answer,
+				age_seconds: 60,
+				answered_in_round: 10,
+			}
+		}
+	}
+
+	fn word(value: U256) -> [u8; 32] {
+		value.to_be_bytes()
+	}
+
+	/// ABI-encodes the return values of an aggregator call.
+	fn encode_return(round: &MockRound, selector: &[u8]) -> Option<Vec<u8>> {
+		if selector == AggregatorV3Interface::decimalsCall::SELECTOR {
+			return Some(word(U256::from(round.decimals)).to_vec());
+		}
+		if selector == AggregatorV3Interface::latestRoundDataCall::SELECTOR {
+			let updated_at = now_seconds() - round.age_seconds;
+			let answer = I256::try_from(round.answer).unwrap();
+			return Some(
+				[
+					word(U256::from(round.round_id)),
+					answer.to_be_bytes::<32>(),
+					word(U256::from(updated_at)),
+					word(U256::from(updated_at)),
+					word(U256::from(round.answered_in_round)),
+				]
+				.concat(),
+			);
+		}
+		None
+	}
+
+	/// Answers `eth_call` for the aggregators it knows, by lowercase address.
+	async fn handle(
+		State(feeds): State<Arc<HashMap<String, MockRound>>>,
+		Json(request): Json<Value>,
+	) -> Json<Value> {
+		let call = &request["params"][0];
+		let input = call
+			.get("input")
+			.or_else(|| call.get("data"))
+			.and_then(Value::as_str)
+			.unwrap();
+		let input = hex::decode(input.trim_start_matches("0x")).unwrap();
+		let to = call["to"].as_str().unwrap().to_lowercase();
+
+		let result = feeds
+			.get(&to)
+			.and_then(|round| encode_return(round, &input[..4]));
+		match result {
+			Some(data) => Json(json!({
+				"jsonrpc": "2.0",
+				"id": request["id"],
+				"result": format!("0x{}", hex::encode(data)),
+			})),
+			None => Json(json!({
+				"jsonrpc": "2.0",
+				"id": request["id"],
+				"error": { "code": 3, "message": "execution reverted" },
+			})),
+		}
+	}
+
+	/// Starts a mock node serving the aggregators and returns its URL.
+	async fn start_mock_node() -> String {
+		let feeds = HashMap::from([
+			(ETH_FEED.to_string(), MockRound::fresh(8, 250_000_000_000)),
+			(USDC_FEED.to_string(), MockRound::fresh(6, 1_000_000)),
+			(
+				STALE_FEED.to_string(),
+				MockRound {
+					age_seconds: 7_200,
+					..MockRound::fresh(8, 10_000_000_000)
+				},
+			),
+			(
+				CARRIED_FEED.to_string(),
+				MockRound {
+					answered_in_round: 9,
+					..MockRound::fresh(8, 10_000_000_000)
+				},
+			),
+		]);
+		let app = axum::Router::new()
+			.route("/", post(handle))
+			.with_state(Arc::new(feeds));
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let url = format!("http://{}", listener.local_addr().unwrap());
+		tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+		url
+	}
+
+	fn config(url: &str) -> toml::Value {
+		toml::from_str(&format!(
+			r#"
+			heartbeat_grace_seconds = 300
+
+			[rpc_urls]
+			1 = "{url}"
+
+			[feeds.eth]
+			chain_id = 1
+			address = "{ETH_FEED}"
+			heartbeat_seconds = 3600
+
+			[feeds.USDC]
+			chain_id = 1
+			address = "{USDC_FEED}"
+			decimals = 6
+
+			[feeds.STALE]
+			chain_id = 1
+			address = "{STALE_FEED}"
+			heartbeat_seconds = 3600
+
+			[feeds.CARRIED]
+			chain_id = 1
+			address = "{CARRIED_FEED}"
+			"#
+		))
+		.unwrap()
+	}
+
+	fn decimal(value: &str) -> Decimal {
+		Decimal::from_str(value).unwrap()
+	}
+
+	#[test]
+	fn test_parse_feeds() {
+		let feeds = parse_feeds(&config("http://localhost:8545")).unwrap();
+		assert_eq!(feeds.len(), 4);
+
+		// Symbols are uppercased; decimals are read on chain unless configured
+		let eth = &feeds["ETH"];
+		assert_eq!(eth.chain_id, 1);
+		assert_eq!(eth.address, ETH_FEED.parse::<Address>().unwrap());
+		assert_eq!(eth.heartbeat_seconds, Some(3600));
+		assert_eq!(eth.decimals, None);
+		assert_eq!(feeds["USDC"].decimals, Some(6));
+	}
+
+	#[test]
+	fn test_parse_feeds_rejects_invalid_entries() {
+		let parse =
+			|feed: &str| parse_feeds(&toml::from_str(&format!("[feeds.ETH]\n{}", feed)).unwrap());
+		let address = format!("address = \"{}\"", ETH_FEED);
+
+		assert!(matches!(
+			parse(&address),
+			Err(ValidationError::MissingField(field)) if field == "feeds.ETH.chain_id"
+		));
+		assert!(matches!(
+			parse("chain_id = 1"),
+			Err(ValidationError::MissingField(field)) if field == "feeds.ETH.address"
+		));
+		assert!(matches!(
+			parse("chain_id = 1\naddress = \"0x1234\""),
+			Err(ValidationError::InvalidValue { field, .. }) if field == "feeds.ETH.address"
+		));
+		assert!(matches!(
+			parse(&format!("chain_id = 1\n{}\nheartbeat_seconds = 0", address)),
+			Err(ValidationError::InvalidValue { field, .. }) if field == "feeds.ETH.heartbeat_seconds"
+		));
+		assert!(matches!(
+			parse(&format!("chain_id = 1\n{}\ndecimals = 29", address)),
+			Err(ValidationError::InvalidValue { field, .. }) if field == "feeds.ETH.decimals"
+		));
+		assert!(parse_feeds(&toml::from_str("feeds = 1").unwrap()).is_err());
+	}
+
+	#[test]
+	fn test_schema_requires_rpc_url_for_feed_chain() {
+		let mut config = config("http://localhost:8545");
+		config["feeds"]["eth"]
+			.as_table_mut()
+			.unwrap()
+			.insert("chain_id".to_string(), toml::Value::Integer(10));
+
+		let result = OnchainFeedsConfigSchema.validate(&config);
+		assert!(matches!(
+			result,
+			Err(ValidationError::InvalidValue { field, .. }) if field == "feeds.ETH.chain_id"
+		));
+	}
+
+	#[test]
+	fn test_staleness_limit() {
+		let pricing = OnchainFeedsPricing::new(&config("http://localhost:8545")).unwrap();
+		let feed = |heartbeat_seconds| FeedConfig {
+			chain_id: 1,
+			address: Address::ZERO,
+			heartbeat_seconds,
+			decimals: None,
+		};
+
+		// Heartbeat plus grace, capped by the global limit
+		assert_eq!(pricing.staleness_limit(&feed(Some(3600))), 3900);
+		assert_eq!(
+			pricing.staleness_limit(&feed(Some(172_800))),
+			DEFAULT_MAX_STALENESS_SECONDS
+		);
+		assert_eq!(
+			pricing.staleness_limit(&feed(None)),
+			DEFAULT_MAX_STALENESS_SECONDS
+		);
+	}
+
+	#[test]
+	fn test_normalise_answer() {
+		let answer = |value: i128| I256::try_from(value).unwrap();
+
+		assert_eq!(
+			normalise_answer("ETH", answer(250_012_345_678), 8).unwrap(),
+			decimal("2500.12345678")
+		);
+		assert_eq!(
+			normalise_answer("DAI", answer(999_800_000_000_000_000), 18).unwrap(),
+			decimal("0.9998")
+		);
+		assert_eq!(normalise_answer("X", answer(42), 0).unwrap(), decimal("42"));
+		assert!(matches!(
+			normalise_answer("ETH", answer(0), 8),
+			Err(PricingError::InvalidData(_))
+		));
+		assert!(matches!(
+			normalise_answer("ETH", answer(-1), 8),
+			Err(PricingError::InvalidData(_))
+		));
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn test_reads_and_crosses_feeds_from_node() {
+		let url = start_mock_node().await;
+		let pricing = OnchainFeedsPricing::new(&config(&url)).unwrap();
+
+		// ETH decimals are read from the aggregator, USDC's come from config
+		assert_eq!(pricing.get_usd_price("eth").await.unwrap(), decimal("2500"));
+		assert_eq!(pricing.get_usd_price("USDC").await.unwrap(), decimal("1"));
+
+		// Neither side is USD, so the rate is derived through USD
+		let converted = pricing.convert_asset("ETH", "USDC", "2").await.unwrap();
+		assert_eq!(decimal(&converted), decimal("5000"));
+		let converted = pricing.convert_asset("USDC", "ETH", "5000").await.unwrap();
+		assert_eq!(decimal(&converted), decimal("2"));
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn test_rejects_stale_and_carried_over_rounds() {
+		let url = start_mock_node().await;
+		let pricing = OnchainFeedsPricing::new(&config(&url)).unwrap();
+
+		// Updated two hours ago against a one hour heartbeat
+		match pricing.get_usd_price("STALE").await {
+			Err(PricingError::PriceNotAvailable(message)) => assert!(message.contains("stale")),
+			other => panic!("expected a stale feed error, got {:?}", other),
+		}
+
+		// answeredInRound lags roundId
+		match pricing.get_usd_price("CARRIED").await {
+			Err(PricingError::PriceNotAvailable(message)) => {
+				assert!(message.contains("carried over"))
+			},
+			other => panic!("expected a carried over round error, got {:?}", other),
+		}
+
+		assert!(matches!(
+			pricing.convert_asset("STALE", "ETH", "1").await,
+			Err(PricingError::PriceNotAvailable(_))
+		));
+		assert!(matches!(
+			pricing.get_usd_price("BTC").await,
+			Err(PricingError::PriceNotAvailable(_))
+		));
+	}
+}
@@ -1,21 +1,35 @@
 //! Pricing oracle implementations for the OIF solver system.
 //!
 //! This module provides pricing oracle implementations for converting between
-//! wei amounts and fiat currencies. Currently supports mock pricing for development.
+//! wei amounts and fiat currencies. Supports mock pricing for development, CoinGecko,
+//! on-chain Chainlink-style price feeds, and a median across several of these.
+//! Recent prices are kept to size rate buffers by volatility.
 
+use alloy_primitives::{utils::format_units, U256};
+use arc_swap::ArcSwap;
+use rust_decimal::Decimal;
 use solver_types::{
-	ImplementationRegistry, PricingError, PricingFactory, PricingInterface, TradingPair,
+	ConfigSchema, ImplementationRegistry, NativeCurrency, PricingError, PricingFactory,
+	PricingInterface, TradingPair,
 };
+use std::collections::HashSet;
+use std::sync::Arc;
+
+pub mod history;
+
+use history::PriceHistory;
 
 /// Re-export implementations
 pub mod implementations {
+	pub mod aggregate;
 	pub mod coingecko;
 	pub mod mock;
+	pub mod onchain_feeds;
 }
 
 /// Get all registered pricing implementations.
 pub fn get_all_implementations() -> Vec<(&'static str, PricingFactory)> {
-	use implementations::{coingecko, mock};
+	use implementations::{aggregate, coingecko, mock, onchain_feeds};
 	vec![
 		(
 			mock::MockPricingRegistry::NAME,
@@ -25,6 +39,37 @@ pub fn get_all_implementations() -> Vec<(&'static str, PricingFactory)> {
 			coingecko::CoinGeckoPricingRegistry::NAME,
 			coingecko::CoinGeckoPricingRegistry::factory(),
 		),
+		(
+			onchain_feeds::OnchainFeedsPricingRegistry::NAME,
+			onchain_feeds::OnchainFeedsPricingRegistry::factory(),
+		),
+		(
+			aggregate::AggregatePricingRegistry::NAME,
+			aggregate::AggregatePricingRegistry::factory(),
+		),
+	]
+}
+
+/// Get configuration schemas for all registered pricing implementations.
+pub fn get_all_schemas() -> Vec<(&'static str, Box<dyn ConfigSchema>)> {
+	use implementations::{aggregate, coingecko, mock, onchain_feeds};
+	vec![
+		(
+			mock::MockPricingRegistry::NAME,
+			mock::MockPricingRegistry::config_schema(),
+		),
+		(
+			coingecko::CoinGeckoPricingRegistry::NAME,
+			coingecko::CoinGeckoPricingRegistry::config_schema(),
+		),
+		(
+			onchain_feeds::OnchainFeedsPricingRegistry::NAME,
+			onchain_feeds::OnchainFeedsPricingRegistry::config_schema(),
+		),
+		(
+			aggregate::AggregatePricingRegistry::NAME,
+			aggregate::AggregatePricingRegistry::config_schema(),
+		),
 	]
 }
 
@@ -39,6 +84,13 @@ pub struct PricingConfig {
 	pub gas_buffer_bps: u32,
 	/// Rate buffer in basis points.
 	pub rate_buffer_bps: u32,
+	/// Standard deviations of the expected price move added to the rate buffer.
+	/// Zero disables the volatility buffer.
+	pub volatility_multiplier: f64,
+	/// How far back prices are kept to measure volatility, in seconds.
+	pub volatility_lookback_seconds: u64,
+	/// Upper bound on the total rate buffer in basis points.
+	pub max_rate_buffer_bps: u32,
 	/// Whether to use live gas estimation.
 	pub enable_live_gas_estimate: bool,
 }
@@ -50,6 +102,9 @@ impl PricingConfig {
 			commission_bps: 20,
 			gas_buffer_bps: 1000,
 			rate_buffer_bps: 14,
+			volatility_multiplier: 0.0,
+			volatility_lookback_seconds: 3600,
+			max_rate_buffer_bps: 500,
 			enable_live_gas_estimate: false,
 		}
 	}
@@ -75,6 +130,19 @@ impl PricingConfig {
 				.get("rate_buffer_bps")
 				.and_then(|v| v.as_integer())
 				.unwrap_or(defaults.rate_buffer_bps as i64) as u32,
+			volatility_multiplier: table
+				.get("volatility_multiplier")
+				.and_then(|v| v.as_float().or_else(|| v.as_integer().map(|i| i as f64)))
+				.unwrap_or(defaults.volatility_multiplier),
+			volatility_lookback_seconds: table
+				.get("volatility_lookback_seconds")
+				.and_then(|v| v.as_integer())
+				.unwrap_or(defaults.volatility_lookback_seconds as i64)
+				as u64,
+			max_rate_buffer_bps: table
+				.get("max_rate_buffer_bps")
+				.and_then(|v| v.as_integer())
+				.unwrap_or(defaults.max_rate_buffer_bps as i64) as u32,
 			enable_live_gas_estimate: table
 				.get("enable_live_gas_estimate")
 				.and_then(|v| v.as_bool())
@@ -87,8 +155,10 @@ impl PricingConfig {
 pub struct PricingService {
 	/// The primary pricing implementation.
 	implementation: Box<dyn PricingInterface>,
-	/// Pricing configuration.
-	config: PricingConfig,
+	/// Pricing configuration, swappable on config reload.
+	config: ArcSwap<PricingConfig>,
+	/// Recent prices used to measure volatility.
+	history: PriceHistory,
 }
 
 impl PricingService {
@@ -96,7 +166,8 @@ impl PricingService {
 	pub fn new(implementation: Box<dyn PricingInterface>) -> Self {
 		Self {
 			implementation,
-			config: PricingConfig::default_values(),
+			config: ArcSwap::from_pointee(PricingConfig::default_values()),
+			history: PriceHistory::new(),
 		}
 	}
 
@@ -107,13 +178,19 @@ impl PricingService {
 	) -> Self {
 		Self {
 			implementation,
-			config,
+			config: ArcSwap::from_pointee(config),
+			history: PriceHistory::new(),
 		}
 	}
 
 	/// Gets the current pricing configuration.
-	pub fn config(&self) -> &PricingConfig {
-		&self.config
+	pub fn config(&self) -> Arc<PricingConfig> {
+		self.config.load_full()
+	}
+
+	/// Replaces the pricing configuration.
+	pub fn update_config(&self, config: PricingConfig) {
+		self.config.store(Arc::new(config));
 	}
 	/// Gets all supported trading pairs.
 	pub async fn get_supported_pairs(&self) -> Vec<TradingPair> {
@@ -143,6 +220,39 @@ impl PricingService {
 			.await
 	}
 
+	/// Converts an amount of a chain's native asset, in its smallest unit, to the specified currency.
+	///
+	/// Ether is priced through `wei_to_currency`; other native assets are
+	/// normalised by their decimals and converted by symbol.
+	pub async fn native_to_currency(
+		&self,
+		raw_amount: &str,
+		native: &NativeCurrency,
+		currency: &str,
+	) -> Result<String, PricingError> {
+		if native.is_ether() {
+			return self.wei_to_currency(raw_amount, currency).await;
+		}
+
+		let raw = U256::from_str_radix(raw_amount, 10).map_err(|e| {
+			PricingError::InvalidData(format!(
+				"Invalid {} amount '{}': {}",
+				native.symbol, raw_amount, e
+			))
+		})?;
+		let amount = format_units(raw, native.decimals).map_err(|e| {
+			PricingError::InvalidData(format!("Invalid {} decimals: {}", native.symbol, e))
+		})?;
+		let converted = self
+			.implementation
+			.convert_asset(&native.symbol, currency, &amount)
+			.await?;
+		let converted: Decimal = converted
+			.parse()
+			.map_err(|e| PricingError::InvalidData(format!("Invalid converted amount: {}", e)))?;
+		Ok(converted.round_dp(2).to_string())
+	}
+
 	/// Converts a currency amount to wei using current ETH price.
 	pub async fn currency_to_wei(
 		&self,
@@ -153,4 +263,153 @@ impl PricingService {
 			.currency_to_wei(currency_amount, currency)
 			.await
 	}
+
+	/// Records the USD price of one unit of an asset in the price history.
+	///
+	/// Callers pass prices they already fetched, so measuring volatility
+	/// costs no extra requests to the pricing source.
+	pub fn record_price(&self, symbol: &str, unit_price_usd: Decimal) {
+		let lookback = self.config.load().volatility_lookback_seconds;
+		self.history.record(symbol, unit_price_usd, lookback);
+	}
+
+	/// Returns the rate buffer for a swap, in basis points of the output value.
+	///
+	/// Swaps between the same assets carry no rate risk and get no buffer.
+	/// Other swaps get `rate_buffer_bps`, plus `volatility_multiplier` standard
+	/// deviations of the expected move of the pair over the horizon when one is
+	/// given and the assets have enough price history. The total is capped at
+	/// `max_rate_buffer_bps`.
+	pub async fn rate_buffer_bps(
+		&self,
+		input_symbols: &[String],
+		output_symbols: &[String],
+		horizon_seconds: Option<u64>,
+	) -> u32 {
+		let inputs: HashSet<String> = input_symbols.iter().map(|s| s.to_uppercase()).collect();
+		let outputs: HashSet<String> = output_symbols.iter().map(|s| s.to_uppercase()).collect();
+		if inputs == outputs {
+			return 0;
+		}
+
+		let config = self.config();
+		let mut buffer_bps = config.rate_buffer_bps as f64;
+		if let Some(horizon) = horizon_seconds.filter(|_| config.volatility_multiplier > 0.0) {
+			// The pair moves with both sides, so their variances add up
+			let side_volatility = |symbols: &HashSet<String>| {
+				let mut highest: f64 = 0.0;
+				for symbol in symbols {
+					if let Some(volatility) = self
+						.history
+						.volatility(symbol, config.volatility_lookback_seconds)
+					{
+						highest = highest.max(volatility);
+					}
+				}
+				highest
+			};
+			let input_volatility = side_volatility(&inputs);
+			let output_volatility = side_volatility(&outputs);
+			let pair_volatility = (input_volatility.powi(2) + output_volatility.powi(2)).sqrt();
+			let expected_move = pair_volatility * (horizon as f64).sqrt();
+			buffer_bps += config.volatility_multiplier * expected_move * 10_000.0;
+
+			tracing::debug!(
+				input_volatility = input_volatility,
+				output_volatility = output_volatility,
+				horizon_seconds = horizon,
+				buffer_bps = buffer_bps,
+				"Applied volatility to rate buffer"
+			);
+		}
+
+		buffer_bps.min(config.max_rate_buffer_bps as f64).ceil() as u32
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_types::current_timestamp;
+
+	fn service(volatility_multiplier: f64, max_rate_buffer_bps: u32) -> PricingService {
+		let implementation =
+			implementations::mock::create_mock_pricing(&toml::Value::Table(Default::default()))
+				.unwrap();
+		PricingService::new_with_config(
+			implementation,
+			PricingConfig {
+				volatility_multiplier,
+				max_rate_buffer_bps,
+				..PricingConfig::default_values()
+			},
+		)
+	}
+
+	fn symbols(symbols: &[&str]) -> Vec<String> {
+		symbols.iter().map(|s| s.to_string()).collect()
+	}
+
+	/// Records four prices ten seconds apart, each `step` times the last.
+	fn record_moves(service: &PricingService, symbol: &str, step: f64) {
+		let start = current_timestamp() - 30;
+		let mut price = 100.0;
+		for i in 0..4 {
+			let price_decimal = Decimal::try_from(price).unwrap();
+			service
+				.history
+				.record_at(symbol, price_decimal, start + i * 10, 3_600);
+			price *= step;
+		}
+	}
+
+	#[tokio::test]
+	async fn test_same_assets_have_no_rate_buffer() {
+		let service = service(2.0, 500);
+		let buffer = service
+			.rate_buffer_bps(&symbols(&["usdc"]), &symbols(&["USDC"]), Some(60))
+			.await;
+		assert_eq!(buffer, 0);
+	}
+
+	#[tokio::test]
+	async fn test_rate_buffer_without_history_is_the_base() {
+		let service = service(2.0, 500);
+		let buffer = service
+			.rate_buffer_bps(&symbols(&["ETH"]), &symbols(&["USDC"]), Some(60))
+			.await;
+		assert_eq!(buffer, 14);
+	}
+
+	#[tokio::test]
+	async fn test_rate_buffer_scales_with_volatility_and_horizon() {
+		let service = service(2.0, 10_000);
+		record_moves(&service, "ETH", 1.001);
+		record_moves(&service, "USDC", 1.0);
+
+		let volatility = service.history.volatility("ETH", 3_600).unwrap();
+		let buffer = service
+			.rate_buffer_bps(&symbols(&["ETH"]), &symbols(&["USDC"]), Some(100))
+			.await;
+		// Base plus two standard deviations of the move over 100 seconds
+		let expected = (14.0 + 2.0 * volatility * 10.0 * 10_000.0).ceil() as u32;
+		assert_eq!(buffer, expected);
+		assert!(buffer > 14);
+
+		// Without a horizon only the base buffer applies
+		let buffer = service
+			.rate_buffer_bps(&symbols(&["ETH"]), &symbols(&["USDC"]), None)
+			.await;
+		assert_eq!(buffer, 14);
+	}
+
+	#[tokio::test]
+	async fn test_rate_buffer_is_capped() {
+		let service = service(50.0, 100);
+		record_moves(&service, "ETH", 1.05);
+		let buffer = service
+			.rate_buffer_bps(&symbols(&["ETH"]), &symbols(&["USDC"]), Some(3_600))
+			.await;
+		assert_eq!(buffer, 100);
+	}
 }
@@ -0,0 +1,364 @@
+//! Self-solving bridge built on the EIP-7683 order flow.
+//!
+//! Instead of calling a third-party bridge, this adapter opens a standard
+//! escrow order on the surplus chain's input settler. The order locks the
+//! transfer amount and asks for the same asset, less a fee, to be delivered to
+//! the solver's own account on the deficit chain. Any OIF solver can fill it
+//! and claim the escrowed inputs, which completes the move with the same
+//! contracts and oracles the solver already uses.
+
+use crate::{BridgeError, BridgeFactory, BridgeInterface, BridgeTransfer, TransferRequest};
+use alloy_primitives::{Address as AlloyAddress, Bytes, FixedBytes, U256};
+use alloy_sol_types::SolCall;
+use async_trait::async_trait;
+use solver_types::{
+	current_timestamp,
+	oracle::OracleRoutes,
+	standards::eip7683::interfaces::{IInputSettlerEscrow, SolMandateOutput, StandardOrder},
+	Address, ConfigSchema, Field, FieldType, ImplementationRegistry, NetworksConfig, Schema,
+	Transaction, ValidationError,
+};
+
+/// Default fee left to the filler, in basis points of the amount.
+const DEFAULT_FEE_BPS: u32 = 10;
+/// Default time a filler has to deliver the output, in seconds.
+const DEFAULT_FILL_DEADLINE_SECONDS: u64 = 3600;
+/// Default time after which the escrowed inputs can be refunded, in seconds.
+const DEFAULT_EXPIRY_SECONDS: u64 = 86_400;
+
+/// Bridge that moves funds by opening EIP-7683 orders for other solvers to fill.
+pub struct Eip7683Bridge {
+	/// Networks configuration with settler addresses.
+	networks: NetworksConfig,
+	/// Oracle routes connecting input and output oracles.
+	oracle_routes: OracleRoutes,
+	/// Fee left to the filler, in basis points of the amount.
+	fee_bps: u32,
+	/// Seconds a filler has to deliver the output.
+	fill_deadline_seconds: u64,
+	/// Seconds after which the escrowed inputs can be refunded.
+	expiry_seconds: u64,
+}
+
+impl Eip7683Bridge {
+	/// Creates a new Eip7683Bridge from its configuration.
+	pub fn new(
+		config: &toml::Value,
+		networks: NetworksConfig,
+		oracle_routes: OracleRoutes,
+	) -> Result<Self, BridgeError> {
+		let get_u64 = |key: &str, default: u64| {
+			config
+				.get(key)
+				.and_then(|v| v.as_integer())
+				.map(|v| v as u64)
+				.unwrap_or(default)
+		};
+		let fill_deadline_seconds = get_u64("fill_deadline_seconds", DEFAULT_FILL_DEADLINE_SECONDS);
+		let expiry_seconds = get_u64("expiry_seconds", DEFAULT_EXPIRY_SECONDS);
+		if expiry_seconds < fill_deadline_seconds {
+			return Err(BridgeError::InvalidConfig(
+				"expiry_seconds must not be shorter than fill_deadline_seconds".to_string(),
+			));
+		}
+
+		Ok(Self {
+			networks,
+			oracle_routes,
+			fee_bps: get_u64("fee_bps", DEFAULT_FEE_BPS as u64) as u32,
+			fill_deadline_seconds,
+			expiry_seconds,
+		})
+	}
+
+	/// Finds an input oracle on the source chain with a route to an output
+	/// oracle on the destination chain.
+	fn oracles_for(&self, from_chain: u64, to_chain: u64) -> Option<(Address, Address)> {
+		let mut routes: Vec<_> = self
+			.oracle_routes
+			.supported_routes
+			.iter()
+			.filter(|(input, _)| input.chain_id == from_chain)
+			.filter_map(|(input, outputs)| {
+				outputs
+					.iter()
+					.find(|output| output.chain_id == to_chain)
+					.map(|output| (input.oracle.clone(), output.oracle.clone()))
+			})
+			.collect();
+		// Pick deterministically when several oracles connect the chains
+		routes.sort_by(|a, b| a.0 .0.cmp(&b.0 .0));
+		routes.into_iter().next()
+	}
+}
+
+/// Left-pads a 20-byte address to 32 bytes.
+fn to_bytes32(address: &Address) -> FixedBytes<32> {
+	let mut bytes = [0u8; 32];
+	bytes[12..32].copy_from_slice(&address.0);
+	FixedBytes::<32>::from(bytes)
+}
+
+/// Configuration schema for the EIP-7683 bridge.
+pub struct Eip7683BridgeSchema;
+
+impl ConfigSchema for Eip7683BridgeSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![],
+			// Optional fields
+			vec![
+				Field::new(
+					"fee_bps",
+					FieldType::Integer {
+						min: Some(0),
+						max: Some(10_000),
+					},
+				),
+				Field::new(
+					"fill_deadline_seconds",
+					FieldType::Integer {
+						min: Some(1),
+						max: Some(u32::MAX as i64),
+					},
+				),
+				Field::new(
+					"expiry_seconds",
+					FieldType::Integer {
+						min: Some(1),
+						max: Some(u32::MAX as i64),
+					},
+				),
+			],
+		);
+
+		schema.validate(config)
+	}
+}
+
+#[async_trait]
+impl BridgeInterface for Eip7683Bridge {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(Eip7683BridgeSchema)
+	}
+
+	fn supports_route(&self, from_chain: u64, to_chain: u64) -> bool {
+		from_chain != to_chain
+			&& self.networks.contains_key(&from_chain)
+			&& self.networks.contains_key(&to_chain)
+			&& self.oracles_for(from_chain, to_chain).is_some()
+	}
+
+	async fn build_transfer(
+		&self,
+		request: &TransferRequest,
+	) -> Result<BridgeTransfer, BridgeError> {
+		let route = || format!("{} -> {}", request.from_chain, request.to_chain);
+		let origin = self
+			.networks
+			.get(&request.from_chain)
+			.ok_or_else(|| BridgeError::UnsupportedRoute(route()))?;
+		let destination = self
+			.networks
+			.get(&request.to_chain)
+			.ok_or_else(|| BridgeError::UnsupportedRoute(route()))?;
+		let (input_oracle, output_oracle) = self
+			.oracles_for(request.from_chain, request.to_chain)
+			.ok_or_else(|| BridgeError::UnsupportedRoute(route()))?;
+
+		let fee = request.amount * U256::from(self.fee_bps) / U256::from(10_000u64);
+		let expected_amount = request.amount - fee;
+
+		let user = AlloyAddress::try_from(request.sender.0.as_slice())
+			.map_err(|e| BridgeError::TransferFailed(format!("Invalid sender: {}", e)))?;
+		let input_oracle = AlloyAddress::try_from(input_oracle.0.as_slice())
+			.map_err(|e| BridgeError::TransferFailed(format!("Invalid input oracle: {}", e)))?;
+		let now = current_timestamp();
+		let nonce = std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.map(|d| d.as_nanos())
+			.unwrap_or_default();
+
+		let order = StandardOrder {
+			user,
+			nonce: U256::from(nonce),
+			originChainId: U256::from(request.from_chain),
+			expires: (now + self.expiry_seconds) as u32,
+			fillDeadline: (now + self.fill_deadline_seconds) as u32,
+			inputOracle: input_oracle,
+			inputs: vec![[U256::from_be_slice(&request.from_token.0), request.amount]],
+			outputs: vec![SolMandateOutput {
+				oracle: to_bytes32(&output_oracle),
+				settler: to_bytes32(&destination.output_settler_address),
+				chainId: U256::from(request.to_chain),
+				token: to_bytes32(&request.to_token),
+				amount: expected_amount,
+				recipient: to_bytes32(&request.recipient),
+				call: Bytes::new(),
+				context: Bytes::new(),
+			}],
+		};
+
+		let open_data = IInputSettlerEscrow::openCall { order }.abi_encode();
+		let transaction = Transaction {
+			from: Some(request.sender.clone()),
+			to: Some(origin.input_settler_address.clone()),
+			data: open_data,
+			value: U256::ZERO,
+			chain_id: request.from_chain,
+			nonce: None,
+			gas_limit: None,
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			metadata: None,
+		};
+
+		Ok(BridgeTransfer {
+			spender: Some(origin.input_settler_address.clone()),
+			transactions: vec![transaction],
+			expected_amount,
+		})
+	}
+}
+
+/// Factory function for creating Eip7683Bridge instances.
+pub fn create_bridge(
+	config: &toml::Value,
+	networks: &NetworksConfig,
+	oracle_routes: &OracleRoutes,
+) -> Result<Box<dyn BridgeInterface>, BridgeError> {
+	Eip7683BridgeSchema
+		.validate(config)
+		.map_err(|e| BridgeError::InvalidConfig(e.to_string()))?;
+	Ok(Box::new(Eip7683Bridge::new(
+		config,
+		networks.clone(),
+		oracle_routes.clone(),
+	)?))
+}
+
+/// Registry for the EIP-7683 bridge implementation.
+pub struct Registry;
+
+impl ImplementationRegistry for Registry {
+	const NAME: &'static str = "eip7683";
+	type Factory = BridgeFactory;
+
+	fn factory() -> Self::Factory {
+		create_bridge
+	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(Eip7683BridgeSchema)
+	}
+}
+
+impl crate::BridgeRegistry for Registry {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_types::{
+		oracle::OracleInfo,
+		utils::tests::builders::{NetworkConfigBuilder, NetworksConfigBuilder},
+	};
+	use std::collections::HashMap;
+
+	fn bridge(config: &str) -> Eip7683Bridge {
+		let networks = NetworksConfigBuilder::new()
+			.add_network(1, NetworkConfigBuilder::new().build())
+			.add_network(137, NetworkConfigBuilder::new().build())
+			.build();
+
+		let mut supported_routes = HashMap::new();
+		supported_routes.insert(
+			OracleInfo {
+				chain_id: 1,
+				oracle: Address(vec![10u8; 20]),
+			},
+			vec![OracleInfo {
+				chain_id: 137,
+				oracle: Address(vec![11u8; 20]),
+			}],
+		);
+
+		let config: toml::Value = toml::from_str(config).unwrap();
+		Eip7683Bridge::new(&config, networks, OracleRoutes { supported_routes }).unwrap()
+	}
+
+	fn request() -> TransferRequest {
+		TransferRequest {
+			asset: "USDC".to_string(),
+			from_chain: 1,
+			to_chain: 137,
+			from_token: Address(vec![1u8; 20]),
+			to_token: Address(vec![2u8; 20]),
+			amount: U256::from(1_000_000u64),
+			sender: Address(vec![3u8; 20]),
+			recipient: Address(vec![4u8; 20]),
+		}
+	}
+
+	#[test]
+	fn test_supports_routes_with_oracles() {
+		let bridge = bridge("");
+		assert!(bridge.supports_route(1, 137));
+		// No oracle route back
+		assert!(!bridge.supports_route(137, 1));
+		assert!(!bridge.supports_route(1, 10));
+	}
+
+	#[tokio::test]
+	async fn test_build_transfer_opens_escrow_order() {
+		let bridge = bridge("fee_bps = 50");
+		let transfer = bridge.build_transfer(&request()).await.unwrap();
+
+		assert_eq!(transfer.expected_amount, U256::from(995_000u64));
+		assert_eq!(transfer.transactions.len(), 1);
+		let tx = &transfer.transactions[0];
+		let input_settler = bridge.networks[&1].input_settler_address.clone();
+		assert_eq!(tx.to, Some(input_settler.clone()));
+		assert_eq!(transfer.spender, Some(input_settler));
+		assert_eq!(tx.from, Some(Address(vec![3u8; 20])));
+
+		let call = IInputSettlerEscrow::openCall::abi_decode(&tx.data).unwrap();
+		let order = call.order;
+		assert_eq!(order.user.as_slice(), &[3u8; 20]);
+		assert_eq!(order.originChainId, U256::from(1));
+		assert_eq!(order.inputOracle.as_slice(), &[10u8; 20]);
+		assert_eq!(order.inputs[0][1], U256::from(1_000_000u64));
+		let output = &order.outputs[0];
+		assert_eq!(output.chainId, U256::from(137));
+		assert_eq!(output.amount, U256::from(995_000u64));
+		assert_eq!(output.oracle.as_slice()[12..], [11u8; 20]);
+		assert_eq!(output.recipient.as_slice()[12..], [4u8; 20]);
+		assert!(order.fillDeadline <= order.expires);
+	}
+
+	#[tokio::test]
+	async fn test_build_transfer_rejects_unknown_route() {
+		let bridge = bridge("");
+		let mut request = request();
+		request.to_chain = 10;
+		assert!(matches!(
+			bridge.build_transfer(&request).await,
+			Err(BridgeError::UnsupportedRoute(_))
+		));
+	}
+
+	#[test]
+	fn test_expiry_must_cover_fill_deadline() {
+		let config: toml::Value =
+			toml::from_str("fill_deadline_seconds = 600\nexpiry_seconds = 60").unwrap();
+		let result = Eip7683Bridge::new(
+			&config,
+			NetworksConfigBuilder::new().build(),
+			OracleRoutes {
+				supported_routes: HashMap::new(),
+			},
+		);
+		assert!(matches!(result, Err(BridgeError::InvalidConfig(_))));
+	}
+}
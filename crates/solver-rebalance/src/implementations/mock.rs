@@ -0,0 +1,215 @@
+//! Mock bridge implementation for development and testing.
+//!
+//! This bridge sends no transactions. It records every transfer it is asked
+//! to build and reports the amount that would arrive after its configured fee,
+//! so rebalancing can be exercised without moving funds.
+
+use crate::{BridgeError, BridgeFactory, BridgeInterface, BridgeTransfer, TransferRequest};
+use alloy_primitives::U256;
+use async_trait::async_trait;
+use solver_types::{
+	oracle::OracleRoutes, ConfigSchema, Field, FieldType, ImplementationRegistry, NetworksConfig,
+	Schema, ValidationError,
+};
+use std::sync::Mutex;
+
+/// Mock bridge that records transfers instead of sending transactions.
+pub struct MockBridge {
+	/// Chains the bridge connects; `None` connects every chain.
+	supported_chains: Option<Vec<u64>>,
+	/// Fee kept by the bridge, in basis points of the amount.
+	fee_bps: u32,
+	/// Transfers built so far.
+	transfers: Mutex<Vec<TransferRequest>>,
+}
+
+impl MockBridge {
+	/// Creates a new MockBridge from its configuration.
+	pub fn new(config: &toml::Value) -> Result<Self, BridgeError> {
+		let supported_chains = config
+			.get("supported_chains")
+			.and_then(|v| v.as_array())
+			.map(|chains| {
+				chains
+					.iter()
+					.filter_map(|c| c.as_integer())
+					.map(|c| c as u64)
+					.collect()
+			});
+		let fee_bps = config
+			.get("fee_bps")
+			.and_then(|v| v.as_integer())
+			.unwrap_or(0) as u32;
+
+		Ok(Self {
+			supported_chains,
+			fee_bps,
+			transfers: Mutex::new(Vec::new()),
+		})
+	}
+
+	/// Returns the transfers built so far.
+	pub fn transfers(&self) -> Vec<TransferRequest> {
+		self.transfers.lock().unwrap().clone()
+	}
+
+	fn supports_chain(&self, chain_id: u64) -> bool {
+		self.supported_chains
+			.as_ref()
+			.is_none_or(|chains| chains.contains(&chain_id))
+	}
+}
+
+/// Configuration schema for the mock bridge.
+pub struct MockBridgeSchema;
+
+impl ConfigSchema for MockBridgeSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![],
+			// Optional fields
+			vec![
+				Field::new(
+					"supported_chains",
+					FieldType::Array(Box::new(FieldType::Integer {
+						min: Some(1),
+						max: None,
+					})),
+				),
+				Field::new(
+					"fee_bps",
+					FieldType::Integer {
+						min: Some(0),
+						max: Some(10_000),
+					},
+				),
+			],
+		);
+
+		schema.validate(config)
+	}
+}
+
+#[async_trait]
+impl BridgeInterface for MockBridge {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(MockBridgeSchema)
+	}
+
+	fn supports_route(&self, from_chain: u64, to_chain: u64) -> bool {
+		from_chain != to_chain && self.supports_chain(from_chain) && self.supports_chain(to_chain)
+	}
+
+	async fn build_transfer(
+		&self,
+		request: &TransferRequest,
+	) -> Result<BridgeTransfer, BridgeError> {
+		if !self.supports_route(request.from_chain, request.to_chain) {
+			return Err(BridgeError::UnsupportedRoute(format!(
+				"{} -> {}",
+				request.from_chain, request.to_chain
+			)));
+		}
+
+		let fee = request.amount * U256::from(self.fee_bps) / U256::from(10_000u64);
+		self.transfers.lock().unwrap().push(request.clone());
+		tracing::debug!(
+			asset = %request.asset,
+			from_chain = request.from_chain,
+			to_chain = request.to_chain,
+			amount = %request.amount,
+			"Mock bridge transfer recorded"
+		);
+
+		Ok(BridgeTransfer {
+			spender: None,
+			transactions: Vec::new(),
+			expected_amount: request.amount - fee,
+		})
+	}
+}
+
+/// Factory function for creating MockBridge instances.
+pub fn create_bridge(
+	config: &toml::Value,
+	_networks: &NetworksConfig,
+	_oracle_routes: &OracleRoutes,
+) -> Result<Box<dyn BridgeInterface>, BridgeError> {
+	MockBridgeSchema
+		.validate(config)
+		.map_err(|e| BridgeError::InvalidConfig(e.to_string()))?;
+	Ok(Box::new(MockBridge::new(config)?))
+}
+
+/// Registry for the mock bridge implementation.
+pub struct Registry;
+
+impl ImplementationRegistry for Registry {
+	const NAME: &'static str = "mock";
+	type Factory = BridgeFactory;
+
+	fn factory() -> Self::Factory {
+		create_bridge
+	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(MockBridgeSchema)
+	}
+}
+
+impl crate::BridgeRegistry for Registry {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_types::Address;
+
+	fn request(from_chain: u64, to_chain: u64) -> TransferRequest {
+		TransferRequest {
+			asset: "USDC".to_string(),
+			from_chain,
+			to_chain,
+			from_token: Address(vec![1u8; 20]),
+			to_token: Address(vec![2u8; 20]),
+			amount: U256::from(1_000_000u64),
+			sender: Address(vec![3u8; 20]),
+			recipient: Address(vec![3u8; 20]),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_mock_bridge_records_transfers() {
+		let config: toml::Value = toml::from_str("fee_bps = 30").unwrap();
+		let bridge = MockBridge::new(&config).unwrap();
+
+		let transfer = bridge.build_transfer(&request(1, 2)).await.unwrap();
+		assert!(transfer.transactions.is_empty());
+		assert_eq!(transfer.expected_amount, U256::from(997_000u64));
+		assert_eq!(bridge.transfers(), vec![request(1, 2)]);
+	}
+
+	#[tokio::test]
+	async fn test_mock_bridge_rejects_unsupported_routes() {
+		let config: toml::Value = toml::from_str("supported_chains = [1, 2]").unwrap();
+		let bridge = MockBridge::new(&config).unwrap();
+
+		assert!(bridge.supports_route(1, 2));
+		assert!(!bridge.supports_route(1, 3));
+		assert!(!bridge.supports_route(1, 1));
+		assert!(matches!(
+			bridge.build_transfer(&request(1, 3)).await,
+			Err(BridgeError::UnsupportedRoute(_))
+		));
+		assert!(bridge.transfers().is_empty());
+	}
+
+	#[test]
+	fn test_mock_bridge_schema() {
+		let valid: toml::Value = toml::from_str("fee_bps = 10").unwrap();
+		assert!(MockBridgeSchema.validate(&valid).is_ok());
+
+		let invalid: toml::Value = toml::from_str("fee_bps = 20000").unwrap();
+		assert!(MockBridgeSchema.validate(&invalid).is_err());
+	}
+}
@@ -0,0 +1,332 @@
+//! Cross-chain inventory rebalancing for the OIF solver system.
+//!
+//! Fills drain liquidity on destination chains while claims accumulate it on
+//! origin chains. This module computes the transfers that bring an asset back
+//! to its target allocation across chains and moves funds through pluggable
+//! bridge adapters. The EIP-7683 adapter self-solves by opening an order on the
+//! surplus chain that pays out on the deficit chain; the mock adapter records
+//! transfers locally for development and tests.
+
+use alloy_primitives::U256;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use solver_types::{
+	oracle::OracleRoutes, Address, ConfigSchema, ImplementationRegistry, NetworksConfig,
+	Transaction,
+};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Re-export implementations
+pub mod implementations {
+	pub mod eip7683;
+	pub mod mock;
+}
+
+/// Errors that can occur during bridge operations.
+#[derive(Debug, Error)]
+pub enum BridgeError {
+	/// Error that occurs when the bridge configuration is invalid.
+	#[error("Invalid configuration: {0}")]
+	InvalidConfig(String),
+	/// Error that occurs when the bridge cannot move funds between two chains.
+	#[error("Unsupported route: {0}")]
+	UnsupportedRoute(String),
+	/// Error that occurs when a transfer cannot be built.
+	#[error("Transfer failed: {0}")]
+	TransferFailed(String),
+}
+
+/// A transfer of one asset between two chains.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferRequest {
+	/// Asset name from the rebalance config.
+	pub asset: String,
+	/// Chain funds are taken from.
+	pub from_chain: u64,
+	/// Chain funds are delivered to.
+	pub to_chain: u64,
+	/// Token address on the source chain.
+	pub from_token: Address,
+	/// Token address on the destination chain.
+	pub to_token: Address,
+	/// Amount taken from the sender, in the token's smallest unit.
+	pub amount: U256,
+	/// Account sending the funds on the source chain.
+	pub sender: Address,
+	/// Account receiving the funds on the destination chain.
+	pub recipient: Address,
+}
+
+/// Transactions that move funds for a transfer request.
+#[derive(Debug, Clone)]
+pub struct BridgeTransfer {
+	/// Contract that must be allowed to spend the amount of the source token.
+	pub spender: Option<Address>,
+	/// Transactions the sender submits on the source chain, in order.
+	pub transactions: Vec<Transaction>,
+	/// Amount expected to arrive on the destination chain.
+	pub expected_amount: U256,
+}
+
+/// Trait defining the interface for bridge adapters.
+///
+/// Adapters turn a transfer request into transactions on the source chain.
+/// Submitting them, and managing the token allowance they need, is left to
+/// the caller.
+#[async_trait]
+pub trait BridgeInterface: Send + Sync {
+	/// Returns the configuration schema for this bridge implementation.
+	fn config_schema(&self) -> Box<dyn ConfigSchema>;
+
+	/// Returns whether the bridge can move funds from one chain to another.
+	fn supports_route(&self, from_chain: u64, to_chain: u64) -> bool;
+
+	/// Builds the transactions that move funds for a transfer request.
+	async fn build_transfer(
+		&self,
+		request: &TransferRequest,
+	) -> Result<BridgeTransfer, BridgeError>;
+}
+
+/// Type alias for bridge factory functions.
+///
+/// This is the function signature that all bridge implementations must provide
+/// to create instances of their bridge interface.
+pub type BridgeFactory = fn(
+	&toml::Value,
+	&NetworksConfig,
+	&OracleRoutes,
+) -> Result<Box<dyn BridgeInterface>, BridgeError>;
+
+/// Registry trait for bridge implementations.
+pub trait BridgeRegistry: ImplementationRegistry<Factory = BridgeFactory> {}
+
+/// Get all registered bridge implementations.
+pub fn get_all_implementations() -> Vec<(&'static str, BridgeFactory)> {
+	use implementations::{eip7683, mock};
+	vec![
+		(eip7683::Registry::NAME, eip7683::Registry::factory()),
+		(mock::Registry::NAME, mock::Registry::factory()),
+	]
+}
+
+/// Get configuration schemas for all registered bridge implementations.
+pub fn get_all_schemas() -> Vec<(&'static str, Box<dyn ConfigSchema>)> {
+	use implementations::{eip7683, mock};
+	vec![
+		(eip7683::Registry::NAME, eip7683::Registry::config_schema()),
+		(mock::Registry::NAME, mock::Registry::config_schema()),
+	]
+}
+
+/// Size and tolerance limits for rebalancing one asset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebalanceLimits {
+	/// Smallest transfer worth making.
+	pub min_transfer: U256,
+	/// Largest single transfer.
+	pub max_transfer: U256,
+	/// Deviation from the target, in basis points of the total, that is tolerated.
+	pub tolerance_bps: u32,
+}
+
+/// A transfer computed by [`plan_transfers`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedTransfer {
+	/// Chain holding more than its target.
+	pub from_chain: u64,
+	/// Chain holding less than its target.
+	pub to_chain: u64,
+	/// Amount to move, in the asset's smallest unit.
+	pub amount: U256,
+}
+
+/// Computes the transfers that bring balances back to their target allocation.
+///
+/// `balances` holds the asset balance per chain and `targets` the share of the
+/// total each chain should hold, in basis points. Chains are only rebalanced
+/// once they deviate from their target by more than the tolerance; surplus is
+/// then moved to the chains with the largest shortfall first. Each transfer is
+/// capped at the maximum size and transfers below the minimum are dropped.
+pub fn plan_transfers(
+	balances: &HashMap<u64, U256>,
+	targets: &[(u64, u32)],
+	limits: &RebalanceLimits,
+) -> Vec<PlannedTransfer> {
+	let bps = U256::from(10_000u64);
+	let balance_of = |chain_id: u64| balances.get(&chain_id).copied().unwrap_or_default();
+	let total = targets.iter().fold(U256::ZERO, |sum, (chain_id, _)| {
+		sum.saturating_add(balance_of(*chain_id))
+	});
+	if total.is_zero() {
+		return Vec::new();
+	}
+	let band = total * U256::from(limits.tolerance_bps) / bps;
+
+	let mut surpluses = Vec::new();
+	let mut deficits = Vec::new();
+	for (chain_id, target_bps) in targets {
+		let balance = balance_of(*chain_id);
+		let target = total * U256::from(*target_bps) / bps;
+		if balance > target.saturating_add(band) {
+			surpluses.push((*chain_id, balance - target));
+		} else if balance.saturating_add(band) < target {
+			deficits.push((*chain_id, target - balance));
+		}
+	}
+	// Largest imbalances first, chain ID as a stable tie-breaker
+	surpluses.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+	deficits.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+	let mut transfers = Vec::new();
+	for (to_chain, shortfall) in deficits.iter_mut() {
+		for (from_chain, excess) in surpluses.iter_mut() {
+			if shortfall.is_zero() {
+				break;
+			}
+			let amount = (*excess).min(*shortfall).min(limits.max_transfer);
+			if amount.is_zero() || amount < limits.min_transfer {
+				continue;
+			}
+			*excess -= amount;
+			*shortfall -= amount;
+			transfers.push(PlannedTransfer {
+				from_chain: *from_chain,
+				to_chain: *to_chain,
+				amount,
+			});
+		}
+	}
+	transfers
+}
+
+/// Service that selects bridge adapters for transfers.
+pub struct RebalanceService {
+	/// Bridge implementations, in the order they are tried.
+	bridges: Vec<(String, Box<dyn BridgeInterface>)>,
+}
+
+impl RebalanceService {
+	/// Creates a new RebalanceService with bridges in order of preference.
+	pub fn new(bridges: Vec<(String, Box<dyn BridgeInterface>)>) -> Self {
+		Self { bridges }
+	}
+
+	/// Returns whether any bridge is configured.
+	pub fn is_empty(&self) -> bool {
+		self.bridges.is_empty()
+	}
+
+	/// Returns the first bridge that supports a route, with its name.
+	pub fn bridge_for(
+		&self,
+		from_chain: u64,
+		to_chain: u64,
+	) -> Option<(&str, &dyn BridgeInterface)> {
+		self.bridges
+			.iter()
+			.find(|(_, bridge)| bridge.supports_route(from_chain, to_chain))
+			.map(|(name, bridge)| (name.as_str(), bridge.as_ref()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn limits(min: u64, max: u64, tolerance_bps: u32) -> RebalanceLimits {
+		RebalanceLimits {
+			min_transfer: U256::from(min),
+			max_transfer: U256::from(max),
+			tolerance_bps,
+		}
+	}
+
+	fn balances(entries: &[(u64, u64)]) -> HashMap<u64, U256> {
+		entries
+			.iter()
+			.map(|(chain_id, balance)| (*chain_id, U256::from(*balance)))
+			.collect()
+	}
+
+	#[test]
+	fn test_plan_moves_surplus_to_deficit() {
+		let transfers = plan_transfers(
+			&balances(&[(1, 900), (2, 100)]),
+			&[(1, 5000), (2, 5000)],
+			&limits(10, 10_000, 500),
+		);
+		assert_eq!(
+			transfers,
+			vec![PlannedTransfer {
+				from_chain: 1,
+				to_chain: 2,
+				amount: U256::from(400),
+			}]
+		);
+	}
+
+	#[test]
+	fn test_plan_respects_tolerance() {
+		// 540 vs a target of 500 is within 5% of the total
+		let transfers = plan_transfers(
+			&balances(&[(1, 540), (2, 460)]),
+			&[(1, 5000), (2, 5000)],
+			&limits(1, 10_000, 500),
+		);
+		assert!(transfers.is_empty());
+	}
+
+	#[test]
+	fn test_plan_caps_and_drops_transfers() {
+		let transfers = plan_transfers(
+			&balances(&[(1, 900), (2, 100)]),
+			&[(1, 5000), (2, 5000)],
+			&limits(10, 300, 0),
+		);
+		assert_eq!(transfers.len(), 1);
+		assert_eq!(transfers[0].amount, U256::from(300));
+
+		let transfers = plan_transfers(
+			&balances(&[(1, 900), (2, 100)]),
+			&[(1, 5000), (2, 5000)],
+			&limits(500, 10_000, 0),
+		);
+		assert!(transfers.is_empty());
+	}
+
+	#[test]
+	fn test_plan_spreads_surplus_across_chains() {
+		let transfers = plan_transfers(
+			&balances(&[(1, 1200), (2, 0), (3, 300)]),
+			&[(1, 4000), (2, 3000), (3, 3000)],
+			&limits(1, 10_000, 0),
+		);
+		assert_eq!(
+			transfers,
+			vec![
+				PlannedTransfer {
+					from_chain: 1,
+					to_chain: 2,
+					amount: U256::from(450),
+				},
+				PlannedTransfer {
+					from_chain: 1,
+					to_chain: 3,
+					amount: U256::from(150),
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn test_plan_ignores_empty_inventory() {
+		let transfers = plan_transfers(
+			&HashMap::new(),
+			&[(1, 5000), (2, 5000)],
+			&limits(1, 10_000, 0),
+		);
+		assert!(transfers.is_empty());
+	}
+}
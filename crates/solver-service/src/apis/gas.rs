@@ -0,0 +1,35 @@
+//! Learned gas API for the OIF Solver.
+//!
+//! This module exposes the gas units the solver has learned from the receipts
+//! of its own confirmed transactions.
+
+use axum::{extract::State, Json};
+use serde::Serialize;
+use solver_core::{engine::gas_model::GasUsageStats, SolverEngine};
+use std::sync::Arc;
+
+/// Response structure for learned gas units.
+#[derive(Debug, Serialize)]
+pub struct GasResponse {
+	/// Whether learned units are used for quoting and profitability.
+	pub enabled: bool,
+	/// Percentile of recorded gas used for estimates.
+	pub percentile: u8,
+	/// Samples a key needs before its learned units are used.
+	pub min_samples: usize,
+	/// Recorded gas usage per flow, chain, transaction type and number of outputs.
+	pub entries: Vec<GasUsageStats>,
+}
+
+/// Handles GET /api/gas requests.
+///
+/// Returns the learned gas table of the running solver.
+pub async fn get_gas(State(solver): State<Arc<SolverEngine>>) -> Json<GasResponse> {
+	let learned = solver.config().learned_gas();
+	Json(GasResponse {
+		enabled: learned.enabled,
+		percentile: learned.percentile,
+		min_samples: learned.min_samples,
+		entries: solver.gas_model().table(),
+	})
+}
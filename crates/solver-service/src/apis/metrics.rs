@@ -0,0 +1,25 @@
+//! Metrics API for the OIF Solver.
+//!
+//! This module exposes counters the solver keeps about its own decisions,
+//! such as how often orders were skipped or deferred and why.
+
+use axum::{extract::State, Json};
+use serde::Serialize;
+use solver_core::{engine::metrics::DecisionCounts, SolverEngine};
+use std::sync::Arc;
+
+/// Response structure for solver metrics.
+#[derive(Debug, Serialize)]
+pub struct MetricsResponse {
+	/// Skipped and deferred orders by reason label since startup.
+	pub decisions: DecisionCounts,
+}
+
+/// Handles GET /api/metrics requests.
+///
+/// Returns the decision counters of the running solver.
+pub async fn get_metrics(State(solver): State<Arc<SolverEngine>>) -> Json<MetricsResponse> {
+	Json(MetricsResponse {
+		decisions: solver.decision_metrics().snapshot(),
+	})
+}
@@ -4,6 +4,8 @@
 //! for the OIF Solver.
 
 pub mod auth;
+pub mod gas;
+pub mod metrics;
 pub mod order;
 pub mod quote;
 pub mod tokens;
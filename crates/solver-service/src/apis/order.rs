@@ -182,6 +182,7 @@ async fn convert_signet_order_to_response(
 			data: settlement_data,
 		},
 		fill_transaction,
+		revert_reason: order.revert_reason.as_ref().map(ToString::to_string),
 	};
 
 	Ok(response)
@@ -343,6 +344,7 @@ async fn convert_eip7683_order_to_response(
 			},
 			// Fill succeeded but later transaction failed
 			OrderStatus::Failed(TransactionType::PostFill)
+			| OrderStatus::Failed(TransactionType::Relay)
 			| OrderStatus::Failed(TransactionType::PreClaim)
 			| OrderStatus::Failed(TransactionType::Claim) => "executed",
 		};
@@ -367,6 +369,7 @@ async fn convert_eip7683_order_to_response(
 			data: settlement_data,
 		},
 		fill_transaction,
+		revert_reason: order.revert_reason.as_ref().map(ToString::to_string),
 	};
 
 	Ok(response)
@@ -381,11 +384,15 @@ mod tests {
 	use serde_json::json;
 	use solver_account::{implementations::local::LocalWallet, AccountService};
 	use solver_config::{Config, ConfigBuilder};
-	use solver_core::{engine::token_manager::TokenManager, EventBus, SolverEngine};
+	use solver_core::{
+		engine::{accounts::SolverAccounts, token_manager::TokenManager},
+		EventBus, SolverEngine,
+	};
 	use solver_delivery::DeliveryService;
 	use solver_discovery::DiscoveryService;
 	use solver_order::{implementations::strategies::simple::create_strategy, OrderService};
 	use solver_pricing::{implementations::mock, PricingService};
+	use solver_rebalance::RebalanceService;
 	use solver_settlement::SettlementService;
 	use solver_storage::{MockStorageInterface, StorageError};
 	use solver_types::utils::tests::builders::OrderBuilder;
@@ -427,8 +434,13 @@ mod tests {
 			networks,
 			delivery.clone(),
 			account.clone(),
+			Default::default(),
 		));
 		let solver_address = addr();
+		let accounts = Arc::new(SolverAccounts::single(
+			solver_address.clone(),
+			delivery.clone(),
+		));
 
 		// Create a mock pricing service for tests
 		let pricing_config = toml::Value::Table(toml::map::Map::new());
@@ -440,6 +452,7 @@ mod tests {
 			storage,
 			account,
 			solver_address,
+			accounts,
 			delivery,
 			discovery,
 			order,
@@ -447,6 +460,7 @@ mod tests {
 			pricing,
 			event_bus,
 			token_manager,
+			Arc::new(RebalanceService::new(Vec::new())),
 		)
 	}
 
@@ -49,7 +49,9 @@
 //! - **Input Priority**: Preference for specific input tokens
 
 use super::custody::{CustodyDecision, CustodyStrategy, EscrowKind, LockKind};
+use super::signing::payloads::solver_quote::sign_quote;
 use crate::eip712::{compact, get_domain_separator};
+use solver_account::AccountService;
 use solver_config::{Config, QuoteConfig};
 use solver_delivery::DeliveryService;
 use solver_settlement::{SettlementInterface, SettlementService};
@@ -67,6 +69,8 @@ pub struct QuoteGenerator {
 	settlement_service: Arc<SettlementService>,
 	/// Reference to delivery service for contract calls.
 	delivery_service: Arc<DeliveryService>,
+	/// Solver account that signs generated quotes, if quotes are signed.
+	solver_account: Option<Arc<AccountService>>,
 }
 
 impl QuoteGenerator {
@@ -83,9 +87,19 @@ impl QuoteGenerator {
 			custody_strategy: CustodyStrategy::new(),
 			settlement_service,
 			delivery_service,
+			solver_account: None,
 		}
 	}
 
+	/// Signs every generated quote with the given solver account.
+	///
+	/// The signature covers the quote terms and expiry so that aggregators can
+	/// verify the quote was issued by this solver.
+	pub fn with_solver_account(mut self, account: Arc<AccountService>) -> Self {
+		self.solver_account = Some(account);
+		self
+	}
+
 	pub async fn generate_quotes(
 		&self,
 		request: &GetQuoteRequest,
@@ -137,7 +151,7 @@ impl QuoteGenerator {
 			},
 		};
 		let validity_seconds = self.get_quote_validity_seconds(config);
-		Ok(Quote {
+		let mut quote = Quote {
 			orders: vec![order],
 			details,
 			valid_until: Some(chrono::Utc::now().timestamp() as u64 + validity_seconds),
@@ -146,7 +160,13 @@ impl QuoteGenerator {
 			provider: "oif-solver".to_string(),
 			cost: None,
 			lock_type,
-		})
+			solver_signature: None,
+			headroom_usd: None,
+		};
+		if let Some(account) = &self.solver_account {
+			quote.solver_signature = Some(sign_quote(&quote, config, account).await?);
+		}
+		Ok(quote)
 	}
 
 	async fn generate_resource_lock_order(
@@ -190,9 +210,17 @@ impl QuoteGenerator {
 			.ethereum_chain_id()
 			.map_err(|e| QuoteError::InvalidRequest(format!("Invalid chain ID: {}", e)))?;
 
+		let input_chain_id = request
+			.available_inputs
+			.first()
+			.ok_or_else(|| QuoteError::InvalidRequest("No available inputs".to_string()))?
+			.asset
+			.ethereum_chain_id()
+			.map_err(|e| QuoteError::InvalidRequest(format!("Invalid chain ID: {}", e)))?;
+
 		// For escrow orders, prefer Direct settlement over other implementations
 		let (settlement, selected_oracle) = self
-			.get_preferred_settlement_for_escrow(chain_id)
+			.get_preferred_settlement_for_escrow(input_chain_id, chain_id)
 			.ok_or_else(|| {
 				QuoteError::InvalidRequest(format!(
 					"No suitable settlement available for escrow on chain {}",
@@ -202,11 +230,11 @@ impl QuoteGenerator {
 
 		match escrow_kind {
 			EscrowKind::Permit2 => {
-				self.generate_permit2_order(request, config, settlement, selected_oracle)
+				self.generate_permit2_order(request, config, settlement.as_ref(), selected_oracle)
 					.await
 			},
 			EscrowKind::Eip3009 => {
-				self.generate_eip3009_order(request, config, settlement, selected_oracle)
+				self.generate_eip3009_order(request, config, settlement.as_ref(), selected_oracle)
 					.await
 			},
 		}
@@ -810,6 +838,7 @@ impl QuoteGenerator {
 		let encoded = call.abi_encode();
 
 		let tx = solver_types::Transaction {
+			from: None,
 			to: Some(solver_types::Address(token_address.0.to_vec())),
 			data: encoded,
 			value: alloy_primitives::U256::ZERO,
@@ -834,12 +863,25 @@ impl QuoteGenerator {
 		Ok(name)
 	}
 
-	/// Get preferred settlement for escrow orders (prioritizes Direct settlement)
+	/// Get preferred settlement for escrow orders.
+	///
+	/// Uses oracle health scores for the route from `input_chain_id` to `chain_id`
+	/// when any settlement is configured with the scored strategy, otherwise
+	/// prioritizes Direct settlement.
 	fn get_preferred_settlement_for_escrow(
 		&self,
+		input_chain_id: u64,
 		chain_id: u64,
-	) -> Option<(&dyn SettlementInterface, solver_types::Address)> {
-		// First, try to get Direct settlement specifically
+	) -> Option<(Arc<dyn SettlementInterface>, solver_types::Address)> {
+		// Prefer the best-scoring settlement and oracle when scoring is enabled
+		if let Some(best) = self
+			.settlement_service
+			.get_best_settlement_for_route(input_chain_id, chain_id)
+		{
+			return Some(best);
+		}
+
+		// Otherwise, try to get Direct settlement specifically
 		if let Some(direct_settlement) = self.settlement_service.get("direct") {
 			if let Some(oracles) = direct_settlement
 				.oracle_config()
@@ -1370,6 +1412,7 @@ mod tests {
 				output_oracles,
 				routes,
 				selection_strategy: solver_settlement::OracleSelectionStrategy::First,
+				health: None,
 			});
 
 		if with_oracles {
@@ -1510,7 +1553,7 @@ mod tests {
 		let request = create_test_request();
 
 		let result = generator
-			.generate_eip3009_order(&request, &config, settlement, selected_oracle)
+			.generate_eip3009_order(&request, &config, settlement.as_ref(), selected_oracle)
 			.await;
 
 		match result {
@@ -1628,6 +1671,8 @@ mod tests {
 				provider: "test".to_string(),
 				cost: None,
 				lock_type: "permit2_escrow".to_string(),
+				solver_signature: None,
+				headroom_usd: None,
 			},
 			Quote {
 				orders: vec![],
@@ -1641,6 +1686,8 @@ mod tests {
 				provider: "test".to_string(),
 				cost: None,
 				lock_type: "permit2_escrow".to_string(),
+				solver_signature: None,
+				headroom_usd: None,
 			},
 			Quote {
 				orders: vec![],
@@ -1654,6 +1701,8 @@ mod tests {
 				provider: "test".to_string(),
 				cost: None,
 				lock_type: "permit2_escrow".to_string(),
+				solver_signature: None,
+				headroom_usd: None,
 			},
 		];
 
@@ -1685,6 +1734,8 @@ mod tests {
 				provider: "test".to_string(),
 				cost: None,
 				lock_type: "permit2_escrow".to_string(),
+				solver_signature: None,
+				headroom_usd: None,
 			},
 			Quote {
 				orders: vec![],
@@ -1698,6 +1749,8 @@ mod tests {
 				provider: "test".to_string(),
 				cost: None,
 				lock_type: "permit2_escrow".to_string(),
+				solver_signature: None,
+				headroom_usd: None,
 			},
 		];
 
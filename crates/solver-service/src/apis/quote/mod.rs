@@ -18,9 +18,10 @@
 //! 1. **Request Reception**: User submits transfer intent with inputs/outputs
 //! 2. **Validation**: Verify request format, supported chains, and token availability
 //! 3. **Capability Check**: Ensure solver can execute on specified chains
-//! 4. **Balance Verification**: Confirm solver has sufficient output tokens
-//! 5. **Quote Generation**: Create multiple quote options with different mechanisms
-//! 6. **Storage**: Persist quotes for later retrieval and execution
+//! 4. **Exposure Check**: Keep in-flight capital within the strategy's limits
+//! 5. **Balance Verification**: Confirm solver has sufficient output tokens
+//! 6. **Quote Generation**: Create multiple quote options with different mechanisms
+//! 7. **Storage**: Persist quotes for later retrieval and execution
 //!
 //! ## Key Features
 //!
@@ -43,8 +44,9 @@
 //!
 //! ## API Integration
 //!
-//! The module exposes three main functions:
+//! The module exposes four main functions:
 //! - `process_quote_request`: Main entry point for quote generation
+//! - `verify_quote`: Check the solver signature on a returned quote
 //! - `get_quote_by_id`: Retrieve stored quotes
 //! - `quote_exists`: Check quote validity
 //!
@@ -75,7 +77,10 @@ pub use validation::QuoteValidator;
 
 use solver_config::{Config, QuoteConfig};
 use solver_core::SolverEngine;
-use solver_types::{GetQuoteRequest, GetQuoteResponse, Quote, QuoteError, StorageKey};
+use solver_types::{
+	current_timestamp, utils::bytes20_to_alloy_address, GetQuoteRequest, GetQuoteResponse, Quote,
+	QuoteError, StorageKey, VerifyQuoteResponse,
+};
 
 use std::time::Duration;
 use tracing::info;
@@ -106,13 +111,22 @@ pub async fn process_quote_request(
 		QuoteValidator::validate_and_collect_requested_outputs(&request, solver)?,
 	);
 
+	// Refuse routes paused for lack of destination inventory
+	QuoteValidator::ensure_routes_active(solver, &supported_outputs)?;
+
+	// Refuse outputs above the exposure limits and report the remaining headroom
+	let headroom =
+		QuoteValidator::ensure_exposure_headroom(&request, solver, &supported_outputs, config)
+			.await?;
+
 	// Check destination balances for required outputs
 	QuoteValidator::ensure_destination_balances(solver, &supported_outputs).await?;
 
 	// Generate quotes using the business logic layer
 	let settlement_service = solver.settlement();
 	let delivery_service = solver.delivery();
-	let quote_generator = QuoteGenerator::new(settlement_service.clone(), delivery_service.clone());
+	let quote_generator = QuoteGenerator::new(settlement_service.clone(), delivery_service.clone())
+		.with_solver_account(solver.account().clone());
 	let mut quotes = quote_generator.generate_quotes(&request, config).await?;
 
 	// Enrich quotes with a preliminary cost breakdown using CostProfitService
@@ -120,9 +134,14 @@ pub async fn process_quote_request(
 		solver.pricing().clone(),
 		solver.delivery().clone(),
 		solver.token_manager().clone(),
+		solver.settlement().clone(),
+		solver.order().clone(),
+		solver.gas_model().clone(),
+		solver.chain_state().clone(),
 	);
 
 	for quote in &mut quotes {
+		quote.headroom_usd = headroom.map(|headroom| headroom.to_string());
 		match cost_profit_service
 			.estimate_cost_for_quote(quote, config)
 			.await
@@ -155,6 +174,29 @@ pub async fn process_quote_request(
 	Ok(GetQuoteResponse { quotes })
 }
 
+/// Verifies the solver signature attached to a quote.
+///
+/// Lets aggregators check that a quote was signed by this solver's account,
+/// that its terms were not altered and that it has not expired.
+pub async fn verify_quote(
+	quote: &Quote,
+	solver: &SolverEngine,
+) -> Result<VerifyQuoteResponse, QuoteError> {
+	let solver_address = solver
+		.account()
+		.get_address()
+		.await
+		.map_err(|e| QuoteError::Internal(format!("Failed to get solver address: {}", e)))?;
+	let solver_address =
+		bytes20_to_alloy_address(&solver_address.0).map_err(QuoteError::Internal)?;
+
+	Ok(signing::payloads::solver_quote::verify_quote_signature(
+		quote,
+		&solver_address,
+		current_timestamp(),
+	))
+}
+
 /// Stores generated quotes with a given TTL.
 ///
 /// Storage errors are logged but do not fail the request.
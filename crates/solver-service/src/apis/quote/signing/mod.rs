@@ -19,6 +19,11 @@
 //! - Batch transfers with witness data
 //! - Cross-chain intent specification
 //!
+//! ### Solver Quote
+//! EIP-712 signature by the solver account over the terms of each returned quote:
+//! - Lets aggregators check that a quote was issued by this solver
+//! - Binds the inputs, outputs and expiry so quotes cannot be altered
+//!
 //! ### EIP-3009 (Future)
 //! Transfer with authorization for USDC and similar tokens:
 //! - Native gasless transfers
@@ -34,4 +39,5 @@
 
 pub mod payloads {
 	pub mod permit2;
+	pub mod solver_quote;
 }
@@ -0,0 +1,467 @@
+//! Solver signatures over quote terms.
+//!
+//! Every quote returned by the solver is signed by the solver of record using EIP-712, so
+//! aggregators can check that a quote was issued by this solver and has not been altered
+//! in transit. The signed `SolverQuote` struct binds the quote ID, lock type, every input
+//! and output leg and the expiry. Informational fields such as the ETA and the cost
+//! breakdown are not signed.
+//!
+//! ## Key Components
+//!
+//! - **Domain Selection**: The configured settlement domain, or the input settler on the
+//!   origin chain when none is configured
+//! - **Digest**: Computed with the shared EIP-712 helpers so verification needs no signer
+//! - **Typed Data**: The same struct in JSON form, handed to the account for signing
+
+use alloy_dyn_abi::TypedData;
+use alloy_primitives::{keccak256, Address as AlloyAddress, Signature, B256, U256};
+use serde_json::json;
+use solver_account::AccountService;
+use solver_config::Config;
+use solver_types::utils::{
+	bytes20_to_alloy_address, compute_domain_hash, compute_final_digest, Eip712AbiEncoder,
+	NAME_SOLVER_QUOTE, QUOTE_INPUT_TYPE, QUOTE_OUTPUT_TYPE, SOLVER_QUOTE_TYPE,
+};
+use solver_types::{
+	standards::eip7930::InteropAddressError, with_0x_prefix, without_0x_prefix, Quote, QuoteError,
+	QuoteSignature, VerifyQuoteResponse,
+};
+
+/// Input or output of a quote as it appears in the signed struct.
+struct QuoteLeg {
+	/// User for inputs, receiver for outputs.
+	account: AlloyAddress,
+	chain_id: u64,
+	asset: AlloyAddress,
+	amount: U256,
+}
+
+/// Returns the EIP-712 domain (chain ID, verifying contract) used to sign a quote.
+pub fn solver_quote_domain(
+	quote: &Quote,
+	config: &Config,
+) -> Result<(u64, AlloyAddress), QuoteError> {
+	if let Some(domain) = &config.settlement.domain {
+		let address = domain.address.parse().map_err(|e| {
+			QuoteError::InvalidRequest(format!("Invalid domain address in config: {}", e))
+		})?;
+		return Ok((domain.chain_id, address));
+	}
+
+	let input = quote
+		.details
+		.available_inputs
+		.first()
+		.ok_or_else(|| QuoteError::InvalidRequest("Quote has no inputs".to_string()))?;
+	let chain_id = input.asset.ethereum_chain_id().map_err(|e| {
+		QuoteError::InvalidRequest(format!("Invalid origin chain ID in asset address: {}", e))
+	})?;
+	let network = config.networks.get(&chain_id).ok_or_else(|| {
+		QuoteError::InvalidRequest(format!(
+			"Origin chain {} missing from networks config",
+			chain_id
+		))
+	})?;
+	let input_settler = bytes20_to_alloy_address(&network.input_settler_address.0)
+		.map_err(QuoteError::InvalidRequest)?;
+
+	Ok((chain_id, input_settler))
+}
+
+/// Computes the EIP-712 digest of a quote's `SolverQuote` struct.
+pub fn solver_quote_digest(
+	quote: &Quote,
+	chain_id: u64,
+	verifying_contract: &AlloyAddress,
+) -> Result<B256, QuoteError> {
+	let (inputs, outputs) = quote_legs(quote)?;
+	let valid_until = quote_expiry(quote)?;
+
+	let type_hash = keccak256(
+		format!(
+			"{}{}{}",
+			SOLVER_QUOTE_TYPE, QUOTE_INPUT_TYPE, QUOTE_OUTPUT_TYPE
+		)
+		.as_bytes(),
+	);
+	let inputs_hash = hash_legs(&keccak256(QUOTE_INPUT_TYPE.as_bytes()), &inputs);
+	let outputs_hash = hash_legs(&keccak256(QUOTE_OUTPUT_TYPE.as_bytes()), &outputs);
+
+	let mut enc = Eip712AbiEncoder::new();
+	enc.push_b256(&type_hash);
+	enc.push_b256(&keccak256(quote.quote_id.as_bytes()));
+	enc.push_b256(&keccak256(quote.lock_type.as_bytes()));
+	enc.push_b256(&inputs_hash);
+	enc.push_b256(&outputs_hash);
+	enc.push_u256(U256::from(valid_until));
+	let struct_hash = keccak256(enc.finish());
+
+	let domain_hash = compute_domain_hash(NAME_SOLVER_QUOTE, chain_id, verifying_contract);
+	Ok(compute_final_digest(&domain_hash, &struct_hash))
+}
+
+/// Builds the `SolverQuote` typed data for a quote, as passed to the signing account.
+pub fn solver_quote_typed_data(
+	quote: &Quote,
+	chain_id: u64,
+	verifying_contract: &AlloyAddress,
+) -> Result<TypedData, QuoteError> {
+	let (inputs, outputs) = quote_legs(quote)?;
+	let valid_until = quote_expiry(quote)?;
+
+	let leg_json = |leg: &QuoteLeg, account_field: &str| {
+		json!({
+			account_field: leg.account.to_string(),
+			"chainId": leg.chain_id,
+			"asset": leg.asset.to_string(),
+			"amount": leg.amount.to_string(),
+		})
+	};
+
+	let typed_data = json!({
+		"types": {
+			"EIP712Domain": [
+				{ "name": "name", "type": "string" },
+				{ "name": "chainId", "type": "uint256" },
+				{ "name": "verifyingContract", "type": "address" }
+			],
+			"SolverQuote": [
+				{ "name": "quoteId", "type": "string" },
+				{ "name": "lockType", "type": "string" },
+				{ "name": "inputs", "type": "QuoteInput[]" },
+				{ "name": "outputs", "type": "QuoteOutput[]" },
+				{ "name": "validUntil", "type": "uint256" }
+			],
+			"QuoteInput": [
+				{ "name": "user", "type": "address" },
+				{ "name": "chainId", "type": "uint256" },
+				{ "name": "asset", "type": "address" },
+				{ "name": "amount", "type": "uint256" }
+			],
+			"QuoteOutput": [
+				{ "name": "receiver", "type": "address" },
+				{ "name": "chainId", "type": "uint256" },
+				{ "name": "asset", "type": "address" },
+				{ "name": "amount", "type": "uint256" }
+			]
+		},
+		"primaryType": "SolverQuote",
+		"domain": {
+			"name": NAME_SOLVER_QUOTE,
+			"chainId": chain_id,
+			"verifyingContract": verifying_contract.to_string()
+		},
+		"message": {
+			"quoteId": quote.quote_id,
+			"lockType": quote.lock_type,
+			"inputs": inputs.iter().map(|leg| leg_json(leg, "user")).collect::<Vec<_>>(),
+			"outputs": outputs.iter().map(|leg| leg_json(leg, "receiver")).collect::<Vec<_>>(),
+			"validUntil": valid_until
+		}
+	});
+
+	serde_json::from_value(typed_data)
+		.map_err(|e| QuoteError::Internal(format!("Failed to build quote typed data: {}", e)))
+}
+
+/// Signs a quote's terms and expiry with the given solver account.
+pub async fn sign_quote(
+	quote: &Quote,
+	config: &Config,
+	account: &AccountService,
+) -> Result<QuoteSignature, QuoteError> {
+	let (chain_id, verifying_contract) = solver_quote_domain(quote, config)?;
+	let typed_data = solver_quote_typed_data(quote, chain_id, &verifying_contract)?;
+
+	let signer = account
+		.get_address()
+		.await
+		.map_err(|e| QuoteError::Internal(format!("Failed to get solver address: {}", e)))?;
+	let signature = account
+		.sign_typed_data(&typed_data)
+		.await
+		.map_err(|e| QuoteError::Internal(format!("Failed to sign quote: {}", e)))?;
+
+	Ok(QuoteSignature {
+		signer: signer.to_string(),
+		chain_id,
+		verifying_contract: verifying_contract.to_string(),
+		signature: with_0x_prefix(&hex::encode(&signature.0)),
+	})
+}
+
+/// Verifies that a quote carries a valid signature by `expected_signer` and has not expired.
+pub fn verify_quote_signature(
+	quote: &Quote,
+	expected_signer: &AlloyAddress,
+	now: u64,
+) -> VerifyQuoteResponse {
+	let expired = quote
+		.valid_until
+		.is_some_and(|valid_until| valid_until < now);
+	let rejected = |signer: Option<AlloyAddress>, reason: &str| VerifyQuoteResponse {
+		valid: false,
+		signer: signer.map(|s| s.to_string()),
+		expired,
+		reason: Some(reason.to_string()),
+	};
+
+	let Some(attestation) = &quote.solver_signature else {
+		return rejected(None, "Quote has no solver signature");
+	};
+	let recovered = match recover_quote_signer(quote, attestation) {
+		Ok(recovered) => recovered,
+		Err(e) => return rejected(None, &e.to_string()),
+	};
+
+	if attestation.signer.parse::<AlloyAddress>().ok() != Some(recovered) {
+		return rejected(
+			Some(recovered),
+			"Signature does not match the claimed signer",
+		);
+	}
+	if recovered != *expected_signer {
+		return rejected(Some(recovered), "Quote was not signed by this solver");
+	}
+	if expired {
+		return rejected(Some(recovered), "Quote has expired");
+	}
+
+	VerifyQuoteResponse {
+		valid: true,
+		signer: Some(recovered.to_string()),
+		expired: false,
+		reason: None,
+	}
+}
+
+/// Recovers the address that produced a quote's solver signature.
+fn recover_quote_signer(
+	quote: &Quote,
+	attestation: &QuoteSignature,
+) -> Result<AlloyAddress, QuoteError> {
+	let verifying_contract: AlloyAddress = attestation
+		.verifying_contract
+		.parse()
+		.map_err(|e| QuoteError::InvalidRequest(format!("Invalid verifying contract: {}", e)))?;
+	let digest = solver_quote_digest(quote, attestation.chain_id, &verifying_contract)?;
+
+	let bytes = hex::decode(without_0x_prefix(&attestation.signature))
+		.map_err(|e| QuoteError::InvalidRequest(format!("Invalid signature hex: {}", e)))?;
+	let signature = Signature::try_from(bytes.as_slice())
+		.map_err(|e| QuoteError::InvalidRequest(format!("Invalid signature: {}", e)))?;
+
+	signature
+		.recover_address_from_prehash(&digest)
+		.map_err(|e| QuoteError::InvalidRequest(format!("Failed to recover signer: {}", e)))
+}
+
+/// Returns the quote's expiry, which every signed quote must have.
+fn quote_expiry(quote: &Quote) -> Result<u64, QuoteError> {
+	quote
+		.valid_until
+		.ok_or_else(|| QuoteError::InvalidRequest("Quote has no expiry".to_string()))
+}
+
+/// Extracts the input and output legs of a quote.
+fn quote_legs(quote: &Quote) -> Result<(Vec<QuoteLeg>, Vec<QuoteLeg>), QuoteError> {
+	let invalid = |e: InteropAddressError| {
+		QuoteError::InvalidRequest(format!("Invalid quote address: {}", e))
+	};
+
+	let inputs = quote
+		.details
+		.available_inputs
+		.iter()
+		.map(|input| {
+			Ok(QuoteLeg {
+				account: input.user.ethereum_address().map_err(invalid)?,
+				chain_id: input.asset.ethereum_chain_id().map_err(invalid)?,
+				asset: input.asset.ethereum_address().map_err(invalid)?,
+				amount: input.amount,
+			})
+		})
+		.collect::<Result<Vec<_>, QuoteError>>()?;
+
+	let outputs = quote
+		.details
+		.requested_outputs
+		.iter()
+		.map(|output| {
+			Ok(QuoteLeg {
+				account: output.receiver.ethereum_address().map_err(invalid)?,
+				chain_id: output.asset.ethereum_chain_id().map_err(invalid)?,
+				asset: output.asset.ethereum_address().map_err(invalid)?,
+				amount: output.amount,
+			})
+		})
+		.collect::<Result<Vec<_>, QuoteError>>()?;
+
+	Ok((inputs, outputs))
+}
+
+/// Hashes an array of legs as `keccak256(hashStruct(leg_0) || ... || hashStruct(leg_n))`.
+fn hash_legs(type_hash: &B256, legs: &[QuoteLeg]) -> B256 {
+	let mut hashes = Vec::with_capacity(legs.len() * 32);
+	for leg in legs {
+		let mut enc = Eip712AbiEncoder::new();
+		enc.push_b256(type_hash);
+		enc.push_address(&leg.account);
+		enc.push_u256(U256::from(leg.chain_id));
+		enc.push_address(&leg.asset);
+		enc.push_u256(leg.amount);
+		hashes.extend_from_slice(keccak256(enc.finish()).as_slice());
+	}
+	keccak256(hashes)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_primitives::{address, uint};
+	use solver_account::implementations::local::LocalWallet;
+	use solver_config::{ConfigBuilder, DomainConfig, SettlementConfig};
+	use solver_types::{
+		standards::eip7930::InteropAddress, AvailableInput, QuoteDetails, RequestedOutput,
+	};
+	use std::collections::HashMap;
+
+	const TEST_PRIVATE_KEY: &str =
+		"ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+	const TEST_SIGNER: AlloyAddress = address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+
+	fn create_test_config() -> Config {
+		ConfigBuilder::new()
+			.settlement(SettlementConfig {
+				implementations: HashMap::new(),
+				domain: Some(DomainConfig {
+					chain_id: 1,
+					address: "0x1234567890123456789012345678901234567890".to_string(),
+				}),
+				settlement_poll_interval_seconds: 3,
+			})
+			.build()
+	}
+
+	fn create_test_quote(valid_until: u64) -> Quote {
+		Quote {
+			orders: vec![],
+			details: QuoteDetails {
+				requested_outputs: vec![RequestedOutput {
+					receiver: InteropAddress::new_ethereum(137, TEST_SIGNER),
+					asset: InteropAddress::new_ethereum(
+						137,
+						address!("2791Bca1f2de4661ED88A30C99A7a9449Aa84174"),
+					),
+					amount: uint!(990_000000_U256),
+					calldata: None,
+				}],
+				available_inputs: vec![AvailableInput {
+					user: InteropAddress::new_ethereum(1, TEST_SIGNER),
+					asset: InteropAddress::new_ethereum(
+						1,
+						address!("A0b86a33E6417c4c2f4066Ca7e40d36c4D5f8E1a"),
+					),
+					amount: uint!(1000_000000_U256),
+					lock: None,
+				}],
+			},
+			valid_until: Some(valid_until),
+			eta: Some(120),
+			quote_id: "test-quote".to_string(),
+			provider: "oif-solver".to_string(),
+			cost: None,
+			lock_type: "permit2_escrow".to_string(),
+			solver_signature: None,
+			headroom_usd: None,
+		}
+	}
+
+	fn create_test_account() -> AccountService {
+		AccountService::new(Box::new(LocalWallet::new(TEST_PRIVATE_KEY).unwrap()))
+	}
+
+	#[test]
+	fn test_digest_matches_typed_data_hash() {
+		let quote = create_test_quote(2_000_000_000);
+		let verifying_contract = address!("1234567890123456789012345678901234567890");
+
+		let digest = solver_quote_digest(&quote, 1, &verifying_contract).unwrap();
+		let typed_data = solver_quote_typed_data(&quote, 1, &verifying_contract).unwrap();
+
+		assert_eq!(digest, typed_data.eip712_signing_hash().unwrap());
+	}
+
+	#[test]
+	fn test_digest_binds_terms_and_expiry() {
+		let quote = create_test_quote(2_000_000_000);
+		let verifying_contract = address!("1234567890123456789012345678901234567890");
+		let digest = solver_quote_digest(&quote, 1, &verifying_contract).unwrap();
+
+		let mut later = quote.clone();
+		later.valid_until = Some(2_000_000_001);
+		assert_ne!(
+			digest,
+			solver_quote_digest(&later, 1, &verifying_contract).unwrap()
+		);
+
+		let mut smaller = quote.clone();
+		smaller.details.requested_outputs[0].amount = uint!(1_U256);
+		assert_ne!(
+			digest,
+			solver_quote_digest(&smaller, 1, &verifying_contract).unwrap()
+		);
+	}
+
+	#[tokio::test]
+	async fn test_sign_and_verify_quote() {
+		let config = create_test_config();
+		let mut quote = create_test_quote(2_000_000_000);
+
+		let signature = sign_quote(&quote, &config, &create_test_account())
+			.await
+			.unwrap();
+		assert_eq!(signature.chain_id, 1);
+		assert_eq!(
+			signature.signer.parse::<AlloyAddress>().unwrap(),
+			TEST_SIGNER
+		);
+		quote.solver_signature = Some(signature);
+
+		let result = verify_quote_signature(&quote, &TEST_SIGNER, 1_000_000_000);
+		assert!(result.valid);
+		assert!(!result.expired);
+		assert_eq!(
+			result.signer.unwrap().parse::<AlloyAddress>().unwrap(),
+			TEST_SIGNER
+		);
+	}
+
+	#[tokio::test]
+	async fn test_verify_rejects_tampered_expired_and_unsigned_quotes() {
+		let config = create_test_config();
+		let mut quote = create_test_quote(2_000_000_000);
+		quote.solver_signature = Some(
+			sign_quote(&quote, &config, &create_test_account())
+				.await
+				.unwrap(),
+		);
+
+		let mut tampered = quote.clone();
+		tampered.details.requested_outputs[0].amount = uint!(1_U256);
+		let result = verify_quote_signature(&tampered, &TEST_SIGNER, 1_000_000_000);
+		assert!(!result.valid);
+
+		let result = verify_quote_signature(&quote, &TEST_SIGNER, 2_000_000_001);
+		assert!(!result.valid);
+		assert!(result.expired);
+
+		let other_solver = address!("70997970C51812dc3A010C7d01b50e0d17dc79C8");
+		let result = verify_quote_signature(&quote, &other_solver, 1_000_000_000);
+		assert!(!result.valid);
+
+		let result = verify_quote_signature(&create_test_quote(2_000_000_000), &TEST_SIGNER, 0);
+		assert!(!result.valid);
+		assert!(result.signer.is_none());
+	}
+}
@@ -11,12 +11,16 @@
 //! 2. **Address Validation** - Validates ERC-7930 interoperable addresses
 //! 3. **Network Support** - Verifies chains are configured with appropriate settlers
 //! 4. **Token Support** - Confirms tokens are supported on their respective chains
-//! 5. **Balance Checks** - Ensures solver has sufficient liquidity
+//! 5. **Route Status** - Rejects routes paused for lack of destination inventory
+//! 6. **Exposure Limits** - Rejects outputs that would exceed in-flight capital limits
+//! 7. **Balance Checks** - Ensures solver has sufficient liquidity
 
 use alloy_primitives::{Address as AlloyAddress, U256};
 use futures::future::try_join_all;
-use solver_core::SolverEngine;
-use solver_types::{GetQuoteRequest, InteropAddress, QuoteError};
+use rust_decimal::Decimal;
+use solver_config::Config;
+use solver_core::{builder::exposure_limits_for, SolverEngine};
+use solver_types::{GetQuoteRequest, InteropAddress, OrderExposure, QuoteError};
 
 /// Main validator for quote requests.
 ///
@@ -350,11 +354,129 @@ impl QuoteValidator {
 		Ok(supported_outputs)
 	}
 
+	/// Ensures none of the requested outputs is delivered on a paused route.
+	///
+	/// The inventory monitor pauses routes whose destination inventory cannot
+	/// cover typical orders, so quotes for them are refused up front instead of
+	/// producing orders the solver would skip.
+	///
+	/// # Arguments
+	///
+	/// * `solver` - The solver engine with the inventory monitor
+	/// * `outputs` - The validated output assets to check
+	///
+	/// # Errors
+	///
+	/// Returns `QuoteError::InsufficientLiquidity` if any route is paused.
+	pub fn ensure_routes_active(
+		solver: &SolverEngine,
+		outputs: &[SupportedAsset],
+	) -> Result<(), QuoteError> {
+		for output in outputs {
+			let (chain_id, evm_addr) = Self::extract_chain_and_address(&output.asset)?;
+			// The zero address stands for the native asset
+			let token = (!evm_addr.is_zero()).then(|| solver_types::Address(evm_addr.to_vec()));
+			if let Some(reason) = solver
+				.inventory()
+				.route_pause_reason(chain_id, token.as_ref())
+			{
+				tracing::warn!(
+					chain_id = chain_id,
+					reason = %reason,
+					"Refusing quote for paused route",
+				);
+				return Err(QuoteError::InsufficientLiquidity);
+			}
+		}
+		Ok(())
+	}
+
+	/// Ensures the requested outputs fit within the solver's exposure limits.
+	///
+	/// The outputs are valued in USD and checked against the capital already
+	/// fronted for unclaimed orders, using the limits of the primary execution
+	/// strategy. Quotes the strategy would skip are refused up front.
+	///
+	/// # Arguments
+	///
+	/// * `request` - The quote request, whose user is the counterparty
+	/// * `solver` - The solver engine with the exposure tracker
+	/// * `outputs` - The validated output assets to check
+	/// * `config` - The solver configuration holding the strategy limits
+	///
+	/// # Returns
+	///
+	/// The remaining USD headroom across the output routes before this quote,
+	/// or `None` when no exposure limit is configured.
+	///
+	/// # Errors
+	///
+	/// Returns `QuoteError::InsufficientLiquidity` if a limit would be exceeded.
+	/// Returns `QuoteError::Internal` if the outputs cannot be valued.
+	pub async fn ensure_exposure_headroom(
+		request: &GetQuoteRequest,
+		solver: &SolverEngine,
+		outputs: &[SupportedAsset],
+		config: &Config,
+	) -> Result<Option<Decimal>, QuoteError> {
+		let limits = exposure_limits_for(config);
+		if limits.is_empty() {
+			return Ok(None);
+		}
+
+		let mut assets = Vec::with_capacity(outputs.len());
+		for output in outputs {
+			let (chain_id, evm_addr) = Self::extract_chain_and_address(&output.asset)?;
+			assets.push((
+				chain_id,
+				solver_types::Address(evm_addr.to_vec()),
+				output.amount,
+			));
+		}
+		let legs = solver
+			.exposure()
+			.value_outputs(&assets)
+			.await
+			.map_err(|e| QuoteError::Internal(format!("Failed to value outputs: {}", e)))?;
+
+		let counterparty = request
+			.user
+			.ethereum_address()
+			.map(|user| alloy_primitives::hex::encode(user.as_slice()))
+			.map_err(|e| QuoteError::InvalidRequest(format!("Invalid user address: {}", e)))?;
+		let exposure = OrderExposure {
+			order_id: String::new(),
+			counterparty,
+			legs,
+		};
+
+		let in_flight = solver.exposure().snapshot();
+		if let Err(reason) = limits.check(&in_flight, &exposure) {
+			tracing::warn!(reason = %reason, "Refusing quote above exposure limits");
+			return Err(QuoteError::InsufficientLiquidity);
+		}
+
+		Ok(exposure
+			.legs
+			.iter()
+			.filter_map(|leg| {
+				limits.headroom(
+					&in_flight,
+					leg.chain_id,
+					&leg.token,
+					Some(&exposure.counterparty),
+				)
+			})
+			.min())
+	}
+
 	/// Ensures the solver has sufficient balance for all requested destination outputs.
 	///
 	/// Performs parallel balance checks for all output tokens to verify the solver
 	/// has enough liquidity to fulfill the quote. This is a critical pre-flight
-	/// check to prevent quote generation for unfulfillable requests.
+	/// check to prevent quote generation for unfulfillable requests. Balances are
+	/// read from the chain-state cache, so repeated quotes within a block do not
+	/// query the RPC again.
 	///
 	/// # Performance
 	///
@@ -363,7 +485,7 @@ impl QuoteValidator {
 	///
 	/// # Arguments
 	///
-	/// * `solver` - The solver engine with token manager
+	/// * `solver` - The solver engine with the chain-state cache
 	/// * `outputs` - The validated output assets to check
 	///
 	/// # Errors
@@ -374,17 +496,24 @@ impl QuoteValidator {
 		solver: &SolverEngine,
 		outputs: &[SupportedAsset],
 	) -> Result<(), QuoteError> {
-		let token_manager = solver.token_manager();
+		let chain_state = solver.chain_state();
+		let solver_address = solver
+			.account()
+			.get_address()
+			.await
+			.map_err(|e| QuoteError::Internal(format!("Balance check failed: {}", e)))?;
+		let solver_address = alloy_primitives::hex::encode(&solver_address.0);
 
 		// Create futures for parallel balance checks
 		let balance_checks = outputs.iter().map(|output| {
 			let output = output.clone();
+			let solver_address = &solver_address;
 			async move {
 				let (chain_id, evm_addr) = Self::extract_chain_and_address(&output.asset)?;
-				let token_addr = solver_types::Address(evm_addr.as_slice().to_vec());
+				let token_hex = alloy_primitives::hex::encode(evm_addr.as_slice());
 
-				let balance_str = token_manager
-					.check_balance(chain_id, &token_addr)
+				let balance_str = chain_state
+					.balance(chain_id, solver_address, Some(&token_hex))
 					.await
 					.map_err(|e| QuoteError::Internal(format!("Balance check failed: {}", e)))?;
 
@@ -392,7 +521,6 @@ impl QuoteValidator {
 					.map_err(|e| QuoteError::Internal(format!("Failed to parse balance: {}", e)))?;
 
 				if balance < output.amount {
-					let token_hex = alloy_primitives::hex::encode(evm_addr.as_slice());
 					tracing::error!(
 						chain_id = chain_id,
 						required = %output.amount,
@@ -406,7 +534,7 @@ impl QuoteValidator {
 						chain_id = chain_id,
 						required = %output.amount,
 						available = %balance,
-						token = %alloy_primitives::hex::encode(evm_addr.as_slice()),
+						token = %token_hex,
 						"Sufficient destination balance"
 					);
 				}
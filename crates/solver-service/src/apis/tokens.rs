@@ -56,7 +56,7 @@ pub async fn get_tokens(State(solver): State<Arc<SolverEngine>>) -> Json<TokensR
 		networks: HashMap::new(),
 	};
 
-	for (chain_id, network) in networks {
+	for (chain_id, network) in networks.iter() {
 		response.networks.insert(
 			chain_id.to_string(),
 			NetworkTokens {
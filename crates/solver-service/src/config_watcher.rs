@@ -0,0 +1,393 @@
+//! Configuration hot-reload for the solver service.
+//!
+//! Watches the configuration file for changes and listens for SIGHUP. On either
+//! trigger the file is loaded and validated again. Changes limited to
+//! hot-reloadable sections are applied to the running solver; anything else is
+//! rejected with a diff-style report and the solver keeps its current config.
+
+use crate::factory_registry::get_registry;
+use solver_config::{Config, ConfigDiff, ConfigError};
+use solver_core::SolverEngine;
+use solver_order::OrderInterface;
+use solver_settlement::{SettlementInterface, SettlementService};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+use tokio::signal::unix::{signal, SignalKind};
+
+/// How often the configuration file is checked for modifications.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Errors that can occur while reloading configuration.
+#[derive(Debug, Error)]
+pub enum ReloadError {
+	/// The new configuration failed to load or validate.
+	#[error("Invalid configuration: {0}")]
+	Config(#[from] ConfigError),
+	/// The new configuration changes sections that need a restart.
+	#[error("Configuration changes require a restart:\n{0}")]
+	RestartRequired(String),
+	/// A component could not be rebuilt from the new configuration.
+	#[error("Failed to rebuild {0}")]
+	Rebuild(String),
+}
+
+/// Watches the configuration file and applies hot-reloadable changes.
+pub struct ConfigWatcher {
+	/// Path of the main configuration file.
+	path: PathBuf,
+	/// Solver engine receiving the reloaded configuration.
+	solver: Arc<SolverEngine>,
+}
+
+impl ConfigWatcher {
+	/// Creates a new watcher for the given configuration file.
+	pub fn new(path: PathBuf, solver: Arc<SolverEngine>) -> Self {
+		Self { path, solver }
+	}
+
+	/// Runs the watcher until the process exits.
+	///
+	/// Only the main configuration file is polled for changes; edits to
+	/// included files are picked up by sending SIGHUP.
+	pub async fn run(self) {
+		let mut sighup = match signal(SignalKind::hangup()) {
+			Ok(sighup) => sighup,
+			Err(e) => {
+				tracing::error!(
+					"Failed to register SIGHUP handler, config reload disabled: {}",
+					e
+				);
+				return;
+			},
+		};
+		let mut interval = tokio::time::interval(POLL_INTERVAL);
+		let mut last_modified = modified_at(&self.path);
+
+		tracing::info!(path = %self.path.display(), "Watching configuration for changes");
+
+		loop {
+			tokio::select! {
+				_ = sighup.recv() => {
+					tracing::info!("Received SIGHUP, reloading configuration");
+				}
+				_ = interval.tick() => {
+					let modified = modified_at(&self.path);
+					if modified == last_modified {
+						continue;
+					}
+					last_modified = modified;
+					tracing::info!("Configuration file changed, reloading");
+				}
+			}
+
+			match self.reload().await {
+				Ok(diff) if diff.is_empty() => {
+					tracing::info!("Configuration unchanged");
+				},
+				Ok(diff) => {
+					tracing::info!("Applied configuration changes:\n{}", diff.report());
+				},
+				Err(e) => {
+					tracing::warn!("Rejected configuration reload: {}", e);
+				},
+			}
+		}
+	}
+
+	/// Reloads the configuration file and applies it if all changes are hot-reloadable.
+	///
+	/// Replacement components are built before anything is swapped, so a
+	/// failed rebuild leaves the running solver untouched.
+	pub async fn reload(&self) -> Result<ConfigDiff, ReloadError> {
+		let path = self.path.to_string_lossy();
+		let current = self.solver.config();
+		let (candidate, diff) = current.reload_from_file(&path).await?;
+
+		if diff.is_empty() {
+			return Ok(diff);
+		}
+		if diff.requires_restart() {
+			return Err(ReloadError::RestartRequired(diff.report()));
+		}
+
+		let changed = |prefix: &str| diff.hot.iter().any(|c| c.path.starts_with(prefix));
+
+		let strategy = if changed("order.strategy") {
+			Some(self.build_strategy(&candidate)?)
+		} else {
+			None
+		};
+
+		let settlements = if changed("settlement.") {
+			Some(self.build_settlements(&candidate)?)
+		} else {
+			None
+		};
+
+		// Orders validate against oracle routes and network tokens, so rebuild
+		// them whenever either changes
+		let orders = if settlements.is_some() || changed("networks.") {
+			let routes = match &settlements {
+				Some(settlements) => {
+					SettlementService::oracle_routes_for(settlements.values().map(|s| s.as_ref()))
+				},
+				None => self.solver.settlement().build_oracle_routes(),
+			};
+			Some(self.build_orders(&candidate, &routes)?)
+		} else {
+			None
+		};
+
+		if let Some(strategy) = strategy {
+			self.solver.order().replace_strategy(strategy);
+		}
+		if let Some(settlements) = settlements {
+			self.solver
+				.settlement()
+				.replace_implementations(settlements);
+		}
+		if let Some(orders) = orders {
+			self.solver.order().replace_implementations(orders);
+		}
+		self.solver.apply_config(candidate);
+
+		Ok(diff)
+	}
+
+	/// Builds the primary execution strategy from the new configuration.
+	fn build_strategy(
+		&self,
+		config: &Config,
+	) -> Result<Box<dyn solver_order::ExecutionStrategy>, ReloadError> {
+		let primary = &config.order.strategy.primary;
+		let table = config
+			.order
+			.strategy
+			.implementations
+			.get(primary)
+			.ok_or_else(|| {
+				ReloadError::Rebuild(format!("strategy '{}': not configured", primary))
+			})?;
+		let factory = get_registry()
+			.strategy
+			.get(primary)
+			.ok_or_else(|| ReloadError::Rebuild(format!("strategy '{}': unknown", primary)))?;
+
+		factory(table).map_err(|e| ReloadError::Rebuild(format!("strategy '{}': {}", primary, e)))
+	}
+
+	/// Builds all settlement implementations from the new configuration.
+	fn build_settlements(
+		&self,
+		config: &Config,
+	) -> Result<HashMap<String, Box<dyn SettlementInterface>>, ReloadError> {
+		let registry = get_registry();
+		let mut settlements = HashMap::new();
+		for (name, table) in &config.settlement.implementations {
+			let factory = registry
+				.settlement
+				.get(name)
+				.ok_or_else(|| ReloadError::Rebuild(format!("settlement '{}': unknown", name)))?;
+			let implementation = factory(table, &config.networks, self.solver.storage().clone())
+				.map_err(|e| ReloadError::Rebuild(format!("settlement '{}': {}", name, e)))?;
+			settlements.insert(name.clone(), implementation);
+		}
+		Ok(settlements)
+	}
+
+	/// Builds all order implementations from the new configuration.
+	fn build_orders(
+		&self,
+		config: &Config,
+		routes: &solver_types::oracle::OracleRoutes,
+	) -> Result<HashMap<String, Box<dyn OrderInterface>>, ReloadError> {
+		let registry = get_registry();
+		let mut orders = HashMap::new();
+		for (name, table) in &config.order.implementations {
+			let factory = registry
+				.order
+				.get(name)
+				.ok_or_else(|| ReloadError::Rebuild(format!("order '{}': unknown", name)))?;
+			let implementation = factory(table, &config.networks, routes)
+				.map_err(|e| ReloadError::Rebuild(format!("order '{}': {}", name, e)))?;
+			orders.insert(name.clone(), implementation);
+		}
+		Ok(orders)
+	}
+}
+
+/// Returns the modification time of a file, if available.
+fn modified_at(path: &Path) -> Option<SystemTime> {
+	std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rust_decimal::Decimal;
+	use solver_account::{implementations::local::LocalWallet, AccountService};
+	use solver_core::{
+		engine::{accounts::SolverAccounts, token_manager::TokenManager},
+		EventBus,
+	};
+	use solver_delivery::DeliveryService;
+	use solver_discovery::DiscoveryService;
+	use solver_order::{implementations::strategies::simple::create_strategy, OrderService};
+	use solver_pricing::{implementations::mock, PricingService};
+	use solver_rebalance::RebalanceService;
+	use solver_storage::{implementations::memory::MemoryStorage, StorageService};
+	use std::str::FromStr;
+	use tempfile::TempDir;
+
+	const TEST_PK: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+	fn config_toml(min_profitability_pct: &str, strategy: &str) -> String {
+		format!(
+			r#"
+[solver]
+id = "test-solver"
+monitoring_timeout_minutes = 5
+min_profitability_pct = {min_profitability_pct}
+
+[networks.1]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.1.rpc_urls]]
+http = "http://localhost:8545"
+[[networks.1.tokens]]
+address = "0xabcdef1234567890abcdef1234567890abcdef12"
+symbol = "TEST"
+decimals = 18
+
+[networks.2]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.2.rpc_urls]]
+http = "http://localhost:8546"
+[[networks.2.tokens]]
+address = "0xabcdef1234567890abcdef1234567890abcdef12"
+symbol = "TEST"
+decimals = 18
+
+[storage]
+primary = "memory"
+cleanup_interval_seconds = 3600
+[storage.implementations.memory]
+
+[delivery]
+[delivery.implementations.test]
+
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "{TEST_PK}"
+
+[discovery]
+[discovery.implementations.test]
+
+[order]
+[order.implementations.test]
+[order.strategy]
+primary = "simple"
+[order.strategy.implementations.simple]
+{strategy}
+
+[settlement]
+[settlement.implementations.test]
+order = "test"
+network_ids = [1, 2]
+"#
+		)
+	}
+
+	/// Writes a configuration file and builds a solver running on it.
+	async fn create_watcher(dir: &TempDir) -> (ConfigWatcher, Arc<SolverEngine>) {
+		let path = dir.path().join("config.toml");
+		std::fs::write(&path, config_toml("1.0", "max_gas_price_gwei = 50")).unwrap();
+		let config = Config::from_file(&path.to_string_lossy()).await.unwrap();
+
+		let storage = Arc::new(StorageService::new(Box::new(MemoryStorage::new())));
+		let account = Arc::new(AccountService::new(Box::new(
+			LocalWallet::new(TEST_PK).unwrap(),
+		)));
+		let delivery = Arc::new(DeliveryService::new(HashMap::new(), 1, 3));
+		let strategy = create_strategy(&config.order.strategy.implementations["simple"]).unwrap();
+		let solver_address = solver_types::Address(vec![0x12; 20]);
+		let token_manager = Arc::new(TokenManager::new(
+			config.networks.clone(),
+			delivery.clone(),
+			account.clone(),
+			Default::default(),
+		));
+		let accounts = Arc::new(SolverAccounts::single(
+			solver_address.clone(),
+			delivery.clone(),
+		));
+		let pricing_impl =
+			mock::create_mock_pricing(&toml::Value::Table(toml::map::Map::new())).unwrap();
+
+		let solver = Arc::new(SolverEngine::new(
+			config,
+			storage,
+			account,
+			solver_address,
+			accounts,
+			delivery,
+			Arc::new(DiscoveryService::new(HashMap::new())),
+			Arc::new(OrderService::new(HashMap::new(), strategy)),
+			Arc::new(SettlementService::new(HashMap::new(), 3)),
+			Arc::new(PricingService::new(pricing_impl)),
+			EventBus::new(64),
+			token_manager,
+			Arc::new(RebalanceService::new(Vec::new())),
+		));
+		(ConfigWatcher::new(path, solver.clone()), solver)
+	}
+
+	#[tokio::test]
+	async fn test_reload_applies_hot_changes() {
+		let dir = TempDir::new().unwrap();
+		let (watcher, solver) = create_watcher(&dir).await;
+
+		std::fs::write(
+			dir.path().join("config.toml"),
+			config_toml("2.5", "max_gas_price_gwei = 50"),
+		)
+		.unwrap();
+		let diff = watcher.reload().await.unwrap();
+
+		assert!(!diff.is_empty());
+		assert_eq!(
+			solver.config().solver.min_profitability_pct,
+			Decimal::from_str("2.5").unwrap()
+		);
+	}
+
+	#[tokio::test]
+	async fn test_failed_rebuild_keeps_running_config() {
+		let dir = TempDir::new().unwrap();
+		let (watcher, solver) = create_watcher(&dir).await;
+
+		// Passes config validation, but the simple strategy rejects a zero gas cap
+		std::fs::write(
+			dir.path().join("config.toml"),
+			config_toml("2.5", "max_gas_price_gwei = 0"),
+		)
+		.unwrap();
+		let result = watcher.reload().await;
+
+		assert!(matches!(result, Err(ReloadError::Rebuild(_))));
+		let config = solver.config();
+		assert_eq!(
+			config.solver.min_profitability_pct,
+			Decimal::from_str("1.0").unwrap()
+		);
+		assert_eq!(
+			config.order.strategy.implementations["simple"].get("max_gas_price_gwei"),
+			Some(&toml::Value::Integer(50))
+		);
+	}
+}
@@ -52,6 +52,7 @@ pub async fn get_domain_separator(
 
 	// Create transaction for contract call
 	let tx = Transaction {
+		from: None,
 		to: Some(contract_address.clone()),
 		data: encoded,
 		value: alloy_primitives::U256::ZERO,
@@ -9,7 +9,7 @@ use solver_core::{SolverBuilder, SolverEngine, SolverFactories};
 use solver_delivery::{DeliveryError, DeliveryInterface};
 use solver_discovery::{DiscoveryError, DiscoveryInterface};
 use solver_order::{ExecutionStrategy, OrderError, OrderInterface, StrategyError};
-
+use solver_rebalance::{BridgeError, BridgeInterface};
 use solver_settlement::{SettlementError, SettlementInterface};
 use solver_storage::{StorageError, StorageInterface};
 use solver_types::{NetworksConfig, PricingError, PricingInterface};
@@ -22,8 +22,9 @@ pub type AccountFactory = fn(&toml::Value) -> Result<Box<dyn AccountInterface>,
 pub type DeliveryFactory = fn(
 	&toml::Value,
 	&NetworksConfig,
-	&solver_types::SecretString,
-	&std::collections::HashMap<u64, solver_types::SecretString>,
+	&solver_account::AccountSigner,
+	&std::collections::HashMap<u64, solver_account::AccountSigner>,
+	&[solver_account::AccountSigner],
 ) -> Result<Box<dyn DeliveryInterface>, DeliveryError>;
 pub type DiscoveryFactory =
 	fn(&toml::Value, &NetworksConfig) -> Result<Box<dyn DiscoveryInterface>, DiscoveryError>;
@@ -39,6 +40,11 @@ pub type SettlementFactory = fn(
 	std::sync::Arc<solver_storage::StorageService>,
 ) -> Result<Box<dyn SettlementInterface>, SettlementError>;
 pub type StrategyFactory = fn(&toml::Value) -> Result<Box<dyn ExecutionStrategy>, StrategyError>;
+pub type BridgeFactory = fn(
+	&toml::Value,
+	&NetworksConfig,
+	&solver_types::oracle::OracleRoutes,
+) -> Result<Box<dyn BridgeInterface>, BridgeError>;
 
 /// Global registry for all implementation factories
 pub struct FactoryRegistry {
@@ -50,6 +56,7 @@ pub struct FactoryRegistry {
 	pub pricing: HashMap<String, PricingFactory>,
 	pub settlement: HashMap<String, SettlementFactory>,
 	pub strategy: HashMap<String, StrategyFactory>,
+	pub bridge: HashMap<String, BridgeFactory>,
 }
 
 impl FactoryRegistry {
@@ -64,6 +71,7 @@ impl FactoryRegistry {
 			pricing: HashMap::new(),
 			settlement: HashMap::new(),
 			strategy: HashMap::new(),
+			bridge: HashMap::new(),
 		}
 	}
 
@@ -106,6 +114,11 @@ impl FactoryRegistry {
 	pub fn register_strategy(&mut self, name: impl Into<String>, factory: StrategyFactory) {
 		self.strategy.insert(name.into(), factory);
 	}
+
+	/// Register a bridge implementation
+	pub fn register_bridge(&mut self, name: impl Into<String>, factory: BridgeFactory) {
+		self.bridge.insert(name.into(), factory);
+	}
 }
 
 // Global registry instance
@@ -164,6 +177,12 @@ pub fn initialize_registry() -> &'static FactoryRegistry {
 			registry.register_strategy(name, factory);
 		}
 
+		// Auto-register all bridge implementations
+		for (name, factory) in solver_rebalance::get_all_implementations() {
+			tracing::debug!("Registering bridge implementation: {}", name);
+			registry.register_bridge(name, factory);
+		}
+
 		registry
 	})
 }
@@ -228,14 +247,31 @@ pub async fn build_solver_from_config(
 		settlement,
 		"settlement"
 	);
-	let account_factories =
-		build_factories!(registry, config.account.implementations, account, "account");
+	// Accounts are keyed by their configured name, which may differ from the implementation
+	let mut account_factories = HashMap::new();
+	for name in config.account.implementations.keys() {
+		let implementation = config.account.implementation_of(name);
+		if let Some(factory) = registry.account.get(implementation) {
+			account_factories.insert(name.clone(), *factory);
+		} else {
+			let available: Vec<_> = registry.account.keys().cloned().collect();
+			return Err(format!(
+				"Unknown account implementation '{}' for account '{}'. Available: [{}]",
+				implementation,
+				name,
+				available.join(", ")
+			)
+			.into());
+		}
+	}
 	let strategy_factories = build_factories!(
 		registry,
 		config.order.strategy.implementations,
 		strategy,
 		"strategy"
 	);
+	let bridge_factories =
+		build_factories!(registry, config.rebalance.implementations, bridge, "bridge");
 
 	let factories = SolverFactories {
 		storage_factories,
@@ -246,6 +282,7 @@ pub async fn build_solver_from_config(
 		pricing_factories,
 		settlement_factories,
 		strategy_factories,
+		bridge_factories,
 	};
 
 	Ok(builder.build(factories).await?)
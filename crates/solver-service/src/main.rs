@@ -11,6 +11,8 @@ use std::sync::Arc;
 
 mod apis;
 mod auth;
+mod cli;
+mod config_watcher;
 mod eip712;
 mod factory_registry;
 mod server;
@@ -23,16 +25,24 @@ use factory_registry::build_solver_from_config;
 #[command(author, version, about, long_about = None)]
 struct Args {
 	/// Path to configuration file
-	#[arg(short, long, default_value = "config.toml")]
+	#[arg(short, long, default_value = "config.toml", global = true)]
 	config: PathBuf,
 
 	/// Log level (trace, debug, info, warn, error)
-	#[arg(short, long, default_value = "info")]
+	#[arg(short, long, default_value = "info", global = true)]
 	log_level: String,
 
 	/// Clear all storage data on startup (fresh start)
 	#[arg(long)]
 	clean: bool,
+
+	/// Disable configuration hot-reload on file change or SIGHUP
+	#[arg(long)]
+	no_reload: bool,
+
+	/// Run an operator command instead of starting the solver
+	#[command(subcommand)]
+	command: Option<cli::Command>,
 }
 
 /// Main entry point for the solver service.
@@ -47,6 +57,12 @@ struct Args {
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
 	let args = Args::parse();
 
+	// Operator commands run against config and storage without the engine,
+	// and keep stdout free of log lines so their output can be piped
+	if let Some(command) = args.command {
+		return cli::run(command, &args.config).await;
+	}
+
 	// Initialize tracing with env filter
 	use tracing_subscriber::{fmt, EnvFilter};
 
@@ -104,6 +120,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 	let solver = build_solver_from_config(config.clone()).await?;
 	let solver = Arc::new(solver);
 
+	// Watch the config file and apply hot-reloadable changes
+	if !args.no_reload {
+		let watcher = config_watcher::ConfigWatcher::new(args.config.clone(), Arc::clone(&solver));
+		tokio::spawn(watcher.run());
+	}
+
 	// Check if API server should be started
 	let api_enabled = config.api.as_ref().is_some_and(|api| api.enabled);
 
@@ -18,11 +18,11 @@ use axum::{
 	Router, ServiceExt,
 };
 use serde_json::Value;
-use solver_config::{ApiConfig, Config};
+use solver_config::{ApiConfig, SharedConfig};
 use solver_core::SolverEngine;
 use solver_types::{
 	api::IntentRequest, APIError, Address, ApiErrorType, GetOrderResponse, GetQuoteRequest,
-	GetQuoteResponse, Order, OrderIdCallback, Transaction,
+	GetQuoteResponse, Order, OrderIdCallback, Quote, StorageKey, Transaction, VerifyQuoteResponse,
 };
 use std::sync::Arc;
 use tokio::net::TcpListener;
@@ -35,8 +35,8 @@ use tower_http::normalize_path::NormalizePath;
 pub struct AppState {
 	/// Reference to the solver engine for processing requests.
 	pub solver: Arc<SolverEngine>,
-	/// Complete configuration.
-	pub config: Config,
+	/// Complete configuration, kept current across hot reloads.
+	pub config: SharedConfig,
 	/// HTTP client for forwarding requests.
 	pub http_client: reqwest::Client,
 	/// Discovery service URL for forwarding orders (if configured).
@@ -55,8 +55,8 @@ pub async fn start_server(
 	api_config: ApiConfig,
 	solver: Arc<SolverEngine>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-	// Get the full config from the solver engine
-	let config = solver.config().clone();
+	// Share the solver's config handle so handlers observe hot reloads
+	let config = solver.shared_config().clone();
 
 	// Create a reusable HTTP client with connection pooling
 	let http_client = reqwest::Client::builder()
@@ -119,8 +119,11 @@ pub async fn start_server(
 	// Build the router with /api base path and quote endpoint
 	let mut api_routes = Router::new()
 		.route("/quotes", post(handle_quote))
+		.route("/quotes/verify", post(handle_verify_quote))
 		.route("/tokens", get(handle_get_tokens))
-		.route("/tokens/{chain_id}", get(handle_get_tokens_for_chain));
+		.route("/tokens/{chain_id}", get(handle_get_tokens_for_chain))
+		.route("/metrics", get(handle_get_metrics))
+		.route("/gas", get(handle_get_gas));
 
 	// Add auth subroutes
 	let auth_routes = Router::new()
@@ -191,7 +194,8 @@ async fn handle_quote(
 	State(state): State<AppState>,
 	Json(request): Json<GetQuoteRequest>,
 ) -> Result<Json<GetQuoteResponse>, APIError> {
-	match crate::apis::quote::process_quote_request(request, &state.solver, &state.config).await {
+	let config = state.config.load_full();
+	match crate::apis::quote::process_quote_request(request, &state.solver, &config).await {
 		Ok(response) => Ok(Json(response)),
 		Err(e) => {
 			tracing::warn!("Quote request failed: {}", e);
@@ -200,6 +204,23 @@ async fn handle_quote(
 	}
 }
 
+/// Handles POST /api/quotes/verify requests.
+///
+/// This endpoint lets aggregators check that a quote carries a valid signature
+/// from this solver over its terms and expiry.
+async fn handle_verify_quote(
+	State(state): State<AppState>,
+	Json(quote): Json<Quote>,
+) -> Result<Json<VerifyQuoteResponse>, APIError> {
+	match crate::apis::quote::verify_quote(&quote, &state.solver).await {
+		Ok(response) => Ok(Json(response)),
+		Err(e) => {
+			tracing::warn!("Quote verification failed: {}", e);
+			Err(APIError::from(e))
+		},
+	}
+}
+
 /// Handles GET /api/orders/{id} requests.
 ///
 /// This endpoint retrieves order details by ID, providing status information
@@ -235,6 +256,22 @@ async fn handle_get_tokens(
 	crate::apis::tokens::get_tokens(State(state.solver)).await
 }
 
+/// Handles GET /api/metrics requests.
+///
+/// Returns skip and defer counts by reason.
+async fn handle_get_metrics(
+	State(state): State<AppState>,
+) -> Json<crate::apis::metrics::MetricsResponse> {
+	crate::apis::metrics::get_metrics(State(state.solver)).await
+}
+
+/// Handles GET /api/gas requests.
+///
+/// Returns the gas units learned from confirmed transactions.
+async fn handle_get_gas(State(state): State<AppState>) -> Json<crate::apis::gas::GasResponse> {
+	crate::apis::gas::get_gas(State(state.solver)).await
+}
+
 /// Handles GET /api/tokens/{chain_id} requests.
 ///
 /// Returns supported tokens for a specific chain.
@@ -363,6 +400,22 @@ async fn create_intent_from_quote(
 			}
 		})?;
 
+	// Quotes are single-use; the intent handler enforces this as well, but
+	// rejecting here spares the user an order that would only be skipped
+	let used = state
+		.solver
+		.storage()
+		.exists(StorageKey::QuoteUsage.as_str(), quote_id)
+		.await
+		.unwrap_or(false);
+	if used {
+		return Err(APIError::UnprocessableEntity {
+			error_type: ApiErrorType::QuoteAlreadyUsed,
+			message: format!("Quote {} has already been accepted", quote_id),
+			details: Some(serde_json::json!({"quoteId": quote_id})),
+		});
+	}
+
 	// Convert Quote to IntentRequest using the TryFrom implementation
 	(&quote, signature, standard)
 		.try_into()
@@ -395,7 +448,7 @@ async fn validate_intent_request(
 	// Get lock_type as string
 	let lock_type = intent.lock_type.as_str();
 
-	// Get solver address from primary account
+	// Get solver address from the solver of record (claimer) account
 	let solver_address =
 		state
 			.solver
@@ -421,6 +474,7 @@ async fn validate_intent_request(
 			let calldata = &tx_data[20..];
 
 			let tx = Transaction {
+				from: None,
 				to: Some(settler_address),
 				data: calldata.to_vec(),
 				value: U256::ZERO,
@@ -0,0 +1,502 @@
+//! Oracle health and cost tracking for scored oracle selection.
+//!
+//! This module records attestation latency, failure counts, and observed
+//! messaging costs per oracle and route in storage, and ranks oracles by a
+//! combined score. Oracles that keep failing on a route are excluded from
+//! selection for that route for a cooldown window.
+
+use alloy_primitives::{hex, U256};
+use serde::{Deserialize, Serialize};
+use solver_storage::{StorageIndexes, StorageService};
+use solver_types::{current_timestamp, Address, StorageKey};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Weight of normalized attestation latency in the oracle score.
+const LATENCY_WEIGHT: f64 = 0.4;
+/// Weight of the failure rate in the oracle score.
+const FAILURE_WEIGHT: f64 = 0.4;
+/// Weight of normalized messaging cost in the oracle score.
+const COST_WEIGHT: f64 = 0.2;
+/// Smoothing factor for the moving averages of latency and cost.
+const EWMA_ALPHA: f64 = 0.2;
+
+/// Configuration for oracle health tracking.
+#[derive(Debug, Clone)]
+pub struct OracleHealthConfig {
+	/// Consecutive failures after which an oracle is excluded
+	pub max_consecutive_failures: u32,
+	/// How long an excluded oracle is skipped before being retried
+	pub exclusion_seconds: u64,
+}
+
+impl Default for OracleHealthConfig {
+	fn default() -> Self {
+		Self {
+			max_consecutive_failures: 3,
+			exclusion_seconds: 1800,
+		}
+	}
+}
+
+/// Recorded statistics for a single input oracle on a route.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OracleStats {
+	/// Input chain the oracle is deployed on
+	pub chain_id: u64,
+	/// Output chain of the route the statistics were recorded for
+	#[serde(default)]
+	pub destination_chain_id: u64,
+	/// Oracle contract address
+	pub oracle: Address,
+	/// Number of successful attestations
+	pub successes: u64,
+	/// Number of failed or timed out attestations
+	pub failures: u64,
+	/// Failures since the last success
+	pub consecutive_failures: u32,
+	/// Moving average of attestation latency in seconds
+	pub avg_latency_secs: Option<f64>,
+	/// Moving average of messaging cost in wei
+	pub avg_cost_wei: Option<String>,
+	/// Timestamp of the most recent failure
+	pub last_failure_at: Option<u64>,
+	/// Timestamp of the most recent update
+	pub updated_at: u64,
+}
+
+impl OracleStats {
+	fn new(chain_id: u64, destination_chain_id: u64, oracle: Address) -> Self {
+		Self {
+			chain_id,
+			destination_chain_id,
+			oracle,
+			successes: 0,
+			failures: 0,
+			consecutive_failures: 0,
+			avg_latency_secs: None,
+			avg_cost_wei: None,
+			last_failure_at: None,
+			updated_at: current_timestamp(),
+		}
+	}
+
+	/// Fraction of attestations that failed, 0.0 when none were recorded.
+	pub fn failure_rate(&self) -> f64 {
+		let total = self.successes + self.failures;
+		if total == 0 {
+			0.0
+		} else {
+			self.failures as f64 / total as f64
+		}
+	}
+
+	/// Average messaging cost as a float, for scoring only.
+	fn avg_cost(&self) -> Option<f64> {
+		self.avg_cost_wei
+			.as_ref()
+			.and_then(|c| c.parse::<f64>().ok())
+	}
+}
+
+/// Folds a new sample into an exponentially weighted moving average.
+fn ewma(current: Option<f64>, sample: f64) -> f64 {
+	match current {
+		Some(avg) => avg + EWMA_ALPHA * (sample - avg),
+		None => sample,
+	}
+}
+
+/// Scores a set of candidate oracles; lower is better.
+///
+/// Latency and cost are normalized against the worst candidate in the set.
+/// Candidates without samples get the average of the known ratios so that
+/// new oracles are neither favoured nor starved.
+pub fn score_oracles(candidates: &[Option<&OracleStats>]) -> Vec<f64> {
+	let latencies: Vec<Option<f64>> = candidates
+		.iter()
+		.map(|s| s.and_then(|s| s.avg_latency_secs))
+		.collect();
+	let costs: Vec<Option<f64>> = candidates
+		.iter()
+		.map(|s| s.and_then(|s| s.avg_cost()))
+		.collect();
+
+	let latency_ratios = normalize(&latencies);
+	let cost_ratios = normalize(&costs);
+
+	candidates
+		.iter()
+		.enumerate()
+		.map(|(i, stats)| {
+			let failure_rate = stats.map(|s| s.failure_rate()).unwrap_or(0.0);
+			LATENCY_WEIGHT * latency_ratios[i]
+				+ FAILURE_WEIGHT * failure_rate
+				+ COST_WEIGHT * cost_ratios[i]
+		})
+		.collect()
+}
+
+/// Normalizes values against the maximum; unknown values get the known average.
+fn normalize(values: &[Option<f64>]) -> Vec<f64> {
+	let max = values.iter().flatten().cloned().fold(0.0_f64, f64::max);
+	let known: Vec<f64> = values
+		.iter()
+		.flatten()
+		.map(|v| if max > 0.0 { v / max } else { 0.0 })
+		.collect();
+	let neutral = if known.is_empty() {
+		0.0
+	} else {
+		known.iter().sum::<f64>() / known.len() as f64
+	};
+
+	values
+		.iter()
+		.map(|v| match v {
+			Some(v) if max > 0.0 => v / max,
+			Some(_) => 0.0,
+			None => neutral,
+		})
+		.collect()
+}
+
+/// Storage-backed tracker of oracle health and cost.
+///
+/// Statistics are cached in memory so that oracle selection can stay synchronous,
+/// and every update is written through to storage to survive restarts.
+pub struct OracleHealthTracker {
+	storage: Arc<StorageService>,
+	config: OracleHealthConfig,
+	stats: RwLock<HashMap<(u64, u64, Address), OracleStats>>,
+}
+
+impl std::fmt::Debug for OracleHealthTracker {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("OracleHealthTracker")
+			.field("config", &self.config)
+			.finish_non_exhaustive()
+	}
+}
+
+impl OracleHealthTracker {
+	/// Creates a tracker and loads previously recorded statistics from storage.
+	pub async fn new(storage: Arc<StorageService>, config: OracleHealthConfig) -> Self {
+		let mut stats = HashMap::new();
+		match storage
+			.retrieve_all::<OracleStats>(StorageKey::OracleHealth.as_str())
+			.await
+		{
+			Ok(records) => {
+				for (_, record) in records {
+					let key = (
+						record.chain_id,
+						record.destination_chain_id,
+						record.oracle.clone(),
+					);
+					stats.insert(key, record);
+				}
+			},
+			Err(e) => {
+				tracing::warn!(error = %e, "Failed to load oracle health statistics");
+			},
+		}
+
+		Self {
+			storage,
+			config,
+			stats: RwLock::new(stats),
+		}
+	}
+
+	/// Generate storage id for an oracle on a route
+	fn storage_id(chain_id: u64, destination_chain_id: u64, oracle: &Address) -> String {
+		format!(
+			"{}-{}-{}",
+			chain_id,
+			destination_chain_id,
+			hex::encode(&oracle.0)
+		)
+	}
+
+	/// Returns the recorded statistics for an oracle on a route, if any.
+	pub fn stats(
+		&self,
+		chain_id: u64,
+		destination_chain_id: u64,
+		oracle: &Address,
+	) -> Option<OracleStats> {
+		self.stats
+			.read()
+			.ok()?
+			.get(&(chain_id, destination_chain_id, oracle.clone()))
+			.cloned()
+	}
+
+	/// Whether an oracle is currently excluded because it keeps failing.
+	pub fn is_excluded(&self, stats: &OracleStats) -> bool {
+		if stats.consecutive_failures < self.config.max_consecutive_failures {
+			return false;
+		}
+		stats.last_failure_at.is_some_and(|failed_at| {
+			current_timestamp().saturating_sub(failed_at) < self.config.exclusion_seconds
+		})
+	}
+
+	/// Selects the best-scoring oracle from the candidates on a route.
+	///
+	/// Excluded oracles are skipped unless every candidate is excluded, in which
+	/// case the best of them is still returned so settlement can proceed.
+	pub fn select(
+		&self,
+		chain_id: u64,
+		destination_chain_id: u64,
+		oracles: &[Address],
+	) -> Option<Address> {
+		let stats: Vec<Option<OracleStats>> = oracles
+			.iter()
+			.map(|oracle| self.stats(chain_id, destination_chain_id, oracle))
+			.collect();
+		let scores = score_oracles(&stats.iter().map(|s| s.as_ref()).collect::<Vec<_>>());
+
+		let pick = |allow_excluded: bool| {
+			oracles
+				.iter()
+				.zip(stats.iter())
+				.zip(scores.iter())
+				.filter(|((_, s), _)| {
+					allow_excluded || !s.as_ref().is_some_and(|s| self.is_excluded(s))
+				})
+				.min_by(|(_, a), (_, b)| a.total_cmp(b))
+				.map(|((oracle, _), _)| oracle.clone())
+		};
+
+		pick(false).or_else(|| pick(true))
+	}
+
+	/// Records a successful attestation and its latency.
+	pub async fn record_success(
+		&self,
+		chain_id: u64,
+		destination_chain_id: u64,
+		oracle: &Address,
+		latency: Duration,
+	) {
+		self.update(chain_id, destination_chain_id, oracle, |stats| {
+			stats.successes += 1;
+			stats.consecutive_failures = 0;
+			stats.avg_latency_secs = Some(ewma(stats.avg_latency_secs, latency.as_secs_f64()));
+		})
+		.await;
+	}
+
+	/// Records a failed or timed out attestation.
+	pub async fn record_failure(&self, chain_id: u64, destination_chain_id: u64, oracle: &Address) {
+		self.update(chain_id, destination_chain_id, oracle, |stats| {
+			stats.failures += 1;
+			stats.consecutive_failures += 1;
+			stats.last_failure_at = Some(current_timestamp());
+		})
+		.await;
+	}
+
+	/// Records a measured messaging cost in wei (e.g. an IGP payment).
+	pub async fn record_cost(
+		&self,
+		chain_id: u64,
+		destination_chain_id: u64,
+		oracle: &Address,
+		cost: U256,
+	) {
+		let sample = cost.to_string().parse::<f64>().unwrap_or(0.0);
+		self.update(chain_id, destination_chain_id, oracle, |stats| {
+			let avg = ewma(stats.avg_cost(), sample);
+			stats.avg_cost_wei = Some(format!("{:.0}", avg));
+		})
+		.await;
+	}
+
+	/// Applies a change to an oracle's statistics and persists the result.
+	async fn update<F>(&self, chain_id: u64, destination_chain_id: u64, oracle: &Address, f: F)
+	where
+		F: FnOnce(&mut OracleStats),
+	{
+		let record = {
+			let mut stats = match self.stats.write() {
+				Ok(stats) => stats,
+				Err(_) => return,
+			};
+			let entry = stats
+				.entry((chain_id, destination_chain_id, oracle.clone()))
+				.or_insert_with(|| {
+					OracleStats::new(chain_id, destination_chain_id, oracle.clone())
+				});
+			f(entry);
+			entry.updated_at = current_timestamp();
+			entry.clone()
+		};
+
+		if let Err(e) = self
+			.storage
+			.store(
+				StorageKey::OracleHealth.as_str(),
+				&Self::storage_id(chain_id, destination_chain_id, oracle),
+				&record,
+				// Indexed so backends that list by index return it on reload
+				Some(StorageIndexes::new().with_field("chain_id", chain_id)),
+			)
+			.await
+		{
+			tracing::warn!(
+				chain_id = chain_id,
+				destination_chain_id = destination_chain_id,
+				oracle = %oracle,
+				error = %e,
+				"Failed to persist oracle health statistics"
+			);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_types::parse_address;
+
+	fn create_tracker() -> OracleHealthTracker {
+		let backend = Box::new(solver_storage::implementations::memory::MemoryStorage::new());
+		OracleHealthTracker {
+			storage: Arc::new(StorageService::new(backend)),
+			config: OracleHealthConfig::default(),
+			stats: RwLock::new(HashMap::new()),
+		}
+	}
+
+	fn oracle(n: u8) -> Address {
+		parse_address(&format!("0x{}", hex::encode([n; 20]))).unwrap()
+	}
+
+	#[test]
+	fn test_score_prefers_faster_oracle() {
+		let mut fast = OracleStats::new(1, 2, oracle(1));
+		fast.avg_latency_secs = Some(30.0);
+		let mut slow = OracleStats::new(1, 2, oracle(2));
+		slow.avg_latency_secs = Some(300.0);
+
+		let scores = score_oracles(&[Some(&fast), Some(&slow)]);
+		assert!(scores[0] < scores[1]);
+	}
+
+	#[test]
+	fn test_score_unknown_oracle_is_neutral() {
+		let mut fast = OracleStats::new(1, 2, oracle(1));
+		fast.avg_latency_secs = Some(30.0);
+		let mut slow = OracleStats::new(1, 2, oracle(2));
+		slow.avg_latency_secs = Some(300.0);
+
+		let scores = score_oracles(&[Some(&fast), Some(&slow), None]);
+		assert!(scores[2] > scores[0]);
+		assert!(scores[2] < scores[1]);
+	}
+
+	#[tokio::test]
+	async fn test_select_excludes_failing_oracle() {
+		let tracker = create_tracker();
+		let oracles = vec![oracle(1), oracle(2)];
+
+		for _ in 0..3 {
+			tracker.record_failure(1, 2, &oracles[0]).await;
+		}
+		tracker
+			.record_success(1, 2, &oracles[1], Duration::from_secs(600))
+			.await;
+
+		assert_eq!(tracker.select(1, 2, &oracles), Some(oracles[1].clone()));
+	}
+
+	#[tokio::test]
+	async fn test_select_scores_each_route_separately() {
+		let tracker = create_tracker();
+		let oracles = vec![oracle(1), oracle(2)];
+
+		// The first oracle keeps failing towards chain 2 but not towards chain 3
+		for _ in 0..3 {
+			tracker.record_failure(1, 2, &oracles[0]).await;
+		}
+		tracker
+			.record_success(1, 3, &oracles[0], Duration::from_secs(30))
+			.await;
+		tracker
+			.record_success(1, 3, &oracles[1], Duration::from_secs(600))
+			.await;
+
+		assert_eq!(tracker.select(1, 2, &oracles), Some(oracles[1].clone()));
+		assert_eq!(tracker.select(1, 3, &oracles), Some(oracles[0].clone()));
+	}
+
+	#[tokio::test]
+	async fn test_select_falls_back_when_all_excluded() {
+		let tracker = create_tracker();
+		let oracles = vec![oracle(1)];
+
+		for _ in 0..3 {
+			tracker.record_failure(1, 2, &oracles[0]).await;
+		}
+
+		assert_eq!(tracker.select(1, 2, &oracles), Some(oracles[0].clone()));
+	}
+
+	#[tokio::test]
+	async fn test_stats_are_persisted() {
+		let tracker = create_tracker();
+		let oracle = oracle(1);
+
+		tracker
+			.record_success(1, 2, &oracle, Duration::from_secs(60))
+			.await;
+		tracker
+			.record_cost(1, 2, &oracle, U256::from(1000u64))
+			.await;
+
+		let stats: OracleStats = tracker
+			.storage
+			.retrieve(
+				StorageKey::OracleHealth.as_str(),
+				&OracleHealthTracker::storage_id(1, 2, &oracle),
+			)
+			.await
+			.unwrap();
+		assert_eq!(stats.successes, 1);
+		assert_eq!(stats.avg_cost_wei.as_deref(), Some("1000"));
+	}
+
+	#[tokio::test]
+	async fn test_stats_reload_from_file_storage() {
+		let temp_dir = tempfile::TempDir::new().unwrap();
+		let mut config = toml::map::Map::new();
+		config.insert(
+			"storage_path".to_string(),
+			toml::Value::String(temp_dir.path().to_string_lossy().into_owned()),
+		);
+		let config = toml::Value::Table(config);
+		let open_storage = || {
+			let backend = solver_storage::implementations::file::create_storage(&config).unwrap();
+			Arc::new(StorageService::new(backend))
+		};
+
+		let tracker = OracleHealthTracker::new(open_storage(), OracleHealthConfig::default()).await;
+		tracker
+			.record_success(1, 2, &oracle(1), Duration::from_secs(60))
+			.await;
+		tracker.record_failure(2, 1, &oracle(2)).await;
+		drop(tracker);
+
+		// A fresh tracker on the same directory sees both records
+		let reloaded =
+			OracleHealthTracker::new(open_storage(), OracleHealthConfig::default()).await;
+		assert_eq!(reloaded.stats(1, 2, &oracle(1)).unwrap().successes, 1);
+		assert_eq!(reloaded.stats(2, 1, &oracle(2)).unwrap().failures, 1);
+		assert!(reloaded.stats(1, 2, &oracle(2)).is_none());
+	}
+}
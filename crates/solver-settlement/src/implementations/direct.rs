@@ -5,7 +5,11 @@
 //! readiness checks using simple transaction receipt verification without
 //! complex attestation mechanisms.
 
-use crate::{utils::parse_oracle_config, OracleConfig, SettlementError, SettlementInterface};
+use crate::{
+	health::OracleHealthTracker,
+	utils::{order_input_oracle, parse_oracle_config, parse_oracle_health_config},
+	OracleConfig, OracleSelectionStrategy, SettlementError, SettlementInterface,
+};
 use alloy_primitives::{hex, FixedBytes, U256};
 use alloy_provider::{Provider, RootProvider};
 use alloy_rpc_types::BlockTransactionsKind;
@@ -121,7 +125,31 @@ impl ConfigSchema for DirectSettlementSchema {
 				Field::new("routes", FieldType::Table(Schema::new(vec![], vec![]))),
 			],
 			// Optional fields
-			vec![Field::new("oracle_selection_strategy", FieldType::String)],
+			vec![
+				Field::new("oracle_selection_strategy", FieldType::String),
+				Field::new(
+					"oracle_scoring",
+					FieldType::Table(Schema::new(
+						vec![],
+						vec![
+							Field::new(
+								"max_consecutive_failures",
+								FieldType::Integer {
+									min: Some(1),
+									max: None,
+								},
+							),
+							Field::new(
+								"exclusion_seconds",
+								FieldType::Integer {
+									min: Some(0),
+									max: None,
+								},
+							),
+						],
+					)),
+				),
+			],
 		);
 
 		schema.validate(config)
@@ -175,30 +203,16 @@ impl SettlementInterface for DirectSettlement {
 			))
 		})?;
 
-		// Get the oracle address for this chain using the selection strategy
-		let oracle_addresses = self.get_input_oracles(origin_chain_id);
-		if oracle_addresses.is_empty() {
+		// Use the oracle the order committed to; the claim rebuilds the order
+		// with it, so any other oracle would change the order id
+		let oracle_address = order_input_oracle(order)?;
+		if !self.is_input_oracle_supported(origin_chain_id, &oracle_address) {
 			return Err(SettlementError::ValidationFailed(format!(
-				"No input oracle configured for chain {}",
+				"Order input oracle is not configured for chain {}",
 				origin_chain_id
 			)));
 		}
 
-		// Use order ID hash as selection context for deterministic oracle selection
-		// This ensures the same oracle is consistently selected for a given order
-		let order_id_bytes = order.id.as_bytes();
-		let mut hasher = std::collections::hash_map::DefaultHasher::new();
-		std::hash::Hasher::write(&mut hasher, order_id_bytes);
-		let selection_context = std::hash::Hasher::finish(&hasher);
-		let oracle_address = self
-			.select_oracle(&oracle_addresses, Some(selection_context))
-			.ok_or_else(|| {
-				SettlementError::ValidationFailed(format!(
-					"Failed to select oracle for chain {}",
-					origin_chain_id
-				))
-			})?;
-
 		// Convert tx hash
 		let hash = FixedBytes::<32>::from_slice(&tx_hash.0);
 
@@ -315,6 +329,7 @@ impl SettlementInterface for DirectSettlement {
 		let data = Vec::new(); // Empty calldata for simple ETH transfer
 
 		Ok(Some(Transaction {
+			from: None,
 			to: Some(order.solver_address.clone()),
 			data,
 			value: U256::ZERO,
@@ -356,6 +371,7 @@ impl SettlementInterface for DirectSettlement {
 		let data = Vec::new(); // Empty calldata for simple ETH transfer
 
 		Ok(Some(Transaction {
+			from: None,
 			to: Some(order.solver_address.clone()),
 			data,
 			value: U256::ZERO,
@@ -379,17 +395,19 @@ impl SettlementInterface for DirectSettlement {
 ///
 /// Optional configuration parameters:
 /// - `oracle_selection_strategy`: Strategy for oracle selection (default: round-robin)
+/// - `oracle_scoring`: Failure exclusion settings for the `Scored` strategy
 pub fn create_settlement(
 	config: &toml::Value,
 	networks: &NetworksConfig,
-	_storage: std::sync::Arc<solver_storage::StorageService>,
+	storage: std::sync::Arc<solver_storage::StorageService>,
 ) -> Result<Box<dyn SettlementInterface>, SettlementError> {
 	// Validate configuration first
 	DirectSettlementSchema::validate_config(config)
 		.map_err(|e| SettlementError::ValidationFailed(format!("Invalid configuration: {}", e)))?;
 
 	// Parse oracle configuration using common utilities
-	let oracle_config = parse_oracle_config(config)?;
+	let mut oracle_config = parse_oracle_config(config)?;
+	let health_config = parse_oracle_health_config(config);
 
 	let dispute_period_seconds = config
 		.get("dispute_period_seconds")
@@ -399,6 +417,11 @@ pub fn create_settlement(
 	// Create settlement service synchronously
 	let settlement = tokio::task::block_in_place(|| {
 		tokio::runtime::Handle::current().block_on(async {
+			if oracle_config.selection_strategy == OracleSelectionStrategy::Scored {
+				oracle_config.health = Some(std::sync::Arc::new(
+					OracleHealthTracker::new(storage, health_config).await,
+				));
+			}
 			DirectSettlement::new(networks, oracle_config, dispute_period_seconds).await
 		})
 	})?;
@@ -416,6 +439,10 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_settlement
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(DirectSettlementSchema)
+	}
 }
 
 impl crate::SettlementRegistry for Registry {}
@@ -468,6 +495,7 @@ mod tests {
 			output_oracles,
 			routes,
 			selection_strategy: OracleSelectionStrategy::RoundRobin,
+			health: None,
 		}
 	}
 
@@ -3,7 +3,11 @@
 //! This module provides a settlement implementation using Hyperlane's cross-chain
 //! messaging protocol for oracle attestations.
 
-use crate::{utils::parse_oracle_config, OracleConfig, SettlementError, SettlementInterface};
+use crate::{
+	health::OracleHealthTracker,
+	utils::{order_input_oracle, parse_oracle_config, parse_oracle_health_config},
+	OracleConfig, OracleSelectionStrategy, SettlementError, SettlementInterface,
+};
 use alloy_primitives::{hex, FixedBytes, U256};
 use alloy_provider::{Provider, RootProvider};
 use alloy_rpc_types::BlockTransactionsKind;
@@ -12,7 +16,7 @@ use alloy_transport_http::Http;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
-use solver_storage::StorageService;
+use solver_storage::{StorageError, StorageService};
 use solver_types::{
 	with_0x_prefix, ConfigSchema, Field, FieldType, FillProof, InteropAddress, NetworksConfig,
 	Order, RequestedOutput, Schema, StorageKey, Transaction, TransactionHash, TransactionReceipt,
@@ -315,6 +319,46 @@ sol! {
 
 	// Alternative dispatch event format
 	event DispatchId(bytes32 indexed messageId);
+
+	// Event emitted by the InterchainGasPaymaster when a message is paid for
+	event GasPayment(
+		bytes32 indexed messageId,
+		uint32 indexed destinationDomain,
+		uint256 gasAmount,
+		uint256 payment
+	);
+
+	interface IMailbox {
+		// Deliver a message to its recipient after ISM verification
+		function process(bytes calldata metadata, bytes calldata message) external payable;
+
+		// Check whether a message has already been processed
+		function delivered(bytes32 messageId) external view returns (bool);
+	}
+}
+
+/// Number of observed interchain gas payments kept per route
+const GAS_PAYMENT_HISTORY_SIZE: usize = 20;
+
+/// Self-relay settings used by the undelivered message watchdog
+#[derive(Debug, Clone)]
+pub struct SelfRelayConfig {
+	/// Whether the solver relays stalled messages itself
+	pub enabled: bool,
+	/// Seconds after submission before a message is considered stalled
+	pub threshold_seconds: u64,
+	/// ISM metadata passed to `Mailbox.process`
+	pub metadata: Vec<u8>,
+}
+
+impl Default for SelfRelayConfig {
+	fn default() -> Self {
+		Self {
+			enabled: false,
+			threshold_seconds: 600,
+			metadata: Vec::new(),
+		}
+	}
 }
 
 /// Message state for a single order
@@ -322,6 +366,16 @@ sol! {
 struct HyperlaneMessageState {
 	submitted: Option<SubmittedMessage>,
 	delivered: Option<DeliveredMessage>,
+	/// Timestamp of our last self-relay attempt, if any
+	#[serde(default)]
+	relay_attempted_at: Option<u64>,
+}
+
+/// Rolling history of interchain gas payments for a single message route
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct GasPaymentHistory {
+	/// Most recent payments in wei, oldest first
+	samples: Vec<String>,
 }
 
 /// Message tracker for managing Hyperlane messages with automatic persistence
@@ -343,6 +397,12 @@ struct SubmittedMessage {
 	submission_timestamp: u64,
 	#[serde(with = "u256_serde")]
 	gas_payment: U256,
+	// Destination gas purchased from the IGP
+	#[serde(with = "u256_serde", default)]
+	gas_amount: U256,
+	// Hex-encoded Hyperlane message, kept for self-relay
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	message: Option<String>,
 	// Store computed payload hash to avoid recomputing
 	#[serde(with = "hex::serde")]
 	payload_hash: [u8; 32],
@@ -447,6 +507,8 @@ impl MessageTracker {
 		destination_chain: u64,
 		tx_hash: TransactionHash,
 		gas_payment: U256,
+		gas_amount: U256,
+		message: Option<Vec<u8>>,
 		payload_hash: [u8; 32],
 		solver_identifier: [u8; 32],
 		fill_timestamp: u32,
@@ -461,6 +523,8 @@ impl MessageTracker {
 				.unwrap()
 				.as_secs(),
 			gas_payment,
+			gas_amount,
+			message: message.map(hex::encode),
 			payload_hash,
 			solver_identifier,
 			fill_timestamp,
@@ -473,6 +537,7 @@ impl MessageTracker {
 			.unwrap_or(HyperlaneMessageState {
 				submitted: None,
 				delivered: None,
+				relay_attempted_at: None,
 			});
 
 		state.submitted = Some(submission);
@@ -525,6 +590,147 @@ impl MessageTracker {
 		let state = self.load_message(order_id).await?;
 		state.submitted.map(|m| m.message_id)
 	}
+
+	/// Returns the interchain gas payment recorded for an order's message
+	pub async fn get_gas_payment(&self, order_id: &str) -> Option<U256> {
+		let state = self.load_message(order_id).await?;
+		state.submitted.map(|m| m.gas_payment)
+	}
+
+	/// Generate storage key for the gas payment history of a message route
+	fn gas_history_key(origin_chain: u64, destination_chain: u64) -> String {
+		format!("hyperlane-igp:{}:{}", origin_chain, destination_chain)
+	}
+
+	/// Record an observed interchain gas payment for a message route
+	pub async fn record_gas_payment(
+		&self,
+		origin_chain: u64,
+		destination_chain: u64,
+		payment: U256,
+	) -> Result<(), SettlementError> {
+		let key = Self::gas_history_key(origin_chain, destination_chain);
+		let mut history = self
+			.storage
+			.retrieve::<GasPaymentHistory>(StorageKey::SettlementGasPayments.as_str(), &key)
+			.await
+			.unwrap_or_default();
+
+		history.samples.push(payment.to_string());
+		if history.samples.len() > GAS_PAYMENT_HISTORY_SIZE {
+			let excess = history.samples.len() - GAS_PAYMENT_HISTORY_SIZE;
+			history.samples.drain(..excess);
+		}
+
+		self.storage
+			.store(
+				StorageKey::SettlementGasPayments.as_str(),
+				&key,
+				&history,
+				None,
+			)
+			.await
+			.map_err(|e| {
+				SettlementError::ValidationFailed(format!("Failed to persist gas payments: {}", e))
+			})
+	}
+
+	/// Average interchain gas payment observed for a message route
+	///
+	/// Returns None when no payment has been recorded for the route yet.
+	pub async fn average_gas_payment(
+		&self,
+		origin_chain: u64,
+		destination_chain: u64,
+	) -> Result<Option<U256>, SettlementError> {
+		let key = Self::gas_history_key(origin_chain, destination_chain);
+		let history = match self
+			.storage
+			.retrieve::<GasPaymentHistory>(StorageKey::SettlementGasPayments.as_str(), &key)
+			.await
+		{
+			Ok(history) => history,
+			Err(StorageError::NotFound) => return Ok(None),
+			Err(e) => {
+				return Err(SettlementError::ValidationFailed(format!(
+					"Failed to load gas payments: {}",
+					e
+				)))
+			},
+		};
+
+		let samples: Vec<U256> = history
+			.samples
+			.iter()
+			.filter_map(|s| s.parse::<U256>().ok())
+			.collect();
+		if samples.is_empty() {
+			return Ok(None);
+		}
+
+		let total = samples
+			.iter()
+			.fold(U256::ZERO, |acc, s| acc.saturating_add(*s));
+		Ok(Some(total / U256::from(samples.len())))
+	}
+
+	/// Watchdog check for a message that has not been delivered in time.
+	///
+	/// Returns the submission when the message is older than `threshold_seconds`,
+	/// has not been marked delivered, and we have not attempted a relay within
+	/// the same threshold window.
+	async fn find_stalled_message(
+		&self,
+		order_id: &str,
+		threshold_seconds: u64,
+	) -> Option<SubmittedMessage> {
+		let state = self.load_message(order_id).await?;
+		if state.delivered.is_some() {
+			return None;
+		}
+		let submission = state.submitted?;
+
+		let now = std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_secs();
+		if now.saturating_sub(submission.submission_timestamp) < threshold_seconds {
+			return None;
+		}
+		if let Some(attempted_at) = state.relay_attempted_at {
+			if now.saturating_sub(attempted_at) < threshold_seconds {
+				return None;
+			}
+		}
+
+		Some(submission)
+	}
+
+	/// Record that we submitted a self-relay for an order's message
+	async fn mark_relay_attempted(&self, order_id: &str) -> Result<(), SettlementError> {
+		let mut state = self.load_message(order_id).await.ok_or_else(|| {
+			SettlementError::ValidationFailed("Message not found in tracker".to_string())
+		})?;
+
+		state.relay_attempted_at = Some(
+			std::time::SystemTime::now()
+				.duration_since(std::time::UNIX_EPOCH)
+				.unwrap()
+				.as_secs(),
+		);
+
+		self.save_message(order_id, &state).await
+	}
+
+	/// Forget a failed self-relay so the watchdog may try again right away
+	async fn clear_relay_attempt(&self, order_id: &str) -> Result<(), SettlementError> {
+		let mut state = self.load_message(order_id).await.ok_or_else(|| {
+			SettlementError::ValidationFailed("Message not found in tracker".to_string())
+		})?;
+
+		state.relay_attempted_at = None;
+		self.save_message(order_id, &state).await
+	}
 }
 
 /// Hyperlane settlement implementation
@@ -534,8 +740,11 @@ pub struct HyperlaneSettlement {
 	oracle_config: OracleConfig,
 	mailbox_addresses: HashMap<u64, solver_types::Address>,
 	igp_addresses: HashMap<u64, solver_types::Address>,
+	/// Output settler per chain, the source of attestation messages
+	output_settlers: HashMap<u64, solver_types::Address>,
 	message_tracker: Arc<MessageTracker>,
 	default_gas_limit: u64,
+	self_relay: SelfRelayConfig,
 }
 
 impl HyperlaneSettlement {
@@ -628,6 +837,7 @@ impl HyperlaneSettlement {
 				.unwrap_or(HyperlaneMessageState {
 					submitted: None,
 					delivered: None,
+					relay_attempted_at: None,
 				});
 
 		// Already delivered?
@@ -650,11 +860,9 @@ impl HyperlaneSettlement {
 		let dest_chain = submission.destination_chain;
 		let payload_hash = submission.payload_hash;
 
-		// Select oracles
-		// We need the input oracle on the destination chain (where we check isProven)
-		let input_oracle = self
-			.select_oracle(&self.get_input_oracles(dest_chain), None)
-			.ok_or_else(|| SettlementError::ValidationFailed("No input oracle".to_string()))?;
+		// We need the input oracle on the destination chain (where we check isProven),
+		// which is the one the order committed to
+		let input_oracle = order_input_oracle(order)?;
 
 		// We need the output oracle on the origin chain (the remote oracle)
 		let output_oracle = self
@@ -751,17 +959,137 @@ impl HyperlaneSettlement {
 		))
 	}
 
+	/// Extract the interchain gas payment for a message from IGP GasPayment logs.
+	///
+	/// Returns `(gas_amount, payment)` summed over all matching events. When an IGP
+	/// address is configured for the chain, only logs emitted by it are considered.
+	fn extract_gas_payment_from_logs(
+		&self,
+		logs: &[solver_types::Log],
+		message_id: [u8; 32],
+		chain_id: u64,
+	) -> (U256, U256) {
+		// GasPayment(bytes32 indexed messageId, uint32 indexed destinationDomain, uint256 gasAmount, uint256 payment)
+		let gas_payment_signature = keccak256("GasPayment(bytes32,uint32,uint256,uint256)");
+		let igp_address = self.igp_addresses.get(&chain_id);
+
+		let mut gas_amount = U256::ZERO;
+		let mut payment = U256::ZERO;
+
+		for log in logs {
+			if log.topics.len() < 2 || log.topics[0].0 != gas_payment_signature.0 {
+				continue;
+			}
+			if log.topics[1].0 != message_id {
+				continue;
+			}
+			if igp_address.is_some_and(|igp| igp.0 != log.address.0) {
+				continue;
+			}
+			if log.data.len() < 64 {
+				continue;
+			}
+
+			gas_amount = gas_amount.saturating_add(U256::from_be_slice(&log.data[0..32]));
+			payment = payment.saturating_add(U256::from_be_slice(&log.data[32..64]));
+		}
+
+		(gas_amount, payment)
+	}
+
+	/// Extract the raw Hyperlane message with the given id from the Mailbox Dispatch events
+	///
+	/// A transaction may dispatch several messages, so the message is matched by
+	/// its id, which is the keccak256 hash of the message.
+	fn extract_message_from_logs(
+		&self,
+		logs: &[solver_types::Log],
+		message_id: [u8; 32],
+	) -> Option<Vec<u8>> {
+		// Dispatch(address indexed sender, uint32 indexed destination, bytes32 indexed recipient, bytes message)
+		let dispatch_signature = keccak256("Dispatch(address,uint32,bytes32,bytes)");
+
+		logs.iter()
+			.filter(|log| !log.topics.is_empty() && log.topics[0].0 == dispatch_signature.0)
+			.find_map(|log| {
+				// ABI-encoded bytes: offset word, length word, then the data
+				let data = &log.data;
+				if data.len() < 64 {
+					return None;
+				}
+				let offset = usize::try_from(U256::from_be_slice(&data[0..32])).ok()?;
+				let len_end = offset.checked_add(32)?;
+				let len = usize::try_from(U256::from_be_slice(data.get(offset..len_end)?)).ok()?;
+				data.get(len_end..len_end.checked_add(len)?)
+					.filter(|m| alloy_primitives::keccak256(m).0 == message_id)
+					.map(|m| m.to_vec())
+			})
+	}
+
+	/// Check whether the Mailbox on a chain has already processed a message
+	async fn is_message_delivered(
+		&self,
+		chain_id: u64,
+		message_id: [u8; 32],
+	) -> Result<bool, SettlementError> {
+		let provider = self.providers.get(&chain_id).ok_or_else(|| {
+			SettlementError::ValidationFailed(format!("No provider for chain {}", chain_id))
+		})?;
+		let mailbox = self.mailbox_addresses.get(&chain_id).ok_or_else(|| {
+			SettlementError::ValidationFailed(format!("No mailbox for chain {}", chain_id))
+		})?;
+
+		let call_data = IMailbox::deliveredCall {
+			messageId: FixedBytes::<32>::from(message_id),
+		};
+
+		let request = alloy_rpc_types::eth::transaction::TransactionRequest {
+			to: Some(alloy_primitives::TxKind::Call(
+				alloy_primitives::Address::from_slice(&mailbox.0),
+			)),
+			input: call_data.abi_encode().into(),
+			..Default::default()
+		};
+
+		let result = provider.call(request).await.map_err(|e| {
+			SettlementError::ValidationFailed(format!("Failed to call delivered: {}", e))
+		})?;
+
+		Ok(result.len() >= 32 && result[31] != 0)
+	}
+
+	/// Attribute a messaging cost to the order's input oracle and route for scored selection
+	async fn record_oracle_cost(
+		&self,
+		order: &Order,
+		origin_chain: u64,
+		destination_chain: u64,
+		cost: U256,
+	) {
+		let Some(health) = self.oracle_config.health.as_ref() else {
+			return;
+		};
+		if let Ok(oracle) = order_input_oracle(order) {
+			health
+				.record_cost(origin_chain, destination_chain, &oracle, cost)
+				.await;
+		}
+	}
+
 	/// Creates a new HyperlaneSettlement instance
+	#[allow(clippy::too_many_arguments)]
 	pub async fn new(
 		networks: &NetworksConfig,
 		oracle_config: OracleConfig,
 		mailbox_addresses: HashMap<u64, solver_types::Address>,
 		igp_addresses: HashMap<u64, solver_types::Address>,
 		default_gas_limit: u64,
+		self_relay: SelfRelayConfig,
 		storage: Arc<StorageService>,
 	) -> Result<Self, SettlementError> {
 		// Create RPC providers for each network that has oracles configured
 		let mut providers = HashMap::new();
+		let mut output_settlers = HashMap::new();
 
 		// Collect unique network IDs from input and output oracles
 		let mut all_network_ids: Vec<u64> = oracle_config
@@ -796,6 +1124,7 @@ impl HyperlaneSettlement {
 			})?);
 
 			providers.insert(*network_id, provider);
+			output_settlers.insert(*network_id, network.output_settler_address.clone());
 		}
 
 		// Validate mailbox addresses are configured for all oracle chains
@@ -816,11 +1145,76 @@ impl HyperlaneSettlement {
 			oracle_config,
 			mailbox_addresses,
 			igp_addresses,
+			output_settlers,
 			message_tracker: Arc::new(message_tracker),
 			default_gas_limit,
+			self_relay,
 		})
 	}
 
+	/// Quote the gas payment for attesting a fill on a route
+	///
+	/// Used for routes without recorded payments. The quote covers a single fill
+	/// description without call data or context.
+	async fn quote_route_gas_payment(
+		&self,
+		origin_chain: u64,
+		destination_chain: u64,
+	) -> Result<U256, SettlementError> {
+		let recipient_oracle = self
+			.get_input_oracles(origin_chain)
+			.into_iter()
+			.next()
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed(format!(
+					"No input oracle configured for chain {}",
+					origin_chain
+				))
+			})?;
+		let output_settler = self
+			.output_settlers
+			.get(&destination_chain)
+			.cloned()
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed(format!(
+					"No output settler for chain {}",
+					destination_chain
+				))
+			})?;
+
+		let payload = encode_fill_description(
+			[0u8; 32],
+			[0u8; 32],
+			0,
+			[0u8; 32],
+			U256::ZERO,
+			[0u8; 32],
+			Vec::new(),
+			Vec::new(),
+		)?;
+		let gas_limit = self.calculate_message_gas_limit(payload.len());
+
+		self.estimate_gas_payment(
+			destination_chain,
+			origin_chain as u32,
+			recipient_oracle,
+			gas_limit,
+			vec![],
+			output_settler,
+			vec![payload],
+		)
+		.await
+	}
+
+	/// Encode the `Mailbox.process` call relaying a dispatched message
+	fn relay_call_data(&self, message: Vec<u8>) -> Vec<u8> {
+		IMailbox::processCall {
+			metadata: self.self_relay.metadata.clone().into(),
+			message: message.into(),
+		}
+		.abi_encode()
+	}
+
 	/// Calculate gas limit for a Hyperlane message
 	fn calculate_message_gas_limit(&self, payload_size: usize) -> U256 {
 		// Base gas for message handling
@@ -949,6 +1343,50 @@ impl ConfigSchema for HyperlaneSettlementSchema {
 					},
 				),
 				Field::new("finalization_required", FieldType::Boolean),
+				Field::new(
+					"oracle_scoring",
+					FieldType::Table(Schema::new(
+						vec![],
+						vec![
+							Field::new(
+								"max_consecutive_failures",
+								FieldType::Integer {
+									min: Some(1),
+									max: None,
+								},
+							),
+							Field::new(
+								"exclusion_seconds",
+								FieldType::Integer {
+									min: Some(0),
+									max: None,
+								},
+							),
+						],
+					)),
+				),
+				Field::new(
+					"self_relay",
+					FieldType::Table(Schema::new(
+						vec![],
+						vec![
+							Field::new("enabled", FieldType::Boolean),
+							Field::new(
+								"threshold_seconds",
+								FieldType::Integer {
+									min: Some(60),
+									max: Some(86400),
+								},
+							),
+							Field::new("metadata", FieldType::String).with_validator(|value| {
+								let metadata = value.as_str().unwrap_or_default();
+								hex::decode(metadata.trim_start_matches("0x"))
+									.map(|_| ())
+									.map_err(|e| format!("Invalid hex metadata: {}", e))
+							}),
+						],
+					)),
+				),
 			],
 		);
 		schema.validate(config)
@@ -995,30 +1433,16 @@ impl SettlementInterface for HyperlaneSettlement {
 			))
 		})?;
 
-		// Get the oracle address using selection strategy
-		let oracle_addresses = self.get_input_oracles(origin_chain_id);
-		if oracle_addresses.is_empty() {
+		// Use the oracle the order committed to; the claim rebuilds the order
+		// with it, so any other oracle would change the order id
+		let oracle_address = order_input_oracle(order)?;
+		if !self.is_input_oracle_supported(origin_chain_id, &oracle_address) {
 			return Err(SettlementError::ValidationFailed(format!(
-				"No input oracle configured for chain {}",
+				"Order input oracle is not configured for chain {}",
 				origin_chain_id
 			)));
 		}
 
-		// Use order ID hash for deterministic oracle selection
-		let order_id_hash = keccak256(&order.id);
-		let selection_context =
-			u64::from_be_bytes(order_id_hash[0..8].try_into().map_err(|_| {
-				SettlementError::ValidationFailed("Failed to convert hash bytes".to_string())
-			})?);
-		let oracle_address = self
-			.select_oracle(&oracle_addresses, Some(selection_context))
-			.ok_or_else(|| {
-				SettlementError::ValidationFailed(format!(
-					"Failed to select oracle for chain {}",
-					origin_chain_id
-				))
-			})?;
-
 		// Get transaction receipt
 		let hash = FixedBytes::<32>::from_slice(&tx_hash.0);
 		let receipt = provider
@@ -1153,9 +1577,13 @@ impl SettlementInterface for HyperlaneSettlement {
 			return Ok(None);
 		}
 
-		let recipient_oracle = self.select_oracle(&input_oracles, None).ok_or_else(|| {
-			SettlementError::ValidationFailed("Failed to select recipient".into())
-		})?;
+		// The message must reach the input oracle the order committed to
+		let recipient_oracle = order_input_oracle(order)?;
+		if !input_oracles.contains(&recipient_oracle) {
+			return Err(SettlementError::ValidationFailed(
+				"Order input oracle is not configured for its origin chain".into(),
+			));
+		}
 
 		// Extract fill details from order
 		let output = extract_output_details(order)?;
@@ -1222,6 +1650,7 @@ impl SettlementInterface for HyperlaneSettlement {
 		};
 
 		Ok(Some(Transaction {
+			from: None,
 			to: Some(oracle_address),
 			data: call_data.abi_encode(),
 			value: gas_payment,
@@ -1309,6 +1738,29 @@ impl SettlementInterface for HyperlaneSettlement {
 			// Compute payload hash once and store it
 			let payload_hash = self.compute_payload_hash(order, solver_id, timestamp)?;
 
+			// Read the actual interchain gas payment from the IGP events
+			let (gas_amount, gas_payment) =
+				self.extract_gas_payment_from_logs(&receipt.logs, message_id, dest_chain);
+			if gas_payment.is_zero() {
+				tracing::warn!(
+					message_id = %hex::encode(message_id),
+					"No GasPayment event found for Hyperlane message"
+				);
+			} else {
+				if let Err(e) = self
+					.message_tracker
+					.record_gas_payment(dest_chain, origin_chain, gas_payment)
+					.await
+				{
+					tracing::warn!(error = %e, "Failed to record Hyperlane gas payment");
+				}
+				self.record_oracle_cost(order, origin_chain, dest_chain, gas_payment)
+					.await;
+			}
+
+			// Keep the raw message so it can be relayed by the watchdog if needed
+			let message = self.extract_message_from_logs(&receipt.logs, message_id);
+
 			// Store in message tracker with all details for later use
 			// PostFill happens on dest_chain, message goes from dest_chain to origin_chain
 			self.message_tracker
@@ -1318,7 +1770,9 @@ impl SettlementInterface for HyperlaneSettlement {
 					dest_chain,   // origin_chain in submission = where message originates from
 					origin_chain, // destination_chain in submission = where message goes to
 					receipt.hash.clone(),
-					U256::ZERO, // TODO: Gas payment would be calculated from actual receipt
+					gas_payment,
+					gas_amount,
+					message,
 					payload_hash,
 					solver_id,
 					timestamp,
@@ -1327,11 +1781,117 @@ impl SettlementInterface for HyperlaneSettlement {
 
 			tracing::info!(
 				message_id = %hex::encode(message_id),
+				gas_payment = %gas_payment,
+				gas_amount = %gas_amount,
 				"Hyperlane message tracked"
 			);
 		}
 		Ok(())
 	}
+
+	async fn estimate_settlement_fee(
+		&self,
+		origin_chain: u64,
+		destination_chain: u64,
+	) -> Result<Option<U256>, SettlementError> {
+		// The message is sent from the destination chain back to the origin chain,
+		// and the IGP is paid in the destination chain's native currency
+		let average = self
+			.message_tracker
+			.average_gas_payment(destination_chain, origin_chain)
+			.await?;
+		match average {
+			Some(fee) => Ok(Some(fee)),
+			// Nothing paid on this route yet, so ask the oracle
+			None => self
+				.quote_route_gas_payment(origin_chain, destination_chain)
+				.await
+				.map(Some),
+		}
+	}
+
+	async fn generate_relay_transaction(
+		&self,
+		order: &Order,
+	) -> Result<Option<Transaction>, SettlementError> {
+		let submission = match self
+			.message_tracker
+			.find_stalled_message(&order.id, self.self_relay.threshold_seconds)
+			.await
+		{
+			Some(submission) => submission,
+			None => return Ok(None),
+		};
+
+		tracing::warn!(
+			order_id = %solver_types::utils::formatting::truncate_id(&order.id),
+			message_id = %hex::encode(submission.message_id),
+			submitted_at = submission.submission_timestamp,
+			"Hyperlane message not delivered within threshold"
+		);
+
+		if !self.self_relay.enabled {
+			return Ok(None);
+		}
+
+		let destination_chain = submission.destination_chain;
+
+		// The public relayer may have delivered it since our last proof check
+		if self
+			.is_message_delivered(destination_chain, submission.message_id)
+			.await?
+		{
+			return Ok(None);
+		}
+
+		let message = match submission
+			.message
+			.as_ref()
+			.and_then(|m| hex::decode(m).ok())
+		{
+			Some(message) => message,
+			None => {
+				tracing::warn!(
+					order_id = %solver_types::utils::formatting::truncate_id(&order.id),
+					"Cannot self-relay Hyperlane message without the dispatched message body"
+				);
+				return Ok(None);
+			},
+		};
+
+		let mailbox = self
+			.mailbox_addresses
+			.get(&destination_chain)
+			.cloned()
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed(format!(
+					"No mailbox for chain {}",
+					destination_chain
+				))
+			})?;
+
+		Ok(Some(Transaction {
+			from: None,
+			to: Some(mailbox),
+			data: self.relay_call_data(message),
+			value: U256::ZERO,
+			chain_id: destination_chain,
+			nonce: None,
+			gas_limit: None,
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			metadata: None,
+		}))
+	}
+
+	async fn record_relay_submitted(&self, order: &Order) -> Result<(), SettlementError> {
+		self.message_tracker.mark_relay_attempted(&order.id).await
+	}
+
+	async fn record_relay_failed(&self, order: &Order) -> Result<(), SettlementError> {
+		self.message_tracker.clear_relay_attempt(&order.id).await
+	}
 }
 
 /// Helper function to parse address tables from config
@@ -1370,6 +1930,36 @@ fn parse_address_table(
 	Ok(result)
 }
 
+/// Parse the self-relay watchdog settings.
+///
+/// The stall threshold falls back to `message_timeout_seconds` when not set explicitly.
+fn parse_self_relay_config(config: &toml::Value) -> Result<SelfRelayConfig, SettlementError> {
+	let mut self_relay = SelfRelayConfig::default();
+
+	if let Some(timeout) = config
+		.get("message_timeout_seconds")
+		.and_then(|v| v.as_integer())
+	{
+		self_relay.threshold_seconds = timeout as u64;
+	}
+
+	if let Some(table) = config.get("self_relay") {
+		if let Some(enabled) = table.get("enabled").and_then(|v| v.as_bool()) {
+			self_relay.enabled = enabled;
+		}
+		if let Some(threshold) = table.get("threshold_seconds").and_then(|v| v.as_integer()) {
+			self_relay.threshold_seconds = threshold as u64;
+		}
+		if let Some(metadata) = table.get("metadata").and_then(|v| v.as_str()) {
+			self_relay.metadata = hex::decode(metadata.trim_start_matches("0x")).map_err(|e| {
+				SettlementError::ValidationFailed(format!("Invalid self-relay metadata: {}", e))
+			})?;
+		}
+	}
+
+	Ok(self_relay)
+}
+
 /// Factory function to create a Hyperlane settlement provider from configuration
 pub fn create_settlement(
 	config: &toml::Value,
@@ -1381,7 +1971,8 @@ pub fn create_settlement(
 		.map_err(|e| SettlementError::ValidationFailed(format!("Invalid configuration: {}", e)))?;
 
 	// Parse oracle configuration using common utilities
-	let oracle_config = parse_oracle_config(config)?;
+	let mut oracle_config = parse_oracle_config(config)?;
+	let health_config = parse_oracle_health_config(config);
 
 	// Parse mailbox addresses
 	let mailbox_addresses = parse_address_table(
@@ -1401,15 +1992,23 @@ pub fn create_settlement(
 		.and_then(|v| v.as_integer())
 		.unwrap_or(500000) as u64;
 
+	let self_relay = parse_self_relay_config(config)?;
+
 	// Create settlement service synchronously
 	let settlement = tokio::task::block_in_place(|| {
 		tokio::runtime::Handle::current().block_on(async {
+			if oracle_config.selection_strategy == OracleSelectionStrategy::Scored {
+				oracle_config.health = Some(Arc::new(
+					OracleHealthTracker::new(storage.clone(), health_config).await,
+				));
+			}
 			HyperlaneSettlement::new(
 				networks,
 				oracle_config,
 				mailbox_addresses,
 				igp_addresses,
 				default_gas_limit,
+				self_relay,
 				storage,
 			)
 			.await
@@ -1429,6 +2028,337 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_settlement
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(HyperlaneSettlementSchema)
+	}
 }
 
 impl crate::SettlementRegistry for Registry {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_primitives::{Address as AlloyAddress, Bytes, LogData};
+	use alloy_sol_types::SolEvent;
+	use solver_storage::implementations::memory::MemoryStorage;
+	use solver_types::{utils::tests::builders::OrderBuilder, Log, H256};
+
+	mod mailbox {
+		alloy_sol_types::sol! {
+			// Mailbox dispatch event carrying the full message
+			event Dispatch(
+				address indexed sender,
+				uint32 indexed destination,
+				bytes32 indexed recipient,
+				bytes message
+			);
+		}
+	}
+
+	const ORIGIN: u64 = 1;
+	const DESTINATION: u64 = 2;
+	const THRESHOLD: u64 = 600;
+
+	fn address(byte: u8) -> solver_types::Address {
+		solver_types::Address(vec![byte; 20])
+	}
+
+	fn create_settlement(self_relay: SelfRelayConfig) -> HyperlaneSettlement {
+		let storage = Arc::new(StorageService::new(Box::new(MemoryStorage::new())));
+		HyperlaneSettlement {
+			providers: HashMap::new(),
+			oracle_config: OracleConfig {
+				input_oracles: HashMap::new(),
+				output_oracles: HashMap::new(),
+				routes: HashMap::new(),
+				selection_strategy: OracleSelectionStrategy::RoundRobin,
+				health: None,
+			},
+			mailbox_addresses: HashMap::from([(DESTINATION, address(0xaa))]),
+			igp_addresses: HashMap::from([(ORIGIN, address(0xbb))]),
+			output_settlers: HashMap::new(),
+			message_tracker: Arc::new(MessageTracker {
+				storage,
+				cache: Arc::new(RwLock::new(HashMap::new())),
+			}),
+			default_gas_limit: 300_000,
+			self_relay,
+		}
+	}
+
+	fn to_log(emitter: &solver_types::Address, data: LogData) -> Log {
+		Log {
+			address: emitter.clone(),
+			topics: data.topics().iter().map(|t| H256(t.0)).collect(),
+			data: data.data.to_vec(),
+		}
+	}
+
+	fn gas_payment_log(
+		emitter: &solver_types::Address,
+		message_id: [u8; 32],
+		gas_amount: u64,
+		payment: u64,
+	) -> Log {
+		let event = GasPayment {
+			messageId: FixedBytes(message_id),
+			destinationDomain: DESTINATION as u32,
+			gasAmount: U256::from(gas_amount),
+			payment: U256::from(payment),
+		};
+		to_log(emitter, event.encode_log_data())
+	}
+
+	/// A Hyperlane v3 message: version, nonce, origin, sender, destination,
+	/// recipient, then the body.
+	fn hyperlane_message(body: &[u8]) -> Vec<u8> {
+		let mut message = vec![3u8];
+		message.extend_from_slice(&7u32.to_be_bytes());
+		message.extend_from_slice(&(DESTINATION as u32).to_be_bytes());
+		message.extend_from_slice(&[0x11; 32]);
+		message.extend_from_slice(&(ORIGIN as u32).to_be_bytes());
+		message.extend_from_slice(&[0x22; 32]);
+		message.extend_from_slice(body);
+		message
+	}
+
+	fn dispatch_log(message: &[u8]) -> Log {
+		let event = mailbox::Dispatch {
+			sender: AlloyAddress::repeat_byte(0x11),
+			destination: ORIGIN as u32,
+			recipient: FixedBytes::repeat_byte(0x22),
+			message: Bytes::copy_from_slice(message),
+		};
+		to_log(&address(0xaa), event.encode_log_data())
+	}
+
+	fn now() -> u64 {
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_secs()
+	}
+
+	async fn store_submission(
+		settlement: &HyperlaneSettlement,
+		order_id: &str,
+		submitted_secs_ago: u64,
+		delivered: bool,
+	) {
+		let message_id = [0x42; 32];
+		let state = HyperlaneMessageState {
+			submitted: Some(SubmittedMessage {
+				message_id,
+				origin_chain: DESTINATION,
+				destination_chain: ORIGIN,
+				submission_tx_hash: TransactionHash(vec![0x01; 32]),
+				submission_timestamp: now() - submitted_secs_ago,
+				gas_payment: U256::from(1000u64),
+				gas_amount: U256::from(200_000u64),
+				message: Some(hex::encode(hyperlane_message(b"payload"))),
+				payload_hash: [0x33; 32],
+				solver_identifier: [0x44; 32],
+				fill_timestamp: 0,
+			}),
+			delivered: delivered.then(|| DeliveredMessage {
+				message_id,
+				delivery_timestamp: now(),
+				payload_hash: [0x33; 32],
+			}),
+			relay_attempted_at: None,
+		};
+		settlement
+			.message_tracker
+			.save_message(order_id, &state)
+			.await
+			.unwrap();
+	}
+
+	#[test]
+	fn test_extract_gas_payment_from_logs() {
+		let settlement = create_settlement(SelfRelayConfig::default());
+		let message_id = [0x42; 32];
+		let igp = address(0xbb);
+		let logs = vec![
+			gas_payment_log(&igp, message_id, 150_000, 3_000),
+			// A top-up for the same message is added to the first payment
+			gas_payment_log(&igp, message_id, 50_000, 1_000),
+			// Payments for other messages or from other contracts are ignored
+			gas_payment_log(&igp, [0x43; 32], 1, 1),
+			gas_payment_log(&address(0xcc), message_id, 1, 1),
+			dispatch_log(&hyperlane_message(b"payload")),
+		];
+
+		let (gas_amount, payment) =
+			settlement.extract_gas_payment_from_logs(&logs, message_id, ORIGIN);
+		assert_eq!(gas_amount, U256::from(200_000u64));
+		assert_eq!(payment, U256::from(4_000u64));
+
+		// Without a configured IGP, payments from any emitter count
+		let (gas_amount, _) =
+			settlement.extract_gas_payment_from_logs(&logs, message_id, DESTINATION);
+		assert_eq!(gas_amount, U256::from(200_001u64));
+	}
+
+	#[test]
+	fn test_extract_message_from_logs() {
+		let settlement = create_settlement(SelfRelayConfig::default());
+		let other = hyperlane_message(&[0xcd; 40]);
+		let message = hyperlane_message(&[0xab; 100]);
+		let message_id = alloy_primitives::keccak256(&message).0;
+		let logs = vec![
+			gas_payment_log(&address(0xbb), message_id, 1, 1),
+			dispatch_log(&other),
+			dispatch_log(&message),
+		];
+
+		// The dispatch carrying the message id is picked, not the first one
+		assert_eq!(
+			settlement.extract_message_from_logs(&logs, message_id),
+			Some(message)
+		);
+		assert_eq!(
+			settlement.extract_message_from_logs(&logs[..2], message_id),
+			None
+		);
+		assert_eq!(
+			settlement.extract_message_from_logs(&logs, [0x42; 32]),
+			None
+		);
+
+		// Truncated event data is not read past its end
+		let mut truncated = logs[2].clone();
+		truncated.data.truncate(80);
+		assert_eq!(
+			settlement.extract_message_from_logs(&[truncated], message_id),
+			None
+		);
+	}
+
+	#[tokio::test]
+	async fn test_find_stalled_message_threshold() {
+		let settlement = create_settlement(SelfRelayConfig::default());
+		let tracker = &settlement.message_tracker;
+
+		store_submission(&settlement, "recent", THRESHOLD - 60, false).await;
+		assert!(tracker
+			.find_stalled_message("recent", THRESHOLD)
+			.await
+			.is_none());
+
+		store_submission(&settlement, "stalled", THRESHOLD + 60, false).await;
+		assert!(tracker
+			.find_stalled_message("stalled", THRESHOLD)
+			.await
+			.is_some());
+
+		store_submission(&settlement, "delivered", THRESHOLD + 60, true).await;
+		assert!(tracker
+			.find_stalled_message("delivered", THRESHOLD)
+			.await
+			.is_none());
+
+		assert!(tracker
+			.find_stalled_message("unknown", THRESHOLD)
+			.await
+			.is_none());
+	}
+
+	#[tokio::test]
+	async fn test_settlement_fee_from_gas_history() {
+		let settlement = create_settlement(SelfRelayConfig::default());
+		let tracker = &settlement.message_tracker;
+		assert_eq!(
+			tracker
+				.average_gas_payment(DESTINATION, ORIGIN)
+				.await
+				.unwrap(),
+			None
+		);
+
+		// Without history the fee is quoted, which fails without a provider
+		// instead of being reported as zero
+		assert!(settlement
+			.estimate_settlement_fee(ORIGIN, DESTINATION)
+			.await
+			.is_err());
+
+		for payment in [100u64, 300] {
+			tracker
+				.record_gas_payment(DESTINATION, ORIGIN, U256::from(payment))
+				.await
+				.unwrap();
+		}
+		assert_eq!(
+			settlement
+				.estimate_settlement_fee(ORIGIN, DESTINATION)
+				.await
+				.unwrap(),
+			Some(U256::from(200u64))
+		);
+	}
+
+	#[tokio::test]
+	async fn test_relay_attempt_suppresses_until_failed() {
+		let settlement = create_settlement(SelfRelayConfig::default());
+		let order = OrderBuilder::new().with_id("stalled").build();
+		store_submission(&settlement, &order.id, THRESHOLD + 60, false).await;
+
+		settlement.record_relay_submitted(&order).await.unwrap();
+		assert!(settlement
+			.message_tracker
+			.find_stalled_message(&order.id, THRESHOLD)
+			.await
+			.is_none());
+
+		// A reverted or dropped relay makes the message eligible again
+		settlement.record_relay_failed(&order).await.unwrap();
+		assert!(settlement
+			.message_tracker
+			.find_stalled_message(&order.id, THRESHOLD)
+			.await
+			.is_some());
+	}
+
+	#[tokio::test]
+	async fn test_no_relay_when_disabled_or_not_stalled() {
+		let settlement = create_settlement(SelfRelayConfig::default());
+		let order = OrderBuilder::new().with_id("stalled").build();
+		store_submission(&settlement, &order.id, THRESHOLD + 60, false).await;
+		assert!(settlement
+			.generate_relay_transaction(&order)
+			.await
+			.unwrap()
+			.is_none());
+
+		let settlement = create_settlement(SelfRelayConfig {
+			enabled: true,
+			..SelfRelayConfig::default()
+		});
+		store_submission(&settlement, &order.id, THRESHOLD - 60, false).await;
+		assert!(settlement
+			.generate_relay_transaction(&order)
+			.await
+			.unwrap()
+			.is_none());
+	}
+
+	#[test]
+	fn test_relay_call_data_encodes_process() {
+		let metadata = vec![0x01, 0x02, 0x03];
+		let settlement = create_settlement(SelfRelayConfig {
+			enabled: true,
+			threshold_seconds: THRESHOLD,
+			metadata: metadata.clone(),
+		});
+		let message = hyperlane_message(b"payload");
+
+		let data = settlement.relay_call_data(message.clone());
+		assert_eq!(data[..4], keccak256("process(bytes,bytes)")[..4]);
+
+		let call = IMailbox::processCall::abi_decode(&data).unwrap();
+		assert_eq!(call.metadata.to_vec(), metadata);
+		assert_eq!(call.message.to_vec(), message);
+	}
+}
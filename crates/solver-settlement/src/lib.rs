@@ -5,6 +5,8 @@
 //! and the final claiming process for solver rewards. It supports different
 //! settlement mechanisms for various order standards.
 
+use alloy_primitives::U256;
+use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use solver_types::{
 	oracle::{OracleInfo, OracleRoutes},
@@ -25,6 +27,11 @@ pub mod implementations {
 /// Common utilities for settlement implementations
 pub mod utils;
 
+/// Oracle health and cost tracking for scored selection
+pub mod health;
+
+use health::OracleHealthTracker;
+
 /// Errors that can occur during settlement operations.
 #[derive(Debug, Error)]
 pub enum SettlementError {
@@ -48,6 +55,9 @@ pub enum OracleSelectionStrategy {
 	RoundRobin,
 	/// Random selection from available oracles
 	Random,
+	/// Pick the oracle with the best latency, reliability, and cost score for
+	/// the route when quoting; other selections use the first oracle
+	Scored,
 }
 
 impl Default for OracleSelectionStrategy {
@@ -67,6 +77,8 @@ pub struct OracleConfig {
 	pub routes: HashMap<u64, Vec<u64>>,
 	/// Strategy for selecting oracles when multiple are available
 	pub selection_strategy: OracleSelectionStrategy,
+	/// Health tracker backing the scored strategy
+	pub health: Option<Arc<OracleHealthTracker>>,
 }
 
 /// Trait defining the interface for settlement mechanisms.
@@ -157,6 +169,9 @@ pub trait SettlementInterface: Send + Sync {
 				let index = (RandomState::new().hash_one(context) as usize) % oracles.len();
 				oracles.get(index).cloned()
 			},
+			// Scores are kept per route, so scored picks are made when quoting
+			// through `SettlementService::get_best_settlement_for_route`
+			OracleSelectionStrategy::Scored => oracles.first().cloned(),
 		}
 	}
 
@@ -232,6 +247,46 @@ pub trait SettlementInterface: Send + Sync {
 	) -> Result<(), SettlementError> {
 		Ok(()) // Default: no-op for settlements that don't need this
 	}
+
+	/// Estimates the messaging fee paid to settle an order on the given route.
+	///
+	/// The fee is denominated in the native currency (wei) of the destination chain,
+	/// where post-fill transactions are submitted. Returns None when the mechanism
+	/// charges no such fee, and an error when the fee cannot be estimated.
+	async fn estimate_settlement_fee(
+		&self,
+		_origin_chain: u64,
+		_destination_chain: u64,
+	) -> Result<Option<U256>, SettlementError> {
+		Ok(None) // Default: no messaging fee
+	}
+
+	/// Generates a transaction to relay a settlement message that has not been
+	/// delivered in time (optional).
+	///
+	/// Called periodically while waiting for claim readiness. For Hyperlane this
+	/// processes the dispatched message on the destination mailbox ourselves
+	/// when the public relayer has stalled.
+	async fn generate_relay_transaction(
+		&self,
+		_order: &Order,
+	) -> Result<Option<Transaction>, SettlementError> {
+		Ok(None) // Default: rely on external delivery
+	}
+
+	/// Records that a relay transaction was sent for an order (optional).
+	///
+	/// Called once the transaction from `generate_relay_transaction` has been
+	/// submitted, so the message is not relayed again while it is pending.
+	async fn record_relay_submitted(&self, _order: &Order) -> Result<(), SettlementError> {
+		Ok(())
+	}
+
+	/// Records that a relay transaction reverted or was dropped (optional), so
+	/// the message may be relayed again on the next check.
+	async fn record_relay_failed(&self, _order: &Order) -> Result<(), SettlementError> {
+		Ok(())
+	}
 }
 
 /// Type alias for settlement factory functions.
@@ -266,11 +321,28 @@ pub fn get_all_implementations() -> Vec<(&'static str, SettlementFactory)> {
 	]
 }
 
+/// Get configuration schemas for all registered settlement implementations.
+///
+/// Returns a vector of (name, schema) tuples, used to validate configuration
+/// without constructing the implementations.
+pub fn get_all_schemas() -> Vec<(&'static str, Box<dyn ConfigSchema>)> {
+	use implementations::{direct, hyperlane};
+
+	vec![
+		(direct::Registry::NAME, direct::Registry::config_schema()),
+		(
+			hyperlane::Registry::NAME,
+			hyperlane::Registry::config_schema(),
+		),
+	]
+}
+
 /// Service managing settlement implementations.
 pub struct SettlementService {
 	/// Map of implementation names to their instances.
 	/// Keys are implementation type names (e.g., "direct", "optimistic").
-	implementations: HashMap<String, Box<dyn SettlementInterface>>,
+	/// Swappable so oracle route changes can be applied on config reload.
+	implementations: ArcSwap<HashMap<String, Arc<dyn SettlementInterface>>>,
 	/// Track order count for round-robin selection
 	selection_counter: Arc<AtomicU64>,
 	/// Poll interval for settlement monitoring in seconds
@@ -288,17 +360,39 @@ impl SettlementService {
 		poll_interval_seconds: u64,
 	) -> Self {
 		Self {
-			implementations,
+			implementations: ArcSwap::from_pointee(Self::into_shared(implementations)),
 			selection_counter: Arc::new(AtomicU64::new(0)),
 			poll_interval_seconds,
 		}
 	}
 
+	/// Converts boxed implementations into shared handles.
+	fn into_shared(
+		implementations: HashMap<String, Box<dyn SettlementInterface>>,
+	) -> HashMap<String, Arc<dyn SettlementInterface>> {
+		implementations
+			.into_iter()
+			.map(|(name, implementation)| (name, Arc::from(implementation)))
+			.collect()
+	}
+
+	/// Replaces all settlement implementations.
+	///
+	/// Callers holding an implementation from before the swap keep using it
+	/// until they finish; new lookups see the replacements.
+	pub fn replace_implementations(
+		&self,
+		implementations: HashMap<String, Box<dyn SettlementInterface>>,
+	) {
+		self.implementations
+			.store(Arc::new(Self::into_shared(implementations)));
+	}
+
 	/// Gets a specific settlement implementation by name.
 	///
 	/// Returns None if the implementation doesn't exist.
-	pub fn get(&self, name: &str) -> Option<&dyn SettlementInterface> {
-		self.implementations.get(name).map(|b| b.as_ref())
+	pub fn get(&self, name: &str) -> Option<Arc<dyn SettlementInterface>> {
+		self.implementations.load().get(name).cloned()
 	}
 
 	/// Get the configured poll interval for settlement monitoring
@@ -308,9 +402,19 @@ impl SettlementService {
 
 	/// Build oracle routes from all settlement implementations.
 	pub fn build_oracle_routes(&self) -> OracleRoutes {
+		Self::oracle_routes_for(self.implementations.load().values().map(|s| s.as_ref()))
+	}
+
+	/// Build oracle routes from a set of settlement implementations.
+	///
+	/// Used to derive routes for replacement implementations before they are
+	/// swapped in.
+	pub fn oracle_routes_for<'a>(
+		implementations: impl IntoIterator<Item = &'a dyn SettlementInterface>,
+	) -> OracleRoutes {
 		let mut supported_routes = HashMap::new();
 
-		for settlement in self.implementations.values() {
+		for settlement in implementations {
 			let config = settlement.oracle_config();
 
 			// For each input oracle
@@ -355,14 +459,14 @@ impl SettlementService {
 		chain_id: u64,
 		oracle_address: &Address,
 		is_input: bool,
-	) -> Result<&dyn SettlementInterface, SettlementError> {
-		for settlement in self.implementations.values() {
+	) -> Result<Arc<dyn SettlementInterface>, SettlementError> {
+		for settlement in self.implementations.load().values() {
 			if is_input {
 				if settlement.is_input_oracle_supported(chain_id, oracle_address) {
-					return Ok(settlement.as_ref());
+					return Ok(settlement.clone());
 				}
 			} else if settlement.is_output_oracle_supported(chain_id, oracle_address) {
-				return Ok(settlement.as_ref());
+				return Ok(settlement.clone());
 			}
 		}
 		Err(SettlementError::ValidationFailed(format!(
@@ -381,7 +485,7 @@ impl SettlementService {
 	pub fn find_settlement_for_order(
 		&self,
 		order: &Order,
-	) -> Result<&dyn SettlementInterface, SettlementError> {
+	) -> Result<Arc<dyn SettlementInterface>, SettlementError> {
 		let order_data = order
 			.parse_order_data()
 			.map_err(|e| SettlementError::ValidationFailed(e.to_string()))?;
@@ -399,14 +503,14 @@ impl SettlementService {
 	pub fn get_any_settlement_for_chain(
 		&self,
 		chain_id: u64,
-	) -> Option<(&dyn SettlementInterface, Address)> {
+	) -> Option<(Arc<dyn SettlementInterface>, Address)> {
 		// Collect all settlements that support this chain with their oracles
 		let mut available_settlements = Vec::new();
 
-		for settlement in self.implementations.values() {
+		for settlement in self.implementations.load().values() {
 			if let Some(oracles) = settlement.oracle_config().input_oracles.get(&chain_id) {
 				if !oracles.is_empty() {
-					available_settlements.push((settlement.as_ref(), oracles.clone()));
+					available_settlements.push((settlement.clone(), oracles.clone()));
 				}
 			}
 		}
@@ -422,13 +526,105 @@ impl SettlementService {
 		if available_settlements.len() == 1 {
 			let (settlement, oracles) = &available_settlements[0];
 			let selected_oracle = settlement.select_oracle(oracles, Some(context))?;
-			return Some((*settlement, selected_oracle));
+			return Some((settlement.clone(), selected_oracle));
 		}
 
 		// Multiple settlements - use first one but apply oracle selection
 		let (settlement, oracles) = &available_settlements[0];
 		let selected_oracle = settlement.select_oracle(oracles, Some(context))?;
-		Some((*settlement, selected_oracle))
+		Some((settlement.clone(), selected_oracle))
+	}
+
+	/// Get the best-scoring settlement and input oracle for a route.
+	///
+	/// Only settlements using the scored strategy and supporting the route take
+	/// part. Scores are computed across all of their input oracles together, so
+	/// the result is comparable between implementations. Returns None when no
+	/// settlement on the route is scored.
+	pub fn get_best_settlement_for_route(
+		&self,
+		input_chain: u64,
+		output_chain: u64,
+	) -> Option<(Arc<dyn SettlementInterface>, Address)> {
+		let mut candidates = Vec::new();
+		for settlement in self.implementations.load().values() {
+			let config = settlement.oracle_config();
+			if config.selection_strategy != OracleSelectionStrategy::Scored
+				|| !settlement.is_route_supported(input_chain, output_chain)
+			{
+				continue;
+			}
+			let Some(health) = config.health.as_ref() else {
+				continue;
+			};
+			for oracle in config.input_oracles.get(&input_chain).into_iter().flatten() {
+				let stats = health.stats(input_chain, output_chain, oracle);
+				let excluded = stats.as_ref().is_some_and(|s| health.is_excluded(s));
+				candidates.push((settlement.clone(), oracle.clone(), stats, excluded));
+			}
+		}
+
+		if candidates.is_empty() {
+			return None;
+		}
+
+		let scores = health::score_oracles(
+			&candidates
+				.iter()
+				.map(|(_, _, stats, _)| stats.as_ref())
+				.collect::<Vec<_>>(),
+		);
+
+		let pick = |allow_excluded: bool| {
+			candidates
+				.iter()
+				.zip(scores.iter())
+				.filter(|((_, _, _, excluded), _)| allow_excluded || !excluded)
+				.min_by(|(_, a), (_, b)| a.total_cmp(b))
+				.map(|((settlement, oracle, _, _), _)| (settlement.clone(), oracle.clone()))
+		};
+
+		pick(false).or_else(|| pick(true))
+	}
+
+	/// Records the outcome of waiting for an attestation on a filled order.
+	///
+	/// `latency` is the time until the order became claimable, or None when
+	/// monitoring gave up. No-op unless the settlement tracks oracle health.
+	pub async fn record_attestation_outcome(
+		&self,
+		order: &Order,
+		fill_proof: &FillProof,
+		latency: Option<std::time::Duration>,
+	) {
+		let Ok(settlement) = self.find_settlement_for_order(order) else {
+			return;
+		};
+		let Some(health) = settlement.oracle_config().health.as_ref() else {
+			return;
+		};
+		let Ok(oracle) = solver_types::utils::parse_address(&fill_proof.oracle_address) else {
+			return;
+		};
+		let (Some(input_chain), Some(output_chain)) = (
+			order.input_chains.first().map(|c| c.chain_id),
+			order.output_chains.first().map(|c| c.chain_id),
+		) else {
+			return;
+		};
+
+		match latency {
+			Some(latency) => {
+				health
+					.record_success(input_chain, output_chain, &oracle, latency)
+					.await
+			},
+			None => {
+				health
+					.record_failure(input_chain, output_chain, &oracle)
+					.await
+			},
+		}
 	}
 
 	/// Gets attestation for a filled order using the appropriate settlement implementation.
@@ -472,6 +668,53 @@ impl SettlementService {
 			.await
 	}
 
+	/// Estimates the messaging fee for a route across all settlements supporting it.
+	///
+	/// Returns the highest estimate so quotes stay conservative, None when no
+	/// settlement on the route charges a fee, and an error when any of them
+	/// cannot estimate its fee.
+	pub async fn estimate_settlement_fee(
+		&self,
+		origin_chain: u64,
+		destination_chain: u64,
+	) -> Result<Option<U256>, SettlementError> {
+		let mut fee: Option<U256> = None;
+		let implementations = self.implementations.load_full();
+		for settlement in implementations.values() {
+			if !settlement.is_route_supported(origin_chain, destination_chain) {
+				continue;
+			}
+			if let Some(estimate) = settlement
+				.estimate_settlement_fee(origin_chain, destination_chain)
+				.await?
+			{
+				fee = Some(fee.map_or(estimate, |f| f.max(estimate)));
+			}
+		}
+		Ok(fee)
+	}
+
+	/// Generates a relay transaction for a stalled settlement message, if needed.
+	pub async fn generate_relay_transaction(
+		&self,
+		order: &Order,
+	) -> Result<Option<Transaction>, SettlementError> {
+		let implementation = self.find_settlement_for_order(order)?;
+		implementation.generate_relay_transaction(order).await
+	}
+
+	/// Records that a relay transaction was sent for an order.
+	pub async fn record_relay_submitted(&self, order: &Order) -> Result<(), SettlementError> {
+		let implementation = self.find_settlement_for_order(order)?;
+		implementation.record_relay_submitted(order).await
+	}
+
+	/// Records that a relay transaction for an order reverted or was dropped.
+	pub async fn record_relay_failed(&self, order: &Order) -> Result<(), SettlementError> {
+		let implementation = self.find_settlement_for_order(order)?;
+		implementation.record_relay_failed(order).await
+	}
+
 	/// Generates a pre-claim transaction if needed by the settlement implementation.
 	pub async fn generate_pre_claim_transaction(
 		&self,
@@ -3,8 +3,9 @@
 //! This module provides shared utilities for parsing oracle configurations
 //! from TOML config files, used by all settlement implementations.
 
+use crate::health::OracleHealthConfig;
 use crate::{OracleConfig, OracleSelectionStrategy, SettlementError};
-use solver_types::{utils::parse_address, Address};
+use solver_types::{utils::parse_address, Address, Order};
 use std::collections::HashMap;
 
 /// Parse an oracle table from TOML configuration.
@@ -133,6 +134,7 @@ pub fn parse_selection_strategy(value: Option<&str>) -> OracleSelectionStrategy
 		Some("First") => OracleSelectionStrategy::First,
 		Some("RoundRobin") => OracleSelectionStrategy::RoundRobin,
 		Some("Random") => OracleSelectionStrategy::Random,
+		Some("Scored") => OracleSelectionStrategy::Scored,
 		_ => OracleSelectionStrategy::default(),
 	}
 }
@@ -185,9 +187,47 @@ pub fn parse_oracle_config(config: &toml::Value) -> Result<OracleConfig, Settlem
 		output_oracles,
 		routes,
 		selection_strategy,
+		health: None,
 	})
 }
 
+/// Parse oracle health tracking settings for the scored strategy.
+///
+/// Expects an optional table like:
+/// ```toml
+/// [oracle_scoring]
+/// max_consecutive_failures = 3
+/// exclusion_seconds = 1800
+/// ```
+pub fn parse_oracle_health_config(config: &toml::Value) -> OracleHealthConfig {
+	let mut health_config = OracleHealthConfig::default();
+
+	if let Some(table) = config.get("oracle_scoring") {
+		if let Some(max_failures) = table
+			.get("max_consecutive_failures")
+			.and_then(|v| v.as_integer())
+		{
+			health_config.max_consecutive_failures = max_failures as u32;
+		}
+		if let Some(exclusion) = table.get("exclusion_seconds").and_then(|v| v.as_integer()) {
+			health_config.exclusion_seconds = exclusion as u64;
+		}
+	}
+
+	health_config
+}
+
+/// Parse the input oracle an order committed to when it was opened.
+///
+/// The claim rebuilds the order with this oracle, so every step after the
+/// order is opened must use it instead of selecting one from configuration.
+pub fn order_input_oracle(order: &Order) -> Result<Address, SettlementError> {
+	let order_data = order
+		.parse_order_data()
+		.map_err(|e| SettlementError::ValidationFailed(e.to_string()))?;
+	parse_address(&order_data.input_oracle()).map_err(SettlementError::ValidationFailed)
+}
+
 /// Validate that all routes reference chains with configured oracles.
 fn validate_routes(
 	input_oracles: &HashMap<u64, Vec<Address>>,
@@ -235,6 +275,10 @@ mod tests {
 			parse_selection_strategy(Some("Random")),
 			OracleSelectionStrategy::Random
 		);
+		assert_eq!(
+			parse_selection_strategy(Some("Scored")),
+			OracleSelectionStrategy::Scored
+		);
 		assert_eq!(
 			parse_selection_strategy(Some("Invalid")),
 			OracleSelectionStrategy::First
@@ -752,4 +796,41 @@ mod tests {
 			OracleSelectionStrategy::First
 		));
 	}
+
+	#[test]
+	fn test_parse_oracle_health_config() {
+		let config: toml::Value = toml::from_str(
+			r#"
+			[oracle_scoring]
+			max_consecutive_failures = 5
+			exclusion_seconds = 600
+			"#,
+		)
+		.unwrap();
+
+		let result = parse_oracle_health_config(&config);
+		assert_eq!(result.max_consecutive_failures, 5);
+		assert_eq!(result.exclusion_seconds, 600);
+
+		// Defaults when the table is absent
+		let result = parse_oracle_health_config(&toml::Value::Table(toml::map::Map::new()));
+		assert_eq!(result.max_consecutive_failures, 3);
+		assert_eq!(result.exclusion_seconds, 1800);
+	}
+
+	#[test]
+	fn test_order_input_oracle() {
+		use solver_types::utils::tests::builders::OrderBuilder;
+
+		let order = OrderBuilder::new().build();
+		assert_eq!(
+			order_input_oracle(&order).unwrap(),
+			parse_address("0x1234567890123456789012345678901234567890").unwrap()
+		);
+
+		let order = OrderBuilder::new()
+			.with_data(serde_json::json!({ "user": "0x1234" }))
+			.build();
+		assert!(order_input_oracle(&order).is_err());
+	}
 }
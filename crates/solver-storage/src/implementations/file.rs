@@ -115,6 +115,20 @@ impl FileHeader {
 
 		now >= self.expires_at
 	}
+
+	/// Time left until the data expires, or None if it never does.
+	fn remaining(&self) -> Option<Duration> {
+		if self.expires_at == 0 {
+			return None;
+		}
+
+		let now = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap()
+			.as_secs();
+
+		Some(Duration::from_secs(self.expires_at.saturating_sub(now)))
+	}
 }
 
 /// Index structure for a namespace.
@@ -606,6 +620,25 @@ impl StorageInterface for FileStorage {
 		Ok(path.exists())
 	}
 
+	async fn get_ttl(&self, key: &str) -> Result<Option<Duration>, StorageError> {
+		let path = self.get_file_path(key);
+
+		let data = match fs::read(&path).await {
+			Ok(data) => data,
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+				return Err(StorageError::NotFound)
+			},
+			Err(e) => return Err(StorageError::Backend(e.to_string())),
+		};
+
+		match FileHeader::deserialize(&data) {
+			Ok(header) if header.is_expired() => Err(StorageError::NotFound),
+			Ok(header) => Ok(header.remaining()),
+			// Legacy file without header never expires
+			Err(_) => Ok(None),
+		}
+	}
+
 	fn config_schema(&self) -> Box<dyn ConfigSchema> {
 		Box::new(FileStorageSchema)
 	}
@@ -712,6 +745,39 @@ impl StorageInterface for FileStorage {
 		Ok(valid_keys)
 	}
 
+	async fn list_keys(&self, namespace: &str) -> Result<Vec<String>, StorageError> {
+		let mut entries = match fs::read_dir(&self.base_path).await {
+			Ok(entries) => entries,
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+			Err(e) => return Err(StorageError::Backend(e.to_string())),
+		};
+
+		// File names are sanitized keys, so "orders:abc" is stored as "orders_abc.bin"
+		let prefix = format!("{}_", namespace);
+		let mut keys = Vec::new();
+		while let Some(entry) = entries
+			.next_entry()
+			.await
+			.map_err(|e| StorageError::Backend(e.to_string()))?
+		{
+			let file_name = entry.file_name();
+			let Some(id) = file_name
+				.to_str()
+				.and_then(|name| name.strip_suffix(".bin"))
+				.and_then(|name| name.strip_prefix(&prefix))
+			else {
+				continue;
+			};
+			let key = format!("{}:{}", namespace, id);
+			// Skip expired entries
+			if self.get_bytes(&key).await.is_ok() {
+				keys.push(key);
+			}
+		}
+
+		Ok(keys)
+	}
+
 	async fn get_batch(&self, keys: &[String]) -> Result<Vec<(String, Vec<u8>)>, StorageError> {
 		let mut results = Vec::new();
 
@@ -803,6 +869,10 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_storage
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(FileStorageSchema)
+	}
 }
 
 impl crate::StorageRegistry for Registry {}
@@ -907,6 +977,35 @@ mod tests {
 		assert!(matches!(result, Err(StorageError::NotFound)));
 	}
 
+	#[tokio::test]
+	async fn test_get_ttl() {
+		let (storage, _temp_dir) = create_test_storage();
+		let value = b"test_value".to_vec();
+
+		storage
+			.set_bytes(
+				"quotes:q1",
+				value.clone(),
+				None,
+				Some(Duration::from_secs(600)),
+			)
+			.await
+			.unwrap();
+		storage
+			.set_bytes("quotes:q2", value, None, None)
+			.await
+			.unwrap();
+
+		let remaining = storage.get_ttl("quotes:q1").await.unwrap().unwrap();
+		assert!(remaining <= Duration::from_secs(600));
+		assert!(remaining >= Duration::from_secs(598));
+		assert_eq!(storage.get_ttl("quotes:q2").await.unwrap(), None);
+		assert!(matches!(
+			storage.get_ttl("quotes:missing").await,
+			Err(StorageError::NotFound)
+		));
+	}
+
 	#[tokio::test]
 	async fn test_indexing_operations() {
 		let (storage, _temp_dir) = create_test_storage_with_ttl();
@@ -1189,4 +1288,30 @@ mod tests {
 			.unwrap();
 		assert_eq!(all_items.len(), 10);
 	}
+
+	#[tokio::test]
+	async fn test_list_keys_includes_unindexed_entries() {
+		let (storage, _temp_dir) = create_test_storage();
+
+		let indexes = StorageIndexes::new().with_field("status", "pending");
+		storage
+			.set_bytes("orders:indexed", b"1".to_vec(), Some(indexes), None)
+			.await
+			.unwrap();
+		storage
+			.set_bytes("orders:plain", b"2".to_vec(), None, None)
+			.await
+			.unwrap();
+		storage
+			.set_bytes("order_by_tx_hash:abc", b"3".to_vec(), None, None)
+			.await
+			.unwrap();
+
+		let mut keys = storage.list_keys("orders").await.unwrap();
+		keys.sort();
+		assert_eq!(keys, vec!["orders:indexed", "orders:plain"]);
+
+		let keys = storage.list_keys("order_by_tx_hash").await.unwrap();
+		assert_eq!(keys, vec!["order_by_tx_hash:abc"]);
+	}
 }
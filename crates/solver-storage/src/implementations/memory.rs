@@ -82,6 +82,16 @@ impl StorageInterface for MemoryStorage {
 		Ok(Vec::new())
 	}
 
+	async fn list_keys(&self, namespace: &str) -> Result<Vec<String>, StorageError> {
+		let prefix = format!("{}:", namespace);
+		let store = self.store.read().await;
+		Ok(store
+			.keys()
+			.filter(|key| key.starts_with(&prefix))
+			.cloned()
+			.collect())
+	}
+
 	async fn get_batch(&self, keys: &[String]) -> Result<Vec<(String, Vec<u8>)>, StorageError> {
 		let store = self.store.read().await;
 		let mut results = Vec::new();
@@ -137,6 +147,10 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_storage
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(MemoryStorageSchema)
+	}
 }
 
 impl crate::StorageRegistry for Registry {}
@@ -196,4 +210,26 @@ mod tests {
 		let retrieved = storage.get_bytes(key).await.unwrap();
 		assert_eq!(retrieved, value2);
 	}
+
+	#[tokio::test]
+	async fn test_list_keys() {
+		let storage = MemoryStorage::new();
+
+		storage
+			.set_bytes("orders:a", b"1".to_vec(), None, None)
+			.await
+			.unwrap();
+		storage
+			.set_bytes("orders:b", b"2".to_vec(), None, None)
+			.await
+			.unwrap();
+		storage
+			.set_bytes("intents:a", b"3".to_vec(), None, None)
+			.await
+			.unwrap();
+
+		let mut keys = storage.list_keys("orders").await.unwrap();
+		keys.sort();
+		assert_eq!(keys, vec!["orders:a", "orders:b"]);
+	}
 }
@@ -5,6 +5,7 @@
 //! or distributed storage systems.
 
 use async_trait::async_trait;
+use migration::{MigrationRegistry, SCHEMA_META_NAMESPACE, SCHEMA_VERSIONS_ID};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use solver_types::{ConfigSchema, ImplementationRegistry};
 use std::collections::HashMap;
@@ -17,6 +18,8 @@ pub mod implementations {
 	pub mod memory;
 }
 
+pub mod migration;
+
 /// Query filter for storage operations.
 ///
 /// Used to filter items when querying storage backends.
@@ -77,6 +80,12 @@ pub enum StorageError {
 	/// Error that occurs during configuration validation.
 	#[error("Configuration error: {0}")]
 	Configuration(String),
+	/// Error that occurs when stored data uses a schema newer than this build.
+	#[error("Unsupported schema: {0}")]
+	UnsupportedSchema(String),
+	/// Error that occurs when upgrading a record to the current schema.
+	#[error("Migration error: {0}")]
+	Migration(String),
 }
 
 /// Trait defining the low-level interface for storage backends.
@@ -130,6 +139,22 @@ pub trait StorageInterface: Send + Sync {
 	/// Implementations should optimize for bulk retrieval where possible.
 	async fn get_batch(&self, keys: &[String]) -> Result<Vec<(String, Vec<u8>)>, StorageError>;
 
+	/// Lists all live keys in a namespace, including entries stored without indexes.
+	///
+	/// Used for offline inspection and export. The default implementation
+	/// falls back to the indexed keys returned by `query`.
+	async fn list_keys(&self, namespace: &str) -> Result<Vec<String>, StorageError> {
+		self.query(namespace, QueryFilter::All).await
+	}
+
+	/// Returns the time a key has left to live (optional operation).
+	///
+	/// Returns None for keys that never expire. Implementations that don't
+	/// support expiration can return Ok(None).
+	async fn get_ttl(&self, _key: &str) -> Result<Option<Duration>, StorageError> {
+		Ok(None) // Default implementation for backends without TTL support
+	}
+
 	/// Returns the configuration schema for validation.
 	fn config_schema(&self) -> Box<dyn ConfigSchema>;
 
@@ -166,6 +191,19 @@ pub fn get_all_implementations() -> Vec<(&'static str, StorageFactory)> {
 	]
 }
 
+/// Get configuration schemas for all registered storage implementations.
+///
+/// Returns a vector of (name, schema) tuples, used to validate configuration
+/// without constructing the implementations.
+pub fn get_all_schemas() -> Vec<(&'static str, Box<dyn ConfigSchema>)> {
+	use implementations::{file, memory};
+
+	vec![
+		(file::Registry::NAME, file::Registry::config_schema()),
+		(memory::Registry::NAME, memory::Registry::config_schema()),
+	]
+}
+
 /// High-level storage service that provides typed operations.
 ///
 /// The StorageService wraps a low-level storage backend and provides
@@ -174,12 +212,44 @@ pub fn get_all_implementations() -> Vec<(&'static str, StorageFactory)> {
 pub struct StorageService {
 	/// The underlying storage backend implementation.
 	backend: Box<dyn StorageInterface>,
+	/// Schema versions and migrations applied to stored records.
+	migrations: MigrationRegistry,
 }
 
 impl StorageService {
 	/// Creates a new StorageService with the specified backend.
 	pub fn new(backend: Box<dyn StorageInterface>) -> Self {
-		Self { backend }
+		Self {
+			backend,
+			migrations: MigrationRegistry::builtin(),
+		}
+	}
+
+	/// Replaces the migration registry used to version records.
+	pub fn with_migrations(mut self, migrations: MigrationRegistry) -> Self {
+		self.migrations = migrations;
+		self
+	}
+
+	/// Serializes a value into a versioned envelope for its namespace.
+	fn encode<T: Serialize>(&self, namespace: &str, data: &T) -> Result<Vec<u8>, StorageError> {
+		let data =
+			serde_json::to_value(data).map_err(|e| StorageError::Serialization(e.to_string()))?;
+		let envelope = migration::wrap(self.migrations.current_version(namespace), data);
+		serde_json::to_vec(&envelope).map_err(|e| StorageError::Serialization(e.to_string()))
+	}
+
+	/// Deserializes stored bytes, upgrading old records to the current schema.
+	fn decode<T: DeserializeOwned>(
+		&self,
+		namespace: &str,
+		bytes: &[u8],
+	) -> Result<T, StorageError> {
+		let value: serde_json::Value = serde_json::from_slice(bytes)
+			.map_err(|e| StorageError::Serialization(e.to_string()))?;
+		let (version, data) = migration::unwrap(value);
+		let data = self.migrations.upgrade(namespace, version, data)?;
+		serde_json::from_value(data).map_err(|e| StorageError::Serialization(e.to_string()))
 	}
 
 	/// Stores a serializable value with optional indexes and time-to-live.
@@ -195,8 +265,7 @@ impl StorageService {
 		ttl: Option<Duration>,
 	) -> Result<(), StorageError> {
 		let key = format!("{}:{}", namespace, id);
-		let bytes =
-			serde_json::to_vec(data).map_err(|e| StorageError::Serialization(e.to_string()))?;
+		let bytes = self.encode(namespace, data)?;
 		self.backend.set_bytes(&key, bytes, indexes, ttl).await
 	}
 
@@ -215,7 +284,8 @@ impl StorageService {
 	/// Retrieves and deserializes a value from storage.
 	///
 	/// The namespace and id are combined to form the lookup key.
-	/// The retrieved bytes are deserialized from JSON.
+	/// The retrieved bytes are deserialized from JSON, upgrading records
+	/// written with an older schema.
 	pub async fn retrieve<T: DeserializeOwned>(
 		&self,
 		namespace: &str,
@@ -223,7 +293,19 @@ impl StorageService {
 	) -> Result<T, StorageError> {
 		let key = format!("{}:{}", namespace, id);
 		let bytes = self.backend.get_bytes(&key).await?;
-		serde_json::from_slice(&bytes).map_err(|e| StorageError::Serialization(e.to_string()))
+		self.decode(namespace, &bytes)
+	}
+
+	/// Returns the time a value has left to live, or None if it never expires.
+	///
+	/// The namespace and id are combined to form the lookup key.
+	pub async fn remaining_ttl(
+		&self,
+		namespace: &str,
+		id: &str,
+	) -> Result<Option<Duration>, StorageError> {
+		let key = format!("{}:{}", namespace, id);
+		self.backend.get_ttl(&key).await
 	}
 
 	/// Removes a value from storage.
@@ -253,8 +335,7 @@ impl StorageService {
 			return Err(StorageError::NotFound);
 		}
 
-		let bytes =
-			serde_json::to_vec(data).map_err(|e| StorageError::Serialization(e.to_string()))?;
+		let bytes = self.encode(namespace, data)?;
 		self.backend.set_bytes(&key, bytes, indexes, None).await
 	}
 
@@ -294,8 +375,7 @@ impl StorageService {
 			return Err(StorageError::NotFound);
 		}
 
-		let bytes =
-			serde_json::to_vec(data).map_err(|e| StorageError::Serialization(e.to_string()))?;
+		let bytes = self.encode(namespace, data)?;
 		self.backend.set_bytes(&key, bytes, indexes, ttl).await
 	}
 
@@ -316,7 +396,7 @@ impl StorageService {
 		for (key, bytes) in results {
 			// Extract ID from key (format: "namespace:id")
 			let id = key.split(':').nth(1).unwrap_or(&key).to_string();
-			match serde_json::from_slice::<T>(&bytes) {
+			match self.decode::<T>(namespace, &bytes) {
 				Ok(item) => items.push((id, item)),
 				Err(e) => {
 					tracing::warn!("Failed to deserialize item {}: {}", key, e);
@@ -328,6 +408,20 @@ impl StorageService {
 		Ok(items)
 	}
 
+	/// Lists the ids of all items in a namespace, whether indexed or not.
+	pub async fn list_ids(&self, namespace: &str) -> Result<Vec<String>, StorageError> {
+		let prefix = format!("{}:", namespace);
+		let mut ids: Vec<String> = self
+			.backend
+			.list_keys(namespace)
+			.await?
+			.into_iter()
+			.filter_map(|key| key.strip_prefix(&prefix).map(str::to_string))
+			.collect();
+		ids.sort();
+		Ok(ids)
+	}
+
 	/// Retrieve all items in a namespace.
 	///
 	/// Uses batch operations for efficiency when loading many items.
@@ -337,4 +431,91 @@ impl StorageService {
 	) -> Result<Vec<(String, T)>, StorageError> {
 		self.query(namespace, QueryFilter::All).await
 	}
+
+	/// Checks that stored data was not written by a newer schema and records
+	/// the schema versions of this build.
+	///
+	/// Call before using storage so a downgraded solver refuses to start
+	/// instead of misreading records it does not understand.
+	pub async fn ensure_schema_compatible(&self) -> Result<(), StorageError> {
+		let mut recorded: HashMap<String, u32> = match self
+			.retrieve(SCHEMA_META_NAMESPACE, SCHEMA_VERSIONS_ID)
+			.await
+		{
+			Ok(recorded) => recorded,
+			Err(StorageError::NotFound) => HashMap::new(),
+			Err(e) => return Err(e),
+		};
+
+		let mut newer: Vec<_> = recorded
+			.iter()
+			.filter(|(namespace, version)| **version > self.migrations.current_version(namespace))
+			.map(|(namespace, version)| {
+				format!(
+					"'{}' is at version {} but this build supports up to {}",
+					namespace,
+					version,
+					self.migrations.current_version(namespace)
+				)
+			})
+			.collect();
+		if !newer.is_empty() {
+			newer.sort();
+			return Err(StorageError::UnsupportedSchema(newer.join(", ")));
+		}
+
+		let mut changed = false;
+		for (namespace, version) in self.migrations.versions() {
+			if recorded.get(namespace) != Some(version) {
+				recorded.insert(namespace.clone(), *version);
+				changed = true;
+			}
+		}
+		if changed {
+			self.store(SCHEMA_META_NAMESPACE, SCHEMA_VERSIONS_ID, &recorded, None)
+				.await?;
+		}
+		Ok(())
+	}
+
+	/// Rewrites every record stored with an older schema in its current layout.
+	///
+	/// Returns the number of records migrated. Indexes are kept, but backends
+	/// with TTL support reset the expiry of rewritten records to the
+	/// namespace default.
+	pub async fn migrate(&self) -> Result<usize, StorageError> {
+		let mut namespaces: Vec<_> = self.migrations.versions().iter().collect();
+		namespaces.sort();
+
+		let mut migrated = 0;
+		for (namespace, current) in namespaces {
+			let mut count = 0;
+			for id in self.list_ids(namespace).await? {
+				let key = format!("{}:{}", namespace, id);
+				let bytes = match self.backend.get_bytes(&key).await {
+					Ok(bytes) => bytes,
+					// Expired or removed since listing
+					Err(StorageError::NotFound) => continue,
+					Err(e) => return Err(e),
+				};
+				let value: serde_json::Value = serde_json::from_slice(&bytes)
+					.map_err(|e| StorageError::Serialization(e.to_string()))?;
+				let (version, data) = migration::unwrap(value);
+				if version == *current {
+					continue;
+				}
+
+				let data = self.migrations.upgrade(namespace, version, data)?;
+				let bytes = serde_json::to_vec(&migration::wrap(*current, data))
+					.map_err(|e| StorageError::Serialization(e.to_string()))?;
+				self.backend.set_bytes(&key, bytes, None, None).await?;
+				count += 1;
+			}
+			if count > 0 {
+				tracing::info!(namespace = %namespace, count, version = current, "Migrated records");
+			}
+			migrated += count;
+		}
+		Ok(migrated)
+	}
 }
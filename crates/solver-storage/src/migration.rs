@@ -0,0 +1,276 @@
+//! Schema versioning and migrations for stored records.
+//!
+//! Every record written through `StorageService` is wrapped in an envelope
+//! carrying the schema version of its namespace. When a stored type such as
+//! `Order` or `Intent` changes shape, the version of its namespace is bumped
+//! and a migration step is registered that upgrades a record by one version.
+//! Old records are upgraded in memory when read, and rewritten in bulk by
+//! `StorageService::migrate`.
+
+use crate::StorageError;
+use serde_json::Value;
+use solver_types::StorageKey;
+use std::collections::HashMap;
+
+/// Namespace holding storage metadata such as the recorded schema versions.
+pub const SCHEMA_META_NAMESPACE: &str = "storage_meta";
+
+/// Id of the record holding the schema version of each namespace.
+pub const SCHEMA_VERSIONS_ID: &str = "schema_versions";
+
+/// Schema version assumed for records written before envelopes existed.
+pub const LEGACY_SCHEMA_VERSION: u32 = 1;
+
+/// Envelope field holding the schema version.
+///
+/// The name is reserved for envelopes; stored types never use it, so a record
+/// is only read as an envelope when it carries this exact marker.
+const VERSION_FIELD: &str = "__solver_schema";
+
+/// Envelope field holding the record itself.
+const DATA_FIELD: &str = "data";
+
+/// Upgrades a record by exactly one schema version.
+pub type MigrationFn = fn(Value) -> Result<Value, String>;
+
+/// Current schema versions per namespace and the steps to reach them.
+#[derive(Clone, Default)]
+pub struct MigrationRegistry {
+	/// Namespace -> current schema version.
+	versions: HashMap<String, u32>,
+	/// (namespace, from version) -> step upgrading to the next version.
+	steps: HashMap<(String, u32), MigrationFn>,
+}
+
+impl MigrationRegistry {
+	/// Creates an empty registry where every namespace is at the legacy version.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Creates the registry describing the record layouts of this build.
+	///
+	/// When a stored type changes shape, bump its namespace version here and
+	/// register the step upgrading records from the previous version.
+	pub fn builtin() -> Self {
+		StorageKey::all().fold(Self::new(), |registry, key| {
+			registry.with_version(key.as_str(), LEGACY_SCHEMA_VERSION)
+		})
+	}
+
+	/// Sets the current schema version of a namespace.
+	pub fn with_version(mut self, namespace: impl Into<String>, version: u32) -> Self {
+		self.versions.insert(namespace.into(), version);
+		self
+	}
+
+	/// Registers the step upgrading records of a namespace from `from_version`.
+	pub fn with_migration(
+		mut self,
+		namespace: impl Into<String>,
+		from_version: u32,
+		step: MigrationFn,
+	) -> Self {
+		self.steps.insert((namespace.into(), from_version), step);
+		self
+	}
+
+	/// Returns the current schema version of a namespace.
+	pub fn current_version(&self, namespace: &str) -> u32 {
+		self.versions
+			.get(namespace)
+			.copied()
+			.unwrap_or(LEGACY_SCHEMA_VERSION)
+	}
+
+	/// Returns the current schema version of every registered namespace.
+	pub fn versions(&self) -> &HashMap<String, u32> {
+		&self.versions
+	}
+
+	/// Upgrades a record from `version` to the current version of its namespace.
+	///
+	/// Fails for records written by a newer build, since their layout is unknown.
+	pub fn upgrade(
+		&self,
+		namespace: &str,
+		version: u32,
+		data: Value,
+	) -> Result<Value, StorageError> {
+		let current = self.current_version(namespace);
+		if version > current {
+			return Err(StorageError::UnsupportedSchema(format!(
+				"'{}' record has schema version {} but this build supports up to {}",
+				namespace, version, current
+			)));
+		}
+
+		(version..current).try_fold(data, |data, from| {
+			let step = self
+				.steps
+				.get(&(namespace.to_string(), from))
+				.ok_or_else(|| {
+					StorageError::Migration(format!(
+						"no migration registered for '{}' from version {}",
+						namespace, from
+					))
+				})?;
+			step(data).map_err(|e| {
+				StorageError::Migration(format!(
+					"'{}' migration from version {} failed: {}",
+					namespace, from, e
+				))
+			})
+		})
+	}
+}
+
+/// Wraps a record in a versioned envelope.
+pub(crate) fn wrap(version: u32, data: Value) -> Value {
+	let mut envelope = serde_json::Map::new();
+	envelope.insert(VERSION_FIELD.to_string(), Value::from(version));
+	envelope.insert(DATA_FIELD.to_string(), data);
+	Value::Object(envelope)
+}
+
+/// Splits a stored value into its schema version and record.
+///
+/// Values without an envelope predate versioning and are treated as
+/// `LEGACY_SCHEMA_VERSION`.
+pub(crate) fn unwrap(value: Value) -> (u32, Value) {
+	match value {
+		Value::Object(mut map)
+			if map.len() == 2
+				&& map.contains_key(DATA_FIELD)
+				&& map.contains_key(VERSION_FIELD) =>
+		{
+			let version = map
+				.get(VERSION_FIELD)
+				.and_then(Value::as_u64)
+				.map(|v| v as u32);
+			match version {
+				Some(version) => (version, map.remove(DATA_FIELD).unwrap_or(Value::Null)),
+				None => (LEGACY_SCHEMA_VERSION, Value::Object(map)),
+			}
+		},
+		other => (LEGACY_SCHEMA_VERSION, other),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{implementations::memory::MemoryStorage, StorageInterface, StorageService};
+	use serde::{Deserialize, Serialize};
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct Record {
+		id: String,
+		amount: String,
+	}
+
+	/// v1 stored the amount as a number, v2 stores it as a string.
+	fn amount_to_string(mut data: Value) -> Result<Value, String> {
+		let amount = data
+			.get("amount")
+			.and_then(Value::as_u64)
+			.ok_or("missing amount")?;
+		data["amount"] = Value::String(amount.to_string());
+		Ok(data)
+	}
+
+	fn registry_v2() -> MigrationRegistry {
+		MigrationRegistry::new()
+			.with_version("records", 2)
+			.with_migration("records", 1, amount_to_string)
+	}
+
+	#[test]
+	fn test_envelope_round_trip() {
+		let data = serde_json::json!({ "id": "a" });
+		assert_eq!(unwrap(wrap(3, data.clone())), (3, data));
+	}
+
+	#[test]
+	fn test_unversioned_values_are_legacy() {
+		let data = serde_json::json!({ "id": "a", "data": 1 });
+		assert_eq!(unwrap(data.clone()), (LEGACY_SCHEMA_VERSION, data));
+	}
+
+	#[test]
+	fn test_legacy_record_shaped_like_envelope() {
+		// A record of its own with `_schema` and `data` fields is not an envelope
+		let data = serde_json::json!({ "_schema": 5, "data": { "id": "a" } });
+		assert_eq!(unwrap(data.clone()), (LEGACY_SCHEMA_VERSION, data));
+	}
+
+	#[test]
+	fn test_upgrade_applies_steps_in_order() {
+		let registry =
+			registry_v2()
+				.with_version("records", 3)
+				.with_migration("records", 2, |mut data| {
+					data["migrated"] = Value::Bool(true);
+					Ok(data)
+				});
+
+		let upgraded = registry
+			.upgrade("records", 1, serde_json::json!({ "amount": 5 }))
+			.unwrap();
+		assert_eq!(
+			upgraded,
+			serde_json::json!({ "amount": "5", "migrated": true })
+		);
+	}
+
+	#[test]
+	fn test_upgrade_rejects_newer_and_missing_steps() {
+		let registry = registry_v2();
+		assert!(matches!(
+			registry.upgrade("records", 3, Value::Null),
+			Err(StorageError::UnsupportedSchema(_))
+		));
+
+		let registry = MigrationRegistry::new().with_version("records", 2);
+		assert!(matches!(
+			registry.upgrade("records", 1, Value::Null),
+			Err(StorageError::Migration(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn test_legacy_records_upgrade_on_read_and_migrate() {
+		let backend = MemoryStorage::new();
+		backend
+			.set_bytes(
+				"records:a",
+				br#"{"id":"a","amount":7}"#.to_vec(),
+				None,
+				None,
+			)
+			.await
+			.unwrap();
+		let storage = StorageService::new(Box::new(backend)).with_migrations(registry_v2());
+
+		let record: Record = storage.retrieve("records", "a").await.unwrap();
+		assert_eq!(record.amount, "7");
+
+		assert_eq!(storage.migrate().await.unwrap(), 1);
+		assert_eq!(storage.migrate().await.unwrap(), 0);
+		let record: Record = storage.retrieve("records", "a").await.unwrap();
+		assert_eq!(record.amount, "7");
+	}
+
+	#[tokio::test]
+	async fn test_schema_check_refuses_newer_storage() {
+		let backend = MemoryStorage::new();
+		let newer = StorageService::new(Box::new(backend)).with_migrations(registry_v2());
+		newer.ensure_schema_compatible().await.unwrap();
+
+		let older = newer.with_migrations(MigrationRegistry::new().with_version("records", 1));
+		assert!(matches!(
+			older.ensure_schema_compatible().await,
+			Err(StorageError::UnsupportedSchema(_))
+		));
+	}
+}
@@ -82,6 +82,10 @@ impl From<PrimitiveSignature> for Signature {
 /// to various blockchain networks.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
+	/// Account that signs and sends the transaction.
+	/// When unset, delivery uses the default signer of the chain.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub from: Option<Address>,
 	/// Recipient address (None for contract creation).
 	pub to: Option<Address>,
 	/// Transaction data/calldata.
@@ -110,6 +114,7 @@ pub struct Transaction {
 impl From<TransactionRequest> for Transaction {
 	fn from(req: TransactionRequest) -> Self {
 		Transaction {
+			from: req.from.map(|addr| Address(addr.as_slice().to_vec())),
 			to: req.to.map(|addr| match addr {
 				alloy_primitives::TxKind::Call(a) => Address(a.as_slice().to_vec()),
 				alloy_primitives::TxKind::Create => panic!("Create transactions not supported"),
@@ -136,7 +141,10 @@ impl From<Transaction> for TransactionRequest {
 			alloy_primitives::TxKind::Call(AlloyAddress::from(addr_bytes))
 		});
 
+		let from = tx.from.map(|from| AlloyAddress::from_slice(&from.0[..20]));
+
 		TransactionRequest {
+			from,
 			chain_id: Some(tx.chain_id),
 			value: Some(tx.value),
 			to,
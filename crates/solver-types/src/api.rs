@@ -23,6 +23,9 @@ pub struct IntentRequest {
 		deserialize_with = "deserialize_lock_type_flexible"
 	)]
 	pub lock_type: LockType,
+	/// Quote the order accepts, absent for direct submissions
+	#[serde(rename = "quoteId", default, skip_serializing_if = "Option::is_none")]
+	pub quote_id: Option<String>,
 }
 
 /// Default lock type for IntentRequest
@@ -97,6 +100,7 @@ pub enum ApiErrorType {
 
 	// Quote errors
 	QuoteNotFound,
+	QuoteAlreadyUsed,
 	QuoteProcessingFailed,
 	QuoteConversionFailed,
 	MissingSignature,
@@ -290,6 +294,51 @@ pub struct Quote {
 	pub cost: Option<CostEstimate>,
 	// Using LockType
 	pub lock_type: String,
+	/// Solver signature over the quote terms and expiry
+	#[serde(
+		rename = "solverSignature",
+		default,
+		skip_serializing_if = "Option::is_none"
+	)]
+	pub solver_signature: Option<QuoteSignature>,
+	/// Remaining USD capacity the solver can front on this quote's routes
+	/// before an exposure limit is reached; absent when no limit applies
+	#[serde(
+		rename = "headroomUsd",
+		default,
+		skip_serializing_if = "Option::is_none"
+	)]
+	pub headroom_usd: Option<String>,
+}
+
+/// EIP-712 signature by the solver over a quote's terms and expiry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuoteSignature {
+	/// Address of the solver account that signed the quote
+	pub signer: String,
+	/// Chain ID of the EIP-712 domain
+	#[serde(rename = "chainId")]
+	pub chain_id: u64,
+	/// Verifying contract of the EIP-712 domain
+	#[serde(rename = "verifyingContract")]
+	pub verifying_contract: String,
+	/// 65-byte ECDSA signature, hex encoded
+	pub signature: String,
+}
+
+/// Result of verifying a solver quote signature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyQuoteResponse {
+	/// Whether the signature is from this solver and the quote has not expired
+	pub valid: bool,
+	/// Address recovered from the signature, if it could be recovered
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub signer: Option<String>,
+	/// Whether the quote's validity period has passed
+	pub expired: bool,
+	/// Reason the quote failed verification
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub reason: Option<String>,
 }
 
 /// Implementation to convert Quote with signature and standard to IntentRequest
@@ -339,6 +388,7 @@ impl IntentRequest {
 			sponsor: user_address,
 			signature: signature_bytes,
 			lock_type,
+			quote_id: Some(quote.quote_id.clone()),
 		})
 	}
 }
@@ -890,6 +940,8 @@ mod tests {
 			provider: "test_solver".to_string(),
 			cost: None,
 			lock_type: "permit2_escrow".to_string(),
+			solver_signature: None,
+			headroom_usd: None,
 		};
 
 		let json = serde_json::to_string(&quote).unwrap();
@@ -918,6 +970,8 @@ mod tests {
 				provider: "test_provider".to_string(),
 				cost: None,
 				lock_type: "permit2_escrow".to_string(),
+				solver_signature: None,
+				headroom_usd: None,
 			}],
 		};
 
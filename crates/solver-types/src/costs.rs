@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 /// Named amount used for cost components.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CostComponent {
-	/// Human-readable component name (e.g., "base-price", "gas-fill", "gas-claim", "buffer-gas", "buffer-rates", "commission")
+	/// Human-readable component name (e.g., "base-price", "gas-fill", "gas-claim", "settlement-fee", "buffer-gas", "buffer-rates", "commission")
 	pub name: String,
 	/// Amount as a decimal string in the display currency (matches CostEstimate.currency)
 	pub amount: String,
@@ -3,7 +3,7 @@
 //! This module defines types related to blockchain transaction submission
 //! and monitoring, including transaction hashes and receipts.
 
-use crate::Address;
+use crate::{Address, FeeEstimate, FeeUrgency};
 
 /// Blockchain transaction hash representation.
 ///
@@ -46,6 +46,9 @@ pub struct TransactionReceipt {
 	/// Block timestamp (Unix timestamp) - extracted from logs if available
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub block_timestamp: Option<u64>,
+	/// Gas used by the transaction, if the delivery implementation reports it.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub gas_used: Option<u64>,
 }
 
 /// Chain data structure containing current blockchain state information.
@@ -62,4 +65,33 @@ pub struct ChainData {
 	pub block_number: u64,
 	/// Timestamp when this data was fetched (Unix timestamp).
 	pub timestamp: u64,
+	/// EIP-1559 fees per urgency level, `None` on chains without a base fee.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub fees: Option<FeeEstimate>,
+}
+
+impl ChainData {
+	/// Returns the gas price expected to be paid at an urgency level, in wei.
+	///
+	/// This is the next base fee plus the tip on EIP-1559 chains, and the
+	/// legacy gas price otherwise.
+	pub fn gas_price_for(&self, urgency: FeeUrgency) -> Option<u128> {
+		match &self.fees {
+			Some(fees) => Some(fees.gas_price(urgency)),
+			None => self.gas_price.parse().ok(),
+		}
+	}
+}
+
+/// Base fees and priority fees of recent blocks, as reported by `eth_feeHistory`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FeeHistory {
+	/// Number of the oldest block in the history.
+	pub oldest_block: u64,
+	/// Base fee per gas of each block in wei, followed by that of the next block.
+	pub base_fee_per_gas: Vec<u128>,
+	/// Fraction of the gas limit used by each block.
+	pub gas_used_ratio: Vec<f64>,
+	/// Priority fees paid at the requested percentiles in each block, in wei.
+	pub reward: Vec<Vec<u128>>,
 }
@@ -22,6 +22,8 @@ pub enum SolverEvent {
 	Delivery(DeliveryEvent),
 	/// Events from the settlement service.
 	Settlement(SettlementEvent),
+	/// Events from the inventory monitor.
+	Inventory(InventoryEvent),
 }
 
 /// Events related to intent discovery.
@@ -51,10 +53,11 @@ pub enum OrderEvent {
 	},
 	/// An order has been skipped due to strategy decision.
 	Skipped { order_id: String, reason: String },
-	/// An order execution has been deferred.
+	/// An order execution has been deferred due to strategy decision.
 	Deferred {
 		order_id: String,
 		retry_after: Duration,
+		reason: String,
 	},
 }
 
@@ -109,8 +112,48 @@ pub enum SettlementEvent {
 	Completed { order_id: String },
 }
 
+/// Events related to solver inventory.
+///
+/// Tokens are hex addresses; `None` stands for the chain's native asset.
+/// Balances and thresholds are in the asset's smallest unit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum InventoryEvent {
+	/// A balance has dropped below its configured minimum.
+	LowBalance {
+		chain_id: u64,
+		token: Option<String>,
+		balance: String,
+		threshold: String,
+	},
+	/// A balance that was low is back at or above its minimum.
+	BalanceRecovered {
+		chain_id: u64,
+		token: Option<String>,
+		balance: String,
+	},
+	/// Quoting and filling towards a destination asset have been paused.
+	RoutePaused {
+		chain_id: u64,
+		token: Option<String>,
+		reason: String,
+	},
+	/// A paused destination asset can be quoted and filled again.
+	RouteResumed {
+		chain_id: u64,
+		token: Option<String>,
+	},
+	/// Funds have been sent through a bridge to rebalance an asset.
+	Rebalanced {
+		asset: String,
+		from_chain: u64,
+		to_chain: u64,
+		amount: String,
+		bridge: String,
+	},
+}
+
 /// Types of transactions in the solver system.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum TransactionType {
 	/// Transaction that prepares an off-chain order on-chain (e.g., openFor).
 	Prepare,
@@ -118,6 +161,8 @@ pub enum TransactionType {
 	Fill,
 	/// Transaction that executes after fill confirmation (optional).
 	PostFill,
+	/// Transaction that relays a stalled settlement message (optional).
+	Relay,
 	/// Transaction that executes before claiming (optional).
 	PreClaim,
 	/// Transaction that claims rewards on the origin chain.
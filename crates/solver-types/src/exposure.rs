@@ -0,0 +1,361 @@
+//! Exposure types for tracking capital the solver has fronted.
+//!
+//! Filling an order pays out on the destination chain before the claim returns
+//! the inputs on the origin chain. Until that claim confirms, the filled amount
+//! is capital at risk. This module defines how that capital is valued, summed
+//! per chain, token and counterparty, and checked against configured limits.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, str::FromStr};
+
+/// A single output the solver pays for an order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExposureLeg {
+	/// Chain the output is paid on.
+	pub chain_id: u64,
+	/// Token address as lowercase hex without the 0x prefix.
+	pub token: String,
+	/// Amount in the token's smallest unit.
+	pub amount: alloy_primitives::U256,
+	/// Value of the amount in USD.
+	pub value_usd: Decimal,
+}
+
+/// Capital the solver fronts to fill one order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderExposure {
+	/// ID of the order.
+	pub order_id: String,
+	/// User the order was opened by, as lowercase hex without the 0x prefix.
+	pub counterparty: String,
+	/// Outputs the solver pays.
+	pub legs: Vec<ExposureLeg>,
+}
+
+impl OrderExposure {
+	/// Returns the total USD value of all legs.
+	pub fn value_usd(&self) -> Decimal {
+		self.legs.iter().map(|leg| leg.value_usd).sum()
+	}
+}
+
+/// Outstanding exposure summed per chain, token and counterparty.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExposureSnapshot {
+	/// In-flight USD value per chain.
+	pub by_chain: HashMap<u64, Decimal>,
+	/// In-flight USD value per (chain, token).
+	pub by_token: HashMap<(u64, String), Decimal>,
+	/// In-flight USD value per counterparty.
+	pub by_counterparty: HashMap<String, Decimal>,
+	/// Total in-flight USD value.
+	pub total: Decimal,
+}
+
+impl ExposureSnapshot {
+	/// Adds the exposure of an order to the totals.
+	pub fn add(&mut self, exposure: &OrderExposure) {
+		for leg in &exposure.legs {
+			*self.by_chain.entry(leg.chain_id).or_default() += leg.value_usd;
+			*self
+				.by_token
+				.entry((leg.chain_id, leg.token.clone()))
+				.or_default() += leg.value_usd;
+		}
+		let value = exposure.value_usd();
+		*self
+			.by_counterparty
+			.entry(exposure.counterparty.clone())
+			.or_default() += value;
+		self.total += value;
+	}
+
+	/// Returns the in-flight value on a chain.
+	pub fn chain(&self, chain_id: u64) -> Decimal {
+		self.by_chain.get(&chain_id).copied().unwrap_or_default()
+	}
+
+	/// Returns the in-flight value of a token on a chain.
+	pub fn token(&self, chain_id: u64, token: &str) -> Decimal {
+		self.by_token
+			.get(&(chain_id, token.to_string()))
+			.copied()
+			.unwrap_or_default()
+	}
+
+	/// Returns the in-flight value for a counterparty.
+	pub fn counterparty(&self, counterparty: &str) -> Decimal {
+		self.by_counterparty
+			.get(counterparty)
+			.copied()
+			.unwrap_or_default()
+	}
+}
+
+/// Limits on the capital the solver fronts, in USD.
+///
+/// Every limit is optional; an unset limit is not enforced.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExposureLimits {
+	/// Largest value a single order may require.
+	pub max_order_value_usd: Option<Decimal>,
+	/// Largest in-flight value per chain.
+	pub max_in_flight_per_chain_usd: Option<Decimal>,
+	/// Largest in-flight value per token on a chain.
+	pub max_in_flight_per_token_usd: Option<Decimal>,
+	/// Largest in-flight value per counterparty.
+	pub max_in_flight_per_counterparty_usd: Option<Decimal>,
+}
+
+impl ExposureLimits {
+	/// Reads limits from a strategy configuration table.
+	///
+	/// Values are decimal strings in USD; missing or unparseable values leave
+	/// the limit unset.
+	pub fn from_table(table: &toml::Value) -> Self {
+		let read = |key: &str| {
+			table
+				.get(key)
+				.and_then(|v| v.as_str())
+				.and_then(|v| Decimal::from_str(v).ok())
+		};
+		Self {
+			max_order_value_usd: read("max_order_value_usd"),
+			max_in_flight_per_chain_usd: read("max_in_flight_per_chain_usd"),
+			max_in_flight_per_token_usd: read("max_in_flight_per_token_usd"),
+			max_in_flight_per_counterparty_usd: read("max_in_flight_per_counterparty_usd"),
+		}
+	}
+
+	/// Returns whether no limit is set.
+	pub fn is_empty(&self) -> bool {
+		self.max_order_value_usd.is_none()
+			&& self.max_in_flight_per_chain_usd.is_none()
+			&& self.max_in_flight_per_token_usd.is_none()
+			&& self.max_in_flight_per_counterparty_usd.is_none()
+	}
+
+	/// Checks whether an order fits within the limits on top of what is in flight.
+	///
+	/// Returns a description of the first limit the order would exceed.
+	pub fn check(&self, in_flight: &ExposureSnapshot, order: &OrderExposure) -> Result<(), String> {
+		let value = order.value_usd();
+		if let Some(max) = self.max_order_value_usd {
+			if value > max {
+				return Err(format!(
+					"Order value {} USD exceeds the single order limit of {} USD",
+					value, max
+				));
+			}
+		}
+
+		// Sum the order per chain and per token so multi-leg orders are checked as a whole
+		let mut pending = ExposureSnapshot::default();
+		pending.add(order);
+
+		if let Some(max) = self.max_in_flight_per_chain_usd {
+			for (chain_id, amount) in &pending.by_chain {
+				let total = in_flight.chain(*chain_id) + amount;
+				if total > max {
+					return Err(format!(
+						"In-flight value on chain {} would reach {} USD, above the limit of {} USD",
+						chain_id, total, max
+					));
+				}
+			}
+		}
+		if let Some(max) = self.max_in_flight_per_token_usd {
+			for ((chain_id, token), amount) in &pending.by_token {
+				let total = in_flight.token(*chain_id, token) + amount;
+				if total > max {
+					return Err(format!(
+						"In-flight value of token 0x{} on chain {} would reach {} USD, above the limit of {} USD",
+						token, chain_id, total, max
+					));
+				}
+			}
+		}
+		if let Some(max) = self.max_in_flight_per_counterparty_usd {
+			let total = in_flight.counterparty(&order.counterparty) + value;
+			if total > max {
+				return Err(format!(
+					"In-flight value for counterparty 0x{} would reach {} USD, above the limit of {} USD",
+					order.counterparty, total, max
+				));
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns how much more value an order paying a token on a chain can add.
+	///
+	/// The headroom is the smallest remaining allowance across the single order,
+	/// chain, token and, when given, counterparty limits. `None` means no limit
+	/// applies.
+	pub fn headroom(
+		&self,
+		in_flight: &ExposureSnapshot,
+		chain_id: u64,
+		token: &str,
+		counterparty: Option<&str>,
+	) -> Option<Decimal> {
+		let remaining =
+			|max: Option<Decimal>, used: Decimal| max.map(|max| (max - used).max(Decimal::ZERO));
+		[
+			self.max_order_value_usd,
+			remaining(self.max_in_flight_per_chain_usd, in_flight.chain(chain_id)),
+			remaining(
+				self.max_in_flight_per_token_usd,
+				in_flight.token(chain_id, token),
+			),
+			counterparty.and_then(|counterparty| {
+				remaining(
+					self.max_in_flight_per_counterparty_usd,
+					in_flight.counterparty(counterparty),
+				)
+			}),
+		]
+		.into_iter()
+		.flatten()
+		.min()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_primitives::U256;
+
+	fn exposure(order_id: &str, counterparty: &str, legs: &[(u64, &str, i64)]) -> OrderExposure {
+		OrderExposure {
+			order_id: order_id.to_string(),
+			counterparty: counterparty.to_string(),
+			legs: legs
+				.iter()
+				.map(|(chain_id, token, value)| ExposureLeg {
+					chain_id: *chain_id,
+					token: token.to_string(),
+					amount: U256::from(*value as u64),
+					value_usd: Decimal::from(*value),
+				})
+				.collect(),
+		}
+	}
+
+	#[test]
+	fn test_snapshot_sums_exposure() {
+		let mut snapshot = ExposureSnapshot::default();
+		snapshot.add(&exposure("a", "user1", &[(1, "aa", 100), (2, "bb", 50)]));
+		snapshot.add(&exposure("b", "user2", &[(1, "aa", 25)]));
+
+		assert_eq!(snapshot.chain(1), Decimal::from(125));
+		assert_eq!(snapshot.chain(2), Decimal::from(50));
+		assert_eq!(snapshot.token(1, "aa"), Decimal::from(125));
+		assert_eq!(snapshot.counterparty("user1"), Decimal::from(150));
+		assert_eq!(snapshot.counterparty("user3"), Decimal::ZERO);
+		assert_eq!(snapshot.total, Decimal::from(175));
+	}
+
+	#[test]
+	fn test_limits_from_table() {
+		let table: toml::Value = toml::from_str(
+			r#"
+			max_order_value_usd = "1000"
+			max_in_flight_per_chain_usd = "5000.5"
+			"#,
+		)
+		.unwrap();
+		let limits = ExposureLimits::from_table(&table);
+
+		assert_eq!(limits.max_order_value_usd, Some(Decimal::from(1000)));
+		assert_eq!(
+			limits.max_in_flight_per_chain_usd,
+			Some(Decimal::new(50005, 1))
+		);
+		assert_eq!(limits.max_in_flight_per_token_usd, None);
+		assert!(!limits.is_empty());
+		assert!(ExposureLimits::default().is_empty());
+	}
+
+	#[test]
+	fn test_check_enforces_each_limit() {
+		let mut in_flight = ExposureSnapshot::default();
+		in_flight.add(&exposure("a", "user1", &[(1, "aa", 400)]));
+		let order = exposure("b", "user1", &[(1, "aa", 200)]);
+
+		let limits = ExposureLimits {
+			max_order_value_usd: Some(Decimal::from(100)),
+			..Default::default()
+		};
+		assert!(limits
+			.check(&in_flight, &order)
+			.unwrap_err()
+			.contains("single order"));
+
+		let limits = ExposureLimits {
+			max_in_flight_per_chain_usd: Some(Decimal::from(500)),
+			..Default::default()
+		};
+		assert!(limits
+			.check(&in_flight, &order)
+			.unwrap_err()
+			.contains("chain 1"));
+
+		let limits = ExposureLimits {
+			max_in_flight_per_token_usd: Some(Decimal::from(500)),
+			..Default::default()
+		};
+		assert!(limits
+			.check(&in_flight, &order)
+			.unwrap_err()
+			.contains("token 0xaa"));
+
+		let limits = ExposureLimits {
+			max_in_flight_per_counterparty_usd: Some(Decimal::from(500)),
+			..Default::default()
+		};
+		assert!(limits
+			.check(&in_flight, &order)
+			.unwrap_err()
+			.contains("counterparty 0xuser1"));
+
+		let limits = ExposureLimits {
+			max_order_value_usd: Some(Decimal::from(200)),
+			max_in_flight_per_chain_usd: Some(Decimal::from(600)),
+			max_in_flight_per_token_usd: Some(Decimal::from(600)),
+			max_in_flight_per_counterparty_usd: Some(Decimal::from(600)),
+		};
+		assert!(limits.check(&in_flight, &order).is_ok());
+	}
+
+	#[test]
+	fn test_headroom_uses_tightest_limit() {
+		let mut in_flight = ExposureSnapshot::default();
+		in_flight.add(&exposure("a", "user1", &[(1, "aa", 400)]));
+
+		let limits = ExposureLimits {
+			max_order_value_usd: Some(Decimal::from(1000)),
+			max_in_flight_per_chain_usd: Some(Decimal::from(700)),
+			max_in_flight_per_token_usd: None,
+			max_in_flight_per_counterparty_usd: Some(Decimal::from(450)),
+		};
+
+		assert_eq!(
+			limits.headroom(&in_flight, 1, "aa", None),
+			Some(Decimal::from(300))
+		);
+		assert_eq!(
+			limits.headroom(&in_flight, 1, "aa", Some("user1")),
+			Some(Decimal::from(50))
+		);
+		assert_eq!(
+			limits.headroom(&in_flight, 2, "bb", None),
+			Some(Decimal::from(700))
+		);
+		assert_eq!(
+			ExposureLimits::default().headroom(&in_flight, 1, "aa", None),
+			None
+		);
+	}
+}
@@ -0,0 +1,196 @@
+//! EIP-1559 fee estimation from recent fee history.
+//!
+//! `eth_feeHistory` reports the base fee of recent blocks and of the next one,
+//! together with the priority fees paid at a few percentiles of each block. A
+//! `FeeEstimate` turns this into fees for three urgency levels. The tip is the
+//! median, over recent non-empty blocks, of the percentile matching the urgency.
+//! The maximum fee lets the base fee rise by the protocol maximum of 12.5% for
+//! a number of consecutive full blocks before the transaction is priced out.
+
+use crate::FeeHistory;
+use serde::{Deserialize, Serialize};
+
+/// Priority fee percentiles requested from `eth_feeHistory`, one per urgency.
+pub const FEE_HISTORY_PERCENTILES: [f64; 3] = [10.0, 50.0, 90.0];
+
+/// How quickly a transaction should be included.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeeUrgency {
+	/// Tips at the 10th percentile; the base fee may rise for one block.
+	Low,
+	/// Tips at the median; the base fee may rise for three blocks.
+	#[default]
+	Medium,
+	/// Tips at the 90th percentile; the base fee may rise for six blocks.
+	High,
+}
+
+impl FeeUrgency {
+	/// Column of the fee history rewards holding this urgency's percentile.
+	fn reward_index(self) -> usize {
+		match self {
+			FeeUrgency::Low => 0,
+			FeeUrgency::Medium => 1,
+			FeeUrgency::High => 2,
+		}
+	}
+
+	/// Number of consecutive full blocks the maximum fee stays above the base fee.
+	fn headroom_blocks(self) -> u32 {
+		match self {
+			FeeUrgency::Low => 1,
+			FeeUrgency::Medium => 3,
+			FeeUrgency::High => 6,
+		}
+	}
+}
+
+/// Fee fields of an EIP-1559 transaction, in wei.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Eip1559Fees {
+	/// Highest total fee per gas the transaction pays.
+	pub max_fee_per_gas: u128,
+	/// Tip per gas paid to the block producer.
+	pub max_priority_fee_per_gas: u128,
+}
+
+/// Fees for each urgency level on one chain, in wei.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeEstimate {
+	/// Base fee per gas of the next block.
+	pub base_fee_per_gas: u128,
+	/// Fees for transactions that can wait.
+	pub low: Eip1559Fees,
+	/// Fees for ordinary transactions.
+	pub medium: Eip1559Fees,
+	/// Fees for transactions that must land quickly.
+	pub high: Eip1559Fees,
+}
+
+impl FeeEstimate {
+	/// Builds an estimate from a fee history.
+	///
+	/// Returns `None` when the chain reports no base fee, i.e. it does not
+	/// support EIP-1559 and legacy gas prices apply.
+	pub fn from_fee_history(history: &FeeHistory) -> Option<Self> {
+		let base_fee_per_gas = *history.base_fee_per_gas.last()?;
+		if base_fee_per_gas == 0 {
+			return None;
+		}
+
+		let fees = |urgency: FeeUrgency| {
+			let tip = median_reward(history, urgency.reward_index());
+			let mut max_base_fee = base_fee_per_gas;
+			for _ in 0..urgency.headroom_blocks() {
+				max_base_fee = max_base_fee.saturating_add(max_base_fee / 8);
+			}
+			Eip1559Fees {
+				max_fee_per_gas: max_base_fee.saturating_add(tip),
+				max_priority_fee_per_gas: tip,
+			}
+		};
+
+		Some(Self {
+			base_fee_per_gas,
+			low: fees(FeeUrgency::Low),
+			medium: fees(FeeUrgency::Medium),
+			high: fees(FeeUrgency::High),
+		})
+	}
+
+	/// Returns the fees for an urgency level.
+	pub fn fees(&self, urgency: FeeUrgency) -> Eip1559Fees {
+		match urgency {
+			FeeUrgency::Low => self.low,
+			FeeUrgency::Medium => self.medium,
+			FeeUrgency::High => self.high,
+		}
+	}
+
+	/// Returns the gas price expected to be paid at an urgency level: the next
+	/// base fee plus the tip.
+	pub fn gas_price(&self, urgency: FeeUrgency) -> u128 {
+		self.base_fee_per_gas
+			.saturating_add(self.fees(urgency).max_priority_fee_per_gas)
+	}
+}
+
+/// Returns the median reward of one percentile column over non-empty blocks.
+///
+/// Empty blocks report zero rewards at every percentile, so they would pull
+/// the tip down without saying anything about competition for inclusion.
+fn median_reward(history: &FeeHistory, index: usize) -> u128 {
+	let mut rewards: Vec<u128> = history
+		.reward
+		.iter()
+		.zip(&history.gas_used_ratio)
+		.filter(|(_, ratio)| **ratio > 0.0)
+		.filter_map(|(rewards, _)| rewards.get(index).copied())
+		.collect();
+	if rewards.is_empty() {
+		return 0;
+	}
+	rewards.sort_unstable();
+	rewards[rewards.len() / 2]
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const GWEI: u128 = 1_000_000_000;
+
+	fn history(base_fees: &[u128], ratios: &[f64], rewards: &[[u128; 3]]) -> FeeHistory {
+		FeeHistory {
+			oldest_block: 100,
+			base_fee_per_gas: base_fees.to_vec(),
+			gas_used_ratio: ratios.to_vec(),
+			reward: rewards.iter().map(|r| r.to_vec()).collect(),
+		}
+	}
+
+	#[test]
+	fn test_estimate_from_fee_history() {
+		let history = history(
+			&[10 * GWEI, 11 * GWEI, 8 * GWEI],
+			&[0.9, 0.4],
+			&[[GWEI, 2 * GWEI, 5 * GWEI], [GWEI, 3 * GWEI, 7 * GWEI]],
+		);
+		let estimate = FeeEstimate::from_fee_history(&history).unwrap();
+
+		// The last base fee is the next block's
+		assert_eq!(estimate.base_fee_per_gas, 8 * GWEI);
+		assert_eq!(estimate.low.max_priority_fee_per_gas, GWEI);
+		assert_eq!(estimate.medium.max_priority_fee_per_gas, 3 * GWEI);
+		assert_eq!(estimate.high.max_priority_fee_per_gas, 7 * GWEI);
+
+		// One block of headroom: 8 gwei * 1.125 + tip
+		assert_eq!(estimate.low.max_fee_per_gas, 9 * GWEI + GWEI);
+		assert!(estimate.high.max_fee_per_gas > estimate.medium.max_fee_per_gas);
+		assert_eq!(estimate.gas_price(FeeUrgency::High), 15 * GWEI);
+	}
+
+	#[test]
+	fn test_empty_blocks_do_not_lower_tips() {
+		let history = history(
+			&[GWEI, GWEI, GWEI, GWEI],
+			&[0.0, 0.5, 0.0],
+			&[[0, 0, 0], [2 * GWEI, 4 * GWEI, 6 * GWEI], [0, 0, 0]],
+		);
+		let estimate = FeeEstimate::from_fee_history(&history).unwrap();
+		assert_eq!(estimate.medium.max_priority_fee_per_gas, 4 * GWEI);
+	}
+
+	#[test]
+	fn test_legacy_chain_has_no_estimate() {
+		assert!(FeeEstimate::from_fee_history(&history(&[0, 0], &[0.5], &[])).is_none());
+		assert!(FeeEstimate::from_fee_history(&history(&[], &[], &[])).is_none());
+	}
+
+	#[test]
+	fn test_urgency_deserializes_lowercase() {
+		let urgency: FeeUrgency = serde_json::from_str("\"high\"").unwrap();
+		assert_eq!(urgency, FeeUrgency::High);
+	}
+}
@@ -16,6 +16,10 @@ pub mod delivery;
 pub mod discovery;
 /// Event types for inter-service communication.
 pub mod events;
+/// Exposure types for tracking fronted capital and its limits.
+pub mod exposure;
+/// EIP-1559 fee estimation from recent fee history.
+pub mod fees;
 /// Network and token configuration types.
 pub mod networks;
 /// Oracle-related types for settlement validation and routing.
@@ -26,6 +30,8 @@ pub mod order;
 pub mod registry;
 /// Secure string type for handling sensitive data.
 pub mod secret_string;
+/// Revert reasons reported by pre-flight transaction simulation.
+pub mod simulation;
 /// Standard-specific types for different cross-chain protocols.
 pub mod standards;
 /// Storage types for managing persistent data.
@@ -48,11 +54,14 @@ pub use costs::{CostComponent, CostEstimate};
 pub use delivery::*;
 pub use discovery::*;
 pub use events::*;
-pub use networks::{NetworkConfig, NetworksConfig, TokenConfig};
+pub use exposure::{ExposureLeg, ExposureLimits, ExposureSnapshot, OrderExposure};
+pub use fees::{Eip1559Fees, FeeEstimate, FeeUrgency, FEE_HISTORY_PERCENTILES};
+pub use networks::{FeeModel, NativeCurrency, NetworkConfig, NetworksConfig, TokenConfig};
 pub use order::*;
 pub use pricing::*;
 pub use registry::ImplementationRegistry;
 pub use secret_string::SecretString;
+pub use simulation::{RevertAction, RevertReason};
 pub use standards::{
 	eip7683::{Eip7683OrderData, MandateOutput as Eip7683Output},
 	eip7930::{InteropAddress, InteropAddressError},
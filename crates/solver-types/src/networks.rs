@@ -69,6 +69,81 @@ pub struct TokenConfig {
 	pub decimals: u8,
 }
 
+/// The native asset of a network, which pays for gas.
+///
+/// # Fields
+///
+/// * `symbol` - The asset symbol used for pricing (e.g., "ETH", "POL")
+/// * `decimals` - The number of decimal places of the asset
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct NativeCurrency {
+	pub symbol: String,
+	pub decimals: u8,
+}
+
+impl Default for NativeCurrency {
+	/// Ether with 18 decimals, the native asset of Ethereum and most rollups.
+	fn default() -> Self {
+		Self {
+			symbol: "ETH".to_string(),
+			decimals: 18,
+		}
+	}
+}
+
+impl NativeCurrency {
+	/// Returns whether this is ether with 18 decimals.
+	pub fn is_ether(&self) -> bool {
+		self.symbol.eq_ignore_ascii_case("ETH") && self.decimals == 18
+	}
+}
+
+/// How a network charges for the data its transactions post to the parent chain.
+///
+/// Rollups charge an L1 data fee on top of execution gas. The fee model names
+/// where that fee can be estimated; plain L1 chains have none.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeeModel {
+	/// Execution gas only.
+	#[default]
+	L1,
+	/// OP-stack rollup, estimated with `GasPriceOracle.getL1Fee`.
+	OpStack {
+		/// Address of the GasPriceOracle predeploy.
+		#[serde(default = "default_gas_price_oracle")]
+		gas_price_oracle: Address,
+	},
+	/// Arbitrum rollup, estimated with `NodeInterface.gasEstimateL1Component`.
+	Arbitrum {
+		/// Address of the NodeInterface precompile.
+		#[serde(default = "default_node_interface")]
+		node_interface: Address,
+	},
+}
+
+impl FeeModel {
+	/// Returns whether transactions pay an L1 data fee.
+	pub fn has_l1_fee(&self) -> bool {
+		!matches!(self, FeeModel::L1)
+	}
+}
+
+/// OP-stack GasPriceOracle predeploy address.
+fn default_gas_price_oracle() -> Address {
+	let mut bytes = vec![0u8; 20];
+	bytes[0] = 0x42;
+	bytes[19] = 0x0f;
+	Address(bytes)
+}
+
+/// Arbitrum NodeInterface precompile address.
+fn default_node_interface() -> Address {
+	let mut bytes = vec![0u8; 20];
+	bytes[19] = 0xc8;
+	Address(bytes)
+}
+
 /// Configuration for a single blockchain network.
 ///
 /// Contains all the network-specific settings required for the solver
@@ -80,6 +155,8 @@ pub struct TokenConfig {
 /// * `input_settler_address` - Address of the input settler contract (for origin chains)
 /// * `output_settler_address` - Address of the output settler contract (for destination chains)
 /// * `tokens` - List of supported tokens on this network
+/// * `native_currency` - The asset gas is paid in, ETH with 18 decimals unless set
+/// * `fee_model` - How L1 data fees are charged, none unless set
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct NetworkConfig {
 	pub rpc_urls: Vec<RpcEndpoint>,
@@ -95,6 +172,12 @@ pub struct NetworkConfig {
 	/// Optional allocator address for TheCompact resource locks
 	#[serde(default)]
 	pub allocator_address: Option<Address>,
+	/// Native asset used to pay for gas
+	#[serde(default)]
+	pub native_currency: NativeCurrency,
+	/// Fee model for L1 data fees
+	#[serde(default)]
+	pub fee_model: FeeModel,
 }
 
 impl NetworkConfig {
@@ -376,6 +459,65 @@ mod tests {
 		assert!(error_msg.contains("expected key to be a number in quotes"));
 	}
 
+	#[test]
+	fn test_native_currency_defaults_to_ether() {
+		use serde_json::json;
+
+		let networks_json = json!({
+			"1": {
+				"rpc_urls": [{"http": "https://mainnet.infura.io"}],
+				"input_settler_address": "0x7d2768dE32b0b80b7a3454c06BdAc94A69DDc7A9",
+				"output_settler_address": "0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f",
+				"tokens": []
+			},
+			"137": {
+				"rpc_urls": [{"http": "https://polygon-rpc.com"}],
+				"input_settler_address": "0xA0b86a33E6776Fb78B3e1E6B2D0d2E8F0C1D2A3B",
+				"output_settler_address": "0x6B175474E89094C44Da98b954EedeAC495271d0F",
+				"tokens": [],
+				"native_currency": {"symbol": "POL", "decimals": 18}
+			}
+		});
+
+		let networks: NetworksConfig = serde_json::from_value(networks_json).unwrap();
+
+		assert_eq!(networks[&1].native_currency, NativeCurrency::default());
+		assert!(networks[&1].native_currency.is_ether());
+		assert_eq!(networks[&137].native_currency.symbol, "POL");
+		assert!(!networks[&137].native_currency.is_ether());
+	}
+
+	#[test]
+	fn test_fee_model_deserialization() {
+		let model: FeeModel = serde_json::from_str(r#"{"type": "op_stack"}"#).unwrap();
+		assert_eq!(
+			model,
+			FeeModel::OpStack {
+				gas_price_oracle: addr("0x420000000000000000000000000000000000000F"),
+			}
+		);
+		assert!(model.has_l1_fee());
+
+		let model: FeeModel = serde_json::from_str(
+			r#"{"type": "arbitrum", "node_interface": "0x00000000000000000000000000000000000000c9"}"#,
+		)
+		.unwrap();
+		assert_eq!(
+			model,
+			FeeModel::Arbitrum {
+				node_interface: addr("0x00000000000000000000000000000000000000c9"),
+			}
+		);
+
+		let network = NetworkConfigBuilder::new().build();
+		let json = serde_json::to_value(&network).unwrap();
+		let mut json = json.as_object().unwrap().clone();
+		json.remove("fee_model");
+		let network: NetworkConfig = serde_json::from_value(json.into()).unwrap();
+		assert_eq!(network.fee_model, FeeModel::L1);
+		assert!(!network.fee_model.has_l1_fee());
+	}
+
 	#[test]
 	fn test_network_config_serialization() {
 		let network = NetworkConfigBuilder::new().build();
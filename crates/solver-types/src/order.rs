@@ -4,6 +4,7 @@
 //! and fill proofs used throughout the order lifecycle.
 
 use alloy_primitives::U256;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
@@ -11,8 +12,9 @@ use std::future::Future;
 use std::pin::Pin;
 
 use crate::{
-	Address, AssetAmount, AvailableInput, ChainData, Eip7683OrderData, RequestedOutput,
-	SettlementType, TransactionHash, TransactionType,
+	Address, AssetAmount, AvailableInput, ChainData, Eip7683OrderData, ExposureSnapshot,
+	FeeUrgency, OrderExposure, RequestedOutput, RevertReason, SettlementType, TransactionHash,
+	TransactionType,
 };
 
 /// Information about a chain and its associated settler contract.
@@ -189,7 +191,12 @@ pub struct Order {
 	/// Standard-specific order data in JSON format.
 	pub data: serde_json::Value,
 	/// The solver's address for this order (for reward attribution).
+	/// This is the claimer account, which must send the claim transaction.
 	pub solver_address: Address,
+	/// Account that submitted the fill transaction.
+	/// Claimed inputs are paid out to this account to replenish its inventory.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub filler_address: Option<Address>,
 	/// Quote ID associated with this order.
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub quote_id: Option<String>,
@@ -222,6 +229,9 @@ pub struct Order {
 	/// Fill proof data when available.
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub fill_proof: Option<FillProof>,
+	/// Why the latest simulation of one of the order's transactions reverted.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub revert_reason: Option<RevertReason>,
 }
 
 impl Order {
@@ -273,6 +283,17 @@ pub struct ExecutionContext {
 	/// Key format: (chain_id, token_address) where token_address is None for native tokens.
 	/// Value is balance as decimal string.
 	pub solver_balances: HashMap<(u64, Option<String>), String>,
+	/// Capital fronted for orders that are executing but not yet claimed.
+	pub in_flight: ExposureSnapshot,
+	/// Capital this order would front, when it could be valued.
+	pub order_exposure: Option<OrderExposure>,
+	/// Expected profit margin of the order in percent, when it was validated.
+	pub profit_margin_pct: Option<Decimal>,
+	/// Urgency the fill transaction will be priced at.
+	pub fill_urgency: FeeUrgency,
+	/// Whether the order accepts a quote still within its validity window.
+	/// Quoted terms are honoured, so checks on current prices are waived.
+	pub quoted: bool,
 	/// Timestamp when this context was built.
 	pub timestamp: u64,
 }
@@ -286,8 +307,8 @@ pub enum ExecutionDecision {
 	Execute(ExecutionParams),
 	/// Skip the order with a reason.
 	Skip(String),
-	/// Defer execution for the specified duration.
-	Defer(std::time::Duration),
+	/// Defer execution for the specified duration with a reason.
+	Defer(std::time::Duration, String),
 }
 
 /// Proof that an order has been filled.
@@ -344,6 +365,9 @@ pub struct OrderResponse {
 	/// Transaction details if order has been executed
 	#[serde(rename = "fillTransaction")]
 	pub fill_transaction: Option<serde_json::Value>,
+	/// Why the latest simulation of an order transaction reverted, if it did
+	#[serde(rename = "revertReason", skip_serializing_if = "Option::is_none")]
+	pub revert_reason: Option<String>,
 }
 
 /// Status of an order in the solver system.
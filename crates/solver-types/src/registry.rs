@@ -3,6 +3,8 @@
 //! This module provides the base trait that all solver implementations must implement
 //! to register themselves with their configuration name and factory function.
 
+use crate::ConfigSchema;
+
 /// Base trait for implementation registries.
 ///
 /// Each implementation module (Storage, Discovery, Account, etc.) must provide
@@ -29,4 +31,9 @@ pub trait ImplementationRegistry {
 	/// Returns the factory function that can create instances of this implementation
 	/// when provided with the appropriate configuration.
 	fn factory() -> Self::Factory;
+
+	/// Get the configuration schema for this implementation.
+	///
+	/// Allows validating configuration without constructing the implementation.
+	fn config_schema() -> Box<dyn ConfigSchema>;
 }
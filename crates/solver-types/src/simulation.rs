@@ -0,0 +1,156 @@
+//! Revert reasons reported by pre-flight transaction simulation.
+//!
+//! Prepare, fill and claim transactions are simulated before they are sent.
+//! When a simulation reverts, the revert data is decoded into a `RevertReason`,
+//! which decides whether the transaction is skipped, retried later or aborted.
+
+use crate::TransactionType;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Why a simulated transaction reverted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "detail", rename_all = "snake_case")]
+pub enum RevertReason {
+	/// The order was already filled, by another solver or an earlier fill.
+	AlreadyFilled,
+	/// A fill deadline or order expiry has passed.
+	DeadlinePassed,
+	/// The order is not in the state the call requires, e.g. already opened,
+	/// claimed or refunded.
+	InvalidOrderStatus,
+	/// The output is meant for another chain or settler.
+	WrongDestination,
+	/// The order cannot be claimed yet.
+	NotYetClaimable,
+	/// The sender holds too little of a token.
+	InsufficientBalance,
+	/// The sender has approved too little of a token to the settler.
+	InsufficientAllowance,
+	/// A token transfer failed without a specific reason.
+	TransferFailed,
+	/// Reverted with a message, from `Error(string)` or the node.
+	Message(String),
+	/// Reverted with a Solidity panic code.
+	Panic(u64),
+	/// Reverted with data matching no known error, as 0x-prefixed hex.
+	Unknown(String),
+}
+
+/// What to do with a transaction whose simulation reverted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevertAction {
+	/// The order can no longer be executed; give it up without sending.
+	Skip,
+	/// The cause may clear up, e.g. once inventory or allowances are topped up.
+	Retry,
+	/// The revert is unexpected; fail the order without sending.
+	Abort,
+}
+
+impl RevertReason {
+	/// Returns what to do with a transaction that reverted for this reason.
+	pub fn action(&self) -> RevertAction {
+		match self {
+			RevertReason::AlreadyFilled
+			| RevertReason::DeadlinePassed
+			| RevertReason::InvalidOrderStatus
+			| RevertReason::WrongDestination => RevertAction::Skip,
+			RevertReason::NotYetClaimable
+			| RevertReason::InsufficientBalance
+			| RevertReason::InsufficientAllowance
+			| RevertReason::TransferFailed => RevertAction::Retry,
+			RevertReason::Message(_) | RevertReason::Panic(_) | RevertReason::Unknown(_) => {
+				RevertAction::Abort
+			},
+		}
+	}
+
+	/// Returns what to do with a transaction of a given type that reverted for
+	/// this reason.
+	///
+	/// Claims are only sent once the fill has paid out, so they are never
+	/// aborted on an unexpected revert but retried instead.
+	pub fn action_for(&self, tx_type: TransactionType) -> RevertAction {
+		match (self.action(), tx_type) {
+			(RevertAction::Abort, TransactionType::Claim) => RevertAction::Retry,
+			(action, _) => action,
+		}
+	}
+}
+
+impl fmt::Display for RevertReason {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			RevertReason::AlreadyFilled => write!(f, "order already filled"),
+			RevertReason::DeadlinePassed => write!(f, "deadline passed"),
+			RevertReason::InvalidOrderStatus => write!(f, "invalid order status"),
+			RevertReason::WrongDestination => write!(f, "wrong destination chain or settler"),
+			RevertReason::NotYetClaimable => write!(f, "order not yet claimable"),
+			RevertReason::InsufficientBalance => write!(f, "insufficient token balance"),
+			RevertReason::InsufficientAllowance => write!(f, "insufficient token allowance"),
+			RevertReason::TransferFailed => write!(f, "token transfer failed"),
+			RevertReason::Message(message) => write!(f, "{}", message),
+			RevertReason::Panic(code) => write!(f, "panic 0x{:02x}", code),
+			RevertReason::Unknown(data) => write!(f, "unknown error {}", data),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_revert_actions() {
+		assert_eq!(RevertReason::AlreadyFilled.action(), RevertAction::Skip);
+		assert_eq!(RevertReason::DeadlinePassed.action(), RevertAction::Skip);
+		assert_eq!(
+			RevertReason::InsufficientAllowance.action(),
+			RevertAction::Retry
+		);
+		assert_eq!(RevertReason::NotYetClaimable.action(), RevertAction::Retry);
+		assert_eq!(RevertReason::Panic(0x11).action(), RevertAction::Abort);
+		assert_eq!(
+			RevertReason::Unknown("0xdeadbeef".to_string()).action(),
+			RevertAction::Abort
+		);
+	}
+
+	#[test]
+	fn test_claims_are_never_aborted() {
+		let unknown = RevertReason::Unknown("0xdeadbeef".to_string());
+		assert_eq!(
+			unknown.action_for(TransactionType::Claim),
+			RevertAction::Retry
+		);
+		assert_eq!(
+			RevertReason::Message("reverted".to_string()).action_for(TransactionType::Claim),
+			RevertAction::Retry
+		);
+		assert_eq!(
+			unknown.action_for(TransactionType::Fill),
+			RevertAction::Abort
+		);
+		assert_eq!(
+			RevertReason::InvalidOrderStatus.action_for(TransactionType::Claim),
+			RevertAction::Skip
+		);
+	}
+
+	#[test]
+	fn test_revert_reason_serialization() {
+		let json = serde_json::to_string(&RevertReason::AlreadyFilled).unwrap();
+		assert_eq!(json, r#"{"kind":"already_filled"}"#);
+
+		let reason = RevertReason::Message("Ownable: caller is not the owner".to_string());
+		let json = serde_json::to_string(&reason).unwrap();
+		assert_eq!(
+			json,
+			r#"{"kind":"message","detail":"Ownable: caller is not the owner"}"#
+		);
+		assert_eq!(serde_json::from_str::<RevertReason>(&json).unwrap(), reason);
+
+		assert_eq!(RevertReason::Panic(0x11).to_string(), "panic 0x11");
+	}
+}
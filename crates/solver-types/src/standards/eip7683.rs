@@ -142,6 +142,9 @@ pub struct Eip7683OrderData {
 	/// Optional lock type determining the custody mechanism
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub lock_type: Option<LockType>,
+	/// Optional input settler the order is opened through
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub input_settler: Option<String>,
 }
 
 /// Represents a MandateOutput of the OIF contracts.
@@ -184,6 +187,103 @@ use crate::{
 #[cfg(feature = "oif-interfaces")]
 use crate::{Order, OrderStatus};
 
+impl Eip7683OrderData {
+	/// Compares the committed terms of this order with those of a quoted order.
+	///
+	/// Covers the user, nonce, deadlines, input oracle, lock type, inputs and
+	/// every output field, and the input settler when the quote names one.
+	/// Identifiers and signatures are ignored since they are only known once
+	/// the order is submitted.
+	///
+	/// # Returns
+	///
+	/// A description of the first differing field, or `None` if the terms match.
+	pub fn quote_mismatch(&self, quoted: &Eip7683OrderData) -> Option<String> {
+		if !self.user.eq_ignore_ascii_case(&quoted.user) {
+			return Some(format!("user {} != quoted {}", self.user, quoted.user));
+		}
+		if self.nonce != quoted.nonce {
+			return Some(format!("nonce {} != quoted {}", self.nonce, quoted.nonce));
+		}
+		if self.origin_chain_id != quoted.origin_chain_id {
+			return Some(format!(
+				"origin chain {} != quoted {}",
+				self.origin_chain_id, quoted.origin_chain_id
+			));
+		}
+		if self.expires != quoted.expires {
+			return Some(format!(
+				"expires {} != quoted {}",
+				self.expires, quoted.expires
+			));
+		}
+		if self.fill_deadline != quoted.fill_deadline {
+			return Some(format!(
+				"fill deadline {} != quoted {}",
+				self.fill_deadline, quoted.fill_deadline
+			));
+		}
+		if !self.input_oracle.eq_ignore_ascii_case(&quoted.input_oracle) {
+			return Some(format!(
+				"input oracle {} != quoted {}",
+				self.input_oracle, quoted.input_oracle
+			));
+		}
+		let lock_type = self.lock_type.unwrap_or_default();
+		let quoted_lock_type = quoted.lock_type.unwrap_or_default();
+		if lock_type != quoted_lock_type {
+			return Some(format!(
+				"lock type {} != quoted {}",
+				lock_type, quoted_lock_type
+			));
+		}
+		if let Some(quoted_settler) = &quoted.input_settler {
+			let input_settler = self.input_settler.as_deref().unwrap_or("none");
+			if !input_settler.eq_ignore_ascii_case(quoted_settler) {
+				return Some(format!(
+					"input settler {} != quoted {}",
+					input_settler, quoted_settler
+				));
+			}
+		}
+		if self.inputs != quoted.inputs {
+			return Some("inputs differ from quote".to_string());
+		}
+		if self.outputs.len() != quoted.outputs.len() {
+			return Some(format!(
+				"{} outputs != quoted {}",
+				self.outputs.len(),
+				quoted.outputs.len()
+			));
+		}
+
+		for (i, (output, expected)) in self.outputs.iter().zip(&quoted.outputs).enumerate() {
+			let field = if output.chain_id != expected.chain_id {
+				"chain"
+			} else if output.token != expected.token {
+				"token"
+			} else if output.amount != expected.amount {
+				"amount"
+			} else if output.recipient != expected.recipient {
+				"recipient"
+			} else if output.oracle != expected.oracle {
+				"oracle"
+			} else if output.settler != expected.settler {
+				"settler"
+			} else if output.call != expected.call {
+				"call"
+			} else if output.context != expected.context {
+				"context"
+			} else {
+				continue;
+			};
+			return Some(format!("output {} {} differs from quote", i, field));
+		}
+
+		None
+	}
+}
+
 /// Implementation of OrderParsable for EIP-7683 orders
 impl OrderParsable for Eip7683OrderData {
 	fn parse_available_inputs(&self) -> Vec<AvailableInput> {
@@ -920,6 +1020,7 @@ impl QuoteParsable for Eip7683OrderData {
 			status: OrderStatus::Created,
 			data: serde_json::to_value(&order_data).unwrap_or(serde_json::Value::Null),
 			solver_address: Address(vec![0u8; 20]), // Dummy address
+			filler_address: None,
 			quote_id: Some(quote.quote_id.clone()),
 			input_chains,
 			output_chains,
@@ -930,6 +1031,7 @@ impl QuoteParsable for Eip7683OrderData {
 			pre_claim_tx_hash: None,
 			claim_tx_hash: None,
 			fill_proof: None,
+			revert_reason: None,
 		}
 	}
 }
@@ -994,10 +1096,44 @@ impl From<interfaces::StandardOrder> for Eip7683OrderData {
 			signature: None,
 			sponsor: None,
 			lock_type: None,
+			input_settler: None,
 		}
 	}
 }
 
+#[cfg(feature = "oif-interfaces")]
+impl Eip7683OrderData {
+	/// Builds the order a quote commits to, with the lock type and input
+	/// settler it was issued for.
+	pub fn from_quote(quote: &Quote) -> Result<Self, Box<dyn std::error::Error>> {
+		let mut order = Self::from(interfaces::StandardOrder::try_from(quote)?);
+		order.lock_type = Some(quote.lock_type.parse::<LockType>()?);
+		order.input_settler = quoted_input_settler(quote);
+		Ok(order)
+	}
+}
+
+/// Returns the input settler named in the message of a quote's order.
+///
+/// Permit2 orders name it as the `spender`, compact orders as the `arbiter`
+/// and EIP-3009 orders as the recipient `to` of each authorization.
+#[cfg(feature = "oif-interfaces")]
+fn quoted_input_settler(quote: &Quote) -> Option<String> {
+	let message = &quote.orders.first()?.message;
+	let message = message
+		.get("eip712")
+		.and_then(|eip712| eip712.get("message"))
+		.unwrap_or(message);
+	let message = message
+		.get("signatures")
+		.and_then(|signatures| signatures.get(0))
+		.unwrap_or(message);
+	["spender", "arbiter", "to"]
+		.iter()
+		.find_map(|key| message.get(*key)?.as_str())
+		.map(str::to_string)
+}
+
 /// Implement TryFrom for converting serde_json::Value to Eip7683OrderData
 /// This allows us to parse intent data that may contain additional fields like sponsor/signature
 impl TryFrom<&serde_json::Value> for Eip7683OrderData {
@@ -1190,6 +1326,152 @@ mod tests {
 		assert_eq!(deserialized.context, Vec::<u8>::new());
 	}
 
+	fn quoted_order() -> Eip7683OrderData {
+		let output = MandateOutputBuilder::new()
+			.oracle([1u8; 32])
+			.settler([2u8; 32])
+			.chain_id(U256::from(10))
+			.token([3u8; 32])
+			.amount(U256::from(990))
+			.recipient([4u8; 32])
+			.build();
+
+		Eip7683OrderDataBuilder::new()
+			.user("0xAbCd567890123456789012345678901234567890")
+			.nonce(U256::from(7))
+			.origin_chain_id(U256::from(1))
+			.expires(1234567890)
+			.fill_deadline(1234567900)
+			.input_oracle("0x1111111111111111111111111111111111111111")
+			.inputs(vec![[U256::from(1), U256::from(1000)]])
+			.outputs(vec![output])
+			.build()
+	}
+
+	#[test]
+	fn test_quote_mismatch_matching_terms() {
+		let quoted = quoted_order();
+		let mut order = quoted.clone();
+		// Address casing, order id and signature are not part of the terms
+		order.user = order.user.to_lowercase();
+		order.order_id = [9u8; 32];
+		order.signature = Some("0xsig".to_string());
+
+		assert!(order.quote_mismatch(&quoted).is_none());
+	}
+
+	#[test]
+	fn test_quote_mismatch_detects_changed_terms() {
+		let quoted = quoted_order();
+
+		let mut order = quoted.clone();
+		order.fill_deadline += 60;
+		assert!(order
+			.quote_mismatch(&quoted)
+			.unwrap()
+			.contains("fill deadline"));
+
+		let mut order = quoted.clone();
+		order.inputs[0][1] = U256::from(999);
+		assert!(order.quote_mismatch(&quoted).unwrap().contains("inputs"));
+
+		let mut order = quoted.clone();
+		order.outputs[0].amount = U256::from(1);
+		assert_eq!(
+			order.quote_mismatch(&quoted).unwrap(),
+			"output 0 amount differs from quote"
+		);
+
+		let mut order = quoted.clone();
+		order.outputs[0].settler = [8u8; 32];
+		assert_eq!(
+			order.quote_mismatch(&quoted).unwrap(),
+			"output 0 settler differs from quote"
+		);
+
+		let mut order = quoted.clone();
+		order.outputs.clear();
+		assert!(order.quote_mismatch(&quoted).unwrap().contains("outputs"));
+	}
+
+	#[test]
+	fn test_quote_mismatch_detects_changed_lock_type() {
+		let mut quoted = quoted_order();
+		quoted.lock_type = Some(LockType::Permit2Escrow);
+
+		// An unset lock type is the default Permit2 escrow
+		let order = quoted.clone();
+		assert!(order.quote_mismatch(&quoted_order()).is_none());
+
+		let mut order = quoted.clone();
+		order.lock_type = Some(LockType::ResourceLock);
+		assert_eq!(
+			order.quote_mismatch(&quoted).unwrap(),
+			"lock type compact_resource_lock != quoted permit2_escrow"
+		);
+	}
+
+	#[test]
+	fn test_quote_mismatch_detects_changed_input_settler() {
+		let mut quoted = quoted_order();
+		quoted.input_settler = Some("0x2222222222222222222222222222222222222222".to_string());
+
+		let mut order = quoted.clone();
+		order.input_settler = Some("0x2222222222222222222222222222222222222222".to_uppercase());
+		assert!(order.quote_mismatch(&quoted).is_none());
+
+		order.input_settler = Some("0x3333333333333333333333333333333333333333".to_string());
+		assert!(order
+			.quote_mismatch(&quoted)
+			.unwrap()
+			.starts_with("input settler 0x3333"));
+
+		order.input_settler = None;
+		assert!(order
+			.quote_mismatch(&quoted)
+			.unwrap()
+			.starts_with("input settler none"));
+
+		// A quote that names no settler does not constrain it
+		let order = quoted.clone();
+		assert!(order.quote_mismatch(&quoted_order()).is_none());
+	}
+
+	#[test]
+	#[cfg(feature = "oif-interfaces")]
+	fn test_quoted_input_settler_from_message() {
+		let quote = |message: serde_json::Value| -> Quote {
+			serde_json::from_value(serde_json::json!({
+				"orders": [{
+					"signatureType": "eip712",
+					"domain": {},
+					"primaryType": "PermitBatchWitnessTransferFrom",
+					"message": message,
+				}],
+				"details": { "requestedOutputs": [], "availableInputs": [] },
+				"validUntil": null,
+				"eta": null,
+				"quoteId": "quote",
+				"provider": "solver",
+				"lock_type": "permit2_escrow",
+			}))
+			.unwrap()
+		};
+		let settler = "0x2222222222222222222222222222222222222222";
+
+		let permit2 = quote(serde_json::json!({ "spender": settler }));
+		assert_eq!(quoted_input_settler(&permit2).as_deref(), Some(settler));
+
+		let compact = quote(serde_json::json!({ "eip712": { "message": { "arbiter": settler } } }));
+		assert_eq!(quoted_input_settler(&compact).as_deref(), Some(settler));
+
+		let eip3009 = quote(serde_json::json!({ "signatures": [{ "to": settler }] }));
+		assert_eq!(quoted_input_settler(&eip3009).as_deref(), Some(settler));
+
+		let unnamed = quote(serde_json::json!({ "nonce": "1" }));
+		assert_eq!(quoted_input_settler(&unnamed), None);
+	}
+
 	#[test]
 	fn test_eip7683_order_data_serialization() {
 		let output = MandateOutputBuilder::new()
@@ -18,6 +18,18 @@ pub enum StorageKey {
 	Quotes,
 	/// Key for storing settlement message data (per implementation)
 	SettlementMessages,
+	/// Key for storing interchain gas payment history per message route
+	SettlementGasPayments,
+	/// Key for storing oracle health and cost statistics
+	OracleHealth,
+	/// Key for storing inventory balance snapshots
+	Inventory,
+	/// Key for storing the last rebalancing transfer per route
+	Rebalances,
+	/// Key for storing gas used by confirmed transactions
+	GasUsage,
+	/// Key for mapping consumed quote IDs to the order that accepted them
+	QuoteUsage,
 }
 
 impl StorageKey {
@@ -29,6 +41,12 @@ impl StorageKey {
 			StorageKey::OrderByTxHash => "order_by_tx_hash",
 			StorageKey::Quotes => "quotes",
 			StorageKey::SettlementMessages => "settlement_messages",
+			StorageKey::SettlementGasPayments => "settlement_gas_payments",
+			StorageKey::OracleHealth => "oracle_health",
+			StorageKey::Inventory => "inventory",
+			StorageKey::Rebalances => "rebalances",
+			StorageKey::GasUsage => "gas_usage",
+			StorageKey::QuoteUsage => "quote_usage",
 		}
 	}
 
@@ -40,6 +58,12 @@ impl StorageKey {
 			Self::OrderByTxHash,
 			Self::Quotes,
 			Self::SettlementMessages,
+			Self::SettlementGasPayments,
+			Self::OracleHealth,
+			Self::Inventory,
+			Self::Rebalances,
+			Self::GasUsage,
+			Self::QuoteUsage,
 		]
 		.into_iter()
 	}
@@ -55,6 +79,12 @@ impl FromStr for StorageKey {
 			"order_by_tx_hash" => Ok(Self::OrderByTxHash),
 			"quotes" => Ok(Self::Quotes),
 			"settlement_messages" => Ok(Self::SettlementMessages),
+			"settlement_gas_payments" => Ok(Self::SettlementGasPayments),
+			"oracle_health" => Ok(Self::OracleHealth),
+			"inventory" => Ok(Self::Inventory),
+			"rebalances" => Ok(Self::Rebalances),
+			"gas_usage" => Ok(Self::GasUsage),
+			"quote_usage" => Ok(Self::QuoteUsage),
 			_ => Err(()),
 		}
 	}
@@ -80,6 +110,15 @@ mod tests {
 			StorageKey::SettlementMessages.as_str(),
 			"settlement_messages"
 		);
+		assert_eq!(
+			StorageKey::SettlementGasPayments.as_str(),
+			"settlement_gas_payments"
+		);
+		assert_eq!(StorageKey::OracleHealth.as_str(), "oracle_health");
+		assert_eq!(StorageKey::Inventory.as_str(), "inventory");
+		assert_eq!(StorageKey::Rebalances.as_str(), "rebalances");
+		assert_eq!(StorageKey::GasUsage.as_str(), "gas_usage");
+		assert_eq!(StorageKey::QuoteUsage.as_str(), "quote_usage");
 	}
 
 	#[test]
@@ -99,6 +138,30 @@ mod tests {
 			"settlement_messages".parse::<StorageKey>().unwrap(),
 			StorageKey::SettlementMessages
 		);
+		assert_eq!(
+			"settlement_gas_payments".parse::<StorageKey>().unwrap(),
+			StorageKey::SettlementGasPayments
+		);
+		assert_eq!(
+			"oracle_health".parse::<StorageKey>().unwrap(),
+			StorageKey::OracleHealth
+		);
+		assert_eq!(
+			"inventory".parse::<StorageKey>().unwrap(),
+			StorageKey::Inventory
+		);
+		assert_eq!(
+			"rebalances".parse::<StorageKey>().unwrap(),
+			StorageKey::Rebalances
+		);
+		assert_eq!(
+			"gas_usage".parse::<StorageKey>().unwrap(),
+			StorageKey::GasUsage
+		);
+		assert_eq!(
+			"quote_usage".parse::<StorageKey>().unwrap(),
+			StorageKey::QuoteUsage
+		);
 
 		// Invalid cases
 		assert!("invalid".parse::<StorageKey>().is_err());
@@ -116,6 +179,12 @@ mod tests {
 			StorageKey::OrderByTxHash,
 			StorageKey::Quotes,
 			StorageKey::SettlementMessages,
+			StorageKey::SettlementGasPayments,
+			StorageKey::OracleHealth,
+			StorageKey::Inventory,
+			StorageKey::Rebalances,
+			StorageKey::GasUsage,
+			StorageKey::QuoteUsage,
 		];
 
 		assert_eq!(all_keys, expected);
@@ -138,6 +207,24 @@ mod tests {
 
 		let settlement_str: &'static str = StorageKey::SettlementMessages.into();
 		assert_eq!(settlement_str, "settlement_messages");
+
+		let gas_payments_str: &'static str = StorageKey::SettlementGasPayments.into();
+		assert_eq!(gas_payments_str, "settlement_gas_payments");
+
+		let oracle_health_str: &'static str = StorageKey::OracleHealth.into();
+		assert_eq!(oracle_health_str, "oracle_health");
+
+		let inventory_str: &'static str = StorageKey::Inventory.into();
+		assert_eq!(inventory_str, "inventory");
+
+		let rebalances_str: &'static str = StorageKey::Rebalances.into();
+		assert_eq!(rebalances_str, "rebalances");
+
+		let gas_usage_str: &'static str = StorageKey::GasUsage.into();
+		assert_eq!(gas_usage_str, "gas_usage");
+
+		let quote_usage_str: &'static str = StorageKey::QuoteUsage.into();
+		assert_eq!(quote_usage_str, "quote_usage");
 	}
 
 	#[test]
@@ -154,7 +241,7 @@ mod tests {
 		use std::collections::HashSet;
 
 		let strings: HashSet<&str> = StorageKey::all().map(|k| k.as_str()).collect();
-		assert_eq!(strings.len(), 5, "String representations should be unique");
+		assert_eq!(strings.len(), 11, "String representations should be unique");
 	}
 
 	#[test]
@@ -165,7 +252,7 @@ mod tests {
 		for key in StorageKey::all() {
 			assert!(set.insert(key)); // Should be unique
 		}
-		assert_eq!(set.len(), 5);
+		assert_eq!(set.len(), 11);
 
 		// Test equality
 		assert_eq!(StorageKey::Orders, StorageKey::Orders);
@@ -16,6 +16,13 @@ pub const PERMIT2_WITNESS_TYPE: &str =
 pub const TOKEN_PERMISSIONS_TYPE: &str = "TokenPermissions(address token,uint256 amount)";
 pub const PERMIT_BATCH_WITNESS_TYPE: &str =
 	"PermitBatchWitnessTransferFrom(TokenPermissions[] permitted,address spender,uint256 nonce,uint256 deadline,Permit2Witness witness)";
+pub const NAME_SOLVER_QUOTE: &str = "OIFSolverQuote";
+pub const QUOTE_INPUT_TYPE: &str =
+	"QuoteInput(address user,uint256 chainId,address asset,uint256 amount)";
+pub const QUOTE_OUTPUT_TYPE: &str =
+	"QuoteOutput(address receiver,uint256 chainId,address asset,uint256 amount)";
+pub const SOLVER_QUOTE_TYPE: &str =
+	"SolverQuote(string quoteId,string lockType,QuoteInput[] inputs,QuoteOutput[] outputs,uint256 validUntil)";
 
 /// Type alias for EIP-712 data extraction result
 pub type Eip712ExtractionResult<'a> =
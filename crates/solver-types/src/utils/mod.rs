@@ -20,7 +20,8 @@ pub use conversion::{
 };
 pub use eip712::{
 	compute_domain_hash, compute_final_digest, Eip712AbiEncoder, DOMAIN_TYPE, MANDATE_OUTPUT_TYPE,
-	NAME_PERMIT2, PERMIT2_WITNESS_TYPE, PERMIT_BATCH_WITNESS_TYPE, TOKEN_PERMISSIONS_TYPE,
+	NAME_PERMIT2, NAME_SOLVER_QUOTE, PERMIT2_WITNESS_TYPE, PERMIT_BATCH_WITNESS_TYPE,
+	QUOTE_INPUT_TYPE, QUOTE_OUTPUT_TYPE, SOLVER_QUOTE_TYPE, TOKEN_PERMISSIONS_TYPE,
 };
 pub use formatting::{format_token_amount, truncate_id, with_0x_prefix, without_0x_prefix};
 pub use helpers::current_timestamp;
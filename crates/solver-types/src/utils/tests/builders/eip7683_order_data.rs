@@ -44,6 +44,7 @@ pub struct Eip7683OrderDataBuilder {
 	signature: Option<String>,
 	sponsor: Option<String>,
 	lock_type: Option<LockType>,
+	input_settler: Option<String>,
 }
 
 impl Default for Eip7683OrderDataBuilder {
@@ -89,6 +90,7 @@ impl Eip7683OrderDataBuilder {
 			signature: None,
 			sponsor: None,
 			lock_type: None,
+			input_settler: None,
 		}
 	}
 
@@ -224,6 +226,12 @@ impl Eip7683OrderDataBuilder {
 		self
 	}
 
+	/// Sets the input settler the order is opened through.
+	pub fn input_settler<S: Into<String>>(mut self, input_settler: S) -> Self {
+		self.input_settler = Some(input_settler.into());
+		self
+	}
+
 	/// Validates the builder state and returns an error if required fields are missing.
 	pub fn validate(&self) -> Result<(), Eip7683OrderDataBuilderError> {
 		if self.user.is_none() {
@@ -290,6 +298,7 @@ impl Eip7683OrderDataBuilder {
 			signature: self.signature,
 			sponsor: self.sponsor,
 			lock_type: self.lock_type,
+			input_settler: self.input_settler,
 		})
 	}
 }
@@ -3,7 +3,9 @@
 //! Provides fluent APIs for constructing network configuration instances with
 //! proper validation and sensible defaults.
 
-use crate::networks::{NetworkConfig, NetworksConfig, RpcEndpoint, TokenConfig};
+use crate::networks::{
+	FeeModel, NativeCurrency, NetworkConfig, NetworksConfig, RpcEndpoint, TokenConfig,
+};
 use crate::{parse_address, Address};
 use std::collections::HashMap;
 
@@ -142,6 +144,8 @@ pub struct NetworkConfigBuilder {
 	input_settler_compact_address: Option<Address>,
 	the_compact_address: Option<Address>,
 	allocator_address: Option<Address>,
+	native_currency: NativeCurrency,
+	fee_model: FeeModel,
 }
 
 impl Default for NetworkConfigBuilder {
@@ -176,6 +180,8 @@ impl NetworkConfigBuilder {
 					.expect("Invalid mock address"),
 			),
 			allocator_address: None,
+			native_currency: NativeCurrency::default(),
+			fee_model: FeeModel::default(),
 		}
 	}
 
@@ -263,6 +269,21 @@ impl NetworkConfigBuilder {
 		Ok(self)
 	}
 
+	/// Sets the native currency gas is paid in.
+	pub fn native_currency(mut self, symbol: &str, decimals: u8) -> Self {
+		self.native_currency = NativeCurrency {
+			symbol: symbol.to_string(),
+			decimals,
+		};
+		self
+	}
+
+	/// Sets the fee model for L1 data fees.
+	pub fn fee_model(mut self, fee_model: FeeModel) -> Self {
+		self.fee_model = fee_model;
+		self
+	}
+
 	/// Sets the optional TheCompact contract address.
 	pub fn the_compact_address(mut self, address: Address) -> Self {
 		self.the_compact_address = Some(address);
@@ -321,6 +342,8 @@ impl NetworkConfigBuilder {
 			input_settler_compact_address: self.input_settler_compact_address,
 			the_compact_address: self.the_compact_address,
 			allocator_address: None, // Default to None for tests
+			native_currency: self.native_currency,
+			fee_model: self.fee_model,
 		})
 	}
 }
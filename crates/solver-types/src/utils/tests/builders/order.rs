@@ -1,7 +1,7 @@
 //! Order builder utilities for creating test and production Order instances.
 
 use crate::order::ChainSettlerInfo;
-use crate::{Address, FillProof, Order, OrderStatus, TransactionHash};
+use crate::{Address, FillProof, Order, OrderStatus, RevertReason, TransactionHash};
 
 /// Builder for creating Order instances with sensible defaults.
 ///
@@ -13,6 +13,7 @@ pub struct OrderBuilder {
 	standard: String,
 	status: OrderStatus,
 	solver_address: Address,
+	filler_address: Option<Address>,
 	created_at: u64,
 	updated_at: u64,
 	data: serde_json::Value,
@@ -26,6 +27,7 @@ pub struct OrderBuilder {
 	pre_claim_tx_hash: Option<TransactionHash>,
 	claim_tx_hash: Option<TransactionHash>,
 	fill_proof: Option<FillProof>,
+	revert_reason: Option<RevertReason>,
 }
 
 impl Default for OrderBuilder {
@@ -36,6 +38,7 @@ impl Default for OrderBuilder {
 			standard: "eip7683".to_string(),
 			status: OrderStatus::Created,
 			solver_address: Address(vec![0x12; 20]),
+			filler_address: None,
 			created_at: timestamp,
 			updated_at: timestamp,
 			// Create minimal data with just the fields needed for the test
@@ -67,6 +70,7 @@ impl Default for OrderBuilder {
 			pre_claim_tx_hash: None,
 			claim_tx_hash: None,
 			fill_proof: None,
+			revert_reason: None,
 		}
 	}
 }
@@ -101,6 +105,12 @@ impl OrderBuilder {
 		self
 	}
 
+	/// Sets the account that submitted the fill.
+	pub fn with_filler_address(mut self, address: Option<Address>) -> Self {
+		self.filler_address = address;
+		self
+	}
+
 	/// Sets the created_at timestamp.
 	pub fn with_created_at(mut self, timestamp: u64) -> Self {
 		self.created_at = timestamp;
@@ -210,6 +220,12 @@ impl OrderBuilder {
 		self
 	}
 
+	/// Sets the revert reason.
+	pub fn with_revert_reason(mut self, reason: Option<RevertReason>) -> Self {
+		self.revert_reason = reason;
+		self
+	}
+
 	/// Convenience method to create an executed order with fill transaction and proof.
 	pub fn executed_with_fill(mut self, tx_hash: TransactionHash, block_number: u64) -> Self {
 		let timestamp = self.updated_at;
@@ -241,6 +257,7 @@ impl OrderBuilder {
 			status: self.status,
 			data: self.data,
 			solver_address: self.solver_address,
+			filler_address: self.filler_address,
 			quote_id: self.quote_id,
 			input_chains: self.input_chains,
 			output_chains: self.output_chains,
@@ -251,6 +268,7 @@ impl OrderBuilder {
 			pre_claim_tx_hash: self.pre_claim_tx_hash,
 			claim_tx_hash: self.claim_tx_hash,
 			fill_proof: self.fill_proof,
+			revert_reason: self.revert_reason,
 		}
 	}
 }
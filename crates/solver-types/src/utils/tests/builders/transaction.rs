@@ -30,6 +30,7 @@ use alloy_primitives::U256;
 /// ```
 #[derive(Debug, Clone)]
 pub struct TransactionBuilder {
+	from: Option<Address>,
 	to: Option<Address>,
 	data: Vec<u8>,
 	value: U256,
@@ -51,6 +52,7 @@ impl TransactionBuilder {
 	/// Creates a new `TransactionBuilder` with default values.
 	pub fn new() -> Self {
 		Self {
+			from: None,
 			to: Some(
 				parse_address("0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef")
 					.expect("Invalid mock address"),
@@ -66,6 +68,12 @@ impl TransactionBuilder {
 		}
 	}
 
+	/// Sets the sending account (None for the chain's default signer).
+	pub fn from(mut self, from: Option<Address>) -> Self {
+		self.from = from;
+		self
+	}
+
 	/// Sets the recipient address (None for contract creation).
 	pub fn to(mut self, to: Option<Address>) -> Self {
 		self.to = to;
@@ -271,6 +279,7 @@ impl TransactionBuilder {
 		self.validate()?;
 
 		Ok(Transaction {
+			from: self.from,
 			to: self.to,
 			data: self.data,
 			value: self.value,